@@ -57,6 +57,11 @@ pub enum MentionUri {
     TerminalSelection {
         line_count: u32,
     },
+    McpResource {
+        server_id: String,
+        uri: Url,
+        name: String,
+    },
 }
 
 impl MentionUri {
@@ -208,6 +213,24 @@ impl MentionUri {
                         .parse::<u32>()
                         .unwrap_or(0);
                     Ok(Self::TerminalSelection { line_count })
+                } else if path == "/agent/mcp-resource" {
+                    let mut server_id = None;
+                    let mut uri = None;
+                    let mut name = None;
+                    for (key, value) in url.query_pairs() {
+                        match key.as_ref() {
+                            "server" => server_id = Some(value.into_owned()),
+                            "uri" => uri = Some(Url::parse(&value)?),
+                            "name" => name = Some(value.into_owned()),
+                            _ => bail!("invalid query parameter"),
+                        }
+                    }
+                    let uri = uri.context("Missing uri for MCP resource")?;
+                    Ok(Self::McpResource {
+                        server_id: server_id.context("Missing server for MCP resource")?,
+                        name: name.unwrap_or_else(|| uri.to_string()),
+                        uri,
+                    })
                 } else {
                     bail!("invalid zed url: {:?}", input);
                 }
@@ -243,6 +266,7 @@ impl MentionUri {
                 ..
             } => selection_name(path.as_deref(), line_range),
             MentionUri::Fetch { url } => url.to_string(),
+            MentionUri::McpResource { name, .. } => name.clone(),
         }
     }
 
@@ -262,6 +286,7 @@ impl MentionUri {
             MentionUri::TerminalSelection { .. } => IconName::Terminal.path().into(),
             MentionUri::Selection { .. } => IconName::Reader.path().into(),
             MentionUri::Fetch { .. } => IconName::ToolWeb.path().into(),
+            MentionUri::McpResource { .. } => IconName::ToolHammer.path().into(),
         }
     }
 
@@ -360,6 +385,18 @@ impl MentionUri {
                     .append_pair("lines", &line_count.to_string());
                 url
             }
+            MentionUri::McpResource {
+                server_id,
+                uri,
+                name,
+            } => {
+                let mut url = Url::parse("zed:///agent/mcp-resource").unwrap();
+                url.query_pairs_mut()
+                    .append_pair("server", server_id)
+                    .append_pair("uri", uri.as_str())
+                    .append_pair("name", name);
+                url
+            }
         }
     }
 }
@@ -683,4 +720,32 @@ mod tests {
         let parsed_single = MentionUri::parse(single_line_uri, PathStyle::local()).unwrap();
         assert_eq!(parsed_single.name(), "Terminal (1 line)");
     }
+
+    #[test]
+    fn test_parse_mcp_resource_uri() {
+        let resource_uri =
+            "zed:///agent/mcp-resource?server=github&uri=repo%3A%2F%2Fzed%2FREADME.md&name=README";
+        let parsed = MentionUri::parse(resource_uri, PathStyle::local()).unwrap();
+        match &parsed {
+            MentionUri::McpResource {
+                server_id,
+                uri,
+                name,
+            } => {
+                assert_eq!(server_id, "github");
+                assert_eq!(uri.as_str(), "repo://zed/README.md");
+                assert_eq!(name, "README");
+            }
+            _ => panic!("Expected McpResource variant"),
+        }
+        assert_eq!(parsed.to_uri().to_string(), resource_uri);
+
+        assert!(
+            MentionUri::parse(
+                "zed:///agent/mcp-resource?uri=repo%3A%2F%2Fzed%2FREADME.md",
+                PathStyle::local()
+            )
+            .is_err()
+        );
+    }
 }
@@ -144,7 +144,11 @@ impl Terminal {
 
     fn truncated_output(&self, cx: &App) -> (String, usize) {
         let terminal = self.terminal.read(cx);
-        let mut content = terminal.get_content();
+        // Prefer the output the command's shell integration marks delimit, which leaves out
+        // anything the shell itself printed around it.
+        let mut content = terminal
+            .last_command_output()
+            .unwrap_or_else(|| terminal.get_content());
 
         let original_content_len = content.len();
 
@@ -230,6 +234,7 @@ pub async fn create_terminal_entity(
     let is_windows = project.read_with(cx, |project, cx| project.path_style(cx).is_windows());
     let (task_command, task_args) = task::ShellBuilder::new(&shell, is_windows)
         .redirect_stdin_to_dev_null()
+        .mark_command_output()
         .build(Some(command.clone()), &args);
 
     project
@@ -5,6 +5,7 @@ mod native_agent_server;
 pub mod outline;
 mod pattern_extraction;
 mod templates;
+mod terminal_sandbox;
 #[cfg(test)]
 mod tests;
 mod thread;
@@ -428,7 +429,9 @@ impl NativeAgent {
         Ok(())
     }
 
-    fn build_project_context(
+    /// Builds the system prompt context for the project's visible worktrees, including their
+    /// rules files and the user's default rules from the prompt store.
+    pub fn build_project_context(
         project: &Entity<Project>,
         prompt_store: Option<&Entity<PromptStore>>,
         cx: &mut App,
@@ -0,0 +1,429 @@
+use agent_settings::TerminalSandboxSettings;
+use anyhow::{Context as _, Result, bail};
+use gpui::App;
+use project::Project;
+use std::path::{Path, PathBuf};
+use util::shell::ShellKind;
+
+/// System directories that sandboxed commands may read, when they exist.
+const SYSTEM_READ_ONLY_PATHS: &[&str] = &[
+    "/usr",
+    "/bin",
+    "/sbin",
+    "/lib",
+    "/lib32",
+    "/lib64",
+    "/etc",
+    "/opt",
+    "/nix/store",
+    "/run/current-system",
+];
+
+/// Directories needed to resolve host names, since `/etc/resolv.conf` often links into `/run`.
+const NETWORK_READ_ONLY_PATHS: &[&str] = &["/run/systemd/resolve"];
+
+/// Directories inside worktrees that configure programs Zed runs outside of the sandbox, such as
+/// git hooks, language servers and tasks, so they are mounted read-only.
+const PROTECTED_WORKTREE_DIRS: &[&str] = &[".git", ".zed", ".vscode"];
+
+/// Directories outside of the worktrees that sandboxed commands may write to.
+const WRITABLE_SYSTEM_PATHS: &[&str] = &["/tmp", "/dev"];
+
+/// Errors printed by commands that tried to write outside of the sandbox.
+const FILESYSTEM_VIOLATIONS: &[&str] = &[
+    "Read-only file system",
+    "Permission denied",
+    "Operation not permitted",
+];
+
+/// Errors printed by commands that tried to use the network without network access.
+const NETWORK_VIOLATIONS: &[&str] = &[
+    "Network is unreachable",
+    "Temporary failure in name resolution",
+    "Could not resolve host",
+];
+
+/// Confines the commands of the terminal tool to the project's worktrees using bubblewrap.
+///
+/// The worktrees are writable, the system directories and the configured read-only paths can
+/// only be read, and everything else (such as the home directory) is hidden from the command.
+#[derive(Debug)]
+pub struct TerminalSandbox {
+    writable_paths: Vec<PathBuf>,
+    read_only_paths: Vec<PathBuf>,
+    allow_network: bool,
+}
+
+impl TerminalSandbox {
+    /// Returns the sandbox to run terminal commands in, or `None` if it's disabled in settings.
+    pub fn new(
+        settings: &TerminalSandboxSettings,
+        project: &Project,
+        cx: &App,
+    ) -> Result<Option<Self>> {
+        if !settings.enabled {
+            return Ok(None);
+        }
+        if !cfg!(target_os = "linux") || !project.is_local() {
+            bail!(
+                "The terminal sandbox is enabled, but it is only supported for local projects on Linux."
+            );
+        }
+        Ok(Some(Self {
+            writable_paths: project
+                .visible_worktrees(cx)
+                .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+                .collect(),
+            read_only_paths: settings.read_only_paths.clone(),
+            allow_network: settings.allow_network,
+        }))
+    }
+
+    /// Wraps a shell command so that `bwrap` runs it inside the sandbox.
+    pub fn wrap_command(&self, command: &str, cwd: Option<&Path>) -> Result<String> {
+        let protected_paths = self.protected_paths().collect::<Vec<_>>();
+        let mut args = vec![
+            "bwrap",
+            "--die-with-parent",
+            "--new-session",
+            "--unshare-user",
+            "--unshare-pid",
+            "--unshare-ipc",
+            "--unshare-uts",
+            "--unshare-cgroup-try",
+        ];
+        if !self.allow_network {
+            args.push("--unshare-net");
+        }
+
+        let network_paths = if self.allow_network {
+            NETWORK_READ_ONLY_PATHS
+        } else {
+            &[]
+        };
+        let read_only_paths = SYSTEM_READ_ONLY_PATHS
+            .iter()
+            .chain(network_paths)
+            .copied()
+            .map(Path::new)
+            .chain(self.read_only_paths.iter().map(PathBuf::as_path));
+        for path in read_only_paths {
+            let path = path_arg(path)?;
+            args.extend(["--ro-bind-try", path, path]);
+        }
+        args.extend(["--proc", "/proc", "--dev", "/dev", "--tmpfs", "/tmp"]);
+        // Bind the worktrees last, so that they stay writable even when they're inside one of
+        // the directories above.
+        for path in &self.writable_paths {
+            let path = path_arg(path)?;
+            args.extend(["--bind", path, path]);
+        }
+        for path in &protected_paths {
+            let path = path_arg(path)?;
+            args.extend(["--ro-bind-try", path, path]);
+        }
+        if let Some(cwd) = cwd {
+            args.extend(["--chdir", path_arg(cwd)?]);
+        }
+        args.extend(["--", "/bin/sh", "-c", command]);
+
+        let args = args
+            .into_iter()
+            .map(|arg| {
+                ShellKind::Posix
+                    .try_quote(arg)
+                    .with_context(|| format!("failed to quote {arg:?} for the terminal sandbox"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(args.join(" "))
+    }
+
+    /// Explains why a failed command may have been blocked by the sandbox, based on its output.
+    ///
+    /// Filesystem errors only count when they name a path the sandbox doesn't let the command
+    /// write to, so that unrelated permission errors inside the worktrees aren't reported.
+    pub fn violation(&self, command: &str, output: &str, cwd: Option<&Path>) -> Option<String> {
+        for line in output.lines() {
+            if line.ends_with("bwrap: not found") || line.ends_with("bwrap: command not found") {
+                return Some(
+                    "The terminal sandbox is enabled, but bubblewrap (`bwrap`) is not installed."
+                        .to_string(),
+                );
+            }
+            if let Some(error) = line.strip_prefix("bwrap: ") {
+                return Some(format!("The terminal sandbox failed to start: {error}"));
+            }
+        }
+
+        let blocked_network = !self.allow_network
+            && NETWORK_VIOLATIONS
+                .iter()
+                .any(|error| output.contains(error));
+        let blocked_filesystem = output
+            .lines()
+            .filter(|line| {
+                FILESYSTEM_VIOLATIONS
+                    .iter()
+                    .any(|error| line.contains(error))
+            })
+            .flat_map(|line| mentioned_paths(line, cwd))
+            .any(|path| !self.is_writable(&path));
+        let restrictions = match (blocked_filesystem, blocked_network) {
+            (_, true) => "has no network access",
+            (true, false) => {
+                "can only write inside the project's worktrees, and not to their .git, .zed or .vscode directories"
+            }
+            (false, false) => return None,
+        };
+        Some(format!(
+            "Command \"{command}\" was likely blocked by the terminal sandbox, which {restrictions}. \
+            Don't try to work around the sandbox; ask the user to run the command or to allow it in the sandbox settings instead."
+        ))
+    }
+
+    fn protected_paths(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.writable_paths
+            .iter()
+            .flat_map(|worktree| PROTECTED_WORKTREE_DIRS.iter().map(|dir| worktree.join(dir)))
+    }
+
+    fn is_writable(&self, path: &Path) -> bool {
+        let Ok(path) = util::paths::normalize_lexically(path) else {
+            return false;
+        };
+        if WRITABLE_SYSTEM_PATHS
+            .iter()
+            .any(|writable| path.starts_with(writable))
+        {
+            return true;
+        }
+        self.writable_paths.iter().any(|worktree| {
+            path.starts_with(worktree)
+                && !PROTECTED_WORKTREE_DIRS
+                    .iter()
+                    .any(|dir| path.starts_with(worktree.join(dir)))
+        })
+    }
+}
+
+/// Returns the paths mentioned in an error line, resolving relative paths against `cwd`.
+fn mentioned_paths(line: &str, cwd: Option<&Path>) -> impl Iterator<Item = PathBuf> {
+    line.split(|c: char| {
+        c.is_whitespace() || matches!(c, '\'' | '"' | '`' | '‘' | '’' | ':' | ',' | '(' | ')')
+    })
+    .filter(|token| token.contains('/') || PROTECTED_WORKTREE_DIRS.contains(token))
+    .filter_map(move |token| {
+        let path = Path::new(token);
+        if path.is_absolute() {
+            Some(path.to_path_buf())
+        } else {
+            cwd.map(|cwd| cwd.join(path))
+        }
+    })
+}
+
+fn path_arg(path: &Path) -> Result<&str> {
+    path.to_str()
+        .with_context(|| format!("path {path:?} is not valid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(allow_network: bool) -> TerminalSandbox {
+        TerminalSandbox {
+            writable_paths: vec![PathBuf::from("/home/zed/my project")],
+            read_only_paths: vec![PathBuf::from("/home/zed/.cargo")],
+            allow_network,
+        }
+    }
+
+    #[test]
+    fn test_wrap_command() {
+        let command = sandbox(false)
+            .wrap_command(
+                "cargo test && echo 'done'",
+                Some(Path::new("/home/zed/my project")),
+            )
+            .unwrap();
+        let args = ShellKind::Posix.split(&command).unwrap();
+
+        assert_eq!(args[0], "bwrap");
+        assert!(args.contains(&"--unshare-net".to_string()));
+        assert!(
+            args.windows(3)
+                .any(|w| w == ["--ro-bind-try", "/home/zed/.cargo", "/home/zed/.cargo"])
+        );
+        assert!(
+            args.windows(3)
+                .any(|w| w == ["--bind", "/home/zed/my project", "/home/zed/my project"])
+        );
+        assert!(
+            args.windows(2)
+                .any(|w| w == ["--chdir", "/home/zed/my project"])
+        );
+        assert_eq!(
+            args[args.len() - 4..],
+            ["--", "/bin/sh", "-c", "cargo test && echo 'done'"]
+        );
+
+        let command = sandbox(true).wrap_command("ls", None).unwrap();
+        let args = ShellKind::Posix.split(&command).unwrap();
+        assert!(!args.contains(&"--unshare-net".to_string()));
+        assert!(!args.contains(&"--chdir".to_string()));
+    }
+
+    #[test]
+    fn test_wrap_command_protects_worktree_config() {
+        let command = sandbox(false).wrap_command("ls", None).unwrap();
+        let args = ShellKind::Posix.split(&command).unwrap();
+
+        let bind = args
+            .windows(3)
+            .position(|w| w == ["--bind", "/home/zed/my project", "/home/zed/my project"])
+            .unwrap();
+        for dir in [".git", ".zed", ".vscode"] {
+            let path = format!("/home/zed/my project/{dir}");
+            let read_only_bind = args
+                .windows(3)
+                .position(|w| w == ["--ro-bind-try", path.as_str(), path.as_str()])
+                .unwrap();
+            assert!(read_only_bind > bind, "{dir} is bound before the worktree");
+        }
+    }
+
+    #[test]
+    fn test_violation() {
+        let sandbox = sandbox(false);
+        let cwd = Some(Path::new("/home/zed/my project"));
+
+        assert_eq!(sandbox.violation("ls", "Cargo.toml\nsrc\n", cwd), None);
+        assert_eq!(
+            sandbox.violation("ls", "sh: 1: bwrap: not found", cwd),
+            Some(
+                "The terminal sandbox is enabled, but bubblewrap (`bwrap`) is not installed."
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            sandbox.violation("ls", "bwrap: setting up uid map: Permission denied", cwd),
+            Some(
+                "The terminal sandbox failed to start: setting up uid map: Permission denied"
+                    .to_string()
+            )
+        );
+
+        let violation = sandbox
+            .violation(
+                "touch ~/.bashrc",
+                "touch: cannot touch '/home/zed/.bashrc': Read-only file system",
+                cwd,
+            )
+            .unwrap();
+        assert!(violation.contains("can only write inside the project's worktrees"));
+        assert!(
+            sandbox
+                .violation(
+                    "echo > .git/hooks/pre-commit",
+                    "sh: 1: cannot create .git/hooks/pre-commit: Read-only file system",
+                    cwd,
+                )
+                .is_some()
+        );
+
+        // Permission errors that don't name a path outside of the worktrees aren't the sandbox's doing.
+        assert_eq!(
+            sandbox.violation("./build.sh", "sh: 1: ./build.sh: Permission denied", cwd),
+            None
+        );
+        assert_eq!(
+            sandbox.violation(
+                "cat /tmp/secret",
+                "cat: /tmp/secret: Permission denied",
+                cwd
+            ),
+            None
+        );
+        assert_eq!(
+            sandbox.violation(
+                "cargo run",
+                "Error: Os { code: 1, kind: PermissionDenied, message: \"Operation not permitted\" }",
+                cwd
+            ),
+            None
+        );
+
+        let violation = sandbox
+            .violation(
+                "curl https://zed.dev",
+                "curl: (6) Could not resolve host: zed.dev",
+                cwd,
+            )
+            .unwrap();
+        assert!(violation.contains("has no network access"));
+        let with_network = self::sandbox(true);
+        assert_eq!(
+            with_network.violation(
+                "curl https://zed.dev",
+                "curl: (6) Could not resolve host: zed.dev",
+                cwd
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_run_in_sandbox() {
+        let bwrap_works = std::process::Command::new("bwrap")
+            .args(["--ro-bind", "/", "/", "true"])
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if !bwrap_works {
+            eprintln!("skipping test_run_in_sandbox, because bwrap is not available");
+            return;
+        }
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let worktree = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir(worktree.join(".git")).unwrap();
+        let sandbox = TerminalSandbox {
+            writable_paths: vec![worktree.clone()],
+            read_only_paths: Vec::new(),
+            allow_network: false,
+        };
+        let run = |command: &str| {
+            let wrapped = sandbox.wrap_command(command, Some(&worktree)).unwrap();
+            let output = std::process::Command::new("/bin/sh")
+                .arg("-c")
+                .arg(wrapped)
+                .output()
+                .unwrap();
+            let text = format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            (output.status.success(), text)
+        };
+
+        let (succeeded, output) = run("echo hello > hello.txt");
+        assert!(succeeded, "{output}");
+        assert_eq!(
+            std::fs::read_to_string(worktree.join("hello.txt")).unwrap(),
+            "hello\n"
+        );
+
+        for command in ["echo '[core]' > .git/config", "touch /etc/zed-sandbox-test"] {
+            let (succeeded, output) = run(command);
+            assert!(!succeeded, "{command} succeeded inside the sandbox");
+            assert!(
+                sandbox
+                    .violation(command, &output, Some(&worktree))
+                    .is_some(),
+                "no violation reported for {command}: {output}"
+            );
+        }
+        assert!(!worktree.join(".git/config").exists());
+    }
+}
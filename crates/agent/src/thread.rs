@@ -216,6 +216,7 @@ impl UserMessage {
         const OPEN_RULES_TAG: &str =
             "<rules>\nThe user has specified the following rules that should be applied:\n";
         const OPEN_DIAGNOSTICS_TAG: &str = "<diagnostics>";
+        const OPEN_MCP_RESOURCES_TAG: &str = "<mcp_resources>";
 
         let mut file_context = OPEN_FILES_TAG.to_string();
         let mut directory_context = OPEN_DIRECTORIES_TAG.to_string();
@@ -225,6 +226,7 @@ impl UserMessage {
         let mut fetch_context = OPEN_FETCH_TAG.to_string();
         let mut rules_context = OPEN_RULES_TAG.to_string();
         let mut diagnostics_context = OPEN_DIAGNOSTICS_TAG.to_string();
+        let mut mcp_resources_context = OPEN_MCP_RESOURCES_TAG.to_string();
 
         for chunk in &self.content {
             let chunk = match chunk {
@@ -320,6 +322,14 @@ impl UserMessage {
                             )
                             .ok();
                         }
+                        MentionUri::McpResource { server_id, uri, .. } => {
+                            write!(
+                                &mut mcp_resources_context,
+                                "\nResource: {} (from {})\n\n{}",
+                                uri, server_id, content
+                            )
+                            .ok();
+                        }
                     }
 
                     language_model::MessageContent::Text(uri.as_link().to_string())
@@ -387,6 +397,13 @@ impl UserMessage {
                 .push(language_model::MessageContent::Text(diagnostics_context));
         }
 
+        if mcp_resources_context.len() > OPEN_MCP_RESOURCES_TAG.len() {
+            mcp_resources_context.push_str("</mcp_resources>\n");
+            message
+                .content
+                .push(language_model::MessageContent::Text(mcp_resources_context));
+        }
+
         if message.content.len() > len_before_context {
             message.content.insert(
                 len_before_context,
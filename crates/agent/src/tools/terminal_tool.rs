@@ -16,7 +16,7 @@ use std::{
 
 use crate::{
     AgentTool, ThreadEnvironment, ToolCallEventStream, ToolPermissionDecision,
-    decide_permission_from_settings,
+    decide_permission_from_settings, terminal_sandbox::TerminalSandbox,
 };
 
 const COMMAND_OUTPUT_LIMIT: u64 = 16 * 1024;
@@ -100,6 +100,11 @@ impl AgentTool for TerminalTool {
             std::slice::from_ref(&input.command),
             settings,
         );
+        let sandbox =
+            match TerminalSandbox::new(&settings.terminal_sandbox, self.project.read(cx), cx) {
+                Ok(sandbox) => sandbox,
+                Err(err) => return Task::ready(Err(err)),
+            };
 
         let authorize = match decision {
             ToolPermissionDecision::Allow => None,
@@ -119,14 +124,13 @@ impl AgentTool for TerminalTool {
                 authorize.await?;
             }
 
+            let command = match &sandbox {
+                Some(sandbox) => sandbox.wrap_command(&input.command, working_dir.as_deref())?,
+                None => input.command.clone(),
+            };
             let terminal = self
                 .environment
-                .create_terminal(
-                    input.command.clone(),
-                    working_dir,
-                    Some(COMMAND_OUTPUT_LIMIT),
-                    cx,
-                )
+                .create_terminal(command, working_dir.clone(), Some(COMMAND_OUTPUT_LIMIT), cx)
                 .await?;
 
             let terminal_id = terminal.id(cx)?;
@@ -182,6 +186,23 @@ impl AgentTool for TerminalTool {
 
             let output = terminal.current_output(cx)?;
 
+            let failed = output
+                .exit_status
+                .as_ref()
+                .is_none_or(|status| status.exit_code != Some(0));
+            if let Some(sandbox) = &sandbox
+                && failed
+                && !timed_out
+                && !user_stopped
+                && let Some(violation) =
+                    sandbox.violation(&input.command, &output.output, working_dir.as_deref())
+            {
+                anyhow::bail!(
+                    "{violation}\n\n{}",
+                    process_content(output, &input.command, false, false)
+                );
+            }
+
             Ok(process_content(
                 output,
                 &input.command,
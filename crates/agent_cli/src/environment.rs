@@ -0,0 +1,92 @@
+use agent_client_protocol as acp;
+use anyhow::{Result, anyhow};
+use futures::future::Shared;
+use gpui::{App, AppContext as _, AsyncApp, Entity, Task};
+use project::Project;
+use std::{path::PathBuf, rc::Rc, time::Duration};
+
+/// Runs terminal commands in the project without a UI, and refuses to spawn subagents.
+pub struct HeadlessThreadEnvironment {
+    pub project: Entity<Project>,
+}
+
+struct HeadlessTerminalHandle {
+    terminal: Entity<acp_thread::Terminal>,
+}
+
+impl agent::TerminalHandle for HeadlessTerminalHandle {
+    fn id(&self, cx: &AsyncApp) -> Result<acp::TerminalId> {
+        Ok(self.terminal.read_with(cx, |term, _cx| term.id().clone()))
+    }
+
+    fn wait_for_exit(&self, cx: &AsyncApp) -> Result<Shared<Task<acp::TerminalExitStatus>>> {
+        Ok(self
+            .terminal
+            .read_with(cx, |term, _cx| term.wait_for_exit()))
+    }
+
+    fn current_output(&self, cx: &AsyncApp) -> Result<acp::TerminalOutputResponse> {
+        Ok(self
+            .terminal
+            .read_with(cx, |term, cx| term.current_output(cx)))
+    }
+
+    fn kill(&self, cx: &AsyncApp) -> Result<()> {
+        cx.update(|cx| {
+            self.terminal.update(cx, |terminal, cx| {
+                terminal.kill(cx);
+            });
+        });
+        Ok(())
+    }
+
+    fn was_stopped_by_user(&self, cx: &AsyncApp) -> Result<bool> {
+        Ok(self
+            .terminal
+            .read_with(cx, |term, _cx| term.was_stopped_by_user()))
+    }
+}
+
+impl agent::ThreadEnvironment for HeadlessThreadEnvironment {
+    fn create_terminal(
+        &self,
+        command: String,
+        cwd: Option<PathBuf>,
+        output_byte_limit: Option<u64>,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<Rc<dyn agent::TerminalHandle>>> {
+        let project = self.project.clone();
+        cx.spawn(async move |cx| {
+            let language_registry =
+                project.read_with(cx, |project, _cx| project.languages().clone());
+            let id = acp::TerminalId::new(uuid::Uuid::new_v4().to_string());
+            let terminal =
+                acp_thread::create_terminal_entity(command, &[], vec![], cwd.clone(), &project, cx)
+                    .await?;
+            let terminal = cx.new(|cx| {
+                acp_thread::Terminal::new(
+                    id,
+                    "",
+                    cwd,
+                    output_byte_limit.map(|limit| limit as usize),
+                    terminal,
+                    language_registry,
+                    cx,
+                )
+            });
+            Ok(Rc::new(HeadlessTerminalHandle { terminal }) as Rc<dyn agent::TerminalHandle>)
+        })
+    }
+
+    fn create_subagent(
+        &self,
+        _parent_thread: Entity<agent::Thread>,
+        _label: String,
+        _initial_prompt: String,
+        _timeout_ms: Option<Duration>,
+        _allowed_tools: Option<Vec<String>>,
+        _cx: &mut App,
+    ) -> Result<Rc<dyn agent::SubagentHandle>> {
+        Err(anyhow!("subagents are not supported by the headless agent"))
+    }
+}
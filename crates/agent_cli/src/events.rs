@@ -0,0 +1,112 @@
+use agent_client_protocol as acp;
+use gpui::App;
+use language::{Point, ToPoint as _};
+use serde::Serialize;
+use std::io::Write as _;
+use std::ops::Range;
+
+/// A line of the JSONL stream written to stdout.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event<'a> {
+    Text {
+        text: &'a str,
+    },
+    Thinking {
+        text: &'a str,
+    },
+    ToolCall {
+        tool_call: &'a acp::ToolCall,
+    },
+    ToolCallUpdate {
+        update: &'a acp::ToolCallUpdate,
+    },
+    /// A tool asked for confirmation, and was allowed or denied based on `--allow-tool`.
+    Permission {
+        tool_call_id: &'a acp::ToolCallId,
+        tool_name: Option<&'a str>,
+        title: Option<&'a str>,
+        allowed: bool,
+    },
+    Retry {
+        attempt: usize,
+        max_attempts: usize,
+        error: &'a str,
+    },
+    Stop {
+        reason: &'a acp::StopReason,
+    },
+    Error {
+        message: String,
+    },
+    Summary(&'a DiffSummary),
+}
+
+impl Event<'_> {
+    pub fn emit(&self) {
+        let mut stdout = std::io::stdout().lock();
+        match serde_json::to_string(self) {
+            Ok(line) => {
+                writeln!(stdout, "{line}").ok();
+                stdout.flush().ok();
+            }
+            Err(err) => log::error!("failed to serialize event: {err}"),
+        }
+    }
+}
+
+#[derive(Default, Serialize)]
+pub struct DiffSummary {
+    pub files: Vec<FileDiffSummary>,
+    pub lines_added: u32,
+    pub lines_removed: u32,
+}
+
+#[derive(Serialize)]
+pub struct FileDiffSummary {
+    pub path: String,
+    pub lines_added: u32,
+    pub lines_removed: u32,
+}
+
+impl DiffSummary {
+    /// Summarizes the edits the agent made, as tracked by the thread's action log.
+    pub fn new(thread: &agent::Thread, cx: &App) -> Self {
+        let mut summary = DiffSummary::default();
+        for (buffer, diff) in thread.action_log().read(cx).changed_buffers(cx) {
+            let buffer = buffer.read(cx);
+            let Some(file) = buffer.file() else {
+                continue;
+            };
+            let buffer_snapshot = buffer.snapshot();
+            let diff_snapshot = diff.read(cx).snapshot(cx);
+            let base_text = diff_snapshot.base_text();
+
+            let mut file_summary = FileDiffSummary {
+                path: file.path().as_unix_str().to_string(),
+                lines_added: 0,
+                lines_removed: 0,
+            };
+            for hunk in diff_snapshot.hunks(&buffer_snapshot) {
+                file_summary.lines_added += line_count(hunk.range);
+                file_summary.lines_removed += line_count(
+                    hunk.diff_base_byte_range.start.to_point(base_text)
+                        ..hunk.diff_base_byte_range.end.to_point(base_text),
+                );
+            }
+
+            summary.lines_added += file_summary.lines_added;
+            summary.lines_removed += file_summary.lines_removed;
+            summary.files.push(file_summary);
+        }
+        summary
+    }
+}
+
+/// Counts the lines a hunk spans, including a last line without a trailing newline.
+fn line_count(range: Range<Point>) -> u32 {
+    if range.is_empty() {
+        return 0;
+    }
+    range.end.row - range.start.row + u32::from(range.end.column > 0)
+}
@@ -0,0 +1,23 @@
+use agent_headless::HeadlessAgentAppState;
+use anyhow::{Context as _, Result};
+use gpui::App;
+use std::path::Path;
+use std::sync::Arc;
+
+pub fn init(settings_path: Option<&Path>, cx: &mut App) -> Result<Arc<HeadlessAgentAppState>> {
+    let user_settings = read_user_settings(settings_path)?;
+    agent_headless::init("Zed Agent CLI", user_settings.as_deref(), cx)
+}
+
+/// Reads the user settings once at startup, from `settings_path` if given or from the user's
+/// settings file otherwise. Unlike the editor, the CLI doesn't watch the file for changes.
+fn read_user_settings(settings_path: Option<&Path>) -> Result<Option<String>> {
+    let path = settings_path.unwrap_or(paths::settings_file().as_path());
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if settings_path.is_none() && err.kind() == std::io::ErrorKind::NotFound => {
+            Ok(None)
+        }
+        Err(err) => Err(err).with_context(|| format!("reading settings from {}", path.display())),
+    }
+}
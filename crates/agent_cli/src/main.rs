@@ -0,0 +1,538 @@
+mod environment;
+mod events;
+mod headless;
+
+use acp_thread::UserMessageId;
+use agent::{
+    ContextServerRegistry, NativeAgent, Templates, Thread, ThreadEvent, ToolCallAuthorization,
+};
+use agent_client_protocol as acp;
+use agent_headless::HeadlessAgentAppState;
+use agent_settings::{AgentProfileId, AgentSettings};
+use anyhow::{Context as _, Result, anyhow};
+use clap::Parser;
+use collections::HashMap;
+use environment::HeadlessThreadEnvironment;
+use events::{DiffSummary, Event};
+use futures::{StreamExt as _, future};
+use gpui::{App, AppContext as _, Application, AsyncApp, Entity};
+use language_model::{LanguageModel, LanguageModelRegistry, SelectedModel};
+use project::Project;
+use reqwest_client::ReqwestClient;
+use settings::Settings as _;
+use std::io::Read as _;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::str::FromStr as _;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// Runs the Zed agent against a project without a UI, streaming its events to stdout as JSONL.
+///
+/// Tools that need confirmation under the `agent.tool_permissions` settings are denied unless
+/// allowed with `--allow-tool`. The last line is a summary of the edits made by the agent.
+#[derive(Parser, Debug)]
+#[command(name = "zed-agent", disable_version_flag = true)]
+struct Args {
+    /// Path to the project the agent works in.
+    #[arg(default_value = ".")]
+    project: PathBuf,
+    /// Prompt to send to the agent. Read from stdin if omitted.
+    #[arg(long, short)]
+    prompt: Option<String>,
+    /// provider/model to use for the agent. Defaults to `agent.default_model` from settings.
+    #[arg(long)]
+    model: Option<String>,
+    /// Agent profile to use, such as `write` or `ask`. Defaults to `agent.default_profile` from settings.
+    #[arg(long)]
+    profile: Option<String>,
+    /// Name of a tool that may run when its permissions require confirmation. Can be repeated.
+    #[arg(long = "allow-tool", value_name = "TOOL")]
+    allowed_tools: Vec<String>,
+    /// Settings file to use instead of the user's settings file.
+    #[arg(long)]
+    settings: Option<PathBuf>,
+    /// Output current environment variables as JSON to stdout
+    #[arg(long, hide = true)]
+    printenv: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    // This prevents errors showing up in the logs, because
+    // project::environment::load_shell_environment() calls
+    // std::env::current_exe().unwrap() --printenv
+    if args.printenv {
+        util::shell_env::print_env();
+        return;
+    }
+
+    env_logger::init();
+
+    let prompt = match args.prompt.clone() {
+        Some(prompt) => prompt,
+        None => {
+            let mut prompt = String::new();
+            if let Err(err) = std::io::stdin().read_to_string(&mut prompt) {
+                eprintln!("Failed to read the prompt from stdin: {err}");
+                std::process::exit(1);
+            }
+            prompt
+        }
+    };
+    if prompt.trim().is_empty() {
+        eprintln!("The prompt is empty");
+        std::process::exit(1);
+    }
+
+    let exit_code = Arc::new(AtomicI32::new(0));
+    let http_client = Arc::new(ReqwestClient::new());
+    let app = Application::headless().with_http_client(http_client);
+
+    app.run({
+        let exit_code = exit_code.clone();
+        move |cx| {
+            let app_state = match headless::init(args.settings.as_deref(), cx) {
+                Ok(app_state) => app_state,
+                Err(err) => {
+                    Event::Error {
+                        message: format!("{err:#}"),
+                    }
+                    .emit();
+                    exit_code.store(1, Ordering::SeqCst);
+                    cx.quit();
+                    return;
+                }
+            };
+
+            cx.spawn(async move |cx| {
+                let succeeded = match run(args, prompt, app_state, cx).await {
+                    Ok(succeeded) => succeeded,
+                    Err(err) => {
+                        Event::Error {
+                            message: format!("{err:#}"),
+                        }
+                        .emit();
+                        false
+                    }
+                };
+                if !succeeded {
+                    exit_code.store(1, Ordering::SeqCst);
+                }
+                cx.update(|cx| cx.quit());
+            })
+            .detach();
+        }
+    });
+
+    std::process::exit(exit_code.load(Ordering::SeqCst));
+}
+
+/// Runs a single turn of the agent, returning whether it ended its turn without errors.
+async fn run(
+    args: Args,
+    prompt: String,
+    app_state: Arc<HeadlessAgentAppState>,
+    cx: &mut AsyncApp,
+) -> Result<bool> {
+    let project_path = args
+        .project
+        .canonicalize()
+        .with_context(|| format!("opening project at {}", args.project.display()))?;
+
+    let authenticate = cx.update(|cx| {
+        LanguageModelRegistry::global(cx).update(cx, |registry, cx| {
+            registry
+                .providers()
+                .iter()
+                .map(|provider| provider.authenticate(cx))
+                .collect::<Vec<_>>()
+        })
+    });
+    future::join_all(authenticate).await;
+
+    let project = cx.update(|cx| {
+        Project::local(
+            app_state.client.clone(),
+            app_state.node_runtime.clone(),
+            app_state.user_store.clone(),
+            app_state.languages.clone(),
+            app_state.fs.clone(),
+            None,
+            project::LocalProjectFlags {
+                init_worktree_trust: false,
+                ..Default::default()
+            },
+            cx,
+        )
+    });
+    let worktree = project
+        .update(cx, |project, cx| {
+            project.create_worktree(project_path, true, cx)
+        })
+        .await?;
+    worktree
+        .update(cx, |worktree, _cx| {
+            worktree.as_local().unwrap().scan_complete()
+        })
+        .await;
+
+    let project_context = cx
+        .update(|cx| NativeAgent::build_project_context(&project, None, cx))
+        .await;
+
+    let thread = cx.update(|cx| -> Result<Entity<Thread>> {
+        let model = resolve_model(args.model.as_deref(), cx)?;
+        let settings = AgentSettings::get_global(cx);
+        let profile_id = args.profile.as_deref().map_or_else(
+            || settings.default_profile.clone(),
+            |profile| AgentProfileId(profile.into()),
+        );
+        anyhow::ensure!(
+            settings.profiles.contains_key(&profile_id),
+            "No agent profile with ID {profile_id}. Available profiles: {}",
+            settings
+                .profiles
+                .keys()
+                .map(|id| id.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let project_context = cx.new(|_cx| project_context);
+        let context_server_registry =
+            cx.new(|cx| ContextServerRegistry::new(project.read(cx).context_server_store(), cx));
+        let thread = cx.new(|cx| {
+            Thread::new(
+                project.clone(),
+                project_context,
+                context_server_registry,
+                Templates::new(),
+                Some(model),
+                cx,
+            )
+        });
+        thread.update(cx, |thread, cx| {
+            thread.add_default_tools(
+                None,
+                Rc::new(HeadlessThreadEnvironment {
+                    project: project.clone(),
+                }),
+                cx,
+            );
+            thread.set_profile(profile_id, cx);
+        });
+        Ok(thread)
+    })?;
+
+    run_turn(&thread, prompt, &args.allowed_tools, cx).await
+}
+
+/// Sends the prompt to the thread, streams the events of the agent's turn and then a summary of its edits,
+/// returning whether the turn succeeded.
+async fn run_turn(
+    thread: &Entity<Thread>,
+    prompt: String,
+    allowed_tools: &[String],
+    cx: &mut AsyncApp,
+) -> Result<bool> {
+    let mut events = thread.update(cx, |thread, cx| {
+        thread.send(UserMessageId::new(), [prompt], cx)
+    })?;
+    let mut tool_names = HashMap::default();
+    let mut stop_reason = None;
+    let mut failed = false;
+    while let Some(event) = events.next().await {
+        let event = match event {
+            Ok(event) => event,
+            Err(err) => {
+                Event::Error {
+                    message: format!("{err:#}"),
+                }
+                .emit();
+                failed = true;
+                break;
+            }
+        };
+        match event {
+            ThreadEvent::AgentText(text) => Event::Text { text: &text }.emit(),
+            ThreadEvent::AgentThinking(text) => Event::Thinking { text: &text }.emit(),
+            ThreadEvent::ToolCall(tool_call) => {
+                if let Some(tool_name) = acp_thread::tool_name_from_meta(&tool_call.meta) {
+                    tool_names.insert(tool_call.tool_call_id.clone(), tool_name.to_string());
+                }
+                Event::ToolCall {
+                    tool_call: &tool_call,
+                }
+                .emit();
+            }
+            ThreadEvent::ToolCallUpdate(acp_thread::ToolCallUpdate::UpdateFields(update)) => {
+                Event::ToolCallUpdate { update: &update }.emit()
+            }
+            ThreadEvent::ToolCallAuthorization(authorization) => {
+                authorize_tool_call(authorization, &tool_names, allowed_tools);
+            }
+            ThreadEvent::Retry(status) => Event::Retry {
+                attempt: status.attempt,
+                max_attempts: status.max_attempts,
+                error: &status.last_error,
+            }
+            .emit(),
+            ThreadEvent::Stop(reason) => {
+                Event::Stop { reason: &reason }.emit();
+                stop_reason = Some(reason);
+            }
+            ThreadEvent::UserMessage(_)
+            | ThreadEvent::ToolCallUpdate(_)
+            | ThreadEvent::SubagentSpawned(_) => {}
+        }
+    }
+
+    let summary = thread.read_with(cx, |thread, cx| DiffSummary::new(thread, cx));
+    Event::Summary(&summary).emit();
+
+    Ok(turn_succeeded(failed, stop_reason.as_ref()))
+}
+
+/// Answers a tool's request for confirmation, allowing the tool call only if the tool was allowed
+/// with `--allow-tool`. Returns whether the tool call was allowed.
+fn authorize_tool_call(
+    authorization: ToolCallAuthorization,
+    tool_names: &HashMap<acp::ToolCallId, String>,
+    allowed_tools: &[String],
+) -> bool {
+    let tool_name = authorization
+        .context
+        .map(|context| context.tool_name)
+        .or_else(|| {
+            tool_names
+                .get(&authorization.tool_call.tool_call_id)
+                .cloned()
+        });
+    let allowed = tool_name
+        .as_ref()
+        .is_some_and(|tool_name| allowed_tools.contains(tool_name));
+    Event::Permission {
+        tool_call_id: &authorization.tool_call.tool_call_id,
+        tool_name: tool_name.as_deref(),
+        title: authorization.tool_call.fields.title.as_deref(),
+        allowed,
+    }
+    .emit();
+
+    let option_id = if allowed {
+        authorization.options.allow_once_option_id()
+    } else {
+        authorization.options.deny_once_option_id()
+    };
+    // Dropping the response without choosing an option also denies the tool call.
+    if let Some(option_id) = option_id {
+        authorization.response.send(option_id).ok();
+    }
+    allowed
+}
+
+/// A turn succeeds when the agent ended it without errors, rather than e.g. refusing the prompt
+/// or running out of tokens.
+fn turn_succeeded(failed: bool, stop_reason: Option<&acp::StopReason>) -> bool {
+    !failed && matches!(stop_reason, Some(acp::StopReason::EndTurn))
+}
+
+fn resolve_model(model_name: Option<&str>, cx: &App) -> Result<Arc<dyn LanguageModel>> {
+    let selected = match model_name {
+        Some(model_name) => SelectedModel::from_str(model_name).map_err(|err| anyhow!(err))?,
+        None => {
+            let selection = AgentSettings::get_global(cx)
+                .default_model
+                .as_ref()
+                .context("No model was given with --model, and `agent.default_model` is not set")?;
+            SelectedModel {
+                provider: selection.provider.0.clone().into(),
+                model: selection.model.clone().into(),
+            }
+        }
+    };
+
+    let registry = LanguageModelRegistry::read_global(cx);
+    registry
+        .available_models(cx)
+        .find(|model| model.id() == selected.model && model.provider_id() == selected.provider)
+        .with_context(|| {
+            format!(
+                "No language model with ID {}/{} was available. Available models: {}",
+                selected.provider.0,
+                selected.model.0,
+                registry
+                    .available_models(cx)
+                    .map(|model| format!("{}/{}", model.provider_id().0, model.id().0))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use futures::channel::oneshot;
+    use gpui::TestAppContext;
+    use language::Point;
+    use language_model::{
+        LanguageModelCompletionEvent, StopReason, fake_provider::FakeLanguageModel,
+    };
+    use prompt_store::ProjectContext;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[test]
+    fn test_authorize_tool_call() {
+        let authorize = |tool_name: Option<&str>, allowed_tools: &[&str]| {
+            let (response_tx, mut response_rx) = oneshot::channel();
+            let authorization = ToolCallAuthorization {
+                tool_call: acp::ToolCallUpdate::new(
+                    acp::ToolCallId::new("tool_call"),
+                    acp::ToolCallUpdateFields::new(),
+                ),
+                options: acp_thread::PermissionOptions::Flat(vec![
+                    acp::PermissionOption::new(
+                        acp::PermissionOptionId::new("allow"),
+                        "Allow",
+                        acp::PermissionOptionKind::AllowOnce,
+                    ),
+                    acp::PermissionOption::new(
+                        acp::PermissionOptionId::new("deny"),
+                        "Deny",
+                        acp::PermissionOptionKind::RejectOnce,
+                    ),
+                ]),
+                response: response_tx,
+                context: tool_name
+                    .map(|tool_name| agent::ToolPermissionContext::new(tool_name, Vec::new())),
+            };
+            let allowed_tools = allowed_tools
+                .iter()
+                .map(|tool| tool.to_string())
+                .collect::<Vec<_>>();
+            let allowed = authorize_tool_call(authorization, &HashMap::default(), &allowed_tools);
+            (allowed, response_rx.try_recv().unwrap())
+        };
+
+        assert_eq!(
+            authorize(Some("terminal"), &["terminal"]),
+            (true, Some(acp::PermissionOptionId::new("allow")))
+        );
+        assert_eq!(
+            authorize(Some("terminal"), &["edit_file"]),
+            (false, Some(acp::PermissionOptionId::new("deny")))
+        );
+        assert_eq!(
+            authorize(None, &["terminal"]),
+            (false, Some(acp::PermissionOptionId::new("deny"))),
+            "Tools with an unknown name should be denied"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_run_turn(cx: &mut TestAppContext) {
+        let (thread, model, _project) = setup(cx).await;
+        let fake_model = model.as_fake();
+
+        let turn = cx.spawn({
+            let thread = thread.clone();
+            |mut cx| async move { run_turn(&thread, "Say hello".into(), &[], &mut cx).await }
+        });
+        cx.run_until_parked();
+        fake_model.send_last_completion_stream_text_chunk("Hello");
+        fake_model.send_last_completion_stream_event(LanguageModelCompletionEvent::Stop(
+            StopReason::EndTurn,
+        ));
+        fake_model.end_last_completion_stream();
+        assert!(turn.await.unwrap(), "Ending the turn should succeed");
+
+        let turn = cx.spawn({
+            let thread = thread.clone();
+            |mut cx| async move { run_turn(&thread, "Say hello".into(), &[], &mut cx).await }
+        });
+        cx.run_until_parked();
+        fake_model.send_last_completion_stream_event(LanguageModelCompletionEvent::Stop(
+            StopReason::Refusal,
+        ));
+        fake_model.end_last_completion_stream();
+        assert!(!turn.await.unwrap(), "Refusing the prompt should fail");
+    }
+
+    #[gpui::test]
+    async fn test_diff_summary(cx: &mut TestAppContext) {
+        let (thread, _model, project) = setup(cx).await;
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/src/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let action_log = thread.read_with(cx, |thread, _| thread.action_log().clone());
+
+        cx.update(|cx| {
+            action_log.update(cx, |log, cx| log.buffer_read(buffer.clone(), cx));
+            buffer.update(cx, |buffer, cx| {
+                buffer
+                    .edit(
+                        [
+                            (Point::new(0, 0)..Point::new(1, 0), ""),
+                            (Point::new(2, 0)..Point::new(2, 5), "THREE\nfour"),
+                        ],
+                        None,
+                        cx,
+                    )
+                    .unwrap()
+            });
+            action_log.update(cx, |log, cx| log.buffer_edited(buffer.clone(), cx));
+        });
+        cx.run_until_parked();
+
+        let summary = thread.read_with(cx, |thread, cx| DiffSummary::new(thread, cx));
+        assert_eq!(
+            summary
+                .files
+                .iter()
+                .map(|file| (file.path.as_str(), file.lines_added, file.lines_removed))
+                .collect::<Vec<_>>(),
+            [("src/main.rs", 2, 2)]
+        );
+        assert_eq!((summary.lines_added, summary.lines_removed), (2, 2));
+    }
+
+    async fn setup(
+        cx: &mut TestAppContext,
+    ) -> (Entity<Thread>, Arc<FakeLanguageModel>, Entity<Project>) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({ "src": { "main.rs": "one\ntwo\nthree" } }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let model = Arc::new(FakeLanguageModel::default());
+        let context_server_store =
+            project.read_with(cx, |project, _| project.context_server_store());
+        let thread = cx.new(|cx| {
+            Thread::new(
+                project.clone(),
+                cx.new(|_cx| ProjectContext::default()),
+                cx.new(|cx| ContextServerRegistry::new(context_server_store, cx)),
+                Templates::new(),
+                Some(model.clone()),
+                cx,
+            )
+        });
+        (thread, model, project)
+    }
+}
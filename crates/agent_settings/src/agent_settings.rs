@@ -1,6 +1,6 @@
 mod agent_profile;
 
-use std::path::{Component, Path};
+use std::path::{Component, Path, PathBuf};
 use std::sync::{Arc, LazyLock};
 
 use agent_client_protocol::ModelId;
@@ -51,6 +51,15 @@ pub struct AgentSettings {
     pub message_editor_min_lines: usize,
     pub show_turn_stats: bool,
     pub tool_permissions: ToolPermissions,
+    pub terminal_sandbox: TerminalSandboxSettings,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TerminalSandboxSettings {
+    pub enabled: bool,
+    /// Paths that sandboxed commands may read, with a leading `~` expanded to the home directory.
+    pub read_only_paths: Vec<PathBuf>,
+    pub allow_network: bool,
 }
 
 impl AgentSettings {
@@ -438,10 +447,33 @@ impl Settings for AgentSettings {
             message_editor_min_lines: agent.message_editor_min_lines.unwrap(),
             show_turn_stats: agent.show_turn_stats.unwrap(),
             tool_permissions: compile_tool_permissions(agent.tool_permissions),
+            terminal_sandbox: terminal_sandbox_settings(agent.terminal_sandbox.unwrap()),
         }
     }
 }
 
+fn terminal_sandbox_settings(content: settings::TerminalSandboxContent) -> TerminalSandboxSettings {
+    let read_only_paths = content
+        .read_only_paths
+        .unwrap()
+        .into_iter()
+        .map(|path| {
+            if path == "~" {
+                util::paths::home_dir().clone()
+            } else if let Some(relative_path) = path.strip_prefix("~/") {
+                util::paths::home_dir().join(relative_path)
+            } else {
+                PathBuf::from(path)
+            }
+        })
+        .collect();
+    TerminalSandboxSettings {
+        enabled: content.enabled.unwrap(),
+        read_only_paths,
+        allow_network: content.allow_network.unwrap(),
+    }
+}
+
 fn compile_tool_permissions(content: Option<settings::ToolPermissionsContent>) -> ToolPermissions {
     let Some(content) = content else {
         return ToolPermissions::default();
@@ -7548,6 +7548,11 @@ pub(crate) fn open_link(
             MentionUri::Fetch { url } => {
                 cx.open_url(url.as_str());
             }
+            MentionUri::McpResource { uri, .. } => {
+                if matches!(uri.scheme(), "http" | "https") {
+                    cx.open_url(uri.as_str());
+                }
+            }
             MentionUri::Diagnostics { .. } => {}
             MentionUri::TerminalSelection { .. } => {}
         })
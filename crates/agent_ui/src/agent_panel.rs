@@ -8,6 +8,7 @@ use db::kvp::{Dismissable, KEY_VALUE_STORE};
 use project::{
     ExternalAgentServerName,
     agent_server_store::{CLAUDE_CODE_NAME, CODEX_NAME, GEMINI_NAME},
+    context_server_store::ContextServerRequestEvent,
 };
 use serde::{Deserialize, Serialize};
 use settings::{LanguageModelProviderSetting, LanguageModelSelection};
@@ -23,6 +24,7 @@ use crate::{
     ToggleOptionsMenu,
     acp::AcpServerView,
     agent_configuration::{AgentConfiguration, AssistantConfigurationEvent},
+    context_server_requests::ContextServerRequests,
     slash_command::SlashCommandCompletionProvider,
     text_thread_editor::{AgentPanelDelegate, TextThreadEditor, make_lsp_adapter_delegate},
     ui::{AgentOnboardingModal, EndTrialUpsell},
@@ -468,6 +470,8 @@ pub struct AgentPanel {
     text_thread_store: Entity<assistant_text_thread::TextThreadStore>,
     prompt_store: Option<Entity<PromptStore>>,
     context_server_registry: Entity<ContextServerRegistry>,
+    context_server_requests: Entity<ContextServerRequests>,
+    _context_server_requests_subscription: Subscription,
     configuration: Option<Entity<AgentConfiguration>>,
     configuration_subscription: Option<Subscription>,
     focus_handle: FocusHandle,
@@ -632,8 +636,23 @@ impl AgentPanel {
         let workspace_id = workspace.database_id();
         let workspace = workspace.weak_handle();
 
+        let context_server_store = project.read(cx).context_server_store();
         let context_server_registry =
-            cx.new(|cx| ContextServerRegistry::new(project.read(cx).context_server_store(), cx));
+            cx.new(|cx| ContextServerRegistry::new(context_server_store.clone(), cx));
+        let context_server_requests =
+            cx.new(|cx| ContextServerRequests::new(&context_server_store, window, cx));
+        // Reveal the panel when a context server asks the user to fill in a form.
+        let context_server_requests_subscription = cx.subscribe_in(
+            &context_server_store,
+            window,
+            |this, _, event: &ContextServerRequestEvent, window, cx| {
+                if let ContextServerRequestEvent::Elicitation(_) = event {
+                    this.workspace
+                        .update(cx, |workspace, cx| workspace.open_panel::<Self>(window, cx))
+                        .log_err();
+                }
+            },
+        );
 
         let thread_store = ThreadStore::global(cx);
         let acp_history = cx.new(|cx| AcpThreadHistory::new(None, window, cx));
@@ -748,6 +767,8 @@ impl AgentPanel {
             configuration_subscription: None,
             focus_handle: cx.focus_handle(),
             context_server_registry,
+            context_server_requests,
+            _context_server_requests_subscription: context_server_requests_subscription,
             previous_view: None,
             _active_view_observation: None,
             new_thread_menu_handle: PopoverMenuHandle::default(),
@@ -3132,6 +3153,7 @@ impl Render for AgentPanel {
             }))
             .child(self.render_toolbar(window, cx))
             .children(self.render_workspace_trust_message(cx))
+            .child(self.context_server_requests.clone())
             .children(self.render_onboarding(window, cx))
             .map(|parent| {
                 // Emit configuration error telemetry before entering the match to avoid borrow conflicts
@@ -8,6 +8,7 @@ mod buffer_codegen;
 mod completion_provider;
 mod context;
 mod context_server_configuration;
+mod context_server_requests;
 mod favorite_models;
 mod inline_assistant;
 mod inline_prompt_editor;
@@ -567,6 +568,7 @@ mod tests {
             message_editor_min_lines: 1,
             tool_permissions: Default::default(),
             show_turn_stats: false,
+            terminal_sandbox: Default::default(),
         };
 
         cx.update(|cx| {
@@ -7,12 +7,17 @@ use std::sync::atomic::AtomicBool;
 use crate::acp::AcpThreadHistory;
 use acp_thread::{AgentSessionInfo, MentionUri};
 use anyhow::Result;
+use context_server::{
+    ContextServerId,
+    protocol::{InitializedContextServerProtocol, ServerCapability},
+};
 use editor::{
     CompletionProvider, Editor, ExcerptId, code_context_menus::COMPLETION_MENU_MAX_WIDTH,
 };
 use futures::FutureExt as _;
 use fuzzy::{PathMatch, StringMatch, StringMatchCandidate};
 use gpui::{App, BackgroundExecutor, Entity, SharedString, Task, WeakEntity};
+use itertools::Itertools as _;
 use language::{Buffer, CodeLabel, CodeLabelBuilder, HighlightId};
 use lsp::CompletionContext;
 use multi_buffer::ToOffset as _;
@@ -63,6 +68,7 @@ pub(crate) enum PromptContextType {
     Thread,
     Rules,
     Diagnostics,
+    McpResource,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -101,6 +107,7 @@ impl TryFrom<&str> for PromptContextType {
             "thread" => Ok(Self::Thread),
             "rule" => Ok(Self::Rules),
             "diagnostics" => Ok(Self::Diagnostics),
+            "resource" => Ok(Self::McpResource),
             _ => Err(format!("Invalid context picker mode: {}", value)),
         }
     }
@@ -115,6 +122,7 @@ impl PromptContextType {
             Self::Thread => "thread",
             Self::Rules => "rule",
             Self::Diagnostics => "diagnostics",
+            Self::McpResource => "resource",
         }
     }
 
@@ -126,6 +134,7 @@ impl PromptContextType {
             Self::Thread => "Threads",
             Self::Rules => "Rules",
             Self::Diagnostics => "Diagnostics",
+            Self::McpResource => "MCP Resources",
         }
     }
 
@@ -137,6 +146,7 @@ impl PromptContextType {
             Self::Thread => IconName::Thread,
             Self::Rules => IconName::Reader,
             Self::Diagnostics => IconName::Warning,
+            Self::McpResource => IconName::ToolHammer,
         }
     }
 }
@@ -148,6 +158,7 @@ pub(crate) enum Match {
     RecentThread(AgentSessionInfo),
     Fetch(SharedString),
     Rules(RulesContextEntry),
+    McpResource(McpResourceMatch),
     Entry(EntryMatch),
 }
 
@@ -161,6 +172,7 @@ impl Match {
             Match::Symbol(_) => 1.,
             Match::Rules(_) => 1.,
             Match::Fetch(_) => 1.,
+            Match::McpResource(_) => 1.,
         }
     }
 }
@@ -184,6 +196,28 @@ pub struct RulesContextEntry {
     pub title: SharedString,
 }
 
+#[derive(Debug, Clone)]
+pub struct McpResourceMatch {
+    pub server_id: ContextServerId,
+    pub entry: McpResourceEntry,
+}
+
+#[derive(Debug, Clone)]
+pub enum McpResourceEntry {
+    Resource {
+        uri: url::Url,
+        name: String,
+        description: Option<String>,
+    },
+    /// A resource template whose arguments haven't all been filled in yet.
+    /// `text` is what gets inserted after `@resource `.
+    Template {
+        name: String,
+        text: String,
+        description: Option<String>,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub struct AvailableCommand {
     pub name: Arc<str>,
@@ -523,6 +557,92 @@ impl<T: PromptCompletionProviderDelegate> PromptCompletionProvider<T> {
         })
     }
 
+    fn completion_for_mcp_resource(
+        mat: McpResourceMatch,
+        source_range: Range<Anchor>,
+        source: Arc<T>,
+        editor: WeakEntity<Editor>,
+        mention_set: WeakEntity<MentionSet>,
+        workspace: Entity<Workspace>,
+        cx: &mut App,
+    ) -> Completion {
+        let variable_highlight_id = cx
+            .theme()
+            .syntax()
+            .highlight_id("variable")
+            .map(HighlightId);
+        let mut label = CodeLabelBuilder::default();
+
+        match mat.entry {
+            McpResourceEntry::Resource {
+                uri,
+                name,
+                description,
+            } => {
+                label.push_str(&name, None);
+                label.push_str(" ", None);
+                label.push_str(&mat.server_id.0, variable_highlight_id);
+
+                let mention_uri = MentionUri::McpResource {
+                    server_id: mat.server_id.0.to_string(),
+                    uri,
+                    name: name.clone(),
+                };
+                let new_text = format!("{} ", mention_uri.as_link());
+                let new_text_len = new_text.len();
+                let icon_path = mention_uri.icon_path(cx);
+                Completion {
+                    replace_range: source_range.clone(),
+                    new_text,
+                    label: label.build(),
+                    documentation: description.map(|description| {
+                        CompletionDocumentation::MultiLinePlainText(description.into())
+                    }),
+                    source: project::CompletionSource::Custom,
+                    icon_path: Some(icon_path),
+                    match_start: None,
+                    snippet_deduplication_key: None,
+                    insert_text_mode: None,
+                    confirm: Some(confirm_completion_callback(
+                        name.into(),
+                        source_range.start,
+                        new_text_len - 1,
+                        mention_uri,
+                        source,
+                        editor,
+                        mention_set,
+                        workspace,
+                    )),
+                }
+            }
+            McpResourceEntry::Template {
+                name,
+                text,
+                description,
+            } => {
+                label.push_str(&name, None);
+                label.push_str(" ", None);
+                label.push_str(&text, variable_highlight_id);
+
+                Completion {
+                    replace_range: source_range,
+                    new_text: format!("@{} {}", PromptContextType::McpResource.keyword(), text),
+                    label: label.build(),
+                    documentation: description.map(|description| {
+                        CompletionDocumentation::MultiLinePlainText(description.into())
+                    }),
+                    source: project::CompletionSource::Custom,
+                    icon_path: Some(PromptContextType::McpResource.icon().path().into()),
+                    match_start: None,
+                    snippet_deduplication_key: None,
+                    insert_text_mode: None,
+                    // Keep the menu open so the remaining template arguments can be completed.
+                    confirm: Some(Arc::new(|_, _, _| true)),
+                }
+            }
+        }
+    }
+
     pub(crate) fn completion_for_action(
         action: PromptContextAction,
         source_range: Range<Anchor>,
@@ -877,6 +997,18 @@ impl<T: PromptCompletionProviderDelegate> PromptCompletionProvider<T> {
 
             Some(PromptContextType::Diagnostics) => Task::ready(Vec::new()),
 
+            Some(PromptContextType::McpResource) => {
+                let project = workspace.read(cx).project().clone();
+                let search_task = search_mcp_resources(query, cancellation_flag, &project, cx);
+                cx.background_spawn(async move {
+                    search_task
+                        .await
+                        .into_iter()
+                        .map(Match::McpResource)
+                        .collect()
+                })
+            }
+
             None if query.is_empty() => {
                 let recent_task = self.recent_context_picker_entries(&workspace, cx);
                 let entries = self
@@ -1075,6 +1207,26 @@ impl<T: PromptCompletionProviderDelegate> PromptCompletionProvider<T> {
             entries.push(PromptContextEntry::Mode(PromptContextType::Fetch));
         }
 
+        if self
+            .source
+            .supports_context(PromptContextType::McpResource, cx)
+            && workspace
+                .read(cx)
+                .project()
+                .read(cx)
+                .context_server_store()
+                .read(cx)
+                .running_servers()
+                .iter()
+                .any(|server| {
+                    server
+                        .client()
+                        .is_some_and(|client| client.capable(ServerCapability::Resources))
+                })
+        {
+            entries.push(PromptContextEntry::Mode(PromptContextType::McpResource));
+        }
+
         if self
             .source
             .supports_context(PromptContextType::Diagnostics, cx)
@@ -1334,6 +1486,15 @@ impl<T: PromptCompletionProviderDelegate> CompletionProvider for PromptCompletio
                                     workspace.clone(),
                                     cx,
                                 ),
+                                Match::McpResource(mat) => Some(Self::completion_for_mcp_resource(
+                                    mat,
+                                    source_range.clone(),
+                                    source.clone(),
+                                    editor.clone(),
+                                    mention_set.clone(),
+                                    workspace.clone(),
+                                    cx,
+                                )),
                                 Match::Entry(EntryMatch { entry, .. }) => {
                                     Self::completion_for_entry(
                                         entry,
@@ -1944,6 +2105,279 @@ pub(crate) fn search_rules(
     })
 }
 
+/// Lists the resources and resource templates of every running MCP server that match `query`.
+///
+/// Once `query` starts with the fixed prefix of a resource template, the template's
+/// arguments are completed one at a time through `completion/complete`.
+pub(crate) fn search_mcp_resources(
+    query: String,
+    cancellation_flag: Arc<AtomicBool>,
+    project: &Entity<Project>,
+    cx: &mut App,
+) -> Task<Vec<McpResourceMatch>> {
+    let servers = project
+        .read(cx)
+        .context_server_store()
+        .update(cx, |store, cx| {
+            store
+                .running_servers()
+                .into_iter()
+                .filter_map(|server| {
+                    let resources = store.resources(&server.id(), cx)?;
+                    Some((server.id(), server.client()?, resources))
+                })
+                .collect::<Vec<_>>()
+        });
+    if servers.is_empty() {
+        return Task::ready(Vec::new());
+    }
+
+    cx.spawn(async move |cx| {
+        let mut entries = Vec::new();
+        let mut template_matches = Vec::new();
+        for (server_id, client, resources) in servers {
+            let resources = resources.await;
+            entries.extend(resources.resources.iter().map(|resource| McpResourceMatch {
+                server_id: server_id.clone(),
+                entry: McpResourceEntry::Resource {
+                    uri: resource.uri.clone(),
+                    name: resource.name.clone(),
+                    description: resource.description.clone(),
+                },
+            }));
+
+            for template in &resources.resource_templates {
+                if let Some(mat) = ResourceTemplateMatch::new(&template.uri_template, &query) {
+                    template_matches.extend(
+                        complete_resource_template(&server_id, &client, template, mat).await,
+                    );
+                } else {
+                    let text = template
+                        .uri_template
+                        .split('{')
+                        .next()
+                        .unwrap_or_default()
+                        .to_string();
+                    entries.push(McpResourceMatch {
+                        server_id: server_id.clone(),
+                        entry: McpResourceEntry::Template {
+                            name: template.name.clone(),
+                            text,
+                            description: template.description.clone(),
+                        },
+                    });
+                }
+            }
+        }
+
+        if !template_matches.is_empty() {
+            return template_matches;
+        }
+        if query.is_empty() {
+            return entries;
+        }
+
+        let candidates = entries
+            .iter()
+            .enumerate()
+            .map(|(id, mat)| match &mat.entry {
+                McpResourceEntry::Resource { name, .. }
+                | McpResourceEntry::Template { name, .. } => StringMatchCandidate::new(id, name),
+            })
+            .collect::<Vec<_>>();
+        let matches = fuzzy::match_strings(
+            &candidates,
+            &query,
+            false,
+            true,
+            100,
+            &cancellation_flag,
+            cx.background_executor().clone(),
+        )
+        .await;
+        matches
+            .into_iter()
+            .map(|mat| entries[mat.candidate_id].clone())
+            .collect()
+    })
+}
+
+async fn complete_resource_template(
+    server_id: &ContextServerId,
+    client: &InitializedContextServerProtocol,
+    template: &context_server::types::ResourceTemplate,
+    mat: ResourceTemplateMatch,
+) -> Vec<McpResourceMatch> {
+    let resource = |uri: &str| {
+        let uri = url::Url::parse(uri).ok()?;
+        Some(McpResourceMatch {
+            server_id: server_id.clone(),
+            entry: McpResourceEntry::Resource {
+                name: uri.to_string(),
+                uri,
+                description: template.description.clone(),
+            },
+        })
+    };
+
+    let Some((argument, value)) = mat.argument else {
+        return resource(&mat.prefix).into_iter().collect();
+    };
+
+    // Servers without the `completions` capability only get the typed value offered back.
+    let mut values = Vec::new();
+    if client.capable(ServerCapability::Completions) {
+        values = client
+            .request::<context_server::types::requests::CompletionComplete>(
+                context_server::types::CompletionCompleteParams {
+                    reference: context_server::types::CompletionReference::Resource(
+                        context_server::types::ResourceReference {
+                            ty: context_server::types::PromptReferenceType::Resource,
+                            uri: template.uri_template.clone(),
+                        },
+                    ),
+                    argument: context_server::types::CompletionArgument {
+                        name: argument,
+                        value: value.clone(),
+                    },
+                    context: (!mat.arguments.is_empty()).then(|| {
+                        context_server::types::CompletionContext {
+                            arguments: Some(mat.arguments.into_iter().collect()),
+                        }
+                    }),
+                    meta: None,
+                },
+            )
+            .await
+            .log_err()
+            .map(|response| response.completion.values)
+            .unwrap_or_default();
+    }
+
+    let mut matches = Vec::new();
+    for completed in values
+        .into_iter()
+        .chain((!value.is_empty()).then_some(value))
+        .unique()
+    {
+        let text = format!("{}{}{}", mat.prefix, completed, mat.suffix);
+        if mat.is_last_argument {
+            matches.extend(resource(&text));
+        } else {
+            matches.push(McpResourceMatch {
+                server_id: server_id.clone(),
+                entry: McpResourceEntry::Template {
+                    name: template.name.clone(),
+                    text,
+                    description: template.description.clone(),
+                },
+            });
+        }
+    }
+    matches
+}
+
+/// How far a typed query gets through the expansion of a resource template.
+#[derive(Debug, PartialEq)]
+struct ResourceTemplateMatch {
+    /// The expansion up to the argument that is being typed.
+    prefix: String,
+    /// The arguments before the one that is being typed, with their values.
+    arguments: Vec<(String, String)>,
+    /// The argument that is being typed and its partial value, or `None` once every
+    /// argument has a value, in which case `prefix` is the complete expansion.
+    argument: Option<(String, String)>,
+    /// The literal text that follows the argument that is being typed.
+    suffix: String,
+    is_last_argument: bool,
+}
+
+impl ResourceTemplateMatch {
+    /// Matches `query` against a [RFC 6570](https://datatracker.ietf.org/doc/html/rfc6570)
+    /// template. Only simple `{name}` expressions are supported, and the query must
+    /// contain the template's leading literal for it to match.
+    fn new(template: &str, query: &str) -> Option<Self> {
+        let parts = parse_resource_template(template)?;
+        if !matches!(parts.first(), Some(ResourceTemplatePart::Literal(_))) {
+            return None;
+        }
+        let mut prefix = String::new();
+        let mut arguments = Vec::new();
+        let mut rest = query;
+
+        for (ix, part) in parts.iter().enumerate() {
+            match part {
+                ResourceTemplatePart::Literal(literal) => {
+                    if let Some(remaining) = rest.strip_prefix(literal.as_str()) {
+                        rest = remaining;
+                    } else if ix > 0 && literal.starts_with(rest) {
+                        rest = "";
+                    } else {
+                        return None;
+                    }
+                    prefix.push_str(literal);
+                }
+                ResourceTemplatePart::Argument(name) => {
+                    let suffix = match parts.get(ix + 1) {
+                        Some(ResourceTemplatePart::Literal(literal)) => literal.as_str(),
+                        _ => "",
+                    };
+                    if !suffix.is_empty()
+                        && let Some(end) = rest.find(suffix)
+                    {
+                        prefix.push_str(&rest[..end]);
+                        arguments.push((name.clone(), rest[..end].to_string()));
+                        rest = &rest[end..];
+                        continue;
+                    }
+
+                    return Some(Self {
+                        prefix,
+                        arguments,
+                        argument: Some((name.clone(), rest.to_string())),
+                        suffix: suffix.to_string(),
+                        is_last_argument: !parts[ix + 1..]
+                            .iter()
+                            .any(|part| matches!(part, ResourceTemplatePart::Argument(_))),
+                    });
+                }
+            }
+        }
+
+        rest.is_empty().then(|| Self {
+            prefix,
+            arguments,
+            argument: None,
+            suffix: String::new(),
+            is_last_argument: true,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum ResourceTemplatePart {
+    Literal(String),
+    Argument(String),
+}
+
+fn parse_resource_template(template: &str) -> Option<Vec<ResourceTemplatePart>> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            parts.push(ResourceTemplatePart::Literal(rest[..start].to_string()));
+        }
+        let end = rest[start..].find('}')? + start;
+        let name = rest[start + 1..end].trim_start_matches(['+', '#', '.', '/', ';', '?', '&']);
+        parts.push(ResourceTemplatePart::Argument(name.to_string()));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(ResourceTemplatePart::Literal(rest.to_string()));
+    }
+    Some(parts)
+}
+
 pub struct SymbolMatch {
     pub symbol: Symbol,
 }
@@ -2325,6 +2759,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resource_template_match() {
+        let template = "github://repos/{owner}/{repo}/issues";
+
+        assert_eq!(ResourceTemplateMatch::new(template, ""), None);
+        assert_eq!(ResourceTemplateMatch::new(template, "git"), None);
+        assert_eq!(ResourceTemplateMatch::new(template, "file:///"), None);
+
+        assert_eq!(
+            ResourceTemplateMatch::new(template, "github://repos/"),
+            Some(ResourceTemplateMatch {
+                prefix: "github://repos/".into(),
+                arguments: vec![],
+                argument: Some(("owner".into(), "".into())),
+                suffix: "/".into(),
+                is_last_argument: false,
+            })
+        );
+
+        assert_eq!(
+            ResourceTemplateMatch::new(template, "github://repos/zed-ind"),
+            Some(ResourceTemplateMatch {
+                prefix: "github://repos/".into(),
+                arguments: vec![],
+                argument: Some(("owner".into(), "zed-ind".into())),
+                suffix: "/".into(),
+                is_last_argument: false,
+            })
+        );
+
+        assert_eq!(
+            ResourceTemplateMatch::new(template, "github://repos/zed-industries/ze"),
+            Some(ResourceTemplateMatch {
+                prefix: "github://repos/zed-industries/".into(),
+                arguments: vec![("owner".into(), "zed-industries".into())],
+                argument: Some(("repo".into(), "ze".into())),
+                suffix: "/issues".into(),
+                is_last_argument: true,
+            })
+        );
+
+        assert_eq!(
+            ResourceTemplateMatch::new(template, "github://repos/zed-industries/zed/issues"),
+            Some(ResourceTemplateMatch {
+                prefix: "github://repos/zed-industries/zed/issues".into(),
+                arguments: vec![
+                    ("owner".into(), "zed-industries".into()),
+                    ("repo".into(), "zed".into()),
+                ],
+                argument: None,
+                suffix: "".into(),
+                is_last_argument: true,
+            })
+        );
+
+        // Templates without a leading literal would match any query.
+        assert_eq!(ResourceTemplateMatch::new("{+path}", "src/main.rs"), None);
+    }
+
     #[gpui::test]
     async fn test_filter_sessions_by_query(cx: &mut TestAppContext) {
         let mut alpha = AgentSessionInfo::new("session-alpha");
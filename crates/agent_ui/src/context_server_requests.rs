@@ -0,0 +1,658 @@
+use anyhow::{Context as _, Result, anyhow};
+use collections::IndexMap;
+use context_server::ContextServerId;
+use context_server::types::{
+    CreateMessageRequest, CreateMessageResult, ElicitAction, ElicitResult, MessageContent,
+    PrimitiveSchema, Role as SamplingRole,
+};
+use editor::Editor;
+use futures::StreamExt as _;
+use gpui::{AsyncWindowContext, Entity, PromptLevel, Subscription};
+use language_model::{
+    LanguageModelRegistry, LanguageModelRequest, LanguageModelRequestMessage, Role,
+};
+use project::context_server_store::{
+    ContextServerRequestEvent, ContextServerStore, ElicitationRequest, SamplingRequest,
+};
+use serde_json::Value;
+use ui::{Callout, Checkbox, prelude::*};
+
+/// Answers the requests context servers send to the client: sampling requests are
+/// confirmed with a prompt, run against the default model and only returned once the
+/// user reviewed the response, while elicitation requests are rendered as forms at the
+/// top of the agent panel.
+pub struct ContextServerRequests {
+    elicitations: Vec<ElicitationForm>,
+    _subscription: Subscription,
+}
+
+struct ElicitationForm {
+    request: ElicitationRequest,
+    fields: Vec<ElicitationField>,
+    error: Option<SharedString>,
+}
+
+struct ElicitationField {
+    name: String,
+    schema: PrimitiveSchema,
+    required: bool,
+    value: FieldValue,
+}
+
+enum FieldValue {
+    Text(Entity<Editor>),
+    Boolean(bool),
+    Choice(Option<usize>),
+}
+
+impl ContextServerRequests {
+    pub fn new(
+        context_server_store: &Entity<ContextServerStore>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let subscription = cx.subscribe_in(
+            context_server_store,
+            window,
+            |this, _, event: &ContextServerRequestEvent, window, cx| match event {
+                ContextServerRequestEvent::Sampling(request) => {
+                    Self::confirm_sampling(request.clone(), window, cx);
+                }
+                ContextServerRequestEvent::Elicitation(request) => {
+                    this.push_elicitation(request.clone(), window, cx);
+                }
+            },
+        );
+        Self {
+            elicitations: Vec::new(),
+            _subscription: subscription,
+        }
+    }
+
+    fn confirm_sampling(request: SamplingRequest, window: &mut Window, cx: &mut Context<Self>) {
+        let message = format!(
+            "The MCP server \"{}\" wants to generate a response with your configured model.",
+            request.server_id
+        );
+        let detail = sampling_request_detail(&request.params);
+        let answer = window.prompt(
+            PromptLevel::Info,
+            &message,
+            Some(&detail),
+            &["Allow", "Deny"],
+            cx,
+        );
+        cx.spawn_in(window, async move |_, cx| {
+            let response = if answer.await == Ok(0) {
+                match sample(request.params.clone(), cx).await {
+                    Ok(result) => review_sampling_result(&request.server_id, result, cx).await,
+                    Err(error) => Err(error),
+                }
+            } else {
+                Err(anyhow!("The user declined the sampling request"))
+            };
+            request.respond(response).await;
+        })
+        .detach();
+    }
+
+    fn push_elicitation(
+        &mut self,
+        request: ElicitationRequest,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let schema = &request.params.requested_schema;
+        let fields = schema
+            .properties
+            .iter()
+            .map(|(name, property)| {
+                let value = match property {
+                    PrimitiveSchema::Boolean { default, .. } => {
+                        FieldValue::Boolean(default.unwrap_or(false))
+                    }
+                    PrimitiveSchema::String {
+                        enum_values: Some(_),
+                        ..
+                    } => FieldValue::Choice(None),
+                    _ => FieldValue::Text(cx.new(|cx| {
+                        let mut editor = Editor::single_line(window, cx);
+                        if let Some(description) = property.description() {
+                            editor.set_placeholder_text(description, window, cx);
+                        }
+                        editor
+                    })),
+                };
+                ElicitationField {
+                    name: name.clone(),
+                    schema: property.clone(),
+                    required: schema.required.contains(name),
+                    value,
+                }
+            })
+            .collect();
+        self.elicitations.push(ElicitationForm {
+            request,
+            fields,
+            error: None,
+        });
+        cx.notify();
+    }
+
+    fn respond(&mut self, ix: usize, action: ElicitAction, cx: &mut Context<Self>) {
+        let Some(form) = self.elicitations.get_mut(ix) else {
+            return;
+        };
+        let content = if action == ElicitAction::Accept {
+            match form.content(cx) {
+                Ok(content) => Some(content),
+                Err(error) => {
+                    form.error = Some(error.to_string().into());
+                    cx.notify();
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+
+        let form = self.elicitations.remove(ix);
+        cx.background_spawn(form.request.respond(ElicitResult {
+            action,
+            content,
+            meta: None,
+        }))
+        .detach();
+        cx.notify();
+    }
+
+    fn render_elicitation(
+        &self,
+        ix: usize,
+        form: &ElicitationForm,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let fields = form
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(field_ix, field)| self.render_field(ix, field_ix, field, cx))
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .id(("elicitation", ix))
+            .p_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                Label::new(format!("{} needs more information", form.request.server_id))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(Label::new(form.request.params.message.clone()))
+            .children(fields)
+            .children(form.error.clone().map(|error| {
+                Callout::new()
+                    .icon(IconName::Warning)
+                    .severity(Severity::Error)
+                    .title(error)
+            }))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .justify_end()
+                    .child(
+                        Button::new(("elicitation-cancel", ix), "Cancel")
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.respond(ix, ElicitAction::Cancel, cx)
+                            })),
+                    )
+                    .child(
+                        Button::new(("elicitation-decline", ix), "Decline")
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.respond(ix, ElicitAction::Decline, cx)
+                            })),
+                    )
+                    .child(
+                        Button::new(("elicitation-accept", ix), "Submit")
+                            .label_size(LabelSize::Small)
+                            .style(ButtonStyle::Filled)
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.respond(ix, ElicitAction::Accept, cx)
+                            })),
+                    ),
+            )
+    }
+
+    fn render_field(
+        &self,
+        ix: usize,
+        field_ix: usize,
+        field: &ElicitationField,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let mut title = field.schema.title().unwrap_or(&field.name).to_string();
+        if field.required {
+            title.push_str(" *");
+        }
+
+        let input = match &field.value {
+            FieldValue::Text(editor) => div()
+                .px_2()
+                .py_1()
+                .rounded_sm()
+                .border_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(editor.clone())
+                .into_any_element(),
+            FieldValue::Boolean(checked) => {
+                Checkbox::new("elicitation-checkbox", ToggleState::from(*checked))
+                    .label(field.schema.description().unwrap_or_default().to_string())
+                    .on_click(cx.listener(move |this, state: &ToggleState, _, cx| {
+                        if let Some(field) = this
+                            .elicitations
+                            .get_mut(ix)
+                            .and_then(|form| form.fields.get_mut(field_ix))
+                        {
+                            field.value = FieldValue::Boolean(state.selected());
+                            cx.notify();
+                        }
+                    }))
+                    .into_any_element()
+            }
+            FieldValue::Choice(selected) => {
+                let PrimitiveSchema::String {
+                    enum_values: Some(options),
+                    enum_names,
+                    ..
+                } = &field.schema
+                else {
+                    return div().into_any_element();
+                };
+                h_flex()
+                    .flex_wrap()
+                    .gap_1()
+                    .children(options.iter().enumerate().map(|(option_ix, option)| {
+                        let label = enum_names
+                            .as_ref()
+                            .and_then(|names| names.get(option_ix))
+                            .unwrap_or(option)
+                            .clone();
+                        Button::new(("elicitation-option", option_ix), label)
+                            .label_size(LabelSize::Small)
+                            .style(ButtonStyle::Outlined)
+                            .toggle_state(*selected == Some(option_ix))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                if let Some(field) = this
+                                    .elicitations
+                                    .get_mut(ix)
+                                    .and_then(|form| form.fields.get_mut(field_ix))
+                                {
+                                    field.value = FieldValue::Choice(Some(option_ix));
+                                    cx.notify();
+                                }
+                            }))
+                    }))
+                    .into_any_element()
+            }
+        };
+
+        v_flex()
+            .id(("elicitation-field", field_ix))
+            .gap_1()
+            .child(Label::new(title).size(LabelSize::Small))
+            .child(input)
+            .into_any_element()
+    }
+}
+
+impl ElicitationForm {
+    /// Collects the form's values, validating them against the requested schema.
+    fn content(&self, cx: &App) -> Result<IndexMap<String, Value>> {
+        let mut content = IndexMap::default();
+        for field in &self.fields {
+            let title = field.schema.title().unwrap_or(&field.name);
+            let value = match (&field.value, &field.schema) {
+                (FieldValue::Boolean(checked), _) => Some(Value::Bool(*checked)),
+                (
+                    FieldValue::Choice(selected),
+                    PrimitiveSchema::String {
+                        enum_values: Some(options),
+                        ..
+                    },
+                ) => selected
+                    .and_then(|selected| options.get(selected))
+                    .map(|option| Value::String(option.clone())),
+                (FieldValue::Choice(_), _) => None,
+                (FieldValue::Text(editor), schema) => {
+                    let text = editor.read(cx).text(cx).trim().to_string();
+                    if text.is_empty() {
+                        None
+                    } else {
+                        Some(parse_text_value(title, text, schema)?)
+                    }
+                }
+            };
+            match value {
+                Some(value) => {
+                    content.insert(field.name.clone(), value);
+                }
+                None if field.required => anyhow::bail!("\"{title}\" is required"),
+                None => {}
+            }
+        }
+        Ok(content)
+    }
+}
+
+fn parse_text_value(title: &str, text: String, schema: &PrimitiveSchema) -> Result<Value> {
+    let (number, minimum, maximum) = match schema {
+        PrimitiveSchema::String {
+            min_length,
+            max_length,
+            ..
+        } => {
+            let length = text.chars().count();
+            if min_length.is_some_and(|min_length| length < min_length) {
+                anyhow::bail!("\"{title}\" is too short");
+            }
+            if max_length.is_some_and(|max_length| length > max_length) {
+                anyhow::bail!("\"{title}\" is too long");
+            }
+            return Ok(Value::String(text));
+        }
+        PrimitiveSchema::Integer {
+            minimum, maximum, ..
+        } => {
+            let number = text
+                .parse::<i64>()
+                .ok()
+                .with_context(|| format!("\"{title}\" must be a whole number"))?;
+            (serde_json::Number::from(number), minimum, maximum)
+        }
+        PrimitiveSchema::Number {
+            minimum, maximum, ..
+        } => {
+            let number = text
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .with_context(|| format!("\"{title}\" must be a number"))?;
+            (number, minimum, maximum)
+        }
+        PrimitiveSchema::Boolean { .. } => anyhow::bail!("\"{title}\" must be a boolean"),
+    };
+
+    let value = number.as_f64().unwrap_or_default();
+    if let Some(minimum) = *minimum
+        && value < minimum
+    {
+        anyhow::bail!("\"{title}\" must be at least {minimum}");
+    }
+    if let Some(maximum) = *maximum
+        && value > maximum
+    {
+        anyhow::bail!("\"{title}\" must be at most {maximum}");
+    }
+    Ok(Value::Number(number))
+}
+
+/// Describes the whole conversation the server wants to sample, so that the user can see
+/// everything that would be sent to the model.
+fn sampling_request_detail(params: &CreateMessageRequest) -> String {
+    let mut detail = String::new();
+    if let Some(system_prompt) = &params.system_prompt {
+        detail.push_str(&format!("System: {system_prompt}\n\n"));
+    }
+    for message in &params.messages {
+        let role = match message.role {
+            SamplingRole::User => "User",
+            SamplingRole::Assistant => "Assistant",
+        };
+        let content = match &message.content {
+            MessageContent::Text { text, .. } => text.as_str(),
+            MessageContent::Image { .. } => "[image]",
+            MessageContent::Audio { .. } => "[audio]",
+            MessageContent::Resource { .. } => "[resource]",
+        };
+        detail.push_str(&format!("{role}: {content}\n\n"));
+    }
+    detail.truncate(detail.trim_end().len());
+    detail
+}
+
+/// Shows the generated response to the user, who decides whether it's sent to the server.
+async fn review_sampling_result(
+    server_id: &ContextServerId,
+    result: CreateMessageResult,
+    cx: &mut AsyncWindowContext,
+) -> Result<CreateMessageResult> {
+    let MessageContent::Text { text, .. } = &result.content else {
+        anyhow::bail!("Only text content is supported in sampling results");
+    };
+    let message = format!("Send this response to the MCP server \"{server_id}\"?");
+    let answer = cx.update(|window, cx| {
+        window.prompt(
+            PromptLevel::Info,
+            &message,
+            Some(text),
+            &["Send", "Discard"],
+            cx,
+        )
+    })?;
+    if answer.await == Ok(0) {
+        Ok(result)
+    } else {
+        Err(anyhow!("The user discarded the generated response"))
+    }
+}
+
+/// Runs a sampling request against the default model, returning the generated text.
+async fn sample(
+    params: CreateMessageRequest,
+    cx: &mut AsyncWindowContext,
+) -> Result<CreateMessageResult> {
+    let model = cx
+        .update(|_, cx| LanguageModelRegistry::read_global(cx).default_model())?
+        .context("No language model is configured")?
+        .model;
+
+    let mut messages = Vec::new();
+    if let Some(system_prompt) = params.system_prompt {
+        messages.push(LanguageModelRequestMessage {
+            role: Role::System,
+            content: vec![system_prompt.into()],
+            cache: false,
+            reasoning_details: None,
+        });
+    }
+    for message in params.messages {
+        let MessageContent::Text { text, .. } = message.content else {
+            anyhow::bail!("Only text content is supported in sampling requests");
+        };
+        messages.push(LanguageModelRequestMessage {
+            role: match message.role {
+                SamplingRole::User => Role::User,
+                SamplingRole::Assistant => Role::Assistant,
+            },
+            content: vec![text.into()],
+            cache: false,
+            reasoning_details: None,
+        });
+    }
+
+    let request = LanguageModelRequest {
+        thread_id: None,
+        prompt_id: None,
+        intent: None,
+        messages,
+        tools: Vec::new(),
+        tool_choice: None,
+        stop: params.stop_sequences.unwrap_or_default(),
+        temperature: params.temperature.map(|temperature| temperature as f32),
+        thinking_allowed: false,
+        thinking_effort: None,
+    };
+
+    let mut stream = model.stream_completion_text(request, cx).await?.stream;
+    let mut text = String::new();
+    while let Some(chunk) = stream.next().await {
+        text.push_str(&chunk?);
+    }
+
+    Ok(CreateMessageResult {
+        role: SamplingRole::Assistant,
+        content: MessageContent::Text {
+            text,
+            annotations: None,
+        },
+        model: model.name().0.to_string(),
+        stop_reason: Some("endTurn".to_string()),
+    })
+}
+
+impl Render for ContextServerRequests {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let forms = self
+            .elicitations
+            .iter()
+            .enumerate()
+            .map(|(ix, form)| self.render_elicitation(ix, form, cx).into_any_element())
+            .collect::<Vec<_>>();
+        v_flex().children(forms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use context_server::{
+        ContextServer,
+        test::FakeTransport,
+        types::{self, SamplingMessage, requests},
+    };
+    use gpui::TestAppContext;
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::{path::Path, sync::Arc};
+    use util::path;
+
+    #[gpui::test]
+    async fn test_sampling_is_reviewed(cx: &mut TestAppContext) {
+        let settings_store = cx.update(SettingsStore::test);
+        cx.set_global(settings_store);
+        cx.update(|cx| {
+            theme::init(theme::LoadThemes::JustBase, cx);
+            LanguageModelRegistry::test(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/project", json!({"file": ""})).await;
+        let project = Project::test(fs, [Path::new(path!("/project"))], cx).await;
+        // Let the store settle on the configured servers, so that it doesn't stop the fake one.
+        cx.run_until_parked();
+
+        let transport = Arc::new(
+            FakeTransport::new(cx.executor()).on_request::<requests::Initialize, _>(|_| async {
+                types::InitializeResponse {
+                    protocol_version: types::ProtocolVersion(
+                        types::LATEST_PROTOCOL_VERSION.to_string(),
+                    ),
+                    capabilities: types::ServerCapabilities::default(),
+                    server_info: types::Implementation {
+                        name: "writer".to_string(),
+                        version: "1.0.0".to_string(),
+                    },
+                    meta: None,
+                }
+            }),
+        );
+        let server = Arc::new(ContextServer::new(
+            ContextServerId("writer".into()),
+            transport.clone(),
+        ));
+        let store = project.read_with(cx, |project, _| project.context_server_store());
+        store.update(cx, |store, cx| store.test_start_server(server, cx));
+        cx.run_until_parked();
+        cx.add_window(|window, cx| ContextServerRequests::new(&store, window, cx));
+
+        let params = CreateMessageRequest {
+            messages: vec![
+                SamplingMessage {
+                    role: SamplingRole::User,
+                    content: MessageContent::Text {
+                        text: "Name this function.".to_string(),
+                        annotations: None,
+                    },
+                },
+                SamplingMessage {
+                    role: SamplingRole::Assistant,
+                    content: MessageContent::Text {
+                        text: "Which function?".to_string(),
+                        annotations: None,
+                    },
+                },
+                SamplingMessage {
+                    role: SamplingRole::User,
+                    content: MessageContent::Text {
+                        text: "fn f(a: i32, b: i32) -> i32 { a + b }".to_string(),
+                        annotations: None,
+                    },
+                },
+            ],
+            model_preferences: None,
+            system_prompt: Some("Answer with a single name.".to_string()),
+            include_context: None,
+            temperature: None,
+            max_tokens: 16,
+            stop_sequences: None,
+            metadata: None,
+        };
+        let sample_and_answer = |review_answer: &'static str, cx: &mut TestAppContext| {
+            let response = cx.executor().spawn({
+                let transport = transport.clone();
+                let params = params.clone();
+                async move { transport.request::<requests::CreateMessage>(params).await }
+            });
+            cx.run_until_parked();
+
+            // The whole conversation is shown before anything is sent to the model.
+            let (_, detail) = cx.pending_prompt().unwrap();
+            assert_eq!(
+                detail,
+                "System: Answer with a single name.\n\n\
+                 User: Name this function.\n\n\
+                 Assistant: Which function?\n\n\
+                 User: fn f(a: i32, b: i32) -> i32 { a + b }"
+            );
+            cx.simulate_prompt_answer("Allow");
+            cx.run_until_parked();
+
+            let model = cx.update(|cx| LanguageModelRegistry::read_global(cx).fake_model());
+            model
+                .as_fake()
+                .send_last_completion_stream_text_chunk("add");
+            model.as_fake().end_last_completion_stream();
+            cx.run_until_parked();
+
+            // The response is only sent to the server once the user reviewed it.
+            let (_, detail) = cx.pending_prompt().unwrap();
+            assert_eq!(detail, "add");
+            cx.simulate_prompt_answer(review_answer);
+            cx.run_until_parked();
+            response
+        };
+
+        let result = sample_and_answer("Send", cx).await.unwrap();
+        let MessageContent::Text { text, .. } = result.content else {
+            panic!("expected a text response");
+        };
+        assert_eq!(text, "add");
+
+        assert!(sample_and_answer("Discard", cx).await.is_err());
+    }
+}
@@ -372,7 +372,7 @@ impl<T: 'static> PromptEditor<T> {
             editor
         });
 
-        self.mention_set.update(cx, |mention_set, _cx| {
+        self.mention_set.update(cx, |mention_set, cx| {
             debug_assert_eq!(
                 creases.len(),
                 mention_set.creases().len(),
@@ -384,7 +384,7 @@ impl<T: 'static> PromptEditor<T> {
                 .zip(creases)
                 .map(|((_, value), id)| (id, value))
                 .collect::<HashMap<_, _>>();
-            mention_set.set_mentions(mentions);
+            mention_set.set_mentions(mentions, cx);
         });
 
         self.assign_completion_provider(cx);
@@ -440,8 +440,9 @@ impl<T: 'static> PromptEditor<T> {
             EditorEvent::Edited { .. } => {
                 let snapshot = editor.update(cx, |editor, cx| editor.snapshot(window, cx));
 
-                self.mention_set
-                    .update(cx, |mention_set, _cx| mention_set.remove_invalid(&snapshot));
+                self.mention_set.update(cx, |mention_set, cx| {
+                    mention_set.remove_invalid(&snapshot, cx)
+                });
 
                 if let Some(workspace) = Workspace::for_window(window, cx) {
                     workspace.update(cx, |workspace, cx| {
@@ -1200,6 +1201,7 @@ impl PromptCompletionProviderDelegate for PromptEditorCompletionProviderDelegate
             PromptContextType::Thread,
             PromptContextType::Fetch,
             PromptContextType::Rules,
+            PromptContextType::McpResource,
         ]
     }
 
@@ -1264,8 +1266,14 @@ impl PromptEditor<BufferCodegen> {
             editor
         });
 
-        let mention_set = cx
-            .new(|_cx| MentionSet::new(project, Some(thread_store.clone()), prompt_store.clone()));
+        let mention_set = cx.new(|cx| {
+            MentionSet::new(
+                project,
+                Some(thread_store.clone()),
+                prompt_store.clone(),
+                cx,
+            )
+        });
 
         let model_selector_menu_handle = PopoverMenuHandle::default();
 
@@ -1418,8 +1426,14 @@ impl PromptEditor<TerminalCodegen> {
             editor
         });
 
-        let mention_set = cx
-            .new(|_cx| MentionSet::new(project, Some(thread_store.clone()), prompt_store.clone()));
+        let mention_set = cx.new(|cx| {
+            MentionSet::new(
+                project,
+                Some(thread_store.clone()),
+                prompt_store.clone(),
+                cx,
+            )
+        });
 
         let model_selector_menu_handle = PopoverMenuHandle::default();
 
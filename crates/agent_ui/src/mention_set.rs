@@ -5,6 +5,12 @@ use agent_servers::{AgentServer, AgentServerDelegate};
 use anyhow::{Context as _, Result, anyhow};
 use assistant_slash_commands::{codeblock_fence_for_path, collect_diagnostics_output};
 use collections::{HashMap, HashSet};
+use context_server::{
+    ContextServerId,
+    client::NotificationSubscription,
+    protocol::InitializedContextServerProtocol,
+    types::{self, Notification as _},
+};
 use editor::{
     Anchor, Editor, EditorSnapshot, ExcerptId, FoldPlaceholder, ToOffset,
     display_map::{Crease, CreaseId, CreaseMetadata, FoldId},
@@ -13,7 +19,7 @@ use editor::{
 use futures::{AsyncReadExt as _, FutureExt as _, future::Shared};
 use gpui::{
     AppContext, ClipboardEntry, Context, Empty, Entity, EntityId, Image, ImageFormat, Img,
-    SharedString, Task, WeakEntity,
+    SharedString, Subscription, Task, WeakEntity,
 };
 use http_client::{AsyncBody, HttpClientWithUrl};
 use itertools::Either;
@@ -21,7 +27,10 @@ use language::Buffer;
 use language_model::LanguageModelImage;
 use multi_buffer::MultiBufferRow;
 use postage::stream::Stream as _;
-use project::{Project, ProjectItem, ProjectPath, Worktree};
+use project::{
+    Project, ProjectItem, ProjectPath, Worktree,
+    context_server_store::{ContextServerStatus, ContextServerStore, ServerStatusChangedEvent},
+};
 use prompt_store::{PromptId, PromptStore};
 use rope::Point;
 use std::{
@@ -63,6 +72,16 @@ pub struct MentionSet {
     thread_store: Option<Entity<ThreadStore>>,
     prompt_store: Option<Entity<PromptStore>>,
     mentions: HashMap<CreaseId, (MentionUri, MentionTask)>,
+    resource_subscriptions: HashMap<ContextServerId, McpResourceSubscriptions>,
+    _subscriptions: Vec<Subscription>,
+}
+
+/// The MCP resources of a server that the set subscribed to with `resources/subscribe`,
+/// so that their mentions are re-read when the server reports an update.
+struct McpResourceSubscriptions {
+    client: Arc<InitializedContextServerProtocol>,
+    uris: HashSet<url::Url>,
+    _updated_subscription: NotificationSubscription,
 }
 
 impl MentionSet {
@@ -70,12 +89,28 @@ impl MentionSet {
         project: WeakEntity<Project>,
         thread_store: Option<Entity<ThreadStore>>,
         prompt_store: Option<Entity<PromptStore>>,
+        cx: &mut Context<Self>,
     ) -> Self {
+        let mut subscriptions = vec![cx.on_release(|this: &mut Self, cx| {
+            for (_, subscriptions) in this.resource_subscriptions.drain() {
+                unsubscribe_from_mcp_resources(subscriptions.client, subscriptions.uris, cx);
+            }
+        })];
+        if let Some(project) = project.upgrade() {
+            let context_server_store = project.read(cx).context_server_store();
+            subscriptions.push(cx.subscribe(
+                &context_server_store,
+                Self::handle_context_server_status_changed,
+            ));
+        }
+
         Self {
             project,
             thread_store,
             prompt_store,
             mentions: HashMap::default(),
+            resource_subscriptions: HashMap::default(),
+            _subscriptions: subscriptions,
         }
     }
 
@@ -106,12 +141,13 @@ impl MentionSet {
         })
     }
 
-    pub fn remove_invalid(&mut self, snapshot: &EditorSnapshot) {
+    pub fn remove_invalid(&mut self, snapshot: &EditorSnapshot, cx: &mut Context<Self>) {
         for (crease_id, crease) in snapshot.crease_snapshot.creases() {
             if !crease.range().start.is_valid(snapshot.buffer_snapshot()) {
                 self.mentions.remove(&crease_id);
             }
         }
+        self.release_mcp_resource_subscriptions(cx);
     }
 
     pub fn insert_mention(&mut self, crease_id: CreaseId, uri: MentionUri, task: MentionTask) {
@@ -147,6 +183,9 @@ impl MentionSet {
                 include_errors,
                 include_warnings,
             } => self.confirm_mention_for_diagnostics(include_errors, include_warnings, cx),
+            MentionUri::McpResource { server_id, uri, .. } => {
+                self.confirm_mention_for_mcp_resource(server_id, uri, cx)
+            }
             MentionUri::PastedImage
             | MentionUri::Selection { .. }
             | MentionUri::TerminalSelection { .. } => {
@@ -155,8 +194,9 @@ impl MentionSet {
         }
     }
 
-    pub fn remove_mention(&mut self, crease_id: &CreaseId) {
+    pub fn remove_mention(&mut self, crease_id: &CreaseId, cx: &mut Context<Self>) {
         self.mentions.remove(crease_id);
+        self.release_mcp_resource_subscriptions(cx);
     }
 
     pub fn creases(&self) -> HashSet<CreaseId> {
@@ -167,8 +207,13 @@ impl MentionSet {
         self.mentions.values().map(|(uri, _)| uri.clone()).collect()
     }
 
-    pub fn set_mentions(&mut self, mentions: HashMap<CreaseId, (MentionUri, MentionTask)>) {
+    pub fn set_mentions(
+        &mut self,
+        mentions: HashMap<CreaseId, (MentionUri, MentionTask)>,
+        cx: &mut Context<Self>,
+    ) {
         self.mentions = mentions;
+        self.release_mcp_resource_subscriptions(cx);
     }
 
     pub fn clear(&mut self) -> impl Iterator<Item = (CreaseId, (MentionUri, MentionTask))> {
@@ -276,6 +321,9 @@ impl MentionSet {
                 include_errors,
                 include_warnings,
             } => self.confirm_mention_for_diagnostics(include_errors, include_warnings, cx),
+            MentionUri::McpResource { server_id, uri, .. } => {
+                self.confirm_mention_for_mcp_resource(server_id, uri, cx)
+            }
             MentionUri::PastedImage => {
                 debug_panic!("pasted image URI should not be included in completions");
                 Task::ready(Err(anyhow!(
@@ -307,7 +355,7 @@ impl MentionSet {
                         // Remove mention
                         editor.edit([(start_anchor..end_anchor, "")], cx);
                     });
-                    this.mentions.remove(&crease_id);
+                    this.remove_mention(&crease_id, cx);
                 })
                 .ok();
             }
@@ -389,6 +437,225 @@ impl MentionSet {
         })
     }
 
+    fn confirm_mention_for_mcp_resource(
+        &mut self,
+        server_id: String,
+        uri: url::Url,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Mention>> {
+        let Some(project) = self.project.upgrade() else {
+            return Task::ready(Err(anyhow!("project not found")));
+        };
+        let server_id = ContextServerId(server_id.into());
+        let Some(server) = project
+            .read(cx)
+            .context_server_store()
+            .read(cx)
+            .get_running_server(&server_id)
+        else {
+            return Task::ready(Err(anyhow!("MCP server {server_id} is not running")));
+        };
+        let Some(client) = server.client() else {
+            return Task::ready(Err(anyhow!("MCP server {server_id} is not initialized")));
+        };
+
+        let subscribe = self.subscribe_to_mcp_resource(&server_id, &client, &uri, cx);
+        cx.spawn(async move |_, _| {
+            if let Some(subscribe) = subscribe {
+                subscribe.await;
+            }
+            read_mcp_resource(&client, uri).await
+        })
+    }
+
+    /// Subscribes to updates of an MCP resource, if the server supports it and the set isn't
+    /// subscribed to the resource yet.
+    fn subscribe_to_mcp_resource(
+        &mut self,
+        server_id: &ContextServerId,
+        client: &Arc<InitializedContextServerProtocol>,
+        uri: &url::Url,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<()>> {
+        let supports_subscribe = client
+            .initialize
+            .capabilities
+            .resources
+            .as_ref()
+            .and_then(|resources| resources.subscribe)
+            .unwrap_or(false);
+        if !supports_subscribe {
+            return None;
+        }
+
+        let subscriptions = self
+            .resource_subscriptions
+            .entry(server_id.clone())
+            .or_insert_with(|| {
+                let this = cx.entity().downgrade();
+                let server_id = server_id.clone();
+                let updated_subscription = client.on_notification(
+                    types::notifications::ResourcesUpdated::METHOD,
+                    Box::new(move |params, cx| {
+                        let Some(params) =
+                            serde_json::from_value::<types::ResourcesUpdatedParams>(params)
+                                .log_err()
+                        else {
+                            return;
+                        };
+                        let server_id = server_id.clone();
+                        let this = this.clone();
+                        cx.spawn(async move |cx| {
+                            this.update(cx, |this, cx| {
+                                this.refresh_mcp_resource(&server_id, &params.uri, cx)
+                            })
+                        })
+                        .detach();
+                    }),
+                );
+                McpResourceSubscriptions {
+                    client: client.clone(),
+                    uris: HashSet::default(),
+                    _updated_subscription: updated_subscription,
+                }
+            });
+        if !subscriptions.uris.insert(uri.clone()) {
+            return None;
+        }
+
+        let client = client.clone();
+        let uri = uri.clone();
+        Some(cx.spawn(async move |_, _| {
+            client
+                .request::<types::requests::ResourcesSubscribe>(types::ResourcesSubscribeParams {
+                    uri,
+                    meta: None,
+                })
+                .await
+                .log_err();
+        }))
+    }
+
+    /// Unsubscribes from the MCP resources that are no longer mentioned.
+    pub fn release_mcp_resource_subscriptions(&mut self, cx: &mut Context<Self>) {
+        let mentioned = self
+            .mentions
+            .values()
+            .filter_map(|(mention_uri, _)| match mention_uri {
+                MentionUri::McpResource { server_id, uri, .. } => Some((server_id.as_str(), uri)),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        self.resource_subscriptions
+            .retain(|server_id, subscriptions| {
+                let released = subscriptions
+                    .uris
+                    .iter()
+                    .filter(|uri| !mentioned.contains(&(server_id.0.as_ref(), *uri)))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                for uri in &released {
+                    subscriptions.uris.remove(uri);
+                }
+                unsubscribe_from_mcp_resources(subscriptions.client.clone(), released, cx);
+                !subscriptions.uris.is_empty()
+            });
+    }
+
+    /// Subscriptions end with the server's connection, so they are dropped when the server
+    /// stops and made again for the mentioned resources once it's running.
+    fn handle_context_server_status_changed(
+        &mut self,
+        context_server_store: Entity<ContextServerStore>,
+        event: &ServerStatusChangedEvent,
+        cx: &mut Context<Self>,
+    ) {
+        let ServerStatusChangedEvent { server_id, status } = event;
+        self.resource_subscriptions.remove(server_id);
+        if *status != ContextServerStatus::Running {
+            return;
+        }
+        let Some(client) = context_server_store
+            .read(cx)
+            .get_running_server(server_id)
+            .and_then(|server| server.client())
+        else {
+            return;
+        };
+
+        let uris = self
+            .mentions
+            .values()
+            .filter_map(|(mention_uri, _)| match mention_uri {
+                MentionUri::McpResource {
+                    server_id: id, uri, ..
+                } if id.as_str() == server_id.0.as_ref() => Some(uri.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for uri in uris {
+            if let Some(subscribe) = self.subscribe_to_mcp_resource(server_id, &client, &uri, cx) {
+                subscribe.detach();
+            }
+        }
+    }
+
+    /// Re-reads every attached mention of the given MCP resource after the server
+    /// reported that it changed.
+    fn refresh_mcp_resource(
+        &mut self,
+        server_id: &ContextServerId,
+        updated_uri: &str,
+        cx: &mut Context<Self>,
+    ) {
+        let crease_ids = self
+            .mentions
+            .iter()
+            .filter_map(|(crease_id, (mention_uri, _))| match mention_uri {
+                MentionUri::McpResource {
+                    server_id: id, uri, ..
+                } if id.as_str() == server_id.0.as_ref() && uri.as_str() == updated_uri => {
+                    Some(*crease_id)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if crease_ids.is_empty() {
+            return;
+        }
+        let Some(client) = self
+            .project
+            .upgrade()
+            .and_then(|project| {
+                project
+                    .read(cx)
+                    .context_server_store()
+                    .read(cx)
+                    .get_running_server(server_id)
+            })
+            .and_then(|server| server.client())
+        else {
+            return;
+        };
+        let Some(uri) = url::Url::parse(updated_uri).log_err() else {
+            return;
+        };
+
+        let task = cx
+            .spawn(async move |_, _| {
+                read_mcp_resource(&client, uri)
+                    .await
+                    .map_err(|e| e.to_string())
+            })
+            .shared();
+        for crease_id in crease_ids {
+            if let Some((_, mention_task)) = self.mentions.get_mut(&crease_id) {
+                *mention_task = task.clone();
+            }
+        }
+    }
+
     fn confirm_mention_for_symbol(
         &self,
         abs_path: PathBuf,
@@ -593,6 +860,7 @@ impl MentionSet {
 mod tests {
     use super::*;
 
+    use context_server::{ContextServer, test::FakeTransport};
     use fs::FakeFs;
     use gpui::TestAppContext;
     use project::Project;
@@ -602,6 +870,7 @@ mod tests {
     use serde_json::json;
     use settings::SettingsStore;
     use std::path::Path;
+    use std::sync::Mutex;
     use theme;
     use util::path;
 
@@ -623,7 +892,7 @@ mod tests {
         fs.insert_tree("/project", json!({"file": ""})).await;
         let project = Project::test(fs, [Path::new(path!("/project"))], cx).await;
         let thread_store = None;
-        let mention_set = cx.new(|_cx| MentionSet::new(project.downgrade(), thread_store, None));
+        let mention_set = cx.new(|cx| MentionSet::new(project.downgrade(), thread_store, None, cx));
 
         let task = mention_set.update(cx, |mention_set, cx| {
             mention_set.confirm_mention_for_thread(acp::SessionId::new("thread-1"), cx)
@@ -637,6 +906,146 @@ mod tests {
             "Unexpected error: {error:#}"
         );
     }
+
+    #[gpui::test]
+    async fn test_mcp_resource_mention_refresh(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/project", json!({"file": ""})).await;
+        let project = Project::test(fs, [Path::new(path!("/project"))], cx).await;
+        // Let the store settle on the configured servers, so that it doesn't stop the fake one.
+        cx.run_until_parked();
+
+        let text = Arc::new(Mutex::new("first version".to_string()));
+        let subscribed_uris = Arc::new(Mutex::new(Vec::new()));
+        let transport = Arc::new(
+            FakeTransport::new(cx.executor())
+                .on_request::<types::requests::Initialize, _>(|_| async {
+                    types::InitializeResponse {
+                        protocol_version: types::ProtocolVersion(
+                            types::LATEST_PROTOCOL_VERSION.to_string(),
+                        ),
+                        capabilities: types::ServerCapabilities {
+                            resources: Some(types::ResourcesCapabilities {
+                                subscribe: Some(true),
+                                list_changed: None,
+                            }),
+                            ..Default::default()
+                        },
+                        server_info: types::Implementation {
+                            name: "notes".to_string(),
+                            version: "1.0.0".to_string(),
+                        },
+                        meta: None,
+                    }
+                })
+                .on_request::<types::requests::ResourcesRead, _>({
+                    let text = text.clone();
+                    move |params| {
+                        let text = text.lock().unwrap().clone();
+                        async move {
+                            types::ResourcesReadResponse {
+                                contents: vec![types::ResourceContentsType::Text(
+                                    types::TextResourceContents {
+                                        uri: params.uri,
+                                        mime_type: None,
+                                        text,
+                                    },
+                                )],
+                                meta: None,
+                            }
+                        }
+                    }
+                })
+                .on_request::<types::requests::ResourcesSubscribe, _>({
+                    let subscribed_uris = subscribed_uris.clone();
+                    move |params| {
+                        subscribed_uris.lock().unwrap().push(params.uri);
+                        async {}
+                    }
+                })
+                .on_request::<types::requests::ResourcesUnsubscribe, _>({
+                    let subscribed_uris = subscribed_uris.clone();
+                    move |params| {
+                        subscribed_uris
+                            .lock()
+                            .unwrap()
+                            .retain(|uri| *uri != params.uri);
+                        async {}
+                    }
+                }),
+        );
+        let server = Arc::new(ContextServer::new(
+            ContextServerId("notes".into()),
+            transport.clone(),
+        ));
+        project.update(cx, |project, cx| {
+            project
+                .context_server_store()
+                .update(cx, |store, cx| store.test_start_server(server, cx))
+        });
+        cx.run_until_parked();
+
+        let mention_set = cx.new(|cx| MentionSet::new(project.downgrade(), None, None, cx));
+        let uri = url::Url::parse("notes://today").unwrap();
+        let crease_id = CreaseId::default();
+        mention_set.update(cx, |mention_set, cx| {
+            let task =
+                mention_set.confirm_mention_for_mcp_resource("notes".to_string(), uri.clone(), cx);
+            let task = cx
+                .spawn(async move |_, _| task.await.map_err(|e| e.to_string()))
+                .shared();
+            mention_set.insert_mention(
+                crease_id,
+                MentionUri::McpResource {
+                    server_id: "notes".to_string(),
+                    uri: uri.clone(),
+                    name: "Today".to_string(),
+                },
+                task,
+            );
+        });
+        cx.run_until_parked();
+        assert_eq!(*subscribed_uris.lock().unwrap(), vec![uri.clone()]);
+        assert_eq!(
+            mention_text(&mention_set, crease_id, cx).await,
+            "first version"
+        );
+
+        *text.lock().unwrap() = "second version".to_string();
+        transport
+            .notify::<types::notifications::ResourcesUpdated>(types::ResourcesUpdatedParams {
+                uri: uri.to_string(),
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            mention_text(&mention_set, crease_id, cx).await,
+            "second version"
+        );
+
+        mention_set.update(cx, |mention_set, cx| {
+            mention_set.remove_mention(&crease_id, cx)
+        });
+        cx.run_until_parked();
+        assert!(subscribed_uris.lock().unwrap().is_empty());
+    }
+
+    async fn mention_text(
+        mention_set: &Entity<MentionSet>,
+        crease_id: CreaseId,
+        cx: &mut TestAppContext,
+    ) -> String {
+        let contents = mention_set
+            .update(cx, |mention_set, cx| mention_set.contents(false, cx))
+            .await
+            .unwrap();
+        match &contents[&crease_id].1 {
+            Mention::Text { content, .. } => content.clone(),
+            mention => panic!("unexpected mention {mention:?}"),
+        }
+    }
 }
 
 /// Inserts a list of images into the editor as context mentions.
@@ -728,8 +1137,8 @@ pub(crate) async fn insert_images_as_context(
             editor.update(cx, |editor, cx| {
                 editor.edit([(start_anchor..end_anchor, "")], cx);
             });
-            mention_set.update(cx, |mention_set, _cx| {
-                mention_set.remove_mention(&crease_id)
+            mention_set.update(cx, |mention_set, cx| {
+                mention_set.remove_mention(&crease_id, cx)
             });
         }
     }
@@ -1105,6 +1514,67 @@ impl Render for ImageHover {
     }
 }
 
+fn unsubscribe_from_mcp_resources(
+    client: Arc<InitializedContextServerProtocol>,
+    uris: impl IntoIterator<Item = url::Url>,
+    cx: &mut App,
+) {
+    let uris = uris.into_iter().collect::<Vec<_>>();
+    if uris.is_empty() {
+        return;
+    }
+    cx.spawn(async move |_| {
+        for uri in uris {
+            client
+                .request::<types::requests::ResourcesUnsubscribe>(
+                    types::ResourcesUnsubscribeParams { uri, meta: None },
+                )
+                .await
+                .log_err();
+        }
+    })
+    .detach();
+}
+
+async fn read_mcp_resource(
+    client: &InitializedContextServerProtocol,
+    uri: url::Url,
+) -> Result<Mention> {
+    let response = client
+        .request::<types::requests::ResourcesRead>(types::ResourcesReadParams {
+            uri: uri.clone(),
+            meta: None,
+        })
+        .await?;
+
+    let mut content = String::new();
+    for contents in response.contents {
+        match contents {
+            types::ResourceContentsType::Text(text) => {
+                if !content.is_empty() {
+                    content.push('\n');
+                }
+                content.push_str(&text.text);
+            }
+            types::ResourceContentsType::Blob(blob) => {
+                log::debug!(
+                    "skipping binary contents of MCP resource {} ({})",
+                    blob.uri,
+                    blob.mime_type.as_deref().unwrap_or("unknown type")
+                );
+            }
+        }
+    }
+    if content.is_empty() {
+        return Err(anyhow!("MCP resource {uri} has no text contents"));
+    }
+
+    Ok(Mention::Text {
+        content,
+        tracked_buffers: Vec::new(),
+    })
+}
+
 async fn fetch_url_content(http_client: Arc<HttpClientWithUrl>, url: String) -> Result<String> {
     #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
     enum ContentType {
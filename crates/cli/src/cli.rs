@@ -15,6 +15,8 @@ pub enum CliRequest {
         urls: Vec<String>,
         diff_paths: Vec<[String; 2]>,
         diff_all: bool,
+        /// The `BASE`, `LOCAL`, `REMOTE` and `MERGED` paths of a three-way merge.
+        merge_paths: Option<[String; 4]>,
         wsl: Option<String>,
         wait: bool,
         open_new_workspace: Option<bool>,
@@ -58,7 +58,9 @@ Examples:
     `zed path-to-your-project`
           Open your project in Zed
     `zed -n path-to-file `
-          Open file/folder in a new window",
+          Open file/folder in a new window
+    `zed --wait --merge BASE LOCAL REMOTE MERGED`
+          Resolve a merge conflict, e.g. as `git mergetool`",
     after_help = "To read from stdin, append '-', e.g. 'ps axf | zed -'"
 )]
 struct Args {
@@ -122,6 +124,11 @@ struct Args {
     /// When directories are provided, recurses into them and shows all changed files in a single multi-diff view.
     #[arg(long, action = clap::ArgAction::Append, num_args = 2, value_names = ["OLD_PATH", "NEW_PATH"])]
     diff: Vec<String>,
+    /// Resolve a three-way merge, writing the result to MERGED. Exits with a non-zero status
+    /// if the merge view is closed with conflicts remaining, so that Zed can be used as
+    /// `git mergetool` together with `--wait`.
+    #[arg(long, num_args = 4, value_names = ["BASE", "LOCAL", "REMOTE", "MERGED"])]
+    merge: Option<Vec<String>>,
     /// Uninstall Zed from user system
     #[cfg(all(
         any(target_os = "linux", target_os = "macos"),
@@ -590,6 +597,19 @@ fn main() -> Result<()> {
         ]);
     }
 
+    let merge_paths = args
+        .merge
+        .as_ref()
+        .map(|merge| -> anyhow::Result<[String; 4]> {
+            Ok([
+                parse_path_with_position(&merge[0])?,
+                parse_path_with_position(&merge[1])?,
+                parse_path_with_position(&merge[2])?,
+                parse_path_with_position(&merge[3])?,
+            ])
+        })
+        .transpose()?;
+
     let (expanded_diff_paths, temp_dirs) = expand_directory_diff_pairs(diff_paths)?;
     diff_paths = expanded_diff_paths;
     // Prevent automatic cleanup of temp directories containing empty stub files
@@ -625,9 +645,9 @@ fn main() -> Result<()> {
         }
     }
 
-    // When only diff paths are provided (no regular paths), add the current
+    // When only diff or merge paths are provided (no regular paths), add the current
     // working directory so the workspace opens with the right context.
-    if paths.is_empty() && urls.is_empty() && !diff_paths.is_empty() {
+    if paths.is_empty() && urls.is_empty() && (!diff_paths.is_empty() || merge_paths.is_some()) {
         if let Ok(cwd) = env::current_dir() {
             paths.push(cwd.to_string_lossy().into_owned());
         }
@@ -664,6 +684,7 @@ fn main() -> Result<()> {
                     urls,
                     diff_paths,
                     diff_all: diff_all_mode,
+                    merge_paths,
                     wsl,
                     wait: args.wait,
                     open_new_workspace,
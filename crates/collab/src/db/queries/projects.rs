@@ -1015,6 +1015,9 @@ impl Database {
                         stash_entries: Vec::new(),
                         remote_upstream_url: db_repository_entry.remote_upstream_url.clone(),
                         remote_origin_url: db_repository_entry.remote_origin_url.clone(),
+                        rebase: None,
+                        submodules: Vec::new(),
+                        bisect: None,
                     });
                 }
             }
@@ -29,6 +29,7 @@ use multi_buffer::{AnchorRangeExt as _, MultiBufferRow};
 use pretty_assertions::assert_eq;
 use project::{
     ProgressToken, ProjectPath, SERVER_PROGRESS_THROTTLE_TIMEOUT,
+    debugger::breakpoint_store::{BreakpointEditAction, BreakpointState, BreakpointStore},
     lsp_store::lsp_ext_command::{ExpandedMacro, LspExtExpandMacro},
     trusted_worktrees::{PathTrust, TrustedWorktrees},
 };
@@ -4286,6 +4287,65 @@ async fn test_add_breakpoints(cx_a: &mut TestAppContext, cx_b: &mut TestAppConte
     assert_eq!(breakpoints_a, breakpoints_b);
 }
 
+#[gpui::test]
+async fn test_function_breakpoints(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let executor = cx_a.executor();
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+    client_a
+        .fs()
+        .insert_tree("/a", json!({ "main.rs": "fn main() {}" }))
+        .await;
+    let (project_a, _) = client_a.build_local_project("/a", cx_a).await;
+    let breakpoint_store_a = project_a.read_with(cx_a, |project, _| project.breakpoint_store());
+    breakpoint_store_a.update(cx_a, |store, cx| {
+        store.add_function_breakpoint("main".into(), cx);
+    });
+
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    let breakpoint_store_b = project_b.read_with(cx_b, |project, _| project.breakpoint_store());
+    executor.run_until_parked();
+
+    let function_breakpoints = |store: &Entity<BreakpointStore>, cx: &mut TestAppContext| {
+        store.read_with(cx, |store, _| {
+            store
+                .function_breakpoints()
+                .iter()
+                .map(|breakpoint| (breakpoint.name.to_string(), breakpoint.state))
+                .collect::<Vec<_>>()
+        })
+    };
+    // The guest receives the host's function breakpoints when joining.
+    assert_eq!(
+        function_breakpoints(&breakpoint_store_b, cx_b),
+        [("main".to_string(), BreakpointState::Enabled)]
+    );
+
+    // Changes made by the guest are applied on the host, and the other way around.
+    breakpoint_store_b.update(cx_b, |store, cx| {
+        store.add_function_breakpoint("helper".into(), cx);
+    });
+    executor.run_until_parked();
+    breakpoint_store_a.update(cx_a, |store, cx| {
+        store.edit_function_breakpoint("helper", BreakpointEditAction::InvertState, cx);
+        store.edit_function_breakpoint("main", BreakpointEditAction::Toggle, cx);
+    });
+    executor.run_until_parked();
+
+    let expected = [("helper".to_string(), BreakpointState::Disabled)];
+    assert_eq!(function_breakpoints(&breakpoint_store_a, cx_a), expected);
+    assert_eq!(function_breakpoints(&breakpoint_store_b, cx_b), expected);
+}
+
 #[gpui::test]
 async fn test_client_can_query_lsp_ext(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
     let mut server = TestServer::start(cx_a.executor()).await;
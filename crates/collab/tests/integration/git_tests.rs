@@ -1,7 +1,11 @@
-use std::path::Path;
+use std::{path::Path, sync::Arc};
 
 use call::ActiveCall;
-use git::status::{FileStatus, StatusCode, TrackedStatus};
+use git::{
+    Oid,
+    repository::{InitialGraphCommitData, RebaseControl, RebaseTodoAction, repo_path},
+    status::{FileStatus, StatusCode, TrackedStatus, UnmergedStatus, UnmergedStatusCode},
+};
 use git_ui::project_diff::ProjectDiff;
 use gpui::{AppContext as _, TestAppContext, VisualTestContext};
 use project::ProjectPath;
@@ -141,3 +145,170 @@ async fn test_project_diff(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext)
         );
     });
 }
+
+#[gpui::test]
+async fn test_interactive_rebase(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.background_executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+
+    client_a
+        .fs()
+        .insert_tree(path!("/a"), json!({ ".git": {}, "a.txt": "a\n" }))
+        .await;
+    // A linear history, oldest first, with three commits on top of the base.
+    let shas = (1..=4)
+        .map(|ix| format!("{ix:040x}").parse::<Oid>().unwrap())
+        .collect::<Vec<_>>();
+    let base = shas[0].to_string();
+    let dot_git = Path::new(path!("/a/.git"));
+    client_a.fs().set_graph_commits(
+        dot_git,
+        shas.iter()
+            .enumerate()
+            .rev()
+            .map(|(ix, sha)| {
+                Arc::new(InitialGraphCommitData {
+                    sha: *sha,
+                    parents: ix.checked_sub(1).map(|ix| shas[ix]).into_iter().collect(),
+                    ref_names: Vec::new(),
+                })
+            })
+            .collect(),
+    );
+    client_a
+        .fs()
+        .with_git_state(dot_git, true, |state| {
+            state.refs.insert("HEAD".into(), shas[3].to_string());
+            for (sha, subject) in shas.iter().zip(["Base", "First", "Second", "Third"]) {
+                state
+                    .commit_messages
+                    .insert(*sha, format!("{subject}\n\nBody"));
+            }
+        })
+        .unwrap();
+    let head = || {
+        client_a
+            .fs()
+            .with_git_state(dot_git, false, |state| {
+                let head = state.refs["HEAD"].parse::<Oid>().unwrap();
+                let commit = state
+                    .graph_commits
+                    .iter()
+                    .find(|commit| commit.sha == head)
+                    .unwrap();
+                (
+                    head,
+                    commit.parents.to_vec(),
+                    state.commit_messages[&head].clone(),
+                )
+            })
+            .unwrap()
+    };
+
+    let (project_a, _) = client_a.build_local_project(path!("/a"), cx_a).await;
+    let project_id = cx_a
+        .read(ActiveCall::global)
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    cx_a.run_until_parked();
+    let repository_b =
+        project_b.read_with(cx_b, |project, cx| project.active_repository(cx).unwrap());
+
+    let todo = repository_b
+        .update(cx_b, |repository, _| {
+            repository.rebase_todo(Some(base.clone()))
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        todo.iter()
+            .map(|entry| entry.subject.as_ref())
+            .collect::<Vec<_>>(),
+        ["First", "Second", "Third"]
+    );
+
+    // Move the last commit first, squash the first one into it, and drop the second one.
+    let [mut first, mut second, third] = todo.try_into().unwrap();
+    first.action = RebaseTodoAction::Squash;
+    second.action = RebaseTodoAction::Drop;
+    repository_b
+        .update(cx_b, |repository, cx| {
+            repository.rebase_interactive(Some(base.clone()), vec![third, first, second], cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    let (rebased_head, parents, message) = head();
+    assert_eq!(parents, [shas[0]]);
+    assert_eq!(message, "Third\n\nBody\n\nFirst\n\nBody");
+
+    // A rebase that stops for conflicts is reported to the guest, and can only be continued
+    // once they are resolved.
+    client_a
+        .fs()
+        .with_git_state(dot_git, true, |state| {
+            state.simulated_replay_conflicts.insert(
+                repo_path("a.txt"),
+                UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                },
+            );
+        })
+        .unwrap();
+    let mut todo = repository_b
+        .update(cx_b, |repository, _| {
+            repository.rebase_todo(Some(base.clone()))
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    todo[0].action = RebaseTodoAction::Reword;
+    todo[0].message = "Reworded".into();
+    repository_b
+        .update(cx_b, |repository, cx| {
+            repository.rebase_interactive(Some(base.clone()), todo, cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    cx_a.run_until_parked();
+    repository_b.read_with(cx_b, |repository, _| {
+        let rebase = repository.rebase.as_ref().unwrap();
+        assert_eq!((rebase.step, rebase.total_steps), (1, 1));
+    });
+    assert_eq!(head().0, rebased_head);
+
+    repository_b
+        .update(cx_b, |repository, cx| {
+            repository.control_rebase(RebaseControl::Continue, cx)
+        })
+        .await
+        .unwrap()
+        .unwrap_err();
+    client_a
+        .fs()
+        .with_git_state(dot_git, true, |state| state.unmerged_paths.clear())
+        .unwrap();
+    repository_b
+        .update(cx_b, |repository, cx| {
+            repository.control_rebase(RebaseControl::Continue, cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    cx_a.run_until_parked();
+    repository_b.read_with(cx_b, |repository, _| {
+        assert_eq!(repository.rebase, None);
+    });
+    let (_, parents, message) = head();
+    assert_eq!(parents, [shas[0]]);
+    assert_eq!(message, "Reworded");
+}
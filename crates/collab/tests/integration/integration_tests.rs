@@ -5444,6 +5444,318 @@ async fn test_document_highlights(
     });
 }
 
+#[gpui::test(iterations = 10)]
+async fn test_call_hierarchy(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree(
+            path!("/root-1"),
+            json!({
+                "a.rs": "fn one() { two(); }",
+                "b.rs": "fn two() { three(); }\nfn three() {}",
+            }),
+        )
+        .await;
+
+    client_a.language_registry().add(rust_lang());
+    let capabilities = lsp::ServerCapabilities {
+        call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+        ..lsp::ServerCapabilities::default()
+    };
+    let mut fake_language_servers = client_a.language_registry().register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: capabilities.clone(),
+            ..FakeLspAdapter::default()
+        },
+    );
+    client_b.language_registry().add(rust_lang());
+    client_b.language_registry().register_fake_lsp_adapter(
+        "Rust",
+        FakeLspAdapter {
+            capabilities,
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (project_a, worktree_id) = client_a.build_local_project(path!("/root-1"), cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+
+    let (buffer_b, _handle) = project_b
+        .update(cx_b, |p, cx| {
+            p.open_buffer_with_lsp((worktree_id, rel_path("b.rs")), cx)
+        })
+        .await
+        .unwrap();
+
+    let lsp_item =
+        |name: &str, path: &str, row: u32, start: u32, end: u32| lsp::CallHierarchyItem {
+            name: name.to_string(),
+            kind: lsp::SymbolKind::FUNCTION,
+            tags: None,
+            detail: Some(format!("fn {name}()")),
+            uri: lsp::Uri::from_file_path(path).unwrap(),
+            range: lsp::Range::new(lsp::Position::new(row, 0), lsp::Position::new(row, end)),
+            selection_range: lsp::Range::new(
+                lsp::Position::new(row, start),
+                lsp::Position::new(row, start + name.len() as u32),
+            ),
+            data: Some(json!({ "id": name })),
+        };
+
+    let fake_language_server = fake_language_servers.next().await.unwrap();
+    fake_language_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>(
+        move |params, _| async move {
+            assert_eq!(
+                params
+                    .text_document_position_params
+                    .text_document
+                    .uri
+                    .as_str(),
+                uri!("file:///root-1/b.rs")
+            );
+            assert_eq!(
+                params.text_document_position_params.position,
+                lsp::Position::new(0, 3)
+            );
+            Ok(Some(vec![lsp_item("two", path!("/root-1/b.rs"), 0, 3, 21)]))
+        },
+    );
+    fake_language_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(
+        move |params, _| async move {
+            // The item must reach the server unchanged after its round trip through the host.
+            assert_eq!(params.item.name, "two");
+            assert_eq!(params.item.data, Some(json!({ "id": "two" })));
+            Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                from: lsp_item("one", path!("/root-1/a.rs"), 0, 3, 19),
+                from_ranges: vec![lsp::Range::new(
+                    lsp::Position::new(0, 11),
+                    lsp::Position::new(0, 14),
+                )],
+            }]))
+        },
+    );
+    fake_language_server.set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.item.data, Some(json!({ "id": "two" })));
+            Ok(Some(vec![lsp::CallHierarchyOutgoingCall {
+                to: lsp_item("three", path!("/root-1/b.rs"), 1, 3, 13),
+                from_ranges: vec![lsp::Range::new(
+                    lsp::Position::new(0, 11),
+                    lsp::Position::new(0, 16),
+                )],
+            }]))
+        },
+    );
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    let items = project_b
+        .update(cx_b, |p, cx| p.prepare_call_hierarchy(&buffer_b, 3, cx))
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let item = items.into_iter().next().unwrap();
+    assert_eq!(item.name, "two");
+    assert_eq!(item.detail.as_deref(), Some("fn two()"));
+    assert_eq!(item.location.buffer, buffer_b);
+    buffer_b.read_with(cx_b, |buffer, _| {
+        assert_eq!(item.selection_range.to_offset(buffer), 3..6);
+    });
+
+    let incoming_calls = project_b
+        .update(cx_b, |p, cx| p.incoming_calls(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(incoming_calls.len(), 1);
+    cx_b.read(|cx| {
+        let call = &incoming_calls[0];
+        assert_eq!(call.item.name, "one");
+        let caller_buffer = call.item.location.buffer.read(cx);
+        assert_eq!(caller_buffer.text(), "fn one() { two(); }");
+        assert_eq!(call.item.selection_range.to_offset(caller_buffer), 3..6);
+        assert_eq!(call.call_sites.len(), 1);
+        assert_eq!(call.call_sites[0].buffer, call.item.location.buffer);
+        assert_eq!(call.call_sites[0].range.to_offset(caller_buffer), 11..14);
+    });
+
+    let outgoing_calls = project_b
+        .update(cx_b, |p, cx| p.outgoing_calls(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(outgoing_calls.len(), 1);
+    cx_b.read(|cx| {
+        let call = &outgoing_calls[0];
+        assert_eq!(call.item.name, "three");
+        let buffer = buffer_b.read(cx);
+        assert_eq!(call.item.location.buffer, buffer_b);
+        assert_eq!(call.item.selection_range.to_offset(buffer), 25..30);
+        assert_eq!(call.call_sites.len(), 1);
+        assert_eq!(call.call_sites[0].buffer, buffer_b);
+        assert_eq!(call.call_sites[0].range.to_offset(buffer), 11..16);
+    });
+}
+
+#[gpui::test(iterations = 10)]
+async fn test_type_hierarchy(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree(
+            path!("/root-1"),
+            json!({
+                "a.rs": "trait A {}",
+                "b.rs": "trait B: A {}",
+                "c.rs": "struct C;",
+            }),
+        )
+        .await;
+
+    client_a.language_registry().add(rust_lang());
+    let capabilities = lsp::ServerCapabilities {
+        type_hierarchy_provider: Some(lsp::TypeHierarchyServerCapability::Simple(true)),
+        ..lsp::ServerCapabilities::default()
+    };
+    let mut fake_language_servers = client_a.language_registry().register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: capabilities.clone(),
+            ..FakeLspAdapter::default()
+        },
+    );
+    client_b.language_registry().add(rust_lang());
+    client_b.language_registry().register_fake_lsp_adapter(
+        "Rust",
+        FakeLspAdapter {
+            capabilities,
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (project_a, worktree_id) = client_a.build_local_project(path!("/root-1"), cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+
+    let (buffer_b, _handle) = project_b
+        .update(cx_b, |p, cx| {
+            p.open_buffer_with_lsp((worktree_id, rel_path("b.rs")), cx)
+        })
+        .await
+        .unwrap();
+
+    let lsp_item = |name: &str, path: &str, start: u32, end: u32| lsp::TypeHierarchyItem {
+        name: name.to_string(),
+        kind: lsp::SymbolKind::INTERFACE,
+        tags: None,
+        detail: Some(format!("trait {name}")),
+        uri: lsp::Uri::from_file_path(path).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, end)),
+        selection_range: lsp::Range::new(
+            lsp::Position::new(0, start),
+            lsp::Position::new(0, start + 1),
+        ),
+        data: Some(json!({ "id": name })),
+    };
+
+    let fake_language_server = fake_language_servers.next().await.unwrap();
+    fake_language_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(
+        move |params, _| async move {
+            assert_eq!(
+                params
+                    .text_document_position_params
+                    .text_document
+                    .uri
+                    .as_str(),
+                uri!("file:///root-1/b.rs")
+            );
+            assert_eq!(
+                params.text_document_position_params.position,
+                lsp::Position::new(0, 6)
+            );
+            Ok(Some(vec![lsp_item("B", path!("/root-1/b.rs"), 6, 13)]))
+        },
+    );
+    fake_language_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+        move |params, _| async move {
+            // The item must reach the server unchanged after its round trip through the host.
+            assert_eq!(params.item.name, "B");
+            assert_eq!(params.item.data, Some(json!({ "id": "B" })));
+            Ok(Some(vec![lsp_item("A", path!("/root-1/a.rs"), 6, 10)]))
+        },
+    );
+    fake_language_server.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.item.data, Some(json!({ "id": "B" })));
+            Ok(Some(vec![lsp_item("C", path!("/root-1/c.rs"), 7, 9)]))
+        },
+    );
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    let items = project_b
+        .update(cx_b, |p, cx| p.prepare_type_hierarchy(&buffer_b, 6, cx))
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let item = items.into_iter().next().unwrap();
+    assert_eq!(item.name, "B");
+    assert_eq!(item.detail.as_deref(), Some("trait B"));
+    assert_eq!(item.location.buffer, buffer_b);
+    buffer_b.read_with(cx_b, |buffer, _| {
+        assert_eq!(item.selection_range.to_offset(buffer), 6..7);
+    });
+
+    let supertypes = project_b
+        .update(cx_b, |p, cx| p.supertypes(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(supertypes.len(), 1);
+    cx_b.read(|cx| {
+        let supertype = &supertypes[0];
+        assert_eq!(supertype.name, "A");
+        let supertype_buffer = supertype.location.buffer.read(cx);
+        assert_eq!(supertype_buffer.text(), "trait A {}");
+        assert_eq!(supertype.selection_range.to_offset(supertype_buffer), 6..7);
+    });
+
+    let subtypes = project_b
+        .update(cx_b, |p, cx| p.subtypes(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(subtypes.len(), 1);
+    assert_eq!(subtypes[0].name, "C");
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_lsp_hover(
     executor: BackgroundExecutor,
@@ -37,7 +37,7 @@ pub const INTERNAL_ERROR: i32 = -32603;
 
 type ResponseHandler = Box<dyn Send + FnOnce(Result<String, Error>)>;
 type NotificationHandler = Box<dyn Send + FnMut(Value, AsyncApp)>;
-type RequestHandler = Box<dyn Send + FnMut(RequestId, &RawValue, AsyncApp)>;
+type RequestHandler = Box<dyn Send + FnMut(Value, AsyncApp) -> Task<Result<Value>>>;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -53,6 +53,7 @@ pub(crate) struct Client {
     name: Arc<str>,
     subscription_set: Arc<Mutex<NotificationSubscriptionSet>>,
     response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
+    request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
     #[allow(clippy::type_complexity)]
     #[allow(dead_code)]
     io_tasks: Mutex<Option<(Task<Option<()>>, Task<Option<()>>)>>,
@@ -101,7 +102,6 @@ struct AnyResponse<'a> {
 }
 
 #[derive(Serialize, Deserialize)]
-#[allow(dead_code)]
 pub(crate) struct Response<T> {
     pub jsonrpc: &'static str,
     pub id: RequestId,
@@ -114,7 +114,6 @@ pub(crate) struct Response<T> {
 pub(crate) enum CspResult<T> {
     #[serde(rename = "result")]
     Ok(Option<T>),
-    #[allow(dead_code)]
     Error(Option<Error>),
 }
 
@@ -202,9 +201,11 @@ impl Client {
             let response_handlers = response_handlers.clone();
             let request_handlers = request_handlers.clone();
             let transport = transport.clone();
+            let outbound_tx = outbound_tx.clone();
             async move |cx| {
                 Self::handle_input(
                     transport,
+                    outbound_tx,
                     subscription_set,
                     request_handlers,
                     response_handlers,
@@ -238,6 +239,7 @@ impl Client {
             server_id,
             subscription_set,
             response_handlers,
+            request_handlers,
             name: server_name,
             next_id: Default::default(),
             outbound_tx,
@@ -252,11 +254,13 @@ impl Client {
     /// Handles input from the server's stdout.
     ///
     /// This function continuously reads lines from the provided stdout stream,
-    /// parses them as JSON-RPC responses or notifications, and dispatches them
-    /// to the appropriate handlers. It processes both responses (which are matched
-    /// to pending requests) and notifications (which trigger registered handlers).
+    /// parses them as JSON-RPC requests, responses or notifications, and dispatches them
+    /// to the appropriate handlers. It processes requests (which are answered by the
+    /// registered request handlers), responses (which are matched to pending requests)
+    /// and notifications (which trigger registered handlers).
     async fn handle_input(
         transport: Arc<dyn Transport>,
+        outbound_tx: channel::Sender<String>,
         subscription_set: Arc<Mutex<NotificationSubscriptionSet>>,
         request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
         response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
@@ -267,14 +271,52 @@ impl Client {
         while let Some(message) = receiver.next().await {
             log::trace!("recv: {}", &message);
             if let Ok(request) = serde_json::from_str::<AnyRequest>(&message) {
-                let mut request_handlers = request_handlers.lock();
-                if let Some(handler) = request_handlers.get_mut(request.method) {
-                    handler(
-                        request.id,
-                        request.params.unwrap_or(RawValue::NULL),
-                        cx.clone(),
-                    );
-                }
+                let params = request
+                    .params
+                    .and_then(|params| serde_json::from_str(params.get()).log_err())
+                    .unwrap_or(Value::Null);
+                let response = request_handlers
+                    .lock()
+                    .get_mut(request.method)
+                    .map(|handler| handler(params, cx.clone()));
+                let method = request.method.to_string();
+                let id = request.id;
+                let outbound_tx = outbound_tx.clone();
+                cx.spawn(async move |_| {
+                    let value = match response {
+                        Some(response) => match response.await {
+                            Ok(result) => CspResult::Ok(Some(result)),
+                            Err(error) => {
+                                log::error!(
+                                    "failed to handle context server request {method:?}: {error:#}"
+                                );
+                                let code = if error.is::<serde_json::Error>() {
+                                    INVALID_PARAMS
+                                } else {
+                                    INTERNAL_ERROR
+                                };
+                                CspResult::Error(Some(Error {
+                                    message: error.to_string(),
+                                    code,
+                                }))
+                            }
+                        },
+                        None => CspResult::Error(Some(Error {
+                            message: format!("Method not found: {method}"),
+                            code: METHOD_NOT_FOUND,
+                        })),
+                    };
+                    if let Some(response) = serde_json::to_string(&Response {
+                        jsonrpc: JSON_RPC_VERSION,
+                        id,
+                        value,
+                    })
+                    .log_err()
+                    {
+                        outbound_tx.send(response).await.log_err();
+                    }
+                })
+                .detach();
             } else if let Ok(response) = serde_json::from_str::<AnyResponse>(&message) {
                 if let Some(handlers) = response_handlers.lock().as_mut()
                     && let Some(handler) = handlers.remove(&response.id)
@@ -452,6 +494,12 @@ impl Client {
         Ok(())
     }
 
+    /// Registers a handler answering requests sent by the context server,
+    /// replacing any previous handler for the same method.
+    pub fn on_request(&self, method: &'static str, handler: RequestHandler) {
+        self.request_handlers.lock().insert(method, handler);
+    }
+
     #[must_use]
     pub fn on_notification(
         &self,
@@ -15,12 +15,16 @@ use std::{fmt::Display, path::PathBuf};
 
 use anyhow::Result;
 use client::Client;
-use gpui::AsyncApp;
+use gpui::{AsyncApp, Task};
 use parking_lot::RwLock;
+use serde_json::Value;
 pub use settings::ContextServerCommand;
 use url::Url;
 
 use crate::transport::HttpTransport;
+use crate::types::Request as _;
+
+type RequestHandler = Arc<dyn Send + Sync + Fn(Value, AsyncApp) -> Task<Result<Value>>>;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ContextServerId(pub Arc<str>);
@@ -41,6 +45,7 @@ pub struct ContextServer {
     client: RwLock<Option<Arc<crate::protocol::InitializedContextServerProtocol>>>,
     configuration: ContextServerTransport,
     request_timeout: Option<Duration>,
+    request_handlers: RwLock<HashMap<&'static str, RequestHandler>>,
 }
 
 impl ContextServer {
@@ -57,6 +62,7 @@ impl ContextServer {
                 working_directory.map(|directory| directory.to_path_buf()),
             ),
             request_timeout: None,
+            request_handlers: RwLock::default(),
         }
     }
 
@@ -94,6 +100,7 @@ impl ContextServer {
             client: RwLock::new(None),
             configuration: ContextServerTransport::Custom(transport),
             request_timeout,
+            request_handlers: RwLock::default(),
         }
     }
 
@@ -105,10 +112,50 @@ impl ContextServer {
         self.client.read().clone()
     }
 
+    /// Registers a handler answering the given request when the server sends it.
+    ///
+    /// Handlers are installed on the client when the server is started, and the
+    /// matching client capabilities are advertised during initialization.
+    pub fn on_request<T: types::Request>(
+        &self,
+        handler: impl 'static + Send + Sync + Fn(T::Params, AsyncApp) -> Task<Result<T::Response>>,
+    ) {
+        let handler: RequestHandler = Arc::new(move |params, cx| {
+            // Requests without parameters may still send an object carrying `_meta`.
+            let params = serde_json::from_value::<T::Params>(params)
+                .or_else(|error| serde_json::from_value(Value::Null).map_err(|_| error));
+            let params = match params {
+                Ok(params) => params,
+                Err(error) => return Task::ready(Err(error.into())),
+            };
+            let response = handler(params, cx.clone());
+            cx.spawn(async move |_| Ok(serde_json::to_value(response.await?)?))
+        });
+        self.request_handlers.write().insert(T::METHOD, handler);
+    }
+
     pub async fn start(&self, cx: &AsyncApp) -> Result<()> {
         self.initialize(self.new_client(cx)?).await
     }
 
+    fn client_capabilities(&self) -> types::ClientCapabilities {
+        let request_handlers = self.request_handlers.read();
+        types::ClientCapabilities {
+            experimental: None,
+            sampling: request_handlers
+                .contains_key(types::requests::CreateMessage::METHOD)
+                .then(|| serde_json::json!({})),
+            roots: request_handlers
+                .contains_key(types::requests::ListRoots::METHOD)
+                .then_some(types::RootsCapabilities {
+                    list_changed: Some(true),
+                }),
+            elicitation: request_handlers
+                .contains_key(types::requests::Elicit::METHOD)
+                .then(|| serde_json::json!({})),
+        }
+    }
+
     fn new_client(&self, cx: &AsyncApp) -> Result<Client> {
         Ok(match &self.configuration {
             ContextServerTransport::Stdio(command, working_directory) => Client::stdio(
@@ -134,12 +181,18 @@ impl ContextServer {
 
     async fn initialize(&self, client: Client) -> Result<()> {
         log::debug!("starting context server {}", self.id);
+        for (method, handler) in self.request_handlers.read().iter() {
+            let handler = handler.clone();
+            client.on_request(*method, Box::new(move |params, cx| handler(params, cx)));
+        }
         let protocol = crate::protocol::ModelContextProtocol::new(client);
         let client_info = types::Implementation {
             name: "Zed".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
         };
-        let initialized_protocol = protocol.initialize(client_info).await?;
+        let initialized_protocol = protocol
+            .initialize(client_info, self.client_capabilities())
+            .await?;
 
         log::debug!(
             "context server {} initialized: {:?}",
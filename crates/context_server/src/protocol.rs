@@ -27,6 +27,7 @@ impl ModelContextProtocol {
     fn supported_protocols() -> Vec<types::ProtocolVersion> {
         vec![
             types::ProtocolVersion(types::LATEST_PROTOCOL_VERSION.to_string()),
+            types::ProtocolVersion(types::VERSION_2025_03_26.to_string()),
             types::ProtocolVersion(types::VERSION_2024_11_05.to_string()),
         ]
     }
@@ -34,14 +35,11 @@ impl ModelContextProtocol {
     pub async fn initialize(
         self,
         client_info: types::Implementation,
+        capabilities: types::ClientCapabilities,
     ) -> Result<InitializedContextServerProtocol> {
         let params = types::InitializeParams {
             protocol_version: types::ProtocolVersion(types::LATEST_PROTOCOL_VERSION.to_string()),
-            capabilities: types::ClientCapabilities {
-                experimental: None,
-                sampling: None,
-                roots: None,
-            },
+            capabilities,
             meta: None,
             client_info,
         };
@@ -77,6 +75,7 @@ pub struct InitializedContextServerProtocol {
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ServerCapability {
+    Completions,
     Experimental,
     Logging,
     Prompts,
@@ -88,6 +87,7 @@ impl InitializedContextServerProtocol {
     /// Check if the server supports a specific capability
     pub fn capable(&self, capability: ServerCapability) -> bool {
         match capability {
+            ServerCapability::Completions => self.initialize.capabilities.completions.is_some(),
             ServerCapability::Experimental => self.initialize.capabilities.experimental.is_some(),
             ServerCapability::Logging => self.initialize.capabilities.logging.is_some(),
             ServerCapability::Prompts => self.initialize.capabilities.prompts.is_some(),
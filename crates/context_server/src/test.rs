@@ -1,8 +1,16 @@
 use anyhow::Context as _;
 use collections::HashMap;
-use futures::{FutureExt, Stream, StreamExt as _, future::BoxFuture, lock::Mutex};
+use futures::{
+    FutureExt, Stream, StreamExt as _, channel::oneshot, future::BoxFuture, lock::Mutex,
+};
 use gpui::BackgroundExecutor;
-use std::{pin::Pin, sync::Arc};
+use std::{
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering::SeqCst},
+    },
+};
 
 use crate::{
     transport::Transport,
@@ -41,6 +49,8 @@ pub struct FakeTransport {
     >,
     tx: futures::channel::mpsc::UnboundedSender<String>,
     rx: Arc<Mutex<futures::channel::mpsc::UnboundedReceiver<String>>>,
+    next_request_id: AtomicUsize,
+    pending_requests: parking_lot::Mutex<HashMap<String, oneshot::Sender<serde_json::Value>>>,
     executor: BackgroundExecutor,
 }
 
@@ -51,6 +61,8 @@ impl FakeTransport {
             request_handlers: Default::default(),
             tx,
             rx: Arc::new(Mutex::new(rx)),
+            next_request_id: AtomicUsize::new(0),
+            pending_requests: Default::default(),
             executor,
         }
     }
@@ -78,6 +90,47 @@ impl FakeTransport {
         );
         self
     }
+
+    /// Sends a request to the client, as a server would, and waits for the client's response.
+    pub async fn request<T: crate::types::Request>(
+        &self,
+        params: T::Params,
+    ) -> anyhow::Result<T::Response> {
+        let id = format!("fake-{}", self.next_request_id.fetch_add(1, SeqCst));
+        let (tx, rx) = oneshot::channel();
+        self.pending_requests.lock().insert(id.clone(), tx);
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": T::METHOD,
+            "params": params,
+        });
+        self.tx
+            .unbounded_send(request.to_string())
+            .context("sending a request")?;
+
+        let mut response = rx.await.context("client dropped the request")?;
+        if let Some(error) = response.get("error") {
+            anyhow::bail!("request failed: {error}");
+        }
+        let result = response
+            .get_mut("result")
+            .map(serde_json::Value::take)
+            .unwrap_or_default();
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Sends a notification to the client, as a server would.
+    pub fn notify<T: crate::types::Notification>(&self, params: T::Params) -> anyhow::Result<()> {
+        let notification = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": T::METHOD,
+            "params": params,
+        });
+        self.tx
+            .unbounded_send(notification.to_string())
+            .context("sending a notification")
+    }
 }
 
 #[async_trait::async_trait]
@@ -101,6 +154,10 @@ impl Transport for FakeTransport {
                 } else {
                     log::debug!("No handler registered for MCP request '{method}'");
                 }
+            } else if let Some(id) = msg.get("id").and_then(|id| id.as_str())
+                && let Some(tx) = self.pending_requests.lock().remove(id)
+            {
+                tx.send(msg).ok();
             }
         }
         Ok(())
@@ -8,10 +8,11 @@ use parking_lot::Mutex as SyncMutex;
 use smol::channel;
 use std::{pin::Pin, sync::Arc};
 
-use crate::transport::Transport;
+use crate::{transport::Transport, types::LATEST_PROTOCOL_VERSION};
 
 // Constants from MCP spec
 const HEADER_SESSION_ID: &str = "Mcp-Session-Id";
+const HEADER_PROTOCOL_VERSION: &str = "MCP-Protocol-Version";
 const EVENT_STREAM_MIME_TYPE: &str = "text/event-stream";
 const JSON_MIME_TYPE: &str = "application/json";
 
@@ -64,7 +65,8 @@ impl HttpTransport {
             .header(
                 "Accept",
                 format!("{}, {}", JSON_MIME_TYPE, EVENT_STREAM_MIME_TYPE),
-            );
+            )
+            .header(HEADER_PROTOCOL_VERSION, LATEST_PROTOCOL_VERSION);
 
         for (key, value) in &self.headers {
             request_builder = request_builder.header(key.as_str(), value.as_str());
@@ -240,7 +242,8 @@ impl Drop for HttpTransport {
                     let mut request_builder = Request::builder()
                         .method(Method::DELETE)
                         .uri(&endpoint)
-                        .header(HEADER_SESSION_ID, &session_id);
+                        .header(HEADER_SESSION_ID, &session_id)
+                        .header(HEADER_PROTOCOL_VERSION, LATEST_PROTOCOL_VERSION);
 
                     // Add authentication headers if present
                     for (key, value) in headers {
@@ -257,3 +260,43 @@ impl Drop for HttpTransport {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use http_client::FakeHttpClient;
+
+    #[gpui::test]
+    async fn test_protocol_version_header(cx: &mut TestAppContext) {
+        let protocol_versions = Arc::new(SyncMutex::new(Vec::new()));
+        let http_client = FakeHttpClient::create({
+            let protocol_versions = protocol_versions.clone();
+            move |request| {
+                protocol_versions.lock().push(
+                    request
+                        .headers()
+                        .get(HEADER_PROTOCOL_VERSION)
+                        .and_then(|value| value.to_str().ok())
+                        .map(ToString::to_string),
+                );
+                async move { Ok(Response::builder().status(202).body(AsyncBody::empty())?) }
+            }
+        });
+        let transport = HttpTransport::new(
+            http_client,
+            "https://mcp.example.com".to_string(),
+            HashMap::default(),
+            cx.executor(),
+        );
+
+        transport
+            .send(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#.to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            *protocol_versions.lock(),
+            [Some(LATEST_PROTOCOL_VERSION.to_string())]
+        );
+    }
+}
@@ -1,10 +1,13 @@
-use anyhow::{Context as _, Result, bail};
+use anyhow::{Context as _, Result, anyhow, bail};
 use async_trait::async_trait;
 use collections::HashMap;
-use dap::{StartDebuggingRequestArguments, adapters::DebugTaskDefinition};
+use dap::{
+    StartDebuggingRequestArguments, StartDebuggingRequestArgumentsRequest,
+    adapters::DebugTaskDefinition,
+};
 use gpui::AsyncApp;
 use std::ffi::OsStr;
-use task::{DebugScenario, ZedDebugConfig};
+use task::{DebugScenario, ReplayRequest, ZedDebugConfig};
 
 use crate::*;
 
@@ -15,6 +18,10 @@ impl GdbDebugAdapter {
     const ADAPTER_NAME: &'static str = "GDB";
 }
 
+fn is_replay(config: &serde_json::Value) -> bool {
+    config.get("request").and_then(|request| request.as_str()) == Some("replay")
+}
+
 /// Ensures that "-i=dap" is present in the GDB argument list.
 fn ensure_dap_interface(mut gdb_args: Vec<String>) -> Vec<String> {
     if !gdb_args.iter().any(|arg| arg.trim() == "-i=dap") {
@@ -71,6 +78,22 @@ impl DebugAdapter for GdbDebugAdapter {
         })
     }
 
+    async fn request_kind(
+        &self,
+        config: &serde_json::Value,
+    ) -> Result<StartDebuggingRequestArgumentsRequest> {
+        match config.get("request") {
+            Some(val) if val == "launch" => Ok(StartDebuggingRequestArgumentsRequest::Launch),
+            Some(val) if val == "attach" => Ok(StartDebuggingRequestArgumentsRequest::Attach),
+            // GDB is started by `rr replay`, already connected to the recording, and launching
+            // the program restarts the replay from the beginning.
+            Some(val) if val == "replay" => Ok(StartDebuggingRequestArgumentsRequest::Launch),
+            _ => Err(anyhow!(
+                "missing or invalid `request` field in config. Expected 'launch', 'attach' or 'replay'"
+            )),
+        }
+    }
+
     fn dap_schema(&self) -> serde_json::Value {
         json!({
             "oneOf": [
@@ -169,6 +192,55 @@ impl DebugAdapter for GdbDebugAdapter {
                             "required": ["pid"]
                         }
                     ]
+                },
+                {
+                    "allOf": [
+                        {
+                            "type": "object",
+                            "required": ["request"],
+                            "properties": {
+                                "request": {
+                                    "type": "string",
+                                    "enum": ["replay"],
+                                    "description": "Request to replay an execution recorded with `rr record`"
+                                }
+                            }
+                        },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "trace_dir": {
+                                    "type": "string",
+                                    "description": "The rr trace directory to replay. Defaults to the latest recording."
+                                },
+                                "process": {
+                                    "type": "string",
+                                    "description": "The recorded process to debug, as a pid or a command name. Defaults to the first process in the recording."
+                                },
+                                "rr_path": {
+                                    "type": "string",
+                                    "description": "Alternative path to the rr executable, if the one in standard path is not desirable"
+                                },
+                                "gdb_path": {
+                                    "type": "string",
+                                    "description": "Alternative path to the GDB executable, if the one in standard path is not desirable"
+                                },
+                                "gdb_args": {
+                                    "type": "array",
+                                    "items": {
+                                        "type":"string"
+                                    },
+                                    "description": "additional arguments given to GDB at startup, not the program debugged",
+                                    "default": []
+                                },
+                                "stopAtBeginningOfMainSubprogram": {
+                                    "type": "boolean",
+                                    "description": "When true, the replay will stop at the program's main procedure, like the 'start' command.",
+                                    "default": false
+                                }
+                            }
+                        }
+                    ]
                 }
             ]
         })
@@ -234,6 +306,28 @@ impl DebugAdapter for GdbDebugAdapter {
                 .or_insert_with(|| delegate.worktree_root_path().to_string_lossy().into());
         }
 
+        let (command, arguments) = if is_replay(&config.config) {
+            let replay: ReplayRequest = serde_json::from_value(config.config.clone())
+                .context("invalid rr replay configuration")?;
+            let rr_path = match &replay.rr_path {
+                Some(rr_path) => rr_path.to_string_lossy().into_owned(),
+                None => delegate
+                    .which(OsStr::new("rr"))
+                    .await
+                    .and_then(|p| p.to_str().map(|s| s.to_string()))
+                    .context("Could not find rr in path")?,
+            };
+            if let Some(configuration) = configuration.as_object_mut() {
+                configuration.insert("request".into(), "launch".into());
+                for key in ["trace_dir", "process", "rr_path"] {
+                    configuration.remove(key);
+                }
+            }
+            (rr_path, replay.rr_args(&gdb_path, gdb_args))
+        } else {
+            (gdb_path, gdb_args)
+        };
+
         let mut base_env = delegate.shell_env().await;
         base_env.extend(user_env.unwrap_or_default());
 
@@ -251,8 +345,8 @@ impl DebugAdapter for GdbDebugAdapter {
         base_env.extend(config_env);
 
         Ok(DebugAdapterBinary {
-            command: Some(gdb_path),
-            arguments: gdb_args,
+            command: Some(command),
+            arguments,
             envs: base_env,
             cwd: Some(delegate.worktree_root_path().to_path_buf()),
             connection: None,
@@ -1,4 +1,5 @@
 pub mod kvp;
+pub mod local_history;
 pub mod query;
 
 // Re-export
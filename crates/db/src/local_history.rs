@@ -0,0 +1,344 @@
+use std::{
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context as _, Result};
+use sha2::{Digest as _, Sha256};
+use sqlez::{
+    bindable::{Bind, Column, StaticColumnCount},
+    statement::Statement,
+};
+use sqlez_macros::sql;
+
+use crate::{query, sqlez::domain::Domain};
+
+/// Snapshots of local files' contents, taken as they are saved or changed on disk, so that
+/// earlier versions can be restored independently of version control.
+///
+/// Contents are stored once per distinct text and shared between the snapshots that have it.
+pub struct LocalHistoryStore(crate::sqlez::thread_safe_connection::ThreadSafeConnection);
+
+impl Domain for LocalHistoryStore {
+    const NAME: &str = stringify!(LocalHistoryStore);
+
+    const MIGRATIONS: &[&str] = &[
+        sql!(
+            CREATE TABLE local_history_contents(
+                id INTEGER PRIMARY KEY,
+                hash TEXT NOT NULL UNIQUE,
+                content TEXT NOT NULL
+            ) STRICT;
+
+            CREATE TABLE local_history_entries(
+                id INTEGER PRIMARY KEY,
+                path TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                source INTEGER NOT NULL,
+                content_id INTEGER NOT NULL REFERENCES local_history_contents(id)
+            ) STRICT;
+
+            CREATE INDEX local_history_entries_by_path ON local_history_entries(path, id);
+        ),
+        sql!(
+            CREATE INDEX local_history_entries_by_content ON local_history_entries(content_id);
+        ),
+    ];
+}
+
+/// How often the contents that no snapshot refers to anymore are removed.
+const REMOVE_UNUSED_CONTENTS_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// When the unused contents are due to be removed next, or `None` if they haven't been removed
+/// since launch.
+static NEXT_UNUSED_CONTENTS_REMOVAL: Mutex<Option<Instant>> = Mutex::new(None);
+
+crate::static_connection!(LOCAL_HISTORY, LocalHistoryStore, []);
+
+/// What caused a snapshot to be taken.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LocalHistorySource {
+    /// The file was saved from the editor.
+    Save,
+    /// The file's contents as they were before it changed on disk.
+    BeforeExternalChange,
+    /// The file's contents after it changed on disk.
+    ExternalChange,
+}
+
+impl LocalHistorySource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Save => "Saved",
+            Self::BeforeExternalChange => "Before External Change",
+            Self::ExternalChange => "External Change",
+        }
+    }
+}
+
+impl StaticColumnCount for LocalHistorySource {}
+
+impl Bind for LocalHistorySource {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        let value: i32 = match self {
+            Self::Save => 0,
+            Self::BeforeExternalChange => 1,
+            Self::ExternalChange => 2,
+        };
+        statement.bind(&value, start_index)
+    }
+}
+
+impl Column for LocalHistorySource {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let value = statement.column_int(start_index)?;
+        let source = match value {
+            0 => Self::Save,
+            1 => Self::BeforeExternalChange,
+            2 => Self::ExternalChange,
+            _ => anyhow::bail!("Invalid LocalHistorySource discriminant {value}"),
+        };
+        Ok((source, start_index + 1))
+    }
+}
+
+/// How many snapshots are kept for each file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LocalHistoryRetention {
+    /// The number of most recent snapshots to keep.
+    pub max_entries: usize,
+    /// How long a snapshot is kept for, regardless of the number of newer ones.
+    pub max_age: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalHistoryEntry {
+    pub id: i64,
+    /// When the snapshot was taken, in seconds since the Unix epoch.
+    pub timestamp: i64,
+    pub source: LocalHistorySource,
+}
+
+fn history_key(abs_path: &Path) -> String {
+    abs_path.to_string_lossy().into_owned()
+}
+
+fn unix_timestamp(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() as i64)
+}
+
+impl LocalHistoryStore {
+    /// Records a snapshot of the file at `abs_path`, unless its most recent snapshot already
+    /// has the same content, then drops the file's snapshots that fall outside of `retention`.
+    ///
+    /// Returns whether a snapshot was recorded.
+    pub async fn record(
+        &self,
+        abs_path: &Path,
+        content: String,
+        source: LocalHistorySource,
+        timestamp: SystemTime,
+        retention: LocalHistoryRetention,
+    ) -> Result<bool> {
+        let path = history_key(abs_path);
+        let hash = format!("{:x}", Sha256::digest(content.as_bytes()));
+        let timestamp = unix_timestamp(timestamp);
+        let cutoff = timestamp.saturating_sub(retention.max_age.as_secs() as i64);
+        let max_entries = retention.max_entries as i64;
+        let recorded = self
+            .write(move |connection| {
+                connection.with_savepoint("record_local_history", || {
+                    let latest_hash = connection.select_row_bound::<&str, String>(sql!(
+                        SELECT local_history_contents.hash
+                        FROM local_history_entries
+                        JOIN local_history_contents
+                            ON local_history_contents.id = local_history_entries.content_id
+                        WHERE local_history_entries.path = ?
+                        ORDER BY local_history_entries.id DESC
+                        LIMIT 1
+                    ))?(&path)?;
+                    if latest_hash.as_ref() == Some(&hash) {
+                        return Ok(false);
+                    }
+
+                    connection.exec_bound::<(&str, &str)>(sql!(
+                        INSERT OR IGNORE INTO local_history_contents(hash, content) VALUES (?, ?)
+                    ))?((&hash, &content))?;
+                    connection.exec_bound::<(&str, i64, LocalHistorySource, &str)>(sql!(
+                        INSERT INTO local_history_entries(path, timestamp, source, content_id)
+                        SELECT ?, ?, ?, id FROM local_history_contents WHERE hash = ?
+                    ))?((&path, timestamp, source, &hash))?;
+
+                    connection.exec_bound::<(&str, i64, &str, i64)>(sql!(
+                        DELETE FROM local_history_entries
+                        WHERE path = ? AND (
+                            timestamp < ? OR id NOT IN (
+                                SELECT id FROM local_history_entries
+                                WHERE path = ?
+                                ORDER BY id DESC
+                                LIMIT ?
+                            )
+                        )
+                    ))?((&path, cutoff, &path, max_entries))?;
+                    Ok(true)
+                })
+            })
+            .await
+            .context("Failed to record local history")?;
+
+        let remove_unused_contents = {
+            let mut next_removal = NEXT_UNUSED_CONTENTS_REMOVAL
+                .lock()
+                .unwrap_or_else(|error| error.into_inner());
+            let now = Instant::now();
+            let due = next_removal.is_none_or(|next| next <= now);
+            if due {
+                *next_removal = Some(now + REMOVE_UNUSED_CONTENTS_INTERVAL);
+            }
+            due
+        };
+        if remove_unused_contents {
+            self.remove_unused_contents().await?;
+        }
+        Ok(recorded)
+    }
+
+    /// Removes the contents that no snapshot refers to anymore. [`Self::record`] calls this at
+    /// most once an hour, rather than after every snapshot it drops.
+    pub async fn remove_unused_contents(&self) -> Result<()> {
+        self.write(|connection| {
+            connection.exec(sql!(
+                DELETE FROM local_history_contents
+                WHERE NOT EXISTS (
+                    SELECT 1 FROM local_history_entries
+                    WHERE local_history_entries.content_id = local_history_contents.id
+                )
+            ))?()
+        })
+        .await
+        .context("Failed to remove unused local history contents")
+    }
+
+    /// The snapshots of the file at `abs_path`, most recent first.
+    pub fn entries(&self, abs_path: &Path) -> Result<Vec<LocalHistoryEntry>> {
+        let path = history_key(abs_path);
+        let rows = self.select_bound::<&str, (i64, i64, LocalHistorySource)>(sql!(
+            SELECT id, timestamp, source FROM local_history_entries
+            WHERE path = ?
+            ORDER BY id DESC
+        ))?(&path)
+        .context("Failed to read local history entries")?;
+        Ok(rows
+            .into_iter()
+            .map(|(id, timestamp, source)| LocalHistoryEntry {
+                id,
+                timestamp,
+                source,
+            })
+            .collect())
+    }
+
+    query! {
+        pub fn content(entry_id: i64) -> Result<Option<String>> {
+            SELECT local_history_contents.content
+            FROM local_history_entries
+            JOIN local_history_contents
+                ON local_history_contents.id = local_history_entries.content_id
+            WHERE local_history_entries.id = (?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RETENTION: LocalHistoryRetention = LocalHistoryRetention {
+        max_entries: 3,
+        max_age: Duration::from_secs(60 * 60),
+    };
+
+    #[gpui::test]
+    async fn test_local_history() {
+        let db = LocalHistoryStore::open_test_db("test_local_history").await;
+        let now = SystemTime::now();
+        let path = Path::new("/root/a.txt");
+        let other_path = Path::new("/root/b.txt");
+
+        for (content, source) in [
+            ("one", LocalHistorySource::Save),
+            ("one", LocalHistorySource::ExternalChange),
+            ("two", LocalHistorySource::Save),
+        ] {
+            db.record(path, content.into(), source, now, RETENTION)
+                .await
+                .unwrap();
+        }
+        db.record(
+            other_path,
+            "one".into(),
+            LocalHistorySource::Save,
+            now,
+            RETENTION,
+        )
+        .await
+        .unwrap();
+
+        // Consecutive snapshots with the same content are recorded once.
+        let entries = db.entries(path).unwrap();
+        assert_eq!(
+            entries.iter().map(|entry| entry.source).collect::<Vec<_>>(),
+            [LocalHistorySource::Save, LocalHistorySource::Save]
+        );
+        assert_eq!(db.content(entries[0].id).unwrap().as_deref(), Some("two"));
+        assert_eq!(db.content(entries[1].id).unwrap().as_deref(), Some("one"));
+
+        // Only the most recent snapshots are kept.
+        for content in ["three", "four"] {
+            db.record(
+                path,
+                content.into(),
+                LocalHistorySource::BeforeExternalChange,
+                now,
+                RETENTION,
+            )
+            .await
+            .unwrap();
+        }
+        let entries = db.entries(path).unwrap();
+        let contents = entries
+            .iter()
+            .map(|entry| db.content(entry.id).unwrap().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(contents, ["four", "three", "two"]);
+        let b_entries = db.entries(other_path).unwrap();
+        assert_eq!(db.content(b_entries[0].id).unwrap().as_deref(), Some("one"));
+
+        // Snapshots older than the maximum age are dropped.
+        db.record(
+            path,
+            "five".into(),
+            LocalHistorySource::Save,
+            now + Duration::from_secs(2 * 60 * 60),
+            RETENTION,
+        )
+        .await
+        .unwrap();
+        let entries = db.entries(path).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(db.content(entries[0].id).unwrap().as_deref(), Some("five"));
+
+        // The contents of dropped snapshots are removed, unless another file's snapshot
+        // shares them.
+        db.remove_unused_contents().await.unwrap();
+        let content_count = db
+            .select_row::<i64>(sql!(SELECT COUNT(*) FROM local_history_contents))
+            .unwrap()()
+        .unwrap();
+        assert_eq!(content_count, Some(2));
+        let b_entries = db.entries(other_path).unwrap();
+        assert_eq!(db.content(b_entries[0].id).unwrap().as_deref(), Some("one"));
+    }
+}
@@ -5,9 +5,10 @@ use crate::session::running::breakpoint_list::BreakpointList;
 
 use crate::{
     ClearAllBreakpoints, Continue, CopyDebugAdapterArguments, Detach, FocusBreakpointList,
-    FocusConsole, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal, FocusVariables,
-    NewProcessModal, NewProcessMode, Pause, RerunSession, StepInto, StepOut, StepOver, Stop,
-    ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker, persistence, spawn_task_or_modal,
+    FocusConsole, FocusDisassembly, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal,
+    FocusVariables, NewProcessModal, NewProcessMode, Pause, RerunSession, ReverseContinue,
+    StepBack, StepInto, StepOut, StepOver, Stop, ToggleExpandItem, ToggleSessionPicker,
+    ToggleThreadPicker, persistence, spawn_task_or_modal,
 };
 use anyhow::{Context as _, Result, anyhow};
 use collections::IndexMap;
@@ -812,6 +813,66 @@ impl DebugPanel {
                                                 }
                                             }),
                                     )
+                                    .when(
+                                        capabilities.supports_step_back.unwrap_or_default(),
+                                        |this| {
+                                            this.child(Divider::vertical())
+                                                .child(
+                                                    IconButton::new(
+                                                        "debug-reverse-continue",
+                                                        IconName::DebugReverseContinue,
+                                                    )
+                                                    .icon_size(IconSize::Small)
+                                                    .on_click(window.listener_for(
+                                                        running_state,
+                                                        |this, _, _window, cx| {
+                                                            this.reverse_continue(cx);
+                                                        },
+                                                    ))
+                                                    .disabled(
+                                                        thread_status != ThreadStatus::Stopped,
+                                                    )
+                                                    .tooltip({
+                                                        let focus_handle = focus_handle.clone();
+                                                        move |_window, cx| {
+                                                            Tooltip::for_action_in(
+                                                                "Reverse Continue",
+                                                                &ReverseContinue,
+                                                                &focus_handle,
+                                                                cx,
+                                                            )
+                                                        }
+                                                    }),
+                                                )
+                                                .child(
+                                                    IconButton::new(
+                                                        "debug-step-back",
+                                                        IconName::DebugStepBack,
+                                                    )
+                                                    .icon_size(IconSize::Small)
+                                                    .on_click(window.listener_for(
+                                                        running_state,
+                                                        |this, _, _window, cx| {
+                                                            this.step_back(cx);
+                                                        },
+                                                    ))
+                                                    .disabled(
+                                                        thread_status != ThreadStatus::Stopped,
+                                                    )
+                                                    .tooltip({
+                                                        let focus_handle = focus_handle.clone();
+                                                        move |_window, cx| {
+                                                            Tooltip::for_action_in(
+                                                                "Step Back",
+                                                                &StepBack,
+                                                                &focus_handle,
+                                                                cx,
+                                                            )
+                                                        }
+                                                    }),
+                                                )
+                                        },
+                                    )
                                     .child(Divider::vertical())
                                     .child(
                                         IconButton::new("debug-restart", IconName::RotateCcw)
@@ -1738,6 +1799,15 @@ impl Render for DebugPanel {
                     .ok();
                 }
             })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusDisassembly, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+                    })
+                    .ok();
+                }
+            })
             .on_action({
                 let this = this.clone();
                 move |_: &ToggleThreadPicker, window, cx| {
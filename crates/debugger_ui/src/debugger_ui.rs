@@ -1,17 +1,19 @@
-use std::any::TypeId;
+use std::{any::TypeId, path::Path, sync::Arc};
 
 use debugger_panel::DebugPanel;
 use editor::{Editor, MultiBufferOffsetUtf16};
-use gpui::{Action, App, DispatchPhase, EntityInputHandler, actions};
+use gpui::{
+    Action, App, Context, DispatchPhase, Entity, EntityInputHandler, WeakEntity, Window, actions,
+};
 use new_process_modal::{NewProcessModal, NewProcessMode};
 use onboarding_modal::DebuggerOnboardingModal;
 use project::debugger::{self, breakpoint_store::SourceBreakpoint, session::ThreadStatus};
 use schemars::JsonSchema;
 use serde::Deserialize;
-use session::DebugSession;
+use session::{DebugSession, running::RunningState};
 use stack_trace_view::StackTraceView;
 use tasks_ui::{Spawn, TaskOverrides};
-use ui::{FluentBuilder, InteractiveElement};
+use ui::{ContextMenu, FluentBuilder, InteractiveElement};
 use util::maybe;
 use workspace::{ItemHandle, ShutdownDebugAdapters, Workspace};
 use zed_actions::ToggleFocus;
@@ -49,10 +51,16 @@ actions!(
         StepInto,
         /// Steps over the current line.
         StepOver,
+        /// Steps into the next machine instruction.
+        StepIntoInstruction,
+        /// Steps over the next machine instruction.
+        StepOverInstruction,
         /// Steps out of the current function.
         StepOut,
         /// Steps back to the previous statement.
         StepBack,
+        /// Runs the program backwards until the previous breakpoint.
+        ReverseContinue,
         /// Stops the debugging session.
         Stop,
         /// Toggles whether to ignore all breakpoints.
@@ -73,6 +81,8 @@ actions!(
         FocusLoadedSources,
         /// Focuses on the terminal panel.
         FocusTerminal,
+        /// Focuses on the disassembly panel.
+        FocusDisassembly,
         /// Shows the stack trace for the current thread.
         ShowStackTrace,
         /// Toggles the thread picker dropdown.
@@ -163,6 +173,8 @@ pub fn init(cx: &mut App) {
 
                 let caps = running_state.capabilities(cx);
                 let supports_step_back = caps.supports_step_back.unwrap_or_default();
+                let supports_instruction_stepping =
+                    caps.supports_stepping_granularity.unwrap_or_default();
                 let supports_detach = running_state.session().read(cx).is_attached();
                 let status = running_state.thread_status(cx);
 
@@ -188,6 +200,24 @@ pub fn init(cx: &mut App) {
                             active_item.update(cx, |item, cx| item.step_over(cx)).ok();
                         }
                     })
+                    .when(supports_instruction_stepping, |div| {
+                        div.on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepIntoInstruction, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.step_in_instruction(cx))
+                                    .ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepOverInstruction, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.step_over_instruction(cx))
+                                    .ok();
+                            }
+                        })
+                    })
                     .on_action({
                         let active_item = active_item.clone();
                         move |_: &StepOut, _, cx| {
@@ -195,9 +225,19 @@ pub fn init(cx: &mut App) {
                         }
                     })
                     .when(supports_step_back, |div| {
-                        let active_item = active_item.clone();
-                        div.on_action(move |_: &StepBack, _, cx| {
-                            active_item.update(cx, |item, cx| item.step_back(cx)).ok();
+                        div.on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepBack, _, cx| {
+                                active_item.update(cx, |item, cx| item.step_back(cx)).ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &ReverseContinue, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.reverse_continue(cx))
+                                    .ok();
+                            }
                         })
                     })
                     .on_action({
@@ -336,27 +376,10 @@ pub fn init(cx: &mut App) {
                                     return;
                                 }
                                 maybe!({
-                                    let (buffer, position, _) = editor
-                                        .update(cx, |editor, cx| {
-                                            let cursor_point: language::Point = editor
-                                                .selections
-                                                .newest(&editor.display_snapshot(cx))
-                                                .head();
-
-                                            editor
-                                                .buffer()
-                                                .read(cx)
-                                                .point_to_buffer_point(cursor_point, cx)
-                                        })
-                                        .ok()??;
-
-                                    let path =
-                                debugger::breakpoint_store::BreakpointStore::abs_path_from_buffer(
-                                    &buffer, cx,
-                                )?;
+                                    let (path, row) = cursor_source_position(&editor, cx)?;
 
                                     let source_breakpoint = SourceBreakpoint {
-                                        row: position.row,
+                                        row,
                                         path,
                                         message: None,
                                         condition: None,
@@ -384,6 +407,88 @@ pub fn init(cx: &mut App) {
                         },
                     );
 
+                    let capabilities = session.capabilities();
+                    window.on_action_when(
+                        session.any_stopped_thread()
+                            && capabilities
+                                .supports_goto_targets_request
+                                .unwrap_or_default(),
+                        TypeId::of::<editor::actions::JumpToCursor>(),
+                        {
+                            let editor = editor.clone();
+                            let active_session = active_session.clone();
+                            move |_, phase, _, cx| {
+                                if phase != DispatchPhase::Bubble {
+                                    return;
+                                }
+                                maybe!({
+                                    let (path, row) = cursor_source_position(&editor, cx)?;
+                                    let running_state =
+                                        active_session.read(cx).running_state().clone();
+                                    let thread_id = running_state.read(cx).selected_thread_id()?;
+                                    running_state
+                                        .read(cx)
+                                        .session()
+                                        .update(cx, |session, cx| {
+                                            session.jump_to_position(path, row, thread_id, cx)
+                                        })
+                                        .detach_and_log_err(cx);
+                                    Some(())
+                                });
+                            }
+                        },
+                    );
+
+                    window.on_action_when(
+                        session.any_stopped_thread()
+                            && capabilities
+                                .supports_step_in_targets_request
+                                .unwrap_or_default(),
+                        TypeId::of::<editor::actions::StepIntoTarget>(),
+                        {
+                            let editor = editor.clone();
+                            let active_session = active_session.clone();
+                            move |_, phase, window, cx| {
+                                if phase != DispatchPhase::Bubble {
+                                    return;
+                                }
+                                maybe!({
+                                    let running_state =
+                                        active_session.read(cx).running_state().clone();
+                                    let stack_frame_id =
+                                        running_state.read(cx).selected_stack_frame_id(cx)?;
+                                    let targets = running_state
+                                        .read(cx)
+                                        .session()
+                                        .update(cx, |session, cx| {
+                                            session.step_in_targets(stack_frame_id, cx)
+                                        });
+                                    let editor = editor.upgrade()?;
+                                    editor.update(cx, |_, cx| {
+                                        cx.spawn_in(window, async move |editor, cx| {
+                                            let Some(targets) = targets.await else {
+                                                return;
+                                            };
+                                            editor
+                                                .update_in(cx, |editor, window, cx| {
+                                                    show_step_in_targets(
+                                                        editor,
+                                                        targets,
+                                                        running_state,
+                                                        window,
+                                                        cx,
+                                                    );
+                                                })
+                                                .ok();
+                                        })
+                                        .detach();
+                                    });
+                                    Some(())
+                                });
+                            }
+                        },
+                    );
+
                     window.on_action(
                         TypeId::of::<editor::actions::EvaluateSelectedText>(),
                         move |_, _, window, cx| {
@@ -437,6 +542,54 @@ pub fn init(cx: &mut App) {
     .detach();
 }
 
+/// Returns the absolute path and zero-based row of the newest cursor in `editor`.
+fn cursor_source_position(editor: &WeakEntity<Editor>, cx: &mut App) -> Option<(Arc<Path>, u32)> {
+    let (buffer, position, _) = editor
+        .update(cx, |editor, cx| {
+            let cursor_point: language::Point = editor
+                .selections
+                .newest(&editor.display_snapshot(cx))
+                .head();
+
+            editor
+                .buffer()
+                .read(cx)
+                .point_to_buffer_point(cursor_point, cx)
+        })
+        .ok()??;
+
+    let path = debugger::breakpoint_store::BreakpointStore::abs_path_from_buffer(&buffer, cx)?;
+    Some((path, position.row))
+}
+
+/// Steps into the only call on the current line, or lets the user pick one when there are several.
+fn show_step_in_targets(
+    editor: &mut Editor,
+    targets: Vec<dap::StepInTarget>,
+    running_state: Entity<RunningState>,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    match targets.as_slice() {
+        [] => {}
+        [target] => {
+            running_state.update(cx, |state, cx| state.step_in_target(target.id, cx));
+        }
+        _ => {
+            let context_menu = ContextMenu::build(window, cx, |mut menu, _, _| {
+                for target in targets {
+                    let running_state = running_state.clone();
+                    menu = menu.entry(target.label, None, move |_, cx| {
+                        running_state.update(cx, |state, cx| state.step_in_target(target.id, cx));
+                    });
+                }
+                menu
+            });
+            editor.deploy_context_menu_at_cursor(context_menu, window, cx);
+        }
+    }
+}
+
 fn spawn_task_or_modal(
     workspace: &mut Workspace,
     action: &Spawn,
@@ -1,5 +1,6 @@
 pub(crate) mod breakpoint_list;
 pub(crate) mod console;
+pub(crate) mod disassembly_view;
 pub(crate) mod loaded_source_list;
 pub(crate) mod memory_view;
 pub(crate) mod module_list;
@@ -18,7 +19,7 @@ use crate::{
     attach_modal::{AttachModal, ModalIntent},
     new_process_modal::resolve_path,
     persistence::{self, DebuggerPaneItem, SerializedLayout},
-    session::running::memory_view::MemoryView,
+    session::running::{disassembly_view::DisassemblyView, memory_view::MemoryView},
 };
 
 use anyhow::{Context as _, Result, anyhow, bail};
@@ -26,7 +27,7 @@ use breakpoint_list::BreakpointList;
 use collections::{HashMap, IndexMap};
 use console::Console;
 use dap::{
-    Capabilities, DapRegistry, RunInTerminalRequestArguments, Thread,
+    Capabilities, DapRegistry, RunInTerminalRequestArguments, SteppingGranularity, Thread,
     adapters::{DebugAdapterName, DebugTaskDefinition},
     client::SessionId,
     debugger_settings::DebuggerSettings,
@@ -89,6 +90,7 @@ pub struct RunningState {
     pub(crate) scenario: Option<DebugScenario>,
     pub(crate) scenario_context: Option<DebugScenarioContext>,
     memory_view: Entity<MemoryView>,
+    disassembly_view: Entity<DisassemblyView>,
 }
 
 impl RunningState {
@@ -751,6 +753,8 @@ impl RunningState {
                 cx,
             )
         });
+        let disassembly_view =
+            cx.new(|cx| DisassemblyView::new(session.clone(), &stack_frame_list, cx));
         let variable_list = cx.new(|cx| {
             VariableList::new(
                 session.clone(),
@@ -873,6 +877,7 @@ impl RunningState {
                 &loaded_source_list,
                 &debug_terminal,
                 &memory_view,
+                &disassembly_view,
                 &mut pane_close_subscriptions,
                 window,
                 cx,
@@ -902,6 +907,7 @@ impl RunningState {
 
         Self {
             memory_view,
+            disassembly_view,
             session,
             workspace,
             focus_handle,
@@ -1268,6 +1274,8 @@ impl RunningState {
             show_summary: false,
             show_command: false,
             show_rerun: false,
+            problem_matchers: Vec::new(),
+            diagnostics_id: task::TaskId("debug".to_string()),
         };
 
         let workspace = self.workspace.clone();
@@ -1348,6 +1356,12 @@ impl RunningState {
                 item_kind,
                 cx,
             )),
+            DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                self.disassembly_view.focus_handle(cx),
+                self.disassembly_view.clone().into(),
+                item_kind,
+                cx,
+            )),
         }
     }
 
@@ -1562,6 +1576,11 @@ impl RunningState {
         &self.variable_list
     }
 
+    #[cfg(test)]
+    pub(crate) fn disassembly_view(&self) -> &Entity<DisassemblyView> {
+        &self.disassembly_view
+    }
+
     #[cfg(test)]
     pub(crate) fn serialized_layout(&self, cx: &App) -> SerializedLayout {
         persistence::build_serialized_layout(&self.panes.root, self.dock_axis, cx)
@@ -1650,6 +1669,38 @@ impl RunningState {
         });
     }
 
+    pub(crate) fn step_in_target(&mut self, target_id: u64, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        let granularity = DebuggerSettings::get_global(cx).stepping_granularity;
+
+        self.session().update(cx, |state, cx| {
+            state.step_in_target(thread_id, target_id, granularity, cx);
+        });
+    }
+
+    pub(crate) fn step_over_instruction(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.step_over(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
+    pub(crate) fn step_in_instruction(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.step_in(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
     pub(crate) fn step_out(&mut self, cx: &mut Context<Self>) {
         let Some(thread_id) = self.thread_id else {
             return;
@@ -1674,6 +1725,16 @@ impl RunningState {
         });
     }
 
+    pub(crate) fn reverse_continue(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.reverse_continue(thread_id, cx);
+        });
+    }
+
     pub fn rerun_session(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some((scenario, context)) = self.scenario.take().zip(self.scenario_context.take())
             && scenario.build.is_some()
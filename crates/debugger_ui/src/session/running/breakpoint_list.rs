@@ -17,7 +17,9 @@ use language::Point;
 use project::{
     Project,
     debugger::{
-        breakpoint_store::{BreakpointEditAction, BreakpointStore, SourceBreakpoint},
+        breakpoint_store::{
+            self, BreakpointEditAction, BreakpointState, BreakpointStore, SourceBreakpoint,
+        },
         dap_store::{DapStore, PersistedAdapterOptions},
         session::Session,
     },
@@ -37,12 +39,15 @@ actions!(
         /// Navigates to the previous breakpoint property in the list.
         PreviousBreakpointProperty,
         /// Navigates to the next breakpoint property in the list.
-        NextBreakpointProperty
+        NextBreakpointProperty,
+        /// Adds a breakpoint on a function, by its name.
+        AddFunctionBreakpoint
     ]
 );
 #[derive(Clone, Copy, PartialEq)]
 pub(crate) enum SelectedBreakpointKind {
     Source,
+    Function,
     Exception,
     Data,
 }
@@ -59,6 +64,8 @@ pub(crate) struct BreakpointList {
     max_width_index: Option<usize>,
     input: Entity<Editor>,
     strip_mode: Option<ActiveBreakpointStripMode>,
+    /// Input for the name of a new function breakpoint, present while one is being added.
+    function_breakpoint_input: Option<Entity<Editor>>,
     serialize_exception_breakpoints_task: Option<Task<anyhow::Result<()>>>,
 }
 
@@ -105,6 +112,7 @@ impl BreakpointList {
                 selected_ix: None,
                 input: cx.new(|cx| Editor::single_line(window, cx)),
                 strip_mode: None,
+                function_breakpoint_input: None,
                 serialize_exception_breakpoints_task: None,
             };
             if let Some(name) = adapter_name {
@@ -139,6 +147,12 @@ impl BreakpointList {
         })
     }
 
+    fn edit_function_breakpoint(&self, name: &str, action: BreakpointEditAction, cx: &mut App) {
+        self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+            breakpoint_store.edit_function_breakpoint(name, action, cx);
+        })
+    }
+
     fn go_to_line_breakpoint(
         &mut self,
         path: Arc<Path>,
@@ -176,8 +190,11 @@ impl BreakpointList {
             self.breakpoints.get(ix).map(|bp| match &bp.kind {
                 BreakpointEntryKind::LineBreakpoint(bp) => (
                     SelectedBreakpointKind::Source,
-                    bp.breakpoint.state
-                        == project::debugger::breakpoint_store::BreakpointState::Enabled,
+                    bp.breakpoint.state == BreakpointState::Enabled,
+                ),
+                BreakpointEntryKind::FunctionBreakpoint(bp) => (
+                    SelectedBreakpointKind::Function,
+                    bp.0.state == BreakpointState::Enabled,
                 ),
                 BreakpointEntryKind::ExceptionBreakpoint(bp) => {
                     (SelectedBreakpointKind::Exception, bp.is_enabled)
@@ -201,11 +218,11 @@ impl BreakpointList {
             ActiveBreakpointStripMode::Condition => "Set Condition",
             ActiveBreakpointStripMode::HitCondition => "Set Hit Condition",
         };
-        let mut is_exception_breakpoint = true;
+        let mut is_read_only = true;
         let active_value = self.selected_ix.and_then(|ix| {
-            self.breakpoints.get(ix).and_then(|bp| {
-                if let BreakpointEntryKind::LineBreakpoint(bp) = &bp.kind {
-                    is_exception_breakpoint = false;
+            self.breakpoints.get(ix).and_then(|bp| match &bp.kind {
+                BreakpointEntryKind::LineBreakpoint(bp) => {
+                    is_read_only = false;
                     match prop {
                         ActiveBreakpointStripMode::Log => bp.breakpoint.message.clone(),
                         ActiveBreakpointStripMode::Condition => bp.breakpoint.condition.clone(),
@@ -213,15 +230,27 @@ impl BreakpointList {
                             bp.breakpoint.hit_condition.clone()
                         }
                     }
-                } else {
-                    None
                 }
+                // Function breakpoints can't log messages.
+                BreakpointEntryKind::FunctionBreakpoint(bp) => match prop {
+                    ActiveBreakpointStripMode::Log => None,
+                    ActiveBreakpointStripMode::Condition => {
+                        is_read_only = false;
+                        bp.0.condition.clone()
+                    }
+                    ActiveBreakpointStripMode::HitCondition => {
+                        is_read_only = false;
+                        bp.0.hit_condition.clone()
+                    }
+                },
+                BreakpointEntryKind::ExceptionBreakpoint(_)
+                | BreakpointEntryKind::DataBreakpoint(_) => None,
             })
         });
 
         self.input.update(cx, |this, cx| {
             this.set_placeholder_text(placeholder, window, cx);
-            this.set_read_only(is_exception_breakpoint);
+            this.set_read_only(is_read_only);
             this.set_text(active_value.as_deref().unwrap_or(""), window, cx);
         });
     }
@@ -240,7 +269,7 @@ impl BreakpointList {
     }
 
     fn select_next(&mut self, _: &menu::SelectNext, window: &mut Window, cx: &mut Context<Self>) {
-        if self.strip_mode.is_some() && self.input.focus_handle(cx).contains_focused(window, cx) {
+        if self.is_input_focused(window, cx) {
             cx.propagate();
             return;
         }
@@ -264,7 +293,7 @@ impl BreakpointList {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if self.strip_mode.is_some() && self.input.focus_handle(cx).contains_focused(window, cx) {
+        if self.is_input_focused(window, cx) {
             cx.propagate();
             return;
         }
@@ -283,7 +312,7 @@ impl BreakpointList {
     }
 
     fn select_first(&mut self, _: &menu::SelectFirst, window: &mut Window, cx: &mut Context<Self>) {
-        if self.strip_mode.is_some() && self.input.focus_handle(cx).contains_focused(window, cx) {
+        if self.is_input_focused(window, cx) {
             cx.propagate();
             return;
         }
@@ -296,7 +325,7 @@ impl BreakpointList {
     }
 
     fn select_last(&mut self, _: &menu::SelectLast, window: &mut Window, cx: &mut Context<Self>) {
-        if self.strip_mode.is_some() && self.input.focus_handle(cx).contains_focused(window, cx) {
+        if self.is_input_focused(window, cx) {
             cx.propagate();
             return;
         }
@@ -308,8 +337,59 @@ impl BreakpointList {
         self.select_ix(ix, window, cx);
     }
 
+    fn is_input_focused(&self, window: &Window, cx: &App) -> bool {
+        (self.strip_mode.is_some() && self.input.focus_handle(cx).contains_focused(window, cx))
+            || self
+                .function_breakpoint_input
+                .as_ref()
+                .is_some_and(|input| input.focus_handle(cx).contains_focused(window, cx))
+    }
+
+    fn add_function_breakpoint(
+        &mut self,
+        _: &AddFunctionBreakpoint,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let input = self
+            .function_breakpoint_input
+            .get_or_insert_with(|| {
+                cx.new(|cx| {
+                    let mut editor = Editor::single_line(window, cx);
+                    editor.set_placeholder_text("Function Name", window, cx);
+                    editor
+                })
+            })
+            .clone();
+        input.focus_handle(cx).focus(window, cx);
+        cx.notify();
+    }
+
+    fn confirm_function_breakpoint(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(input) = self.function_breakpoint_input.take() else {
+            return;
+        };
+        let name = input.read(cx).text(cx);
+        let name = name.trim();
+        if !name.is_empty() {
+            self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+                breakpoint_store.add_function_breakpoint(Arc::from(name), cx);
+            });
+        }
+        self.focus_handle.focus(window, cx);
+        cx.notify();
+    }
+
     fn dismiss(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
-        if self.input.focus_handle(cx).contains_focused(window, cx) {
+        if self
+            .function_breakpoint_input
+            .as_ref()
+            .is_some_and(|input| input.focus_handle(cx).contains_focused(window, cx))
+        {
+            self.function_breakpoint_input.take();
+            self.focus_handle.focus(window, cx);
+            cx.notify();
+        } else if self.input.focus_handle(cx).contains_focused(window, cx) {
             self.focus_handle.focus(window, cx);
         } else if self.strip_mode.is_some() {
             self.strip_mode.take();
@@ -319,6 +399,14 @@ impl BreakpointList {
         }
     }
     fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self
+            .function_breakpoint_input
+            .as_ref()
+            .is_some_and(|input| input.focus_handle(cx).is_focused(window))
+        {
+            self.confirm_function_breakpoint(window, cx);
+            return;
+        }
         let Some(entry) = self.selected_ix.and_then(|ix| self.breakpoints.get_mut(ix)) else {
             return;
         };
@@ -341,8 +429,8 @@ impl BreakpointList {
                             );
                         }
                     }
-                    ActiveBreakpointStripMode::Condition => {
-                        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &entry.kind {
+                    ActiveBreakpointStripMode::Condition => match &entry.kind {
+                        BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                             Self::edit_line_breakpoint_inner(
                                 &self.breakpoint_store,
                                 line_breakpoint.breakpoint.path.clone(),
@@ -351,9 +439,19 @@ impl BreakpointList {
                                 cx,
                             );
                         }
-                    }
-                    ActiveBreakpointStripMode::HitCondition => {
-                        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &entry.kind {
+                        BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                            self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+                                breakpoint_store.edit_function_breakpoint(
+                                    &function_breakpoint.0.name,
+                                    BreakpointEditAction::EditCondition(Arc::from(text)),
+                                    cx,
+                                );
+                            });
+                        }
+                        _ => {}
+                    },
+                    ActiveBreakpointStripMode::HitCondition => match &entry.kind {
+                        BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                             Self::edit_line_breakpoint_inner(
                                 &self.breakpoint_store,
                                 line_breakpoint.breakpoint.path.clone(),
@@ -362,7 +460,17 @@ impl BreakpointList {
                                 cx,
                             );
                         }
-                    }
+                        BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                            self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+                                breakpoint_store.edit_function_breakpoint(
+                                    &function_breakpoint.0.name,
+                                    BreakpointEditAction::EditHitCondition(Arc::from(text)),
+                                    cx,
+                                );
+                            });
+                        }
+                        _ => {}
+                    },
                 }
                 self.focus_handle.focus(window, cx);
             } else {
@@ -377,7 +485,8 @@ impl BreakpointList {
                 let row = line_breakpoint.breakpoint.row;
                 self.go_to_line_breakpoint(path, row, window, cx);
             }
-            BreakpointEntryKind::DataBreakpoint(_)
+            BreakpointEntryKind::FunctionBreakpoint(_)
+            | BreakpointEntryKind::DataBreakpoint(_)
             | BreakpointEntryKind::ExceptionBreakpoint(_) => {}
         }
     }
@@ -388,13 +497,13 @@ impl BreakpointList {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let Some(entry) = self.selected_ix.and_then(|ix| self.breakpoints.get_mut(ix)) else {
-            return;
-        };
-        if self.strip_mode.is_some() && self.input.focus_handle(cx).contains_focused(window, cx) {
+        if self.is_input_focused(window, cx) {
             cx.propagate();
             return;
         }
+        let Some(entry) = self.selected_ix.and_then(|ix| self.breakpoints.get_mut(ix)) else {
+            return;
+        };
 
         match &mut entry.kind {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
@@ -402,6 +511,10 @@ impl BreakpointList {
                 let row = line_breakpoint.breakpoint.row;
                 self.edit_line_breakpoint(path, row, BreakpointEditAction::InvertState, cx);
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                let name = function_breakpoint.0.name.clone();
+                self.edit_function_breakpoint(&name, BreakpointEditAction::InvertState, cx);
+            }
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => {
                 let id = exception_breakpoint.id.clone();
                 self.toggle_exception_breakpoint(&id, cx);
@@ -424,10 +537,23 @@ impl BreakpointList {
             return;
         };
 
-        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &mut entry.kind {
-            let path = line_breakpoint.breakpoint.path.clone();
-            let row = line_breakpoint.breakpoint.row;
-            self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
+        match &mut entry.kind {
+            BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
+                let path = line_breakpoint.breakpoint.path.clone();
+                let row = line_breakpoint.breakpoint.row;
+                self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
+            }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                let name = function_breakpoint.0.name.clone();
+                self.edit_function_breakpoint(&name, BreakpointEditAction::Toggle, cx);
+            }
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                let id = data_breakpoint.0.dap.data_id.clone();
+                if let Some(session) = &self.session {
+                    session.update(cx, |this, cx| this.remove_data_breakpoint(&id, cx));
+                }
+            }
+            BreakpointEntryKind::ExceptionBreakpoint(_) => {}
         }
         cx.notify();
     }
@@ -585,6 +711,7 @@ impl BreakpointList {
 
         let remove_breakpoint_tooltip = selection_kind.map(|(kind, _)| match kind {
             SelectedBreakpointKind::Source => "Remove breakpoint from a breakpoint list",
+            SelectedBreakpointKind::Function => "Remove function breakpoint from a breakpoint list",
             SelectedBreakpointKind::Exception => {
                 "Exception Breakpoints cannot be removed from the breakpoint list"
             }
@@ -603,6 +730,28 @@ impl BreakpointList {
         });
 
         h_flex()
+            .child(
+                IconButton::new("add-function-breakpoint-breakpoint-list", IconName::Plus)
+                    .icon_size(IconSize::Small)
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |_window, cx| {
+                            Tooltip::for_action_in(
+                                "Add Function Breakpoint",
+                                &AddFunctionBreakpoint,
+                                &focus_handle,
+                                cx,
+                            )
+                        }
+                    })
+                    .on_click({
+                        let focus_handle = focus_handle.clone();
+                        move |_, window, cx| {
+                            focus_handle.focus(window, cx);
+                            window.dispatch_action(AddFunctionBreakpoint.boxed_clone(), cx)
+                        }
+                    }),
+            )
             .child(
                 IconButton::new(
                     "disable-breakpoint-breakpoint-list",
@@ -650,7 +799,8 @@ impl BreakpointList {
                         })
                     })
                     .disabled(
-                        selection_kind.map(|kind| kind.0) != Some(SelectedBreakpointKind::Source),
+                        selection_kind
+                            .is_none_or(|(kind, _)| kind == SelectedBreakpointKind::Exception),
                     )
                     .on_click({
                         move |_, window, cx| {
@@ -709,6 +859,18 @@ impl Render for BreakpointList {
                 })
             })
         });
+        let function_breakpoints = self
+            .breakpoint_store
+            .read(cx)
+            .function_breakpoints()
+            .iter()
+            .map(|breakpoint| BreakpointEntry {
+                kind: BreakpointEntryKind::FunctionBreakpoint(FunctionBreakpoint(
+                    breakpoint.clone(),
+                )),
+                weak: weak.clone(),
+            })
+            .collect::<Vec<_>>();
         let exception_breakpoints = self.session.as_ref().into_iter().flat_map(|session| {
             session
                 .read(cx)
@@ -733,6 +895,7 @@ impl Render for BreakpointList {
         });
         self.breakpoints.extend(
             breakpoints
+                .chain(function_breakpoints)
                 .chain(data_breakpoints)
                 .chain(exception_breakpoints),
         );
@@ -748,11 +911,14 @@ impl Render for BreakpointList {
                     let dir_len = line_bp.dir.as_ref().map(|d| d.len()).unwrap_or(0);
                     (name_and_line.len() + dir_len) as f32 * text_pixels
                 }
+                BreakpointEntryKind::FunctionBreakpoint(function_bp) => {
+                    function_bp.0.name.len() as f32 * text_pixels
+                }
                 BreakpointEntryKind::ExceptionBreakpoint(exc_bp) => {
                     exc_bp.data.label.len() as f32 * text_pixels
                 }
                 BreakpointEntryKind::DataBreakpoint(data_bp) => {
-                    data_bp.0.context.human_readable_label().len() as f32 * text_pixels
+                    data_bp.0.label.len() as f32 * text_pixels
                 }
             })
             .position_max_by(|left, right| left.total_cmp(right));
@@ -771,6 +937,7 @@ impl Render for BreakpointList {
             .on_action(cx.listener(Self::unset_breakpoint))
             .on_action(cx.listener(Self::next_breakpoint_property))
             .on_action(cx.listener(Self::previous_breakpoint_property))
+            .on_action(cx.listener(Self::add_function_breakpoint))
             .size_full()
             .pt_1()
             .child(self.render_list(cx))
@@ -807,6 +974,24 @@ impl Render for BreakpointList {
                             .child(self.input.clone()),
                     )
             })
+            .when_some(self.function_breakpoint_input.clone(), |this, input| {
+                this.child(Divider::horizontal().color(DividerColor::Border))
+                    .child(
+                        h_flex()
+                            .p_1()
+                            .gap_1()
+                            .rounded_sm()
+                            .bg(cx.theme().colors().editor_background)
+                            .border_1()
+                            .border_color(cx.theme().colors().border_transparent)
+                            .child(
+                                Icon::new(IconName::Code)
+                                    .color(Color::Debugger)
+                                    .size(IconSize::Small),
+                            )
+                            .child(input),
+                    )
+            })
     }
 }
 
@@ -962,6 +1147,112 @@ impl LineBreakpoint {
     }
 }
 
+#[derive(Clone, Debug)]
+struct FunctionBreakpoint(breakpoint_store::FunctionBreakpoint);
+
+impl FunctionBreakpoint {
+    fn render(
+        &self,
+        props: SupportedBreakpointProperties,
+        strip_mode: Option<ActiveBreakpointStripMode>,
+        ix: usize,
+        is_selected: bool,
+        focus_handle: FocusHandle,
+        list: WeakEntity<BreakpointList>,
+    ) -> ListItem {
+        let color = if self.0.state.is_enabled() {
+            Color::Debugger
+        } else {
+            Color::Muted
+        };
+        let is_enabled = self.0.state.is_enabled();
+        let name = self.0.name.clone();
+
+        ListItem::new(SharedString::from(format!(
+            "function-breakpoint-ui-item-{}",
+            self.0.name
+        )))
+        .toggle_state(is_selected)
+        .inset(true)
+        .on_click({
+            let list = list.clone();
+            move |_, window, cx| {
+                list.update(cx, |list, cx| list.select_ix(Some(ix), window, cx))
+                    .ok();
+            }
+        })
+        .on_secondary_mouse_down(|_, _, cx| {
+            cx.stop_propagation();
+        })
+        .start_slot(
+            div()
+                .id(SharedString::from(format!(
+                    "function-breakpoint-ui-item-{}-click-handler",
+                    self.0.name
+                )))
+                .child(Icon::new(IconName::Code).color(color).size(IconSize::Small))
+                .tooltip({
+                    let focus_handle = focus_handle.clone();
+                    move |_window, cx| {
+                        Tooltip::for_action_in(
+                            if is_enabled {
+                                "Disable Function Breakpoint"
+                            } else {
+                                "Enable Function Breakpoint"
+                            },
+                            &ToggleEnableBreakpoint,
+                            &focus_handle,
+                            cx,
+                        )
+                    }
+                })
+                .on_click({
+                    let list = list.clone();
+                    move |_, _, cx| {
+                        list.update(cx, |this, cx| {
+                            this.edit_function_breakpoint(
+                                &name,
+                                BreakpointEditAction::InvertState,
+                                cx,
+                            );
+                        })
+                        .ok();
+                    }
+                }),
+        )
+        .child(
+            h_flex()
+                .w_full()
+                .gap_1()
+                .min_h(rems_from_px(26.))
+                .justify_between()
+                .child(
+                    v_flex()
+                        .py_1()
+                        .gap_1()
+                        .justify_center()
+                        .id(("function-breakpoint-label", ix))
+                        .child(
+                            Label::new(self.0.name.to_string())
+                                .size(LabelSize::Small)
+                                .line_height_style(ui::LineHeightStyle::UiLabel),
+                        ),
+                )
+                .child(BreakpointOptionsStrip {
+                    props,
+                    breakpoint: BreakpointEntry {
+                        kind: BreakpointEntryKind::FunctionBreakpoint(self.clone()),
+                        weak: list,
+                    },
+                    is_selected,
+                    focus_handle,
+                    strip_mode,
+                    index: ix,
+                }),
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 struct ExceptionBreakpoint {
     id: String,
@@ -1045,7 +1336,7 @@ impl DataBreakpoint {
                         .justify_center()
                         .id(("data-breakpoint-label", ix))
                         .child(
-                            Label::new(self.0.context.human_readable_label())
+                            Label::new(self.0.label.clone())
                                 .size(LabelSize::Small)
                                 .line_height_style(ui::LineHeightStyle::UiLabel),
                         ),
@@ -1173,6 +1464,7 @@ impl ExceptionBreakpoint {
 #[derive(Clone, Debug)]
 enum BreakpointEntryKind {
     LineBreakpoint(LineBreakpoint),
+    FunctionBreakpoint(FunctionBreakpoint),
     ExceptionBreakpoint(ExceptionBreakpoint),
     DataBreakpoint(DataBreakpoint),
 }
@@ -1201,6 +1493,15 @@ impl BreakpointEntry {
                 focus_handle,
                 self.weak.clone(),
             ),
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => function_breakpoint
+                .render(
+                    props.for_function_breakpoints(),
+                    strip_mode,
+                    ix,
+                    is_selected,
+                    focus_handle,
+                    self.weak.clone(),
+                ),
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => exception_breakpoint
                 .render(
                     props.for_exception_breakpoints(),
@@ -1228,6 +1529,11 @@ impl BreakpointEntry {
                 line_breakpoint.breakpoint.path, line_breakpoint.breakpoint.row
             )
             .into(),
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => format!(
+                "function-breakpoint-control-strip--{}",
+                function_breakpoint.0.name
+            )
+            .into(),
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => format!(
                 "exception-breakpoint-control-strip--{}",
                 exception_breakpoint.id
@@ -1255,6 +1561,9 @@ impl BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.breakpoint.condition.is_some()
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                function_breakpoint.0.condition.is_some()
+            }
             // We don't support conditions on exception/data breakpoints
             _ => false,
         }
@@ -1265,6 +1574,9 @@ impl BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.breakpoint.hit_condition.is_some()
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                function_breakpoint.0.hit_condition.is_some()
+            }
             _ => false,
         }
     }
@@ -1305,6 +1617,10 @@ impl From<&Capabilities> for SupportedBreakpointProperties {
 }
 
 impl SupportedBreakpointProperties {
+    fn for_function_breakpoints(self) -> Self {
+        // Function breakpoints always stop execution, so they can't log messages.
+        self.difference(Self::LOG)
+    }
     fn for_exception_breakpoints(self) -> Self {
         // TODO: we don't yet support conditions for exception breakpoints at the data layer, hence all props are disabled here.
         Self::empty()
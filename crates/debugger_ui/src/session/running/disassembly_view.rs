@@ -0,0 +1,259 @@
+use std::{ops::Range, sync::Arc};
+
+use collections::HashSet;
+use dap::{DisassembledInstruction, StackFrameId};
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, ListHorizontalSizingBehavior, ScrollStrategy,
+    Subscription, UniformListScrollHandle, uniform_list,
+};
+use project::debugger::session::Session;
+use settings::Settings;
+use theme::ThemeSettings;
+use ui::{Tooltip, WithScrollbar, prelude::*};
+
+use crate::session::running::stack_frame_list::{StackFrameList, StackFrameListEvent};
+
+/// How many instructions are shown before the selected stack frame's instruction pointer.
+const INSTRUCTIONS_BEFORE: i64 = 50;
+/// How many instructions are disassembled around the selected stack frame's instruction pointer.
+const INSTRUCTION_COUNT: u64 = 200;
+
+pub(crate) struct DisassemblyView {
+    session: Entity<Session>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    /// The instruction pointer of the selected stack frame, which the disassembly is centered on.
+    instruction_pointer: Option<String>,
+    instructions: Arc<[DisassembledInstruction]>,
+    /// Whether the instruction pointer should be scrolled into view once it's disassembled.
+    scroll_to_instruction_pointer: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        stack_frame_list: &Entity<StackFrameList>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(stack_frame_list, |this, _, event, cx| {
+                if let StackFrameListEvent::SelectedStackFrameChanged(stack_frame_id) = event {
+                    this.select_stack_frame(*stack_frame_id, cx);
+                }
+            }),
+            cx.observe(&session, |_, _, cx| cx.notify()),
+        ];
+
+        let mut this = Self {
+            session,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            instruction_pointer: None,
+            instructions: Arc::default(),
+            scroll_to_instruction_pointer: false,
+            _subscriptions,
+        };
+        if let Some(stack_frame_id) = stack_frame_list.read(cx).opened_stack_frame_id() {
+            this.select_stack_frame(stack_frame_id, cx);
+        }
+        this
+    }
+
+    fn select_stack_frame(&mut self, stack_frame_id: StackFrameId, cx: &mut Context<Self>) {
+        self.instruction_pointer = self
+            .session
+            .read(cx)
+            .stack_frame(stack_frame_id)
+            .and_then(|stack_frame| stack_frame.instruction_pointer_reference.clone());
+        self.instructions = Arc::default();
+        self.scroll_to_instruction_pointer = true;
+        cx.notify();
+    }
+
+    /// Requests the instructions around the instruction pointer, which are disassembled lazily
+    /// so that hidden views don't send a request on every stop.
+    fn fetch_instructions(&mut self, cx: &mut Context<Self>) {
+        let Some(instruction_pointer) = self.instruction_pointer.clone() else {
+            return;
+        };
+        let Some(instructions) = self.session.update(cx, |session, cx| {
+            session.disassemble(
+                instruction_pointer,
+                -INSTRUCTIONS_BEFORE,
+                INSTRUCTION_COUNT,
+                cx,
+            )
+        }) else {
+            return;
+        };
+
+        self.instructions = instructions;
+        if self.scroll_to_instruction_pointer
+            && let Some(ix) = self.instruction_pointer_ix()
+        {
+            self.scroll_to_instruction_pointer = false;
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn instructions(&self) -> &[DisassembledInstruction] {
+        &self.instructions
+    }
+
+    pub(crate) fn instruction_pointer_ix(&self) -> Option<usize> {
+        let instruction_pointer = parse_address(self.instruction_pointer.as_deref()?)?;
+        self.instructions.iter().position(|instruction| {
+            parse_address(&instruction.address) == Some(instruction_pointer)
+        })
+    }
+
+    pub(crate) fn toggle_breakpoint(&mut self, address: String, cx: &mut Context<Self>) {
+        self.session.update(cx, |session, cx| {
+            session.toggle_instruction_breakpoint(address, cx);
+        });
+    }
+
+    fn render_instruction(
+        &self,
+        ix: usize,
+        is_current: bool,
+        breakpoints: &HashSet<u64>,
+        supports_breakpoints: bool,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let instruction = &self.instructions[ix];
+        let has_breakpoint = parse_address(&instruction.address)
+            .is_some_and(|address| breakpoints.contains(&address));
+
+        let gutter = div()
+            .id(("disassembly-breakpoint", ix))
+            .w_4()
+            .flex_none()
+            .when(has_breakpoint, |this| {
+                this.child(
+                    Icon::new(IconName::DebugBreakpoint)
+                        .size(IconSize::XSmall)
+                        .color(Color::Debugger),
+                )
+            })
+            .when(supports_breakpoints, |this| {
+                let address = instruction.address.clone();
+                this.cursor_pointer()
+                    .tooltip(Tooltip::text(if has_breakpoint {
+                        "Remove Instruction Breakpoint"
+                    } else {
+                        "Set Instruction Breakpoint"
+                    }))
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.toggle_breakpoint(address.clone(), cx);
+                    }))
+            });
+
+        let colors = cx.theme().colors();
+        h_flex()
+            .id(("disassembly-instruction", ix))
+            .w_full()
+            .px_1()
+            .gap_2()
+            .when(is_current, |this| {
+                this.bg(colors.editor_debugger_active_line_background)
+            })
+            .hover(|style| style.bg(colors.element_hover))
+            .child(gutter)
+            .child(
+                div()
+                    .flex_none()
+                    .text_color(colors.text_muted)
+                    .child(instruction.address.clone()),
+            )
+            .when_some(instruction.instruction_bytes.clone(), |this, bytes| {
+                this.child(
+                    div()
+                        .flex_none()
+                        .min_w_32()
+                        .text_color(colors.text_muted)
+                        .child(bytes),
+                )
+            })
+            .child(div().flex_none().child(instruction.instruction.clone()))
+            .when_some(instruction.symbol.clone(), |this, symbol| {
+                this.child(
+                    div()
+                        .flex_none()
+                        .text_color(colors.text_muted)
+                        .child(format!("<{symbol}>")),
+                )
+            })
+            .into_any()
+    }
+
+    fn render_instructions(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        uniform_list(
+            "disassembly-view",
+            self.instructions.len(),
+            cx.processor(|this, range: Range<usize>, _, cx| {
+                let current_ix = this.instruction_pointer_ix();
+                let session = this.session.read(cx);
+                let supports_breakpoints = session
+                    .capabilities()
+                    .supports_instruction_breakpoints
+                    .unwrap_or_default();
+                let breakpoints = session
+                    .instruction_breakpoints()
+                    .filter_map(|breakpoint| parse_address(&breakpoint.instruction_reference))
+                    .collect::<HashSet<_>>();
+                range
+                    .map(|ix| {
+                        this.render_instruction(
+                            ix,
+                            current_ix == Some(ix),
+                            &breakpoints,
+                            supports_breakpoints,
+                            cx,
+                        )
+                    })
+                    .collect()
+            }),
+        )
+        .track_scroll(&self.scroll_handle)
+        .with_horizontal_sizing_behavior(ListHorizontalSizingBehavior::Unconstrained)
+        .size_full()
+    }
+}
+
+/// Addresses are formatted by the debug adapter, so they're compared by value rather than by text.
+fn parse_address(address: &str) -> Option<u64> {
+    parse_int::parse::<u64>(address).ok()
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.fetch_instructions(cx);
+        let buffer_font = ThemeSettings::get_global(cx).buffer_font.clone();
+        let content = if self.instruction_pointer.is_none() {
+            Label::new("No instruction pointer for the selected stack frame")
+                .color(Color::Muted)
+                .into_any_element()
+        } else {
+            self.render_instructions(cx).into_any_element()
+        };
+
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .font(buffer_font)
+            .text_ui_sm(cx)
+            .child(content)
+            .vertical_scrollbar_for(&self.scroll_handle, window, cx)
+    }
+}
@@ -447,6 +447,7 @@ impl MemoryView {
                                 condition: None,
                                 hit_condition: None,
                             },
+                            info.can_persist.unwrap_or_default(),
                             cx,
                         );
                     });
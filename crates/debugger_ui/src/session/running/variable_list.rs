@@ -812,9 +812,14 @@ impl VariableList {
         let session = self.session.downgrade();
         let access_type = data_info.access_type;
         cx.spawn(async move |_, cx| {
-            let Some((data_id, access_types)) = data_breakpoint
-                .await
-                .and_then(|info| Some((info.data_id?, info.access_types)))
+            let Some((data_id, access_types, can_persist)) =
+                data_breakpoint.await.and_then(|info| {
+                    Some((
+                        info.data_id?,
+                        info.access_types,
+                        info.can_persist.unwrap_or_default(),
+                    ))
+                })
             else {
                 return;
             };
@@ -841,6 +846,7 @@ impl VariableList {
                         condition: None,
                         hit_condition: None,
                     },
+                    can_persist,
                     cx,
                 );
                 cx.notify();
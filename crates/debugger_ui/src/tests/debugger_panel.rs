@@ -9,8 +9,9 @@ use dap::{
     adapters::DebugTaskDefinition,
     client::SessionId,
     requests::{
-        Continue, Disconnect, Launch, Next, RunInTerminal, SetBreakpoints, StackTrace,
-        StartDebugging, StepBack, StepIn, StepOut, Threads,
+        Continue, Disconnect, Goto, GotoTargets, Launch, Next, ReverseContinue, RunInTerminal,
+        SetBreakpoints, SetDataBreakpoints, SetFunctionBreakpoints, StackTrace, StartDebugging,
+        StepBack, StepIn, StepInTargets, StepOut, Threads,
     },
 };
 use editor::{
@@ -18,9 +19,13 @@ use editor::{
     actions::{self},
 };
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use parking_lot::Mutex;
 use project::{
     FakeFs, Project,
-    debugger::session::{ThreadId, ThreadStatus},
+    debugger::{
+        breakpoint_store::{BreakpointEditAction, BreakpointState, DataBreakpoint},
+        session::{ThreadId, ThreadStatus},
+    },
 };
 use serde_json::json;
 use std::{
@@ -997,6 +1002,20 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
         })
     });
 
+    client.on_request::<ReverseContinue, _>(move |_, _| {
+        Err(ErrorResponse {
+            error: Some(dap::Message {
+                id: 1,
+                format: "error".into(),
+                variables: None,
+                send_telemetry: None,
+                show_user: None,
+                url: None,
+                url_label: None,
+            }),
+        })
+    });
+
     client.on_request::<Continue, _>(move |_, _| {
         Err(ErrorResponse {
             error: Some(dap::Message {
@@ -1035,6 +1054,7 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
         "step_over",
         "continue_thread",
         "step_back",
+        "reverse_continue",
         "step_in",
         "step_out",
     ] {
@@ -1042,6 +1062,7 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
             "step_over" => running_state.step_over(cx),
             "continue_thread" => running_state.continue_thread(cx),
             "step_back" => running_state.step_back(cx),
+            "reverse_continue" => running_state.reverse_continue(cx),
             "step_in" => running_state.step_in(cx),
             "step_out" => running_state.step_out(cx),
             _ => unreachable!(),
@@ -1054,7 +1075,7 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
                     .thread_status(cx)
                     .expect("There should be an active thread selected"),
                 match *operation {
-                    "continue_thread" => ThreadStatus::Running,
+                    "continue_thread" | "reverse_continue" => ThreadStatus::Running,
                     _ => ThreadStatus::Stepping,
                 },
                 "Thread status was not set to correct intermediate state after {} request",
@@ -1950,3 +1971,306 @@ async fn test_adapter_shutdown_with_child_sessions_on_app_quit(
         "Child session should have received disconnect request"
     );
 }
+
+#[gpui::test]
+async fn test_function_breakpoints_are_sent_to_adapter(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+
+    breakpoint_store.update(cx, |breakpoint_store, cx| {
+        breakpoint_store.add_function_breakpoint("main".into(), cx);
+        breakpoint_store.add_function_breakpoint("helper".into(), cx);
+        breakpoint_store.edit_function_breakpoint("helper", BreakpointEditAction::InvertState, cx);
+    });
+
+    let function_breakpoints = Arc::new(Mutex::new(Vec::new()));
+    start_debug_session(&workspace, cx, {
+        let function_breakpoints = function_breakpoints.clone();
+        move |client| {
+            client.on_request::<dap::requests::Initialize, _>(|_, _| {
+                Ok(dap::Capabilities {
+                    supports_function_breakpoints: Some(true),
+                    supports_conditional_breakpoints: Some(true),
+                    ..Default::default()
+                })
+            });
+            let function_breakpoints = function_breakpoints.clone();
+            client.on_request::<SetFunctionBreakpoints, _>(move |_, args| {
+                *function_breakpoints.lock() = args
+                    .breakpoints
+                    .into_iter()
+                    .map(|breakpoint| (breakpoint.name, breakpoint.condition))
+                    .collect();
+                Ok(dap::SetFunctionBreakpointsResponse {
+                    breakpoints: Vec::new(),
+                })
+            });
+        }
+    })
+    .unwrap();
+    cx.run_until_parked();
+
+    // Disabled function breakpoints aren't sent on session start.
+    assert_eq!(*function_breakpoints.lock(), [("main".to_string(), None)]);
+
+    breakpoint_store.update(cx, |breakpoint_store, cx| {
+        breakpoint_store.edit_function_breakpoint(
+            "main",
+            BreakpointEditAction::EditCondition("argc > 1".into()),
+            cx,
+        );
+        breakpoint_store.edit_function_breakpoint("helper", BreakpointEditAction::InvertState, cx);
+    });
+    cx.run_until_parked();
+
+    assert_eq!(
+        *function_breakpoints.lock(),
+        [
+            ("main".to_string(), Some("argc > 1".to_string())),
+            ("helper".to_string(), None),
+        ]
+    );
+
+    breakpoint_store.update(cx, |breakpoint_store, cx| {
+        breakpoint_store.edit_function_breakpoint("main", BreakpointEditAction::Toggle, cx);
+    });
+    cx.run_until_parked();
+
+    assert_eq!(*function_breakpoints.lock(), [("helper".to_string(), None)]);
+}
+
+#[gpui::test]
+async fn test_jump_to_position_and_step_in_target(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "let a = 1;\nfoo(bar(), baz());\nlet b = 2;",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_goto_targets_request: Some(true),
+                supports_step_in_targets_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+    client.on_request::<GotoTargets, _>(move |_, args| {
+        assert_eq!(args.source.path.as_deref(), Some(path!("/project/main.rs")));
+        Ok(dap::GotoTargetsResponse {
+            targets: vec![dap::GotoTarget {
+                id: args.line * 10,
+                label: format!("line {}", args.line),
+                line: args.line,
+                column: None,
+                end_line: None,
+                end_column: None,
+                instruction_pointer_reference: None,
+            }],
+        })
+    });
+    let goto_targets = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<Goto, _>({
+        let goto_targets = goto_targets.clone();
+        move |_, args| {
+            goto_targets.lock().push((args.thread_id, args.target_id));
+            Ok(())
+        }
+    });
+    client.on_request::<StepInTargets, _>(move |_, args| {
+        assert_eq!(args.frame_id, 7);
+        Ok(dap::StepInTargetsResponse {
+            targets: ["bar", "baz"]
+                .into_iter()
+                .enumerate()
+                .map(|(ix, label)| dap::StepInTarget {
+                    id: ix as u64 + 1,
+                    label: label.into(),
+                    line: None,
+                    column: None,
+                    end_line: None,
+                    end_column: None,
+                })
+                .collect(),
+        })
+    });
+    let step_in_targets = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<StepIn, _>({
+        let step_in_targets = step_in_targets.clone();
+        move |_, args| {
+            step_in_targets.lock().push(args.target_id);
+            Ok(())
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    // Rows are zero-based, while the adapter is told about one-based lines.
+    session
+        .update(cx, |session, cx| {
+            session.jump_to_position(
+                Arc::from(Path::new(path!("/project/main.rs"))),
+                2,
+                ThreadId(1),
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    assert_eq!(*goto_targets.lock(), [(1, 30)]);
+
+    let targets = session
+        .update(cx, |session, cx| session.step_in_targets(7, cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        targets
+            .iter()
+            .map(|target| target.label.as_str())
+            .collect::<Vec<_>>(),
+        ["bar", "baz"]
+    );
+
+    session.update(cx, |session, cx| {
+        session.step_in_target(
+            ThreadId(1),
+            targets[1].id,
+            dap::SteppingGranularity::Statement,
+            cx,
+        )
+    });
+    cx.run_until_parked();
+    assert_eq!(*step_in_targets.lock(), [Some(2)]);
+}
+
+#[gpui::test]
+async fn test_persisted_data_breakpoints_are_sent_to_adapter(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+
+    let data_breakpoint = |data_id: &str, state| DataBreakpoint {
+        data_id: data_id.into(),
+        label: format!("Variable: {data_id}").into(),
+        access_type: None,
+        condition: None,
+        hit_condition: None,
+        state,
+    };
+    breakpoint_store.update(cx, |breakpoint_store, cx| {
+        breakpoint_store.with_serialized_data_breakpoints(
+            vec![
+                data_breakpoint("counter", BreakpointState::Enabled),
+                data_breakpoint("flag", BreakpointState::Disabled),
+            ],
+            cx,
+        );
+    });
+
+    let data_breakpoints = Arc::new(Mutex::new(Vec::new()));
+    let session = start_debug_session(&workspace, cx, {
+        let data_breakpoints = data_breakpoints.clone();
+        move |client| {
+            client.on_request::<dap::requests::Initialize, _>(|_, _| {
+                Ok(dap::Capabilities {
+                    supports_data_breakpoints: Some(true),
+                    ..Default::default()
+                })
+            });
+            let data_breakpoints = data_breakpoints.clone();
+            client.on_request::<SetDataBreakpoints, _>(move |_, args| {
+                *data_breakpoints.lock() = args
+                    .breakpoints
+                    .into_iter()
+                    .map(|breakpoint| breakpoint.data_id)
+                    .collect();
+                Ok(dap::SetDataBreakpointsResponse {
+                    breakpoints: Vec::new(),
+                })
+            });
+        }
+    })
+    .unwrap();
+    cx.run_until_parked();
+
+    // Disabled data breakpoints aren't sent on session start.
+    assert_eq!(*data_breakpoints.lock(), ["counter".to_string()]);
+    session.read_with(cx, |session, _| {
+        assert_eq!(
+            session
+                .data_breakpoints()
+                .map(|breakpoint| breakpoint.label.to_string())
+                .collect::<Vec<_>>(),
+            ["Variable: counter", "Variable: flag"]
+        );
+    });
+
+    session.update(cx, |session, cx| {
+        session.toggle_data_breakpoint("flag", cx);
+        session.remove_data_breakpoint("counter", cx);
+    });
+    cx.run_until_parked();
+
+    assert_eq!(*data_breakpoints.lock(), ["flag".to_string()]);
+    breakpoint_store.read_with(cx, |breakpoint_store, _| {
+        assert_eq!(
+            breakpoint_store.data_breakpoints(),
+            [data_breakpoint("flag", BreakpointState::Enabled)]
+        );
+    });
+}
@@ -0,0 +1,161 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    persistence::DebuggerPaneItem,
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    StackFrame,
+    requests::{Disassemble, Initialize, Scopes, SetInstructionBreakpoints, StackTrace, Threads},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use parking_lot::Mutex;
+use project::{FakeFs, Project};
+use std::sync::Arc;
+use util::path;
+
+fn instruction(address: &str, instruction: &str) -> dap::DisassembledInstruction {
+    dap::DisassembledInstruction {
+        address: address.into(),
+        instruction_bytes: None,
+        instruction: instruction.into(),
+        symbol: None,
+        location: None,
+        line: None,
+        column: None,
+        end_line: None,
+        end_column: None,
+        presentation_hint: None,
+    }
+}
+
+#[gpui::test]
+async fn test_disassembly_follows_stack_frame(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_disassemble_request: Some(true),
+                supports_instruction_breakpoints: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: None,
+                line: 0,
+                column: 0,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: Some("0x1008".into()),
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+    client.on_request::<Disassemble, _>(move |_, args| {
+        assert_eq!(args.memory_reference, "0x1008");
+        Ok(dap::DisassembleResponse {
+            instructions: vec![
+                instruction("0x1000", "push rbp"),
+                instruction("0x1004", "mov rbp, rsp"),
+                instruction("0x1008", "call 0x2000"),
+                instruction("0x100c", "ret"),
+            ],
+        })
+    });
+    let instruction_breakpoints = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<SetInstructionBreakpoints, _>({
+        let instruction_breakpoints = instruction_breakpoints.clone();
+        move |_, args| {
+            *instruction_breakpoints.lock() = args
+                .breakpoints
+                .iter()
+                .map(|breakpoint| breakpoint.instruction_reference.clone())
+                .collect();
+            Ok(dap::SetInstructionBreakpointsResponse {
+                breakpoints: Vec::new(),
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+    running_state.update_in(cx, |this, window, cx| {
+        this.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+        cx.refresh_windows();
+    });
+    cx.run_until_parked();
+    // The view picks up the disassembly on its next render after the adapter responds.
+    cx.update(|_, cx| cx.refresh_windows());
+    cx.run_until_parked();
+
+    let disassembly_view = running_state.update(cx, |running_state, _| {
+        running_state.disassembly_view().clone()
+    });
+    disassembly_view.update(cx, |view, _| {
+        assert_eq!(
+            view.instructions()
+                .iter()
+                .map(|instruction| instruction.instruction.as_str())
+                .collect::<Vec<_>>(),
+            ["push rbp", "mov rbp, rsp", "call 0x2000", "ret"]
+        );
+        assert_eq!(view.instruction_pointer_ix(), Some(2));
+    });
+
+    disassembly_view.update(cx, |view, cx| view.toggle_breakpoint("0x100c".into(), cx));
+    cx.run_until_parked();
+    assert_eq!(*instruction_breakpoints.lock(), ["0x100c"]);
+
+    disassembly_view.update(cx, |view, cx| view.toggle_breakpoint("0x100c".into(), cx));
+    cx.run_until_parked();
+    assert!(instruction_breakpoints.lock().is_empty());
+}
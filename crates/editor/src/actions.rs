@@ -375,6 +375,10 @@ actions!(
     [
         /// Runs program execution to the current cursor position.
         RunToCursor,
+        /// Moves the next statement to execute to the current cursor position, without running the code in between.
+        JumpToCursor,
+        /// Picks which call on the current line to step into.
+        StepIntoTarget,
         /// Evaluates the selected text in the debugger context.
         EvaluateSelectedText
     ]
@@ -37,6 +37,7 @@ pub mod movement;
 mod persistence;
 mod rust_analyzer_ext;
 pub mod scroll;
+mod selection_ranges;
 mod selections_collection;
 pub mod semantic_tokens;
 mod split;
@@ -114,11 +115,11 @@ use git::blame::{GitBlame, GlobalBlameRenderer};
 use gpui::{
     Action, Animation, AnimationExt, AnyElement, App, AppContext, AsyncWindowContext,
     AvailableSpace, Background, Bounds, ClickEvent, ClipboardEntry, ClipboardItem, Context,
-    DispatchPhase, Edges, Entity, EntityInputHandler, EventEmitter, FocusHandle, FocusOutEvent,
-    Focusable, FontId, FontStyle, FontWeight, Global, HighlightStyle, Hsla, KeyContext, Modifiers,
-    MouseButton, MouseDownEvent, MouseMoveEvent, PaintQuad, ParentElement, Pixels, PressureStage,
-    Render, ScrollHandle, SharedString, SharedUri, Size, Stateful, Styled, Subscription, Task,
-    TextRun, TextStyle, TextStyleRefinement, UTF16Selection, UnderlineStyle,
+    CursorStyle, DispatchPhase, Edges, Entity, EntityInputHandler, EventEmitter, FocusHandle,
+    FocusOutEvent, Focusable, FontId, FontStyle, FontWeight, Global, HighlightStyle, Hsla,
+    KeyContext, Modifiers, MouseButton, MouseDownEvent, MouseMoveEvent, PaintQuad, ParentElement,
+    Pixels, PressureStage, Render, ScrollHandle, SharedString, SharedUri, Size, Stateful, Styled,
+    Subscription, Task, TextRun, TextStyle, TextStyleRefinement, UTF16Selection, UnderlineStyle,
     UniformListScrollHandle, WeakEntity, WeakFocusHandle, Window, div, point, prelude::*,
     pulsating_between, px, relative, size,
 };
@@ -1297,6 +1298,8 @@ pub struct Editor {
     gutter_breakpoint_indicator: (Option<PhantomBreakpointIndicator>, Option<Task<()>>),
     pub(crate) gutter_diff_review_indicator: (Option<PhantomDiffReviewIndicator>, Option<Task<()>>),
     pub(crate) diff_review_drag_state: Option<DiffReviewDragState>,
+    /// The row the active debug line indicator is being dragged to, to set the next statement.
+    pub(crate) active_debug_line_drag_target: Option<Anchor>,
     /// Active diff review overlays. Multiple overlays can be open simultaneously
     /// when hunks have comments stored.
     pub(crate) diff_review_overlays: Vec<DiffReviewOverlay>,
@@ -1336,6 +1339,7 @@ pub struct Editor {
     refresh_colors_task: Task<()>,
     use_document_folding_ranges: bool,
     refresh_folding_ranges_task: Task<()>,
+    selection_ranges_task: Task<()>,
     inlay_hints: Option<LspInlayHintData>,
     folding_newlines: Task<()>,
     select_next_is_case_sensitive: Option<bool>,
@@ -2525,6 +2529,7 @@ impl Editor {
             gutter_breakpoint_indicator: (None, None),
             gutter_diff_review_indicator: (None, None),
             diff_review_drag_state: None,
+            active_debug_line_drag_target: None,
             diff_review_overlays: Vec::new(),
             stored_review_comments: Vec::new(),
             next_review_comment_id: 0,
@@ -2561,6 +2566,7 @@ impl Editor {
             refresh_colors_task: Task::ready(()),
             use_document_folding_ranges: false,
             refresh_folding_ranges_task: Task::ready(()),
+            selection_ranges_task: Task::ready(()),
             inlay_hints: None,
             next_color_inlay_id: 0,
             post_scroll_update: Task::ready(()),
@@ -2777,6 +2783,34 @@ impl Editor {
         ));
     }
 
+    /// Shows a context menu just below the newest cursor, e.g. to choose between the targets of
+    /// an action invoked from the keyboard.
+    pub fn deploy_context_menu_at_cursor(
+        &mut self,
+        context_menu: Entity<ContextMenu>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let source = self.selections.newest_anchor().head();
+        let editor_snapshot = self.snapshot(window, cx);
+        let Some(content_origin) = self
+            .last_bounds
+            .map(|bounds| bounds.origin + point(self.gutter_dimensions.width, Pixels::ZERO))
+        else {
+            return;
+        };
+        let Some(cursor_position) = self.to_pixel_point(source, &editor_snapshot, window, cx)
+        else {
+            return;
+        };
+        let line_height = self.style(cx).text.line_height_in_pixels(window.rem_size());
+        let position = content_origin + cursor_position + point(Pixels::ZERO, line_height);
+
+        self.mouse_context_menu =
+            MouseContextMenu::pinned_to_editor(self, source, position, context_menu, window, cx);
+        cx.notify();
+    }
+
     pub fn mouse_menu_is_focused(&self, window: &Window, cx: &App) -> bool {
         self.mouse_context_menu
             .as_ref()
@@ -4495,6 +4529,10 @@ impl Editor {
             self.cancel_diff_review_drag(cx);
             dismissed = true;
         }
+        if self.active_debug_line_drag_target.is_some() {
+            self.cancel_active_debug_line_drag(cx);
+            dismissed = true;
+        }
         if !self.diff_review_overlays.is_empty() {
             self.dismiss_all_diff_review_overlays(cx);
             dismissed = true;
@@ -8823,6 +8861,7 @@ impl Editor {
         };
 
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
+        let jump_to_cursor = window.is_action_available(&JumpToCursor, cx);
 
         let toggle_state_msg = breakpoint.as_ref().map_or(None, |bp| match bp.1.state {
             BreakpointState::Enabled => Some("Disable"),
@@ -8851,6 +8890,24 @@ impl Editor {
 
                         window.dispatch_action(Box::new(RunToCursor), cx);
                     })
+                    .when(!jump_to_cursor, |this| this.separator())
+                })
+                .when(jump_to_cursor, |this| {
+                    let weak_editor = weak_editor.clone();
+                    this.entry("Jump to here", None, move |window, cx| {
+                        weak_editor
+                            .update(cx, |editor, cx| {
+                                editor.change_selections(
+                                    SelectionEffects::no_scroll(),
+                                    window,
+                                    cx,
+                                    |s| s.select_ranges([Point::new(row, 0)..Point::new(row, 0)]),
+                                );
+                            })
+                            .ok();
+
+                        window.dispatch_action(Box::new(JumpToCursor), cx);
+                    })
                     .separator()
                 })
                 .when_some(toggle_state_msg, |this, msg| {
@@ -9060,6 +9117,87 @@ impl Editor {
             })
     }
 
+    /// The display row that execution is currently stopped at, if any.
+    pub(crate) fn active_debug_line_row(&self, snapshot: &DisplaySnapshot) -> Option<DisplayRow> {
+        self.highlighted_rows
+            .get(&TypeId::of::<ActiveDebugLine>())
+            .and_then(|lines| lines.last())
+            .map(|line| line.range.start.to_display_point(snapshot).row())
+    }
+
+    fn render_active_debug_line_indicator(
+        &self,
+        row: DisplayRow,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id("active_debug_line_indicator")
+            .cursor(CursorStyle::ResizeUpDown)
+            .child(
+                Icon::new(IconName::ArrowRight)
+                    .size(IconSize::XSmall)
+                    .color(Color::Debugger),
+            )
+            .tooltip(Tooltip::text("Drag to Set the Next Statement"))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |editor, _: &MouseDownEvent, window, cx| {
+                    cx.stop_propagation();
+                    editor.set_active_debug_line_drag_target(row, window, cx);
+                }),
+            )
+    }
+
+    pub(crate) fn update_active_debug_line_drag(
+        &mut self,
+        display_row: DisplayRow,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.active_debug_line_drag_target.is_some() {
+            self.set_active_debug_line_drag_target(display_row, window, cx);
+        }
+    }
+
+    fn set_active_debug_line_drag_target(
+        &mut self,
+        display_row: DisplayRow,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.snapshot(window, cx);
+        let point = snapshot
+            .display_snapshot
+            .display_point_to_point(DisplayPoint::new(display_row, 0), Bias::Left);
+        self.active_debug_line_drag_target = Some(snapshot.buffer_snapshot().anchor_before(point));
+        cx.notify();
+    }
+
+    /// Moves execution to the row the active debug line indicator was dropped on.
+    pub(crate) fn end_active_debug_line_drag(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(target) = self.active_debug_line_drag_target.take() {
+            let snapshot = self.display_snapshot(cx);
+            let target_row = target.to_display_point(&snapshot).row();
+            if self.active_debug_line_row(&snapshot) != Some(target_row) {
+                self.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.select_anchor_ranges([target..target])
+                });
+                window.focus(&self.focus_handle, cx);
+                window.dispatch_action(Box::new(JumpToCursor), cx);
+            }
+        }
+        cx.notify();
+    }
+
+    pub(crate) fn cancel_active_debug_line_drag(&mut self, cx: &mut Context<Self>) {
+        self.active_debug_line_drag_target = None;
+        cx.notify();
+    }
+
     fn build_tasks_context(
         project: &Entity<Project>,
         buffer: &Entity<Buffer>,
@@ -16420,9 +16558,16 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let Some(visible_row_count) = self.visible_row_count() else {
+        if self.select_larger_lsp_selection_ranges(window, cx) {
             return;
-        };
+        }
+        self.select_larger_tree_sitter_nodes(window, cx);
+    }
+
+    fn select_larger_tree_sitter_nodes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.visible_row_count().is_none() {
+            return;
+        }
         let old_selections: Box<[_]> = self
             .selections
             .all::<MultiBufferOffset>(&self.display_snapshot(cx))
@@ -16437,7 +16582,7 @@ impl Editor {
         let buffer = self.buffer.read(cx).snapshot(cx);
 
         let mut selected_larger_node = false;
-        let mut new_selections = old_selections
+        let new_selections = old_selections
             .iter()
             .map(|selection| {
                 let old_range = selection.start..selection.end;
@@ -16492,6 +16637,23 @@ impl Editor {
             return; // don't put this call in the history
         }
 
+        self.select_larger_ranges(old_selections, new_selections, window, cx);
+    }
+
+    /// Selects ranges enclosing the old selections, remembering the old selections so that
+    /// [`Self::select_smaller_syntax_node`] can restore them.
+    fn select_larger_ranges(
+        &mut self,
+        old_selections: Box<[Selection<MultiBufferOffset>]>,
+        mut new_selections: Vec<Selection<MultiBufferOffset>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(visible_row_count) = self.visible_row_count() else {
+            return;
+        };
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+
         // scroll based on transformation done to the last selection created by the user
         let (last_old, last_new) = old_selections
             .last()
@@ -16506,13 +16668,11 @@ impl Editor {
             should_newest_selection_be_reversed
         };
 
-        if selected_larger_node {
-            self.select_syntax_node_history.disable_clearing = true;
-            self.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
-                s.select(new_selections.clone());
-            });
-            self.select_syntax_node_history.disable_clearing = false;
-        }
+        self.select_syntax_node_history.disable_clearing = true;
+        self.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select(new_selections.clone());
+        });
+        self.select_syntax_node_history.disable_clearing = false;
 
         let start_row = last_new.start.to_display_point(&display_map).row().0;
         let end_row = last_new.end.to_display_point(&display_map).row().0;
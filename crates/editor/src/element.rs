@@ -39,24 +39,24 @@ use feature_flags::{DiffReviewFeatureFlag, FeatureFlagAppExt as _};
 use file_icons::FileIcons;
 use git::{Oid, blame::BlameEntry, commit::ParsedCommitMessage, status::FileStatus};
 use gpui::{
-    Action, Along, AnyElement, App, AppContext, AvailableSpace, Axis as ScrollbarAxis, BorderStyle,
-    Bounds, ClickEvent, ClipboardItem, ContentMask, Context, Corner, Corners, CursorStyle,
-    DispatchPhase, Edges, Element, ElementInputHandler, Entity, Focusable as _, FontId, FontWeight,
-    GlobalElementId, Hitbox, HitboxBehavior, Hsla, InteractiveElement, IntoElement, IsZero,
-    KeybindingKeystroke, Length, Modifiers, ModifiersChangedEvent, MouseButton, MouseClickEvent,
-    MouseDownEvent, MouseMoveEvent, MousePressureEvent, MouseUpEvent, PaintQuad, ParentElement,
-    Pixels, PressureStage, ScrollDelta, ScrollHandle, ScrollWheelEvent, ShapedLine, SharedString,
-    Size, StatefulInteractiveElement, Style, Styled, StyledText, TextAlign, TextRun,
-    TextStyleRefinement, WeakEntity, Window, anchored, checkerboard, deferred, div, fill,
-    linear_color_stop, linear_gradient, outline, point, px, quad, relative, size, solid_background,
-    transparent_black,
+    AccessibilityNode, AccessibilityRole, Action, Along, AnyElement, App, AppContext,
+    AvailableSpace, Axis as ScrollbarAxis, BorderStyle, Bounds, ClickEvent, ClipboardItem,
+    ContentMask, Context, Corner, Corners, CursorStyle, DispatchPhase, Edges, Element,
+    ElementInputHandler, Entity, Focusable as _, FontId, FontWeight, GlobalElementId, Hitbox,
+    HitboxBehavior, Hsla, InteractiveElement, IntoElement, IsZero, KeybindingKeystroke, Length,
+    Modifiers, ModifiersChangedEvent, MouseButton, MouseClickEvent, MouseDownEvent, MouseMoveEvent,
+    MousePressureEvent, MouseUpEvent, PaintQuad, ParentElement, Pixels, PressureStage, ScrollDelta,
+    ScrollHandle, ScrollWheelEvent, ShapedLine, SharedString, Size, StatefulInteractiveElement,
+    Style, Styled, StyledText, TextAlign, TextRun, TextStyleRefinement, WeakEntity, Window,
+    anchored, checkerboard, deferred, div, fill, linear_color_stop, linear_gradient, outline,
+    point, px, quad, relative, size, solid_background, transparent_black,
 };
 use itertools::Itertools;
 use language::{IndentGuideSettings, language_settings::ShowWhitespaceSetting};
 use markdown::Markdown;
 use multi_buffer::{
     Anchor, ExcerptId, ExcerptInfo, ExpandExcerptDirection, ExpandInfo, MultiBufferPoint,
-    MultiBufferRow, RowInfo,
+    MultiBufferRow, RowInfo, ToOffset as _,
 };
 
 use edit_prediction_types::EditPredictionGranularity;
@@ -947,6 +947,12 @@ impl EditorElement {
             return;
         }
 
+        if editor.active_debug_line_drag_target.is_some() {
+            editor.end_active_debug_line_drag(window, cx);
+            cx.stop_propagation();
+            return;
+        }
+
         let text_hitbox = &position_map.text_hitbox;
         let end_selection = editor.has_pending_selection();
         let pending_nonempty_selections = editor.has_pending_nonempty_selection();
@@ -1250,6 +1256,7 @@ impl EditorElement {
         if editor.diff_review_drag_state.is_some() {
             editor.update_diff_review_drag(valid_point.row(), window, cx);
         }
+        editor.update_active_debug_line_drag(valid_point.row(), window, cx);
 
         let hovered_diff_control = position_map
             .diff_hunk_control_bounds
@@ -3159,6 +3166,45 @@ impl EditorElement {
         })
     }
 
+    fn layout_active_debug_line_indicator(
+        &self,
+        line_height: Pixels,
+        range: Range<DisplayRow>,
+        scroll_position: gpui::Point<ScrollOffset>,
+        gutter_dimensions: &GutterDimensions,
+        gutter_hitbox: &Hitbox,
+        snapshot: &EditorSnapshot,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        if self.split_side == Some(SplitSide::Left) {
+            return None;
+        }
+
+        self.editor.update(cx, |editor, cx| {
+            // While dragging, the indicator follows the mouse to preview the next statement.
+            let display_row = match editor.active_debug_line_drag_target {
+                Some(target) => target.to_display_point(&snapshot.display_snapshot).row(),
+                None => editor.active_debug_line_row(&snapshot.display_snapshot)?,
+            };
+            if !range.contains(&display_row) {
+                return None;
+            }
+
+            let indicator = editor.render_active_debug_line_indicator(display_row, cx);
+            Some(prepaint_gutter_button(
+                indicator.into_any_element(),
+                display_row,
+                line_height,
+                gutter_dimensions,
+                scroll_position,
+                gutter_hitbox,
+                window,
+                cx,
+            ))
+        })
+    }
+
     fn should_render_diff_review_button(
         &self,
         range: Range<DisplayRow>,
@@ -6308,6 +6354,10 @@ impl EditorElement {
                 test_indicator.paint(window, cx);
             }
 
+            if let Some(indicator) = layout.active_debug_line_indicator.as_mut() {
+                indicator.paint(window, cx);
+            }
+
             if let Some(diff_review_button) = layout.diff_review_button.as_mut() {
                 diff_review_button.paint(window, cx);
             }
@@ -7476,6 +7526,42 @@ impl EditorElement {
         }
     }
 
+    /// Describes the editor to assistive technologies with its visible text as the value,
+    /// and the newest selection as the text selection when it's visible.
+    fn accessibility_node(
+        &self,
+        bounds: Bounds<Pixels>,
+        layout: &EditorLayout,
+        cx: &App,
+    ) -> AccessibilityNode {
+        let snapshot = &layout.position_map.snapshot;
+        let buffer_snapshot = snapshot.buffer_snapshot();
+        let visible_rows = &layout.visible_display_row_range;
+        let start = DisplayPoint::new(visible_rows.start, 0).to_offset(snapshot, Bias::Left);
+        let end = if visible_rows.end > snapshot.max_point().row() {
+            buffer_snapshot.len()
+        } else {
+            DisplayPoint::new(visible_rows.end, 0).to_offset(snapshot, Bias::Left)
+        };
+
+        let editor = self.editor.read(cx);
+        let mut node = AccessibilityNode::new(AccessibilityRole::TextInput)
+            .value(
+                buffer_snapshot
+                    .text_for_range(start..end)
+                    .collect::<String>(),
+            )
+            .bounds(bounds)
+            .focus_handle(&editor.focus_handle);
+        let selection = editor.selections.newest_anchor();
+        let selection_start = selection.start.to_offset(buffer_snapshot);
+        let selection_end = selection.end.to_offset(buffer_snapshot);
+        if start <= selection_start && selection_end <= end {
+            node = node.text_selection(selection_start - start..selection_end - start);
+        }
+        node
+    }
+
     fn paint_mouse_context_menu(
         &mut self,
         layout: &mut EditorLayout,
@@ -9822,6 +9908,22 @@ impl Element for EditorElement {
                         }
                     }
 
+                    // Highlight the row the next statement is being dragged to
+                    if let Some(target) = self.editor.read(cx).active_debug_line_drag_target {
+                        let colors = cx.theme().colors();
+                        highlighted_rows.insert(
+                            target.to_display_point(&snapshot.display_snapshot).row(),
+                            LineHighlight {
+                                background: solid_background(
+                                    colors.editor_debugger_active_line_background.opacity(0.5),
+                                ),
+                                border: Some(colors.debugger_accent),
+                                include_gutter: true,
+                                type_id: None,
+                            },
+                        );
+                    }
+
                     let highlighted_gutter_ranges =
                         self.editor.read(cx).gutter_highlights_in_range(
                             start_anchor..end_anchor,
@@ -10576,6 +10678,20 @@ impl Element for EditorElement {
                     } else {
                         Vec::new()
                     };
+                    let active_debug_line_indicator = if show_breakpoints {
+                        self.layout_active_debug_line_indicator(
+                            line_height,
+                            start_row..end_row,
+                            scroll_position,
+                            &gutter_dimensions,
+                            &gutter_hitbox,
+                            &snapshot,
+                            window,
+                            cx,
+                        )
+                    } else {
+                        None
+                    };
 
                     let git_gutter_width = Self::gutter_strip_width(line_height)
                         + gutter_dimensions
@@ -10831,6 +10947,7 @@ impl Element for EditorElement {
                         mouse_context_menu,
                         test_indicators,
                         breakpoints,
+                        active_debug_line_indicator,
                         diff_review_button,
                         crease_toggles,
                         crease_trailers,
@@ -10878,44 +10995,48 @@ impl Element for EditorElement {
             line_height: Some(self.style.text.line_height),
             ..Default::default()
         };
+        let accessibility_node = (window.is_accessibility_enabled() && !layout.mode.is_minimap())
+            .then(|| self.accessibility_node(bounds, layout, cx));
         let rem_size = self.rem_size(cx);
-        window.with_rem_size(rem_size, |window| {
-            window.with_text_style(Some(text_style), |window| {
-                window.with_content_mask(Some(ContentMask { bounds }), |window| {
-                    self.paint_mouse_listeners(layout, window, cx);
-                    self.paint_background(layout, window, cx);
-                    self.paint_indent_guides(layout, window, cx);
+        window.with_accessibility_node(accessibility_node, |window| {
+            window.with_rem_size(rem_size, |window| {
+                window.with_text_style(Some(text_style), |window| {
+                    window.with_content_mask(Some(ContentMask { bounds }), |window| {
+                        self.paint_mouse_listeners(layout, window, cx);
+                        self.paint_background(layout, window, cx);
+                        self.paint_indent_guides(layout, window, cx);
+
+                        if layout.gutter_hitbox.size.width > Pixels::ZERO {
+                            self.paint_blamed_display_rows(layout, window, cx);
+                            self.paint_line_numbers(layout, window, cx);
+                        }
 
-                    if layout.gutter_hitbox.size.width > Pixels::ZERO {
-                        self.paint_blamed_display_rows(layout, window, cx);
-                        self.paint_line_numbers(layout, window, cx);
-                    }
+                        self.paint_text(layout, window, cx);
 
-                    self.paint_text(layout, window, cx);
+                        if layout.gutter_hitbox.size.width > Pixels::ZERO {
+                            self.paint_gutter_highlights(layout, window, cx);
+                            self.paint_gutter_indicators(layout, window, cx);
+                        }
 
-                    if layout.gutter_hitbox.size.width > Pixels::ZERO {
-                        self.paint_gutter_highlights(layout, window, cx);
-                        self.paint_gutter_indicators(layout, window, cx);
-                    }
+                        if !layout.blocks.is_empty() {
+                            window.with_element_namespace("blocks", |window| {
+                                self.paint_blocks(layout, window, cx);
+                            });
+                        }
 
-                    if !layout.blocks.is_empty() {
                         window.with_element_namespace("blocks", |window| {
-                            self.paint_blocks(layout, window, cx);
+                            if let Some(mut sticky_header) = layout.sticky_buffer_header.take() {
+                                sticky_header.paint(window, cx)
+                            }
                         });
-                    }
 
-                    window.with_element_namespace("blocks", |window| {
-                        if let Some(mut sticky_header) = layout.sticky_buffer_header.take() {
-                            sticky_header.paint(window, cx)
-                        }
+                        self.paint_sticky_headers(layout, window, cx);
+                        self.paint_minimap(layout, window, cx);
+                        self.paint_scrollbars(layout, window, cx);
+                        self.paint_edit_prediction_popover(layout, window, cx);
+                        self.paint_mouse_context_menu(layout, window, cx);
                     });
-
-                    self.paint_sticky_headers(layout, window, cx);
-                    self.paint_minimap(layout, window, cx);
-                    self.paint_scrollbars(layout, window, cx);
-                    self.paint_edit_prediction_popover(layout, window, cx);
-                    self.paint_mouse_context_menu(layout, window, cx);
-                });
+                })
             })
         })
     }
@@ -11010,6 +11131,7 @@ pub struct EditorLayout {
     selections: Vec<(PlayerColor, Vec<SelectionLayout>)>,
     test_indicators: Vec<AnyElement>,
     breakpoints: Vec<AnyElement>,
+    active_debug_line_indicator: Option<AnyElement>,
     diff_review_button: Option<AnyElement>,
     crease_toggles: Vec<Option<AnyElement>>,
     expand_toggles: Vec<Option<(AnyElement, gpui::Point<Pixels>)>>,
@@ -361,7 +361,17 @@ pub fn show_link_definition(
         async move {
             let result = match &trigger_point {
                 TriggerPoint::Text(_) => {
-                    if let Some((url_range, url)) = find_url(&buffer, text_anchor, cx.clone()) {
+                    if let Some((link_range, link)) =
+                        find_document_link(&buffer, project.clone(), text_anchor, cx).await
+                    {
+                        let range = maybe!({
+                            let range = snapshot.anchor_range_in_excerpt(excerpt_id, link_range)?;
+                            Some(RangeInEditor::Text(range))
+                        });
+                        Some((range, vec![link]))
+                    } else if let Some((url_range, url)) =
+                        find_url(&buffer, text_anchor, cx.clone())
+                    {
                         this.read_with(cx, |_, _| {
                             let range = maybe!({
                                 let range =
@@ -485,6 +495,53 @@ pub fn show_link_definition(
     editor.hovered_link_state = Some(hovered_link_state);
 }
 
+/// Finds the language server's document link at the given position, resolving its target if the server left it out.
+pub(crate) async fn find_document_link(
+    buffer: &Entity<language::Buffer>,
+    project: Option<Entity<Project>>,
+    position: text::Anchor,
+    cx: &mut AsyncWindowContext,
+) -> Option<(Range<text::Anchor>, HoverLink)> {
+    let project = project?;
+    let lsp_store = project.read_with(cx, |project, cx| {
+        project
+            .any_language_server_supports(
+                buffer.read(cx),
+                |capabilities| capabilities.document_link_provider.is_some(),
+                cx,
+            )
+            .then(|| project.lsp_store())
+    })?;
+    let links = lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.fetch_document_links(buffer, cx)
+        })
+        .await;
+    let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+    let link = links.into_iter().find(|link| {
+        link.range.start.cmp(&position, &snapshot).is_le()
+            && link.range.end.cmp(&position, &snapshot).is_ge()
+    })?;
+    let link = lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.resolve_document_link(link, buffer.clone(), cx)
+        })
+        .await
+        .log_err()?;
+    let target = link.target?;
+    let hover_link = match target.to_file_path() {
+        Ok(path) => {
+            let path = path.to_string_lossy().into_owned();
+            let resolved_path = project
+                .update(cx, |project, cx| project.resolve_abs_path(&path, cx))
+                .await?;
+            HoverLink::File(resolved_path)
+        }
+        Err(_) => HoverLink::Url(target.to_string()),
+    };
+    Some((link.range, hover_link))
+}
+
 pub(crate) fn find_url(
     buffer: &Entity<language::Buffer>,
     position: text::Anchor,
@@ -794,6 +851,7 @@ mod tests {
     use lsp::request::{GotoDefinition, GotoTypeDefinition};
     use multi_buffer::MultiBufferOffset;
     use settings::InlayHintSettingsContent;
+    use std::time::Duration;
     use util::{assert_set_eq, path};
     use workspace::item::Item;
 
@@ -1414,6 +1472,72 @@ mod tests {
         assert_eq!(cx.opened_url(), Some("https://zed.dev/releases".into()));
     }
 
+    #[gpui::test]
+    async fn test_document_links(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state(indoc! {"
+            // See the serde docs for detailsˇ.
+        "});
+
+        let mut document_link_requests = cx
+            .set_request_handler::<lsp::request::DocumentLinkRequest, _, _>(
+                move |_, _, _| async move {
+                    Ok(Some(vec![lsp::DocumentLink {
+                        range: lsp::Range::new(
+                            lsp::Position::new(0, 11),
+                            lsp::Position::new(0, 21),
+                        ),
+                        target: None,
+                        tooltip: Some("Open serde documentation".to_string()),
+                        data: Some(serde_json::json!({ "crate": "serde" })),
+                    }]))
+                },
+            );
+        let mut resolve_requests = cx
+            .set_request_handler::<lsp::request::DocumentLinkResolve, _, _>(
+                move |link, _, _| async move {
+                    assert_eq!(link.data, Some(serde_json::json!({ "crate": "serde" })));
+                    Ok(lsp::DocumentLink {
+                        target: Some("https://docs.rs/serde".parse().unwrap()),
+                        ..link
+                    })
+                },
+            );
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            // See the serde dˇocs for details.
+        "});
+
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        cx.background_executor.run_until_parked();
+        cx.background_executor
+            .advance_clock(Duration::from_millis(100));
+        document_link_requests.next().await;
+        resolve_requests.next().await;
+        cx.background_executor.run_until_parked();
+        cx.assert_editor_text_highlights(
+            HighlightKey::HoveredLinkState,
+            indoc! {"
+            // See the «serde docsˇ» for details.
+        "},
+        );
+
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        assert_eq!(cx.opened_url(), Some("https://docs.rs/serde".into()));
+    }
+
     #[test]
     fn test_link_pattern_file_candidates() {
         let candidates: Vec<String> = link_pattern_file_candidates("[LinkTitle](link_file.txt)")
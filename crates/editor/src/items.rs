@@ -1671,9 +1671,10 @@ impl SearchableItem for Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let text = self.buffer.read(cx);
-        let text = text.snapshot(cx);
-        let text = text.text_for_range(identifier.clone()).collect::<Vec<_>>();
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let text = snapshot
+            .text_for_range(identifier.clone())
+            .collect::<Vec<_>>();
         let text: Cow<_> = if text.len() == 1 {
             text.first().cloned().unwrap().into()
         } else {
@@ -1681,7 +1682,9 @@ impl SearchableItem for Editor {
             joined_chunks.into()
         };
 
-        if let Some(replacement) = query.replacement_for(&text) {
+        if let Some(replacement) =
+            query.replacement_for(&text, snapshot.language_at(identifier.start))
+        {
             self.transact(window, cx, |this, _, cx| {
                 this.edit([(identifier.clone(), Arc::from(&*replacement))], cx);
             });
@@ -1695,16 +1698,15 @@ impl SearchableItem for Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let text = self.buffer.read(cx);
-        let text = text.snapshot(cx);
+        let snapshot = self.buffer.read(cx).snapshot(cx);
         let mut edits = vec![];
 
-        // A regex might have replacement variables so we cannot apply
-        // the same replacement to all matches
-        if query.is_regex() {
+        // A regex or structural pattern might have replacement variables
+        // so we cannot apply the same replacement to all matches
+        if query.has_per_match_replacements() {
             edits = matches
                 .filter_map(|m| {
-                    let text = text.text_for_range(m.clone()).collect::<Vec<_>>();
+                    let text = snapshot.text_for_range(m.clone()).collect::<Vec<_>>();
 
                     let text: Cow<_> = if text.len() == 1 {
                         text.first().cloned().unwrap().into()
@@ -1714,7 +1716,7 @@ impl SearchableItem for Editor {
                     };
 
                     query
-                        .replacement_for(&text)
+                        .replacement_for(&text, snapshot.language_at(m.start))
                         .map(|replacement| (m.clone(), Arc::from(&*replacement)))
                 })
                 .collect();
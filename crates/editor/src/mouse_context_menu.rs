@@ -1,8 +1,8 @@
 use crate::{
     Copy, CopyAndTrim, CopyPermalinkToLine, Cut, DisplayPoint, DisplaySnapshot, Editor,
     EvaluateSelectedText, FindAllReferences, GoToDeclaration, GoToDefinition, GoToImplementation,
-    GoToTypeDefinition, Paste, Rename, RevealInFileManager, RunToCursor, SelectMode,
-    SelectionEffects, SelectionExt, ToDisplayPoint, ToggleCodeActions,
+    GoToTypeDefinition, JumpToCursor, Paste, Rename, RevealInFileManager, RunToCursor, SelectMode,
+    SelectionEffects, SelectionExt, StepIntoTarget, ToDisplayPoint, ToggleCodeActions,
     actions::{Format, FormatSelections},
     selections_collection::SelectionsCollection,
 };
@@ -13,9 +13,11 @@ use std::ops::Range;
 use text::PointUtf16;
 use workspace::OpenInTerminal;
 use zed_actions::agent::AddSelectionToThread;
+use zed_actions::call_hierarchy::{ShowIncomingCalls, ShowOutgoingCalls};
 use zed_actions::preview::{
     markdown::OpenPreview as OpenMarkdownPreview, svg::OpenPreview as OpenSvgPreview,
 };
+use zed_actions::type_hierarchy::{ShowSubtypes, ShowSupertypes};
 
 #[derive(Debug)]
 pub enum MenuPosition {
@@ -218,6 +220,8 @@ pub fn deploy_context_menu(
 
         let evaluate_selection = window.is_action_available(&EvaluateSelectedText, cx);
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
+        let jump_to_cursor = window.is_action_available(&JumpToCursor, cx);
+        let step_into_target = window.is_action_available(&StepIntoTarget, cx);
         let disable_ai = DisableAiSettings::is_ai_disabled_for_buffer(
             editor.buffer.read(cx).as_singleton().as_ref(),
             cx,
@@ -247,11 +251,20 @@ pub fn deploy_context_menu(
                 .when(run_to_cursor, |builder| {
                     builder.action("Run to Cursor", Box::new(RunToCursor))
                 })
+                .when(jump_to_cursor, |builder| {
+                    builder.action("Jump to Here", Box::new(JumpToCursor))
+                })
+                .when(step_into_target, |builder| {
+                    builder.action("Step Into Target", Box::new(StepIntoTarget))
+                })
                 .when(evaluate_selection && has_selections, |builder| {
                     builder.action("Evaluate Selection", Box::new(EvaluateSelectedText))
                 })
                 .when(
-                    run_to_cursor || (evaluate_selection && has_selections),
+                    run_to_cursor
+                        || jump_to_cursor
+                        || step_into_target
+                        || (evaluate_selection && has_selections),
                     |builder| builder.separator(),
                 )
                 .action("Go to Definition", Box::new(GoToDefinition))
@@ -262,6 +275,10 @@ pub fn deploy_context_menu(
                     "Find All References",
                     Box::new(FindAllReferences::default()),
                 )
+                .action("Show Incoming Calls", Box::new(ShowIncomingCalls))
+                .action("Show Outgoing Calls", Box::new(ShowOutgoingCalls))
+                .action("Show Supertypes", Box::new(ShowSupertypes))
+                .action("Show Subtypes", Box::new(ShowSubtypes))
                 .separator()
                 .action("Rename Symbol", Box::new(Rename))
                 .action("Format Buffer", Box::new(Format))
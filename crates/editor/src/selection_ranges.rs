@@ -0,0 +1,308 @@
+use collections::HashMap;
+use futures::future::join_all;
+use language::language_settings::language_settings;
+use multi_buffer::{ExcerptId, MultiBufferOffset, ToOffset as _};
+use project::lsp_command::GetSelectionRanges;
+use text::{BufferId, Selection, SelectionGoal};
+use ui::{Context, Window};
+use util::ResultExt as _;
+
+use crate::{Editor, HideMouseCursorOrigin};
+
+impl Editor {
+    /// Expands every selection to the smallest enclosing range reported by the language server's
+    /// `textDocument/selectionRange`, falling back to the syntax tree if the server returns nothing.
+    /// Like the syntax tree path, ranges that start or end inside a fold are skipped.
+    ///
+    /// Returns `false` without doing anything when the language server should not be asked,
+    /// e.g. because a selection spans multiple excerpts, the setting is disabled for its language
+    /// or none of the buffer's language servers support selection ranges.
+    pub(super) fn select_larger_lsp_selection_ranges(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(project) = self.project.clone() else {
+            return false;
+        };
+        let display_snapshot = self.display_snapshot(cx);
+        let old_selections: Box<[Selection<MultiBufferOffset>]> = self
+            .selections
+            .all::<MultiBufferOffset>(&display_snapshot)
+            .into();
+        if old_selections.is_empty() {
+            return false;
+        }
+
+        let snapshot = display_snapshot.buffer_snapshot();
+        let multi_buffer = self.buffer.read(cx);
+        let mut requests = HashMap::<BufferId, (_, Vec<(usize, ExcerptId)>, Vec<_>)>::default();
+        for (selection_ix, selection) in old_selections.iter().enumerate() {
+            let start = snapshot.anchor_after(selection.start);
+            let end = snapshot.anchor_before(selection.end);
+            if start.excerpt_id != end.excerpt_id {
+                return false;
+            }
+            let Some(buffer_id) = start.text_anchor.buffer_id else {
+                return false;
+            };
+            let Some(buffer) = multi_buffer.buffer(buffer_id) else {
+                return false;
+            };
+            let enabled = language_settings(
+                buffer.read(cx).language().map(|l| l.name()),
+                buffer.read(cx).file(),
+                cx,
+            )
+            .document_selection_ranges
+            .enabled();
+            if !enabled
+                || !project.read(cx).any_language_server_supports(
+                    buffer.read(cx),
+                    GetSelectionRanges::check_server_capabilities,
+                    cx,
+                )
+            {
+                return false;
+            }
+
+            let (_, selections, positions) = requests
+                .entry(buffer_id)
+                .or_insert_with(|| (buffer, Vec::new(), Vec::new()));
+            selections.push((selection_ix, start.excerpt_id));
+            positions.push(start.text_anchor);
+        }
+
+        self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+
+        let tasks = project.update(cx, |project, cx| {
+            requests
+                .into_values()
+                .map(|(buffer, selections, positions)| {
+                    let task = project.selection_ranges(&buffer, positions, cx);
+                    async move { (selections, task.await) }
+                })
+                .collect::<Vec<_>>()
+        });
+
+        self.selection_ranges_task = cx.spawn_in(window, async move |editor, cx| {
+            let results = join_all(tasks).await;
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    let display_snapshot = editor.display_snapshot(cx);
+                    let current_selections = editor
+                        .selections
+                        .all::<MultiBufferOffset>(&display_snapshot);
+                    let selections_changed = current_selections.len() != old_selections.len()
+                        || current_selections
+                            .iter()
+                            .zip(old_selections.iter())
+                            .any(|(current, old)| current.range() != old.range());
+                    if selections_changed {
+                        return;
+                    }
+
+                    let snapshot = display_snapshot.buffer_snapshot();
+                    let mut new_selections = old_selections.to_vec();
+                    let mut selected_larger_range = false;
+                    for (selections, selection_ranges) in results {
+                        let Some(selection_ranges) = selection_ranges.log_err() else {
+                            continue;
+                        };
+                        for ((selection_ix, excerpt_id), ranges) in
+                            selections.into_iter().zip(selection_ranges)
+                        {
+                            let selection = &mut new_selections[selection_ix];
+                            let old_range = selection.range();
+                            let larger_ranges = ranges
+                                .into_iter()
+                                .filter_map(|range| {
+                                    snapshot.anchor_range_in_excerpt(excerpt_id, range)
+                                })
+                                .map(|range| {
+                                    range.start.to_offset(snapshot)..range.end.to_offset(snapshot)
+                                })
+                                .filter(|range| {
+                                    range.start <= old_range.start
+                                        && range.end >= old_range.end
+                                        && *range != old_range
+                                })
+                                .collect::<Vec<_>>();
+                            let larger_range = larger_ranges
+                                .iter()
+                                .find(|range| {
+                                    !display_snapshot.intersects_fold(range.start)
+                                        && !display_snapshot.intersects_fold(range.end)
+                                })
+                                .or(larger_ranges.last());
+                            if let Some(larger_range) = larger_range {
+                                selection.start = larger_range.start;
+                                selection.end = larger_range.end;
+                                selection.goal = SelectionGoal::None;
+                                selected_larger_range = true;
+                            }
+                        }
+                    }
+
+                    if selected_larger_range {
+                        editor.select_larger_ranges(old_selections, new_selections, window, cx);
+                    } else {
+                        editor.select_larger_tree_sitter_nodes(window, cx);
+                    }
+                })
+                .ok();
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{self, AtomicUsize},
+    };
+
+    use gpui::TestAppContext;
+    use settings::DocumentSelectionRanges;
+    use text::Point;
+
+    use crate::{
+        SelectLargerSyntaxNode, SelectSmallerSyntaxNode,
+        editor_tests::{init_test, update_test_language_settings},
+        test::editor_lsp_test_context::EditorLspTestContext,
+    };
+
+    #[gpui::test]
+    async fn test_select_larger_syntax_node_uses_lsp_selection_ranges(cx: &mut TestAppContext) {
+        init_test(cx, |settings| {
+            settings.defaults.document_selection_ranges = Some(DocumentSelectionRanges::On);
+        });
+
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state("fn main() { println!(\"hello ˇworld\"); }");
+        let request_count = Arc::new(AtomicUsize::new(0));
+        cx.set_request_handler::<lsp::request::SelectionRangeRequest, _, _>({
+            let request_count = request_count.clone();
+            move |_, params, _| {
+                request_count.fetch_add(1, atomic::Ordering::SeqCst);
+                async move {
+                    assert_eq!(params.positions, vec![lsp::Position::new(0, 28)]);
+                    let range = |start, end| {
+                        lsp::Range::new(lsp::Position::new(0, start), lsp::Position::new(0, end))
+                    };
+                    Ok(Some(vec![lsp::SelectionRange {
+                        range: range(28, 33),
+                        parent: Some(Box::new(lsp::SelectionRange {
+                            range: range(12, 34),
+                            parent: Some(Box::new(lsp::SelectionRange {
+                                range: range(0, 38),
+                                parent: None,
+                            })),
+                        })),
+                    }]))
+                }
+            }
+        });
+
+        cx.update_editor(|editor, window, cx| {
+            editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+        });
+        cx.run_until_parked();
+        cx.assert_editor_state("fn main() { println!(\"hello «worldˇ»\"); }");
+
+        cx.update_editor(|editor, window, cx| {
+            editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+        });
+        cx.run_until_parked();
+        cx.assert_editor_state("fn main() { «ˇprintln!(\"hello world\")»; }");
+        assert_eq!(request_count.load(atomic::Ordering::SeqCst), 2);
+
+        cx.update_editor(|editor, window, cx| {
+            editor.select_smaller_syntax_node(&SelectSmallerSyntaxNode, window, cx);
+        });
+        cx.assert_editor_state("fn main() { println!(\"hello «ˇworld»\"); }");
+
+        update_test_language_settings(&mut cx.cx.cx, |settings| {
+            settings.defaults.document_selection_ranges = Some(DocumentSelectionRanges::Off);
+        });
+        cx.update_editor(|editor, window, cx| {
+            editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            request_count.load(atomic::Ordering::SeqCst),
+            2,
+            "Language server should not be queried when LSP selection ranges are disabled"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_lsp_selection_ranges_skip_folds(cx: &mut TestAppContext) {
+        init_test(cx, |settings| {
+            settings.defaults.document_selection_ranges = Some(DocumentSelectionRanges::On);
+        });
+
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state("fn main() { println!(\"hello ˇworld\"); }");
+        cx.set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(|_, _, _| async move {
+            let range = |start, end| {
+                lsp::Range::new(lsp::Position::new(0, start), lsp::Position::new(0, end))
+            };
+            Ok(Some(vec![lsp::SelectionRange {
+                range: range(28, 33),
+                parent: Some(Box::new(lsp::SelectionRange {
+                    range: range(12, 34),
+                    parent: Some(Box::new(lsp::SelectionRange {
+                        range: range(0, 38),
+                        parent: None,
+                    })),
+                })),
+            }]))
+        });
+
+        cx.update_editor(|editor, window, cx| {
+            editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+        });
+        cx.run_until_parked();
+        cx.assert_editor_state("fn main() { println!(\"hello «worldˇ»\"); }");
+
+        // `println!(…)` starts inside the fold, so the enclosing function is selected instead.
+        cx.update_editor(|editor, window, cx| {
+            editor.fold_ranges(
+                vec![Point::new(0, 10)..Point::new(0, 13)],
+                false,
+                window,
+                cx,
+            );
+            editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+        });
+        cx.run_until_parked();
+        cx.update_editor(|editor, _, cx| {
+            let selections = editor.selections.all::<Point>(&editor.display_snapshot(cx));
+            assert_eq!(
+                selections
+                    .iter()
+                    .map(|selection| selection.range())
+                    .collect::<Vec<_>>(),
+                [Point::new(0, 0)..Point::new(0, 38)]
+            );
+        });
+    }
+}
@@ -3,17 +3,19 @@ use anyhow::{Context as _, Result, bail};
 use collections::{HashMap, HashSet};
 use futures::future::{self, BoxFuture, join_all};
 use git::{
-    Oid, RunHook,
+    DOT_GIT, GITMODULES, Oid, RunHook,
     blame::Blame,
     repository::{
-        AskPassDelegate, Branch, CommitDataReader, CommitDetails, CommitOptions, FetchOptions,
-        GRAPH_CHUNK_SIZE, GitRepository, GitRepositoryCheckpoint, InitialGraphCommitData, LogOrder,
-        LogSource, PushOptions, Remote, RepoPath, ResetMode, Worktree,
+        AskPassDelegate, BisectProgress, BisectTerm, Branch, CommitDataReader, CommitDetails,
+        CommitOptions, FetchOptions, GRAPH_CHUNK_SIZE, GitRepository, GitRepositoryCheckpoint,
+        InitialGraphCommitData, LogOrder, LogSource, PushOptions, RebaseControl, RebaseProgress,
+        RebaseTodoAction, RebaseTodoEntry, Remote, RepoPath, ResetMode, Tag, Worktree,
     },
     status::{
         DiffTreeType, FileStatus, GitStatus, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus,
         UnmergedStatus,
     },
+    submodule::{Gitlink, Submodule, parse_gitmodules},
 };
 use gpui::{AsyncApp, BackgroundExecutor, SharedString, Task};
 use ignore::gitignore::GitignoreBuilder;
@@ -51,6 +53,20 @@ pub struct FakeGitRepositoryState {
     pub simulated_index_write_error_message: Option<String>,
     pub refs: HashMap<String, String>,
     pub graph_commits: Vec<Arc<InitialGraphCommitData>>,
+    /// Messages of the commits in `graph_commits`, keyed by sha.
+    pub commit_messages: HashMap<Oid, String>,
+    pub rebase_progress: Option<RebaseProgress>,
+    /// The todo list of the interactive rebase in progress, or of the last one.
+    pub rebase_todo: Vec<RebaseTodoEntry>,
+    pub tags: HashMap<String, Tag>,
+    /// The names of the tags pushed to each remote, keyed by remote name.
+    pub pushed_tags: HashMap<String, HashSet<String>>,
+    /// Paths that the next cherry-pick or revert will leave conflicted.
+    pub simulated_replay_conflicts: HashMap<RepoPath, UnmergedStatus>,
+    /// Commits recorded in the index for submodules, keyed by path.
+    pub submodule_commits: HashMap<RepoPath, Oid>,
+    /// The bisect in progress, which treats `graph_commits` as a linear history.
+    pub bisect: Option<BisectProgress>,
 }
 
 impl FakeGitRepositoryState {
@@ -69,11 +85,155 @@ impl FakeGitRepositoryState {
             oids: Default::default(),
             remotes: HashMap::default(),
             graph_commits: Vec::new(),
+            commit_messages: HashMap::default(),
+            rebase_progress: None,
+            rebase_todo: Vec::new(),
+            tags: HashMap::default(),
+            pushed_tags: HashMap::default(),
+            simulated_replay_conflicts: HashMap::default(),
+            submodule_commits: HashMap::default(),
+            bisect: None,
+        }
+    }
+
+    /// Resolves a ref name or sha to a commit in `graph_commits`.
+    fn resolve_commit(&self, commit: &str) -> Result<Oid> {
+        let sha = self.refs.get(commit).map_or(commit, String::as_str);
+        let sha = sha
+            .parse::<Oid>()
+            .with_context(|| format!("unknown revision {commit}"))?;
+        anyhow::ensure!(
+            self.graph_commits
+                .iter()
+                .any(|graph_commit| graph_commit.sha == sha),
+            "unknown revision {commit}"
+        );
+        Ok(sha)
+    }
+
+    /// Replays the commits in `todo` onto `onto`, as new commits, and moves HEAD to the last one.
+    fn apply_rebase_todo(
+        &mut self,
+        onto: Option<Oid>,
+        todo: &[RebaseTodoEntry],
+        mut new_sha: impl FnMut() -> Oid,
+    ) -> Result<()> {
+        let mut messages = Vec::<String>::new();
+        for entry in todo {
+            match entry.action {
+                RebaseTodoAction::Pick | RebaseTodoAction::Reword => {
+                    messages.push(entry.message.to_string())
+                }
+                RebaseTodoAction::Squash | RebaseTodoAction::Fixup => {
+                    let previous = messages.last_mut().with_context(|| {
+                        format!("cannot {} without a previous commit", entry.action.as_str())
+                    })?;
+                    if entry.action == RebaseTodoAction::Squash {
+                        previous.push_str("\n\n");
+                        previous.push_str(&entry.message);
+                    }
+                }
+                RebaseTodoAction::Drop => {}
+            }
+        }
+
+        let rebased = todo
+            .iter()
+            .map(|entry| entry.sha.as_ref())
+            .collect::<HashSet<_>>();
+        self.graph_commits
+            .retain(|commit| !rebased.contains(commit.sha.to_string().as_str()));
+        let mut head = onto;
+        for message in messages {
+            let sha = new_sha();
+            self.graph_commits.insert(
+                0,
+                Arc::new(InitialGraphCommitData {
+                    sha,
+                    parents: head.into_iter().collect(),
+                    ref_names: Vec::new(),
+                }),
+            );
+            self.commit_messages.insert(sha, message);
+            head = Some(sha);
+        }
+        if let Some(head) = head {
+            self.refs.insert("HEAD".into(), head.to_string());
+        }
+        Ok(())
+    }
+
+    /// Narrows down the bisect in progress and checks out the next commit to test, the way
+    /// `git bisect` would on a linear history.
+    fn advance_bisect(&mut self) {
+        let Some(bisect) = self.bisect.as_mut() else {
+            return;
+        };
+        let Some(bad) = bisect.bad.clone() else {
+            return;
+        };
+        if bisect.good.is_empty() {
+            return;
+        }
+
+        bisect.remaining = self
+            .graph_commits
+            .iter()
+            .map(|commit| SharedString::from(commit.sha.to_string()))
+            .skip_while(|sha| *sha != bad)
+            .take_while(|sha| !bisect.good.contains(sha))
+            .collect();
+        let next = if bisect.remaining.len() == 1 {
+            bisect.first_bad = Some(bad.clone());
+            Some(bad)
+        } else {
+            let candidates = bisect
+                .remaining
+                .iter()
+                .filter(|sha| **sha != bad && !bisect.skipped.contains(sha))
+                .collect::<Vec<_>>();
+            candidates
+                .get(candidates.len() / 2)
+                .map(|sha| (*sha).clone())
+        };
+        if let Some(next) = next {
+            self.refs.insert("HEAD".into(), next.to_string());
         }
     }
 }
 
 impl FakeGitRepository {
+    /// Cherry-picks or reverts `commit`, which is left in progress when there are simulated
+    /// conflicts, and otherwise committed on top of HEAD.
+    fn replay_commit(&self, head_ref: &'static str, commit: String) -> BoxFuture<'_, Result<()>> {
+        let new_sha = Oid::random(&mut *self.executor.rng().lock());
+        self.with_state_async(true, move |state| {
+            if state.refs.contains_key("CHERRY_PICK_HEAD") || state.refs.contains_key("REVERT_HEAD")
+            {
+                bail!("a cherry-pick or revert is already in progress");
+            }
+            if !state.simulated_replay_conflicts.is_empty() {
+                state
+                    .unmerged_paths
+                    .extend(state.simulated_replay_conflicts.drain());
+                state.refs.insert(head_ref.into(), commit);
+                return Ok(());
+            }
+
+            let parent = state.refs.get("HEAD").and_then(|sha| sha.parse().ok());
+            state.graph_commits.insert(
+                0,
+                Arc::new(InitialGraphCommitData {
+                    sha: new_sha,
+                    parents: parent.into_iter().collect(),
+                    ref_names: Vec::new(),
+                }),
+            );
+            state.refs.insert("HEAD".into(), new_sha.to_string());
+            Ok(())
+        })
+    }
+
     fn with_state_async<F, T>(&self, write: bool, f: F) -> BoxFuture<'static, Result<T>>
     where
         F: 'static + Send + FnOnce(&mut FakeGitRepositoryState) -> Result<T>,
@@ -568,6 +728,372 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn rebase_todo(&self, base: Option<String>) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>> {
+        self.with_state_async(false, move |state| {
+            let base = base.map(|base| state.resolve_commit(&base)).transpose()?;
+            let mut entries = Vec::new();
+            let mut sha = state
+                .refs
+                .get("HEAD")
+                .and_then(|sha| sha.parse::<Oid>().ok());
+            while let Some(current) = sha.filter(|sha| Some(*sha) != base) {
+                let commit = state
+                    .graph_commits
+                    .iter()
+                    .find(|commit| commit.sha == current)
+                    .with_context(|| format!("unknown commit {current}"))?;
+                if commit.parents.len() <= 1 {
+                    let message = state
+                        .commit_messages
+                        .get(&current)
+                        .cloned()
+                        .unwrap_or_default();
+                    entries.push(RebaseTodoEntry {
+                        action: RebaseTodoAction::Pick,
+                        sha: current.to_string().into(),
+                        subject: message
+                            .lines()
+                            .next()
+                            .unwrap_or_default()
+                            .to_string()
+                            .into(),
+                        message: message.into(),
+                    });
+                }
+                sha = commit.parents.first().copied();
+            }
+            if let Some(base) = base
+                && sha.is_none()
+            {
+                bail!("{base} is not an ancestor of HEAD");
+            }
+            entries.reverse();
+            Ok(entries)
+        })
+    }
+
+    fn rebase_interactive(
+        &self,
+        base: Option<String>,
+        todo: Vec<RebaseTodoEntry>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let rng = self.executor.rng();
+        self.with_state_async(true, move |state| {
+            if state.rebase_progress.is_some() {
+                bail!("a rebase is already in progress");
+            }
+            let onto = base.map(|base| state.resolve_commit(&base)).transpose()?;
+            if let Some(first) = todo
+                .iter()
+                .find(|entry| entry.action != RebaseTodoAction::Drop)
+                && first.action.melds_into_previous()
+            {
+                bail!("cannot {} without a previous commit", first.action.as_str());
+            }
+            state.rebase_todo = todo;
+
+            // A simulated conflict stops the rebase at its first commit, until it is continued.
+            if !state.simulated_replay_conflicts.is_empty() {
+                state
+                    .unmerged_paths
+                    .extend(state.simulated_replay_conflicts.drain());
+                let step = state
+                    .rebase_todo
+                    .iter()
+                    .position(|entry| entry.action != RebaseTodoAction::Drop)
+                    .unwrap_or_default();
+                state.rebase_progress = Some(RebaseProgress {
+                    head_name: state.current_branch_name.clone().map(Into::into),
+                    onto: onto.map(|onto| onto.to_string()).unwrap_or_default().into(),
+                    step: step + 1,
+                    total_steps: state.rebase_todo.len(),
+                });
+                return Ok(());
+            }
+
+            let todo = state.rebase_todo.clone();
+            state.apply_rebase_todo(onto, &todo, || Oid::random(&mut *rng.lock()))
+        })
+    }
+
+    fn control_rebase(
+        &self,
+        control: RebaseControl,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let rng = self.executor.rng();
+        self.with_state_async(true, move |state| {
+            let progress = state
+                .rebase_progress
+                .clone()
+                .context("no rebase in progress")?;
+            if control == RebaseControl::Continue && !state.unmerged_paths.is_empty() {
+                bail!("cannot continue with unresolved conflicts");
+            }
+            state.unmerged_paths.clear();
+            state.rebase_progress = None;
+
+            let mut todo = state.rebase_todo.clone();
+            if control == RebaseControl::Abort || todo.is_empty() {
+                return Ok(());
+            }
+            if control == RebaseControl::Skip
+                && let Some(entry) = progress.step.checked_sub(1).and_then(|ix| todo.get_mut(ix))
+            {
+                entry.action = RebaseTodoAction::Drop;
+            }
+            let onto = progress.onto.parse::<Oid>().ok();
+            state.apply_rebase_todo(onto, &todo, || Oid::random(&mut *rng.lock()))
+        })
+    }
+
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>> {
+        let fut = self.with_state_async(false, |state| Ok(state.rebase_progress.clone()));
+        self.executor
+            .spawn(async move { fut.await.ok().flatten() })
+            .boxed()
+    }
+
+    fn bisect_start(
+        &self,
+        bad: Option<String>,
+        good: Vec<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.bisect.is_some() {
+                bail!("a bisect is already in progress");
+            }
+            let head = state.refs.get("HEAD").context("no HEAD")?.clone();
+            state.refs.insert("BISECT_START".into(), head);
+            state.bisect = Some(BisectProgress {
+                bad: bad.map(Into::into),
+                good: good.into_iter().map(Into::into).collect(),
+                ..BisectProgress::default()
+            });
+            state.advance_bisect();
+            Ok(())
+        })
+    }
+
+    fn bisect_mark(
+        &self,
+        term: BisectTerm,
+        commit: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let head = state.refs.get("HEAD").cloned();
+            let bisect = state.bisect.as_mut().context("no bisect in progress")?;
+            let commit = SharedString::from(commit.or(head).context("no HEAD")?);
+            match term {
+                BisectTerm::Bad => bisect.bad = Some(commit),
+                BisectTerm::Good => bisect.good.push(commit),
+                BisectTerm::Skip => bisect.skipped.push(commit),
+            }
+            state.advance_bisect();
+            Ok(())
+        })
+    }
+
+    fn bisect_reset(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.bisect.take().is_none() {
+                bail!("no bisect in progress");
+            }
+            if let Some(head) = state.refs.remove("BISECT_START") {
+                state.refs.insert("HEAD".into(), head);
+            }
+            Ok(())
+        })
+    }
+
+    fn bisect_progress(&self) -> BoxFuture<'_, Result<Option<BisectProgress>>> {
+        self.with_state_async(false, |state| Ok(state.bisect.clone()))
+    }
+
+    fn cherry_pick(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.replay_commit("CHERRY_PICK_HEAD", commit)
+    }
+
+    fn revert(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.replay_commit("REVERT_HEAD", commit)
+    }
+
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        self.with_state_async(false, |state| {
+            let mut tags = state.tags.values().cloned().collect::<Vec<_>>();
+            tags.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(tags)
+        })
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.tags.contains_key(&name) {
+                bail!("tag '{name}' already exists");
+            }
+            let sha = match commit {
+                Some(commit) => commit,
+                None => state.refs.get("HEAD").context("no HEAD")?.clone(),
+            };
+            state.tags.insert(
+                name.clone(),
+                Tag {
+                    name: name.into(),
+                    sha: sha.into(),
+                    message: message.map(Into::into),
+                },
+            );
+            Ok(())
+        })
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.tags.remove(&name).is_none() {
+                bail!("no such tag: {name}");
+            }
+            Ok(())
+        })
+    }
+
+    fn push_tag(
+        &self,
+        name: String,
+        remote: String,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        self.with_state_async(false, move |state| {
+            if !state.tags.contains_key(&name) {
+                bail!("src refspec refs/tags/{name} does not match any");
+            }
+            if !state.remotes.contains_key(&remote) {
+                bail!("'{remote}' does not appear to be a git repository");
+            }
+            let stderr = if state
+                .pushed_tags
+                .entry(remote)
+                .or_default()
+                .insert(name.clone())
+            {
+                format!(" * [new tag]         {name} -> {name}\n")
+            } else {
+                "Everything up-to-date\n".to_string()
+            };
+            Ok(git::repository::RemoteCommandOutput {
+                stdout: String::new(),
+                stderr,
+            })
+        })
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        let fs = self.fs.clone();
+        let workdir_path = self.dot_git_path.parent().unwrap().to_path_buf();
+        let gitlinks = self.with_state_async(false, |state| {
+            Ok(state
+                .submodule_commits
+                .iter()
+                .map(|(path, sha)| {
+                    let gitlink = Gitlink {
+                        sha: Some(*sha),
+                        conflicted: state.unmerged_paths.contains_key(path),
+                    };
+                    (path.clone(), gitlink)
+                })
+                .collect::<HashMap<_, _>>())
+        });
+        async move {
+            let gitlinks = gitlinks.await?;
+            let Ok(contents) = fs.load(&workdir_path.join(GITMODULES)).await else {
+                return Ok(Vec::new());
+            };
+            let mut submodules = parse_gitmodules(&contents)
+                .into_iter()
+                .filter_map(|entry| {
+                    let path = RepoPath::new(&entry.path).ok()?;
+                    let dot_git = workdir_path.join(path.as_std_path()).join(DOT_GIT);
+                    let checked_out_sha = fs
+                        .with_git_state(&dot_git, false, |state| {
+                            state.refs.get("HEAD").and_then(|sha| sha.parse().ok())
+                        })
+                        .ok()
+                        .flatten();
+                    let gitlink = gitlinks.get(&path);
+                    Some(Submodule::new(entry, path, gitlink, checked_out_sha))
+                })
+                .collect::<Vec<_>>();
+            submodules.sort_by(|a, b| a.path.cmp(&b.path));
+            Ok(submodules)
+        }
+        .boxed()
+    }
+
+    fn update_submodules(
+        &self,
+        paths: Vec<RepoPath>,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        let fs = self.fs.clone();
+        let dot_git_path = self.dot_git_path.clone();
+        let workdir_path = dot_git_path.parent().unwrap().to_path_buf();
+        let commits = self.with_state_async(false, |state| {
+            let mut commits = state
+                .submodule_commits
+                .iter()
+                .map(|(path, sha)| (path.clone(), *sha))
+                .collect::<Vec<_>>();
+            commits.sort_by(|a, b| a.0.cmp(&b.0));
+            Ok(commits)
+        });
+        async move {
+            let mut stdout = String::new();
+            for (path, sha) in commits.await? {
+                if !paths.is_empty() && !paths.contains(&path) {
+                    continue;
+                }
+                // Cloning uninitialized submodules isn't simulated, so only existing ones are updated.
+                let dot_git = workdir_path.join(path.as_std_path()).join(DOT_GIT);
+                let updated = fs.with_git_state(&dot_git, true, |state| {
+                    state.refs.insert("HEAD".into(), sha.to_string());
+                });
+                if updated.is_ok() {
+                    stdout.push_str(&format!(
+                        "Submodule path '{}': checked out '{sha}'\n",
+                        path.as_unix_str()
+                    ));
+                }
+            }
+            // Real submodules keep their git dir inside the parent's, so the parent rescans too.
+            fs.with_git_state(&dot_git_path, true, |_| {})?;
+            Ok(git::repository::RemoteCommandOutput {
+                stdout,
+                stderr: String::new(),
+            })
+        }
+        .boxed()
+    }
+
     fn commit(
         &self,
         _message: gpui::SharedString,
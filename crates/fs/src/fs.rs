@@ -51,7 +51,7 @@ use collections::{BTreeMap, btree_map};
 use fake_git_repo::FakeGitRepositoryState;
 #[cfg(feature = "test-support")]
 use git::{
-    repository::{InitialGraphCommitData, RepoPath, repo_path},
+    repository::{InitialGraphCommitData, RebaseProgress, RepoPath, repo_path},
     status::{FileStatus, StatusCode, TrackedStatus, UnmergedStatus},
 };
 
@@ -1977,6 +1977,13 @@ impl FakeFs {
         .unwrap();
     }
 
+    pub fn set_rebase_progress_for_repo(&self, dot_git: &Path, progress: Option<RebaseProgress>) {
+        self.with_git_state(dot_git, true, |state| {
+            state.rebase_progress = progress;
+        })
+        .unwrap();
+    }
+
     /// Put the given git repository into a state with the given status,
     /// by mutating the head, index, and unmerged state.
     pub fn set_status_for_repo(&self, dot_git: &Path, statuses: &[(&str, FileStatus)]) {
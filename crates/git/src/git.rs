@@ -5,6 +5,7 @@ mod remote;
 pub mod repository;
 pub mod stash;
 pub mod status;
+pub mod submodule;
 
 pub use crate::hosting_provider::*;
 pub use crate::remote::*;
@@ -19,6 +20,7 @@ use std::str::FromStr;
 
 pub const DOT_GIT: &str = ".git";
 pub const GITIGNORE: &str = ".gitignore";
+pub const GITMODULES: &str = ".gitmodules";
 pub const FSMONITOR_DAEMON: &str = "fsmonitor--daemon";
 pub const LFS_DIR: &str = "lfs";
 pub const COMMIT_MESSAGE: &str = "COMMIT_EDITMSG";
@@ -77,10 +79,30 @@ actions!(
         Pull,
         /// Pulls changes from the remote repository with rebase.
         PullRebase,
+        /// Continues the rebase in progress once its conflicts are resolved.
+        ContinueRebase,
+        /// Skips the commit that the rebase in progress stopped on.
+        SkipRebase,
+        /// Aborts the rebase in progress, restoring the branch to where it started.
+        AbortRebase,
         /// Fetches changes from the remote repository.
         Fetch,
         /// Fetches changes from a specific remote.
         FetchFrom,
+        /// Initializes and updates the submodules of the active repository.
+        UpdateSubmodules,
+        /// Starts a bisect with the current commit marked as bad.
+        StartBisect,
+        /// Marks the commit being bisected as good.
+        BisectGood,
+        /// Marks the commit being bisected as bad.
+        BisectBad,
+        /// Skips the commit being bisected.
+        BisectSkip,
+        /// Runs a command on each commit until the bisect finds the first bad one.
+        BisectRun,
+        /// Ends the bisect, checking out the commit it started from.
+        ResetBisect,
         /// Creates a new commit with staged changes.
         Commit,
         /// Amends the last commit with staged changes.
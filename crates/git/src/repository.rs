@@ -1,7 +1,8 @@
 use crate::commit::parse_git_diff_name_status;
 use crate::stash::GitStash;
 use crate::status::{DiffTreeType, GitStatus, StatusCode, TreeDiff};
-use crate::{Oid, RunHook, SHORT_SHA_LENGTH};
+use crate::submodule::{Submodule, parse_gitlinks, parse_gitmodules, read_checked_out_sha};
+use crate::{GITMODULES, Oid, RunHook, SHORT_SHA_LENGTH};
 use anyhow::{Context as _, Result, anyhow, bail};
 use collections::HashMap;
 use futures::channel::oneshot;
@@ -382,6 +383,148 @@ pub enum ResetMode {
     Mixed,
 }
 
+/// What to do with a commit during an interactive rebase.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum RebaseTodoAction {
+    /// Apply the commit as-is.
+    #[default]
+    Pick,
+    /// Apply the commit and replace its message.
+    Reword,
+    /// Meld the commit into the previous one, combining their messages.
+    Squash,
+    /// Meld the commit into the previous one, keeping only the previous message.
+    Fixup,
+    /// Leave the commit out.
+    Drop,
+}
+
+impl RebaseTodoAction {
+    pub const ALL: [Self; 5] = [
+        Self::Pick,
+        Self::Reword,
+        Self::Squash,
+        Self::Fixup,
+        Self::Drop,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword => "reword",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+        }
+    }
+
+    /// Whether this action melds the commit into the one before it.
+    pub fn melds_into_previous(&self) -> bool {
+        matches!(self, Self::Squash | Self::Fixup)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RebaseTodoEntry {
+    pub action: RebaseTodoAction,
+    pub sha: SharedString,
+    pub subject: SharedString,
+    /// The full commit message. For [`RebaseTodoAction::Reword`], this is the new message.
+    pub message: SharedString,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RebaseControl {
+    Continue,
+    Skip,
+    Abort,
+}
+
+impl RebaseControl {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Continue => "continue",
+            Self::Skip => "skip",
+            Self::Abort => "abort",
+        }
+    }
+}
+
+/// The state of a rebase that has stopped, either for conflicts or because a commit failed to apply.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RebaseProgress {
+    /// The branch being rebased, or `None` if HEAD was detached when the rebase started.
+    pub head_name: Option<SharedString>,
+    pub onto: SharedString,
+    /// The 1-based index of the todo entry that is currently being applied.
+    pub step: usize,
+    pub total_steps: usize,
+}
+
+/// How a commit tested during a bisect is marked.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum BisectTerm {
+    Good,
+    Bad,
+    Skip,
+}
+
+impl BisectTerm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Good => "good",
+            Self::Bad => "bad",
+            Self::Skip => "skip",
+        }
+    }
+
+    /// Interprets the exit code of a test command the way `git bisect run` does.
+    ///
+    /// Returns `None` for codes that should stop the bisect, such as a command killed by a signal.
+    pub fn from_exit_code(code: i32) -> Option<Self> {
+        match code {
+            0 => Some(Self::Good),
+            125 => Some(Self::Skip),
+            1..=127 => Some(Self::Bad),
+            _ => None,
+        }
+    }
+}
+
+/// The state of a bisect in progress.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct BisectProgress {
+    pub bad: Option<SharedString>,
+    pub good: Vec<SharedString>,
+    pub skipped: Vec<SharedString>,
+    /// The commits that may still be the first bad commit, newest first.
+    ///
+    /// Empty until both a good and a bad commit have been marked.
+    pub remaining: Vec<SharedString>,
+    /// The first bad commit, once the bisect has narrowed it down.
+    pub first_bad: Option<SharedString>,
+}
+
+impl BisectProgress {
+    /// Roughly how many more commits have to be tested before the first bad commit is found.
+    pub fn remaining_steps(&self) -> usize {
+        if self.first_bad.is_some() || self.remaining.len() <= 1 {
+            0
+        } else {
+            self.remaining.len().ilog2() as usize
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Tag {
+    pub name: SharedString,
+    /// The commit the tag points to, with annotated tags peeled.
+    pub sha: SharedString,
+    /// The first line of the tag's annotation, or `None` for lightweight tags.
+    pub message: Option<SharedString>,
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum FetchOptions {
     All,
@@ -722,6 +865,117 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Lists the commits that an interactive rebase onto `base` would replay, oldest first.
+    ///
+    /// When `base` is `None`, every commit reachable from HEAD is listed, as with `git rebase --root`.
+    fn rebase_todo(&self, base: Option<String>) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>>;
+
+    /// Starts an interactive rebase onto `base` that applies `todo` in order.
+    ///
+    /// Returns successfully if the rebase stops for conflicts; use [`GitRepository::rebase_progress`]
+    /// to check whether it is still in progress.
+    fn rebase_interactive(
+        &self,
+        base: Option<String>,
+        todo: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn control_rebase(
+        &self,
+        control: RebaseControl,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the progress of the rebase in progress, if any.
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>>;
+
+    /// Starts a bisect, marking `bad` and `good` if given.
+    ///
+    /// Once both a good and a bad commit are known, the next commit to test is checked out.
+    fn bisect_start(
+        &self,
+        bad: Option<String>,
+        good: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Marks `commit`, or HEAD when `commit` is `None`, and checks out the next commit to test.
+    fn bisect_mark(
+        &self,
+        term: BisectTerm,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Ends the bisect, checking out the branch or commit it was started from.
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the progress of the bisect in progress, if any.
+    fn bisect_progress(&self) -> BoxFuture<'_, Result<Option<BisectProgress>>>;
+
+    /// Applies the changes introduced by `commit` on top of HEAD.
+    ///
+    /// Returns successfully if the cherry-pick stops for conflicts, which are reported through
+    /// `CHERRY_PICK_HEAD` like those of a merge.
+    fn cherry_pick(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Creates a commit on top of HEAD that undoes the changes introduced by `commit`.
+    ///
+    /// Returns successfully if the revert stops for conflicts, which are reported through
+    /// `REVERT_HEAD` like those of a merge.
+    fn revert(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>>;
+
+    /// Creates a tag pointing at `commit`, or at HEAD when `commit` is `None`.
+    ///
+    /// The tag is annotated if a `message` is given, and lightweight otherwise.
+    fn create_tag(
+        &self,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>>;
+
+    fn push_tag(
+        &self,
+        name: String,
+        remote: String,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
+    /// Lists the submodules declared in `.gitmodules`, sorted by path.
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>>;
+
+    /// Initializes the given submodules if needed and checks out their recorded commits.
+    ///
+    /// Updates every submodule when `paths` is empty.
+    fn update_submodules(
+        &self,
+        paths: Vec<RepoPath>,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
     fn push(
         &self,
         branch_name: String,
@@ -882,6 +1136,44 @@ impl RealGitRepository {
         *self.any_git_binary_help_output.lock() = Some(output.clone());
         output
     }
+
+    /// Runs a `cherry-pick` or `revert` of a single commit. Both leave `head_ref` behind when
+    /// they stop for conflicts, in which case the operation is left in progress.
+    fn replay_commit(
+        &self,
+        subcommand: &'static str,
+        head_ref: &'static str,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_dir = self.path();
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                anyhow::ensure!(
+                    !git_dir.join("CHERRY_PICK_HEAD").exists()
+                        && !git_dir.join("REVERT_HEAD").exists(),
+                    "A cherry-pick or revert is already in progress"
+                );
+
+                let output = new_command(&git_binary_path)
+                    .envs(env.iter())
+                    .current_dir(&working_directory?)
+                    .args([subcommand, "--no-edit", &commit])
+                    .output()
+                    .await?;
+
+                anyhow::ensure!(
+                    output.status.success() || git_dir.join(head_ref).exists(),
+                    "Failed to {}:\n{}",
+                    subcommand,
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -2018,61 +2310,571 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
-    fn commit(
+    fn rebase_todo(&self, base: Option<String>) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let range = match base {
+                    Some(base) => format!("{base}..HEAD"),
+                    None => "HEAD".to_string(),
+                };
+                let output = new_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args([
+                        "--no-optional-locks",
+                        "log",
+                        "--reverse",
+                        "--topo-order",
+                        "--no-merges",
+                        "--format=%H%x00%s%x00%B%x1e",
+                        &range,
+                        "--",
+                    ])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list commits to rebase:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                Ok(stdout
+                    .split('\x1e')
+                    .filter_map(|commit| {
+                        let mut fields = commit.trim_start().splitn(3, '\0');
+                        let sha = fields.next().filter(|sha| !sha.is_empty())?;
+                        let subject = fields.next()?;
+                        let message = fields.next()?;
+                        Some(RebaseTodoEntry {
+                            action: RebaseTodoAction::Pick,
+                            sha: sha.to_string().into(),
+                            subject: subject.to_string().into(),
+                            message: message.trim_end().to_string().into(),
+                        })
+                    })
+                    .collect())
+            })
+            .boxed()
+    }
+
+    fn rebase_interactive(
         &self,
-        message: SharedString,
-        name_and_email: Option<(SharedString, SharedString)>,
-        options: CommitOptions,
-        ask_pass: AskPassDelegate,
+        base: Option<String>,
+        todo: Vec<RebaseTodoEntry>,
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
+        let git_dir = self.path();
         let working_directory = self.working_directory();
         let git_binary_path = self.any_git_binary_path.clone();
-        let executor = self.executor.clone();
-        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
-        // which we want to block on.
-        async move {
-            let mut cmd = new_command(git_binary_path);
-            cmd.current_dir(&working_directory?)
-                .envs(env.iter())
-                .args(["commit", "--quiet", "-m"])
-                .arg(&message.to_string())
-                .arg("--cleanup=strip")
-                .arg("--no-verify")
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped());
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                anyhow::ensure!(
+                    read_rebase_progress(&git_dir).is_none(),
+                    "A rebase is already in progress"
+                );
+                if let Some(first) = todo
+                    .iter()
+                    .find(|entry| entry.action != RebaseTodoAction::Drop)
+                {
+                    anyhow::ensure!(
+                        !first.action.melds_into_previous(),
+                        "Cannot {} without a previous commit",
+                        first.action.as_str()
+                    );
+                }
 
-            if options.amend {
-                cmd.arg("--amend");
-            }
+                // Git hands us its own todo list to edit, which we replace with ours. Rewording
+                // is done by amending the picked commit, so that Git never needs to open an editor.
+                let script_dir = git_dir.join("zed-rebase");
+                if script_dir.exists() {
+                    std::fs::remove_dir_all(&script_dir)?;
+                }
+                std::fs::create_dir_all(&script_dir)?;
+                let mut script = String::new();
+                for (ix, entry) in todo.iter().enumerate() {
+                    if entry.action == RebaseTodoAction::Reword {
+                        let message_path = script_dir.join(format!("message-{ix}"));
+                        std::fs::write(&message_path, entry.message.as_bytes())?;
+                        script.push_str(&format!(
+                            "pick {}\nexec {} commit --amend --only --allow-empty --quiet --file={}\n",
+                            entry.sha,
+                            shell_quote(&git_binary_path),
+                            shell_quote(&message_path),
+                        ));
+                    } else {
+                        script.push_str(&format!("{} {}\n", entry.action.as_str(), entry.sha));
+                    }
+                }
+                let todo_path = script_dir.join("git-rebase-todo");
+                std::fs::write(&todo_path, script)?;
 
-            if options.signoff {
-                cmd.arg("--signoff");
-            }
+                let output = new_command(&git_binary_path)
+                    .envs(env.iter())
+                    .env("GIT_SEQUENCE_EDITOR", format!("cp {}", shell_quote(&todo_path)))
+                    .env("GIT_EDITOR", "true")
+                    .current_dir(&working_directory)
+                    .args(["rebase", "--interactive"])
+                    .arg(base.as_deref().unwrap_or("--root"))
+                    .output()
+                    .await?;
 
-            if let Some((name, email)) = name_and_email {
-                cmd.arg("--author").arg(&format!("{name} <{email}>"));
-            }
+                // A rebase that stops for conflicts exits with an error, but is left in progress
+                // so that it can be continued once they are resolved.
+                anyhow::ensure!(
+                    output.status.success() || read_rebase_progress(&git_dir).is_some(),
+                    "Failed to rebase:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
 
-            run_git_command(env, ask_pass, cmd, executor).await?;
+    fn control_rebase(
+        &self,
+        control: RebaseControl,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_dir = self.path();
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let progress_before = read_rebase_progress(&git_dir);
+                anyhow::ensure!(progress_before.is_some(), "No rebase in progress");
 
-            Ok(())
-        }
-        .boxed()
+                let output = new_command(&git_binary_path)
+                    .envs(env.iter())
+                    .env("GIT_EDITOR", "true")
+                    .current_dir(&working_directory?)
+                    .args(["rebase", &format!("--{}", control.as_str())])
+                    .output()
+                    .await?;
+
+                // Moving on to a later step that stops for conflicts is not a failure.
+                let progress_after = read_rebase_progress(&git_dir);
+                anyhow::ensure!(
+                    output.status.success()
+                        || (control != RebaseControl::Abort
+                            && progress_after.is_some()
+                            && progress_after != progress_before),
+                    "Failed to {} rebase:\n{}",
+                    control.as_str(),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
     }
 
-    fn push(
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>> {
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move { read_rebase_progress(&git_dir) })
+            .boxed()
+    }
+
+    fn bisect_start(
         &self,
-        branch_name: String,
-        remote_branch_name: String,
-        remote_name: String,
-        options: Option<PushOptions>,
-        ask_pass: AskPassDelegate,
+        bad: Option<String>,
+        good: Vec<String>,
         env: Arc<HashMap<String, String>>,
-        cx: AsyncApp,
-    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
         let working_directory = self.working_directory();
-        let executor = cx.background_executor().clone();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                // `git bisect start` only accepts good commits after a bad one.
+                anyhow::ensure!(
+                    bad.is_some() || good.is_empty(),
+                    "A bad commit is required to mark good commits when starting a bisect"
+                );
+                let mut args = vec!["bisect".to_string(), "start".to_string()];
+                args.extend(bad);
+                args.extend(good);
+                args.push("--".to_string());
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs((*env).clone())
+                    .run(&args)
+                    .await?;
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn bisect_mark(
+        &self,
+        term: BisectTerm,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                let mut args = vec!["bisect".to_string(), term.as_str().to_string()];
+                args.extend(commit);
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs((*env).clone())
+                    .run(&args)
+                    .await?;
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs((*env).clone())
+                    .run(&["bisect", "reset"])
+                    .await?;
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn bisect_progress(&self) -> BoxFuture<'_, Result<Option<BisectProgress>>> {
+        let git_dir = self.path();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                if !git_dir.join("BISECT_START").exists() {
+                    return Ok(None);
+                }
+                let git = GitBinary::new(git_binary_path, working_directory?, executor);
+
+                let refs = git
+                    .run(&[
+                        "for-each-ref",
+                        "--format=%(refname) %(objectname)",
+                        "refs/bisect/",
+                    ])
+                    .await?;
+                let log = smol::fs::read_to_string(git_dir.join("BISECT_LOG"))
+                    .await
+                    .unwrap_or_default();
+                let mut progress = parse_bisect_refs(&refs, &log);
+
+                if let Some(first_bad) = &progress.first_bad {
+                    progress.remaining = vec![first_bad.clone()];
+                } else if let Some(bad) = &progress.bad
+                    && !progress.good.is_empty()
+                {
+                    let mut args =
+                        vec!["rev-list".to_string(), bad.to_string(), "--not".to_string()];
+                    args.extend(progress.good.iter().map(|sha| sha.to_string()));
+                    progress.remaining = git
+                        .run(&args)
+                        .await?
+                        .lines()
+                        .map(|sha| sha.to_string().into())
+                        .collect();
+                }
+                Ok(Some(progress))
+            })
+            .boxed()
+    }
+
+    fn cherry_pick(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.replay_commit("cherry-pick", "CHERRY_PICK_HEAD", commit, env)
+    }
+
+    fn revert(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.replay_commit("revert", "REVERT_HEAD", commit, env)
+    }
+
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let fields = [
+                    "%(refname:strip=2)",
+                    "%(objecttype)",
+                    "%(objectname)",
+                    "%(*objectname)",
+                    "%(contents:subject)",
+                ]
+                .join("%00");
+                let output = new_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args([
+                        "for-each-ref",
+                        "--sort=-creatordate",
+                        &format!("--format={fields}"),
+                        "refs/tags",
+                    ])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list tags:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                Ok(stdout
+                    .lines()
+                    .filter_map(|line| {
+                        let mut fields = line.splitn(5, '\0');
+                        let name = fields.next().filter(|name| !name.is_empty())?;
+                        let object_type = fields.next()?;
+                        let object_sha = fields.next()?;
+                        let peeled_sha = fields.next()?;
+                        let subject = fields.next()?;
+                        // Only annotated tags have a subject of their own; for lightweight tags
+                        // this is the subject of the commit they point to.
+                        let is_annotated = object_type == "tag";
+                        Some(Tag {
+                            name: name.to_string().into(),
+                            sha: if peeled_sha.is_empty() {
+                                object_sha.to_string().into()
+                            } else {
+                                peeled_sha.to_string().into()
+                            },
+                            message: is_annotated.then(|| subject.to_string().into()),
+                        })
+                    })
+                    .collect())
+            })
+            .boxed()
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut args = vec!["tag".to_string()];
+                if let Some(message) = message {
+                    args.push("--annotate".to_string());
+                    args.push(format!("--message={message}"));
+                }
+                // Keeps a name starting with a dash from being read as an option.
+                args.push("--".to_string());
+                args.push(name);
+                args.extend(commit);
+
+                let output = new_command(&git_binary_path)
+                    .envs(env.iter())
+                    .current_dir(&working_directory?)
+                    .args(args)
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to create tag:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&["tag", "-d", "--", &name])
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
+    fn push_tag(
+        &self,
+        name: String,
+        remote: String,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        let git_binary_path = self.system_git_binary_path.clone();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let git_binary_path = git_binary_path.context("git not found on $PATH, can't push")?;
+            let mut command = new_command(git_binary_path);
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .arg("push")
+                .arg(remote)
+                .arg(format!("refs/tags/{name}"))
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            run_git_command(env, ask_pass, command, executor).await
+        }
+        .boxed()
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                let gitmodules =
+                    match smol::fs::read_to_string(working_directory.join(GITMODULES)).await {
+                        Ok(contents) => parse_gitmodules(&contents),
+                        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                            return Ok(Vec::new());
+                        }
+                        Err(error) => return Err(error.into()),
+                    };
+                if gitmodules.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                let output = new_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args(["ls-files", "--stage", "-z", "--"])
+                    .args(gitmodules.iter().map(|entry| entry.path.as_str()))
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list submodules:\n{}",
+                    String::from_utf8_lossy(&output.stderr),
+                );
+                let gitlinks = parse_gitlinks(&String::from_utf8_lossy(&output.stdout));
+
+                let mut submodules = Vec::with_capacity(gitmodules.len());
+                for entry in gitmodules {
+                    let Some(path) = RepoPath::new(&entry.path).log_err() else {
+                        continue;
+                    };
+                    let checked_out_sha =
+                        read_checked_out_sha(&working_directory.join(&entry.path)).await;
+                    let gitlink = gitlinks.get(&entry.path);
+                    submodules.push(Submodule::new(entry, path, gitlink, checked_out_sha));
+                }
+                submodules.sort_by(|a, b| a.path.cmp(&b.path));
+                Ok(submodules)
+            })
+            .boxed()
+    }
+
+    fn update_submodules(
+        &self,
+        paths: Vec<RepoPath>,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        let git_binary_path = self.system_git_binary_path.clone();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let git_binary_path =
+                git_binary_path.context("git not found on $PATH, can't update submodules")?;
+            let mut command = new_command(git_binary_path);
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["submodule", "update", "--init", "--"])
+                .args(paths.iter().map(|path| path.as_unix_str()))
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            run_git_command(env, ask_pass, command, executor).await
+        }
+        .boxed()
+    }
+
+    fn commit(
+        &self,
+        message: SharedString,
+        name_and_email: Option<(SharedString, SharedString)>,
+        options: CommitOptions,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let executor = self.executor.clone();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let mut cmd = new_command(git_binary_path);
+            cmd.current_dir(&working_directory?)
+                .envs(env.iter())
+                .args(["commit", "--quiet", "-m"])
+                .arg(&message.to_string())
+                .arg("--cleanup=strip")
+                .arg("--no-verify")
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            if options.amend {
+                cmd.arg("--amend");
+            }
+
+            if options.signoff {
+                cmd.arg("--signoff");
+            }
+
+            if let Some((name, email)) = name_and_email {
+                cmd.arg("--author").arg(&format!("{name} <{email}>"));
+            }
+
+            run_git_command(env, ask_pass, cmd, executor).await?;
+
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn push(
+        &self,
+        branch_name: String,
+        remote_branch_name: String,
+        remote_name: String,
+        options: Option<PushOptions>,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
         let git_binary_path = self.system_git_binary_path.clone();
         // Note: Do not spawn this command on the background thread, it might pop open the credential helper
         // which we want to block on.
@@ -2658,6 +3460,80 @@ impl GitRepository for RealGitRepository {
     }
 }
 
+/// Reads the state Git keeps in the repository directory while a rebase is in progress.
+fn read_rebase_progress(git_dir: &Path) -> Option<RebaseProgress> {
+    let read = |path: PathBuf| {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|contents| contents.trim().to_string())
+    };
+
+    let merge_dir = git_dir.join("rebase-merge");
+    let apply_dir = git_dir.join("rebase-apply");
+    let (state_dir, step_file, total_file) = if merge_dir.is_dir() {
+        (merge_dir, "msgnum", "end")
+    } else if apply_dir.join("rebasing").exists() {
+        (apply_dir, "next", "last")
+    } else {
+        return None;
+    };
+
+    let head_name = read(state_dir.join("head-name"))
+        .filter(|name| name != "detached HEAD")
+        .map(|name| {
+            name.strip_prefix("refs/heads/")
+                .unwrap_or(&name)
+                .to_string()
+                .into()
+        });
+    let read_number = |file: &str| {
+        read(state_dir.join(file))
+            .and_then(|number| number.parse().ok())
+            .unwrap_or(0)
+    };
+    Some(RebaseProgress {
+        head_name,
+        onto: read(state_dir.join("onto")).unwrap_or_default().into(),
+        step: read_number(step_file),
+        total_steps: read_number(total_file),
+    })
+}
+
+/// Reads the marked commits from the output of `git for-each-ref refs/bisect/`, and the first bad
+/// commit from the bisect log once it has been found.
+fn parse_bisect_refs(refs: &str, log: &str) -> BisectProgress {
+    let mut progress = BisectProgress::default();
+    for line in refs.lines() {
+        let Some((name, sha)) = line.split_once(' ') else {
+            continue;
+        };
+        let sha = SharedString::from(sha.to_string());
+        let Some(name) = name.strip_prefix("refs/bisect/") else {
+            continue;
+        };
+        if name == "bad" {
+            progress.bad = Some(sha);
+        } else if name.starts_with("good-") {
+            progress.good.push(sha);
+        } else if name.starts_with("skip-") {
+            progress.skipped.push(sha);
+        }
+    }
+    progress.first_bad = log.lines().rev().find_map(|line| {
+        let sha = line
+            .strip_prefix("# first bad commit: [")?
+            .split(']')
+            .next()?;
+        Some(sha.to_string().into())
+    });
+    progress
+}
+
+/// Quotes a path for use in the commands Git runs through its shell.
+fn shell_quote(path: &Path) -> String {
+    format!("'{}'", path.to_string_lossy().replace('\'', "'\\''"))
+}
+
 async fn run_commit_data_reader(
     git_binary_path: PathBuf,
     working_directory: PathBuf,
@@ -3220,6 +4096,7 @@ fn checkpoint_author_envs() -> HashMap<String, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::submodule::SubmoduleStatus;
     use gpui::TestAppContext;
 
     fn disable_git_global_config() {
@@ -3576,6 +4453,481 @@ mod tests {
         assert_eq!(upstream.branch_name(), Some("feature/git-pull-request"));
     }
 
+    #[gpui::test]
+    async fn test_interactive_rebase(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let mut base = None;
+        for (file, contents) in [("a", "a"), ("b", "b"), ("c", "c"), ("d", "d")] {
+            smol::fs::write(repo_dir.path().join(file), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(file)], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                format!("Add {file}\n\nBody of {file}").into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            base.get_or_insert(repo.head_sha().await.unwrap());
+        }
+
+        let todo = repo.rebase_todo(base.clone()).await.unwrap();
+        assert_eq!(
+            todo.iter()
+                .map(|entry| (entry.subject.as_ref(), entry.message.as_ref()))
+                .collect::<Vec<_>>(),
+            [
+                ("Add b", "Add b\n\nBody of b"),
+                ("Add c", "Add c\n\nBody of c"),
+                ("Add d", "Add d\n\nBody of d"),
+            ]
+        );
+        assert!(
+            todo.iter()
+                .all(|entry| entry.action == RebaseTodoAction::Pick)
+        );
+
+        let [b, c, d]: [RebaseTodoEntry; 3] = todo.try_into().unwrap();
+        let rebased = vec![
+            d,
+            RebaseTodoEntry {
+                action: RebaseTodoAction::Reword,
+                message: "Add b and c".into(),
+                ..b
+            },
+            RebaseTodoEntry {
+                action: RebaseTodoAction::Fixup,
+                ..c
+            },
+        ];
+        repo.rebase_interactive(base.clone(), rebased, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_progress().await, None);
+        assert_eq!(
+            repo.rebase_todo(base.clone())
+                .await
+                .unwrap()
+                .iter()
+                .map(|entry| entry.subject.as_ref())
+                .collect::<Vec<_>>(),
+            ["Add d", "Add b and c"]
+        );
+        for file in ["a", "b", "c", "d"] {
+            assert_eq!(
+                smol::fs::read_to_string(repo_dir.path().join(file))
+                    .await
+                    .unwrap(),
+                file
+            );
+        }
+
+        // Dropping a commit that a later one depends on stops the rebase for conflicts.
+        for contents in ["a1", "a2"] {
+            smol::fs::write(repo_dir.path().join("a"), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path("a")], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                format!("Change a to {contents}").into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+        }
+        let head_before_rebase = repo.head_sha().await;
+        let mut todo = repo.rebase_todo(base.clone()).await.unwrap();
+        todo[2].action = RebaseTodoAction::Drop;
+        repo.rebase_interactive(base.clone(), todo, env.clone())
+            .await
+            .unwrap();
+        let progress = repo.rebase_progress().await.unwrap();
+        assert_eq!(progress.head_name.as_deref(), Some("master"));
+        assert!(progress.step > 0 && progress.step <= progress.total_steps);
+
+        repo.control_rebase(RebaseControl::Continue, env.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(repo.rebase_progress().await, Some(progress));
+
+        repo.control_rebase(RebaseControl::Abort, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_progress().await, None);
+        assert_eq!(repo.head_sha().await, head_before_rebase);
+    }
+
+    #[gpui::test]
+    async fn test_cherry_pick_revert_and_tags(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        let commit_file = async |file: &str, contents: &str| {
+            smol::fs::write(repo_dir.path().join(file), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(file)], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                format!("Set {file} to {contents}").into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            repo.head_sha().await.unwrap()
+        };
+
+        commit_file("a", "a").await;
+        repo.create_branch("other".into(), None).await.unwrap();
+        let other_commit = commit_file("a", "b").await;
+        repo.change_branch("master".into()).await.unwrap();
+        commit_file("b", "b").await;
+
+        repo.cherry_pick(other_commit.clone(), env.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            smol::fs::read_to_string(repo_dir.path().join("a"))
+                .await
+                .unwrap(),
+            "b"
+        );
+
+        let cherry_picked_commit = repo.head_sha().await.unwrap();
+        repo.revert(cherry_picked_commit, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            smol::fs::read_to_string(repo_dir.path().join("a"))
+                .await
+                .unwrap(),
+            "a"
+        );
+
+        // A conflicting cherry-pick is left in progress, like a conflicting merge.
+        let head = commit_file("a", "c").await;
+        repo.cherry_pick(other_commit.clone(), env.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.revparse_batch(vec!["CHERRY_PICK_HEAD".into()])
+                .await
+                .unwrap(),
+            [Some(other_commit.clone())]
+        );
+        repo.revert(head.clone(), env.clone()).await.unwrap_err();
+
+        repo.create_tag(
+            "v1".into(),
+            Some(other_commit.clone()),
+            Some("Release 1".into()),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        repo.create_tag("latest".into(), None, None, env.clone())
+            .await
+            .unwrap();
+        repo.create_tag("--force".into(), None, None, env.clone())
+            .await
+            .unwrap_err();
+        let mut tags = repo.tags().await.unwrap();
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(
+            tags,
+            [
+                Tag {
+                    name: "latest".into(),
+                    sha: head.into(),
+                    message: None,
+                },
+                Tag {
+                    name: "v1".into(),
+                    sha: other_commit.clone().into(),
+                    message: Some("Release 1".into()),
+                },
+            ]
+        );
+
+        repo.delete_tag("latest".into()).await.unwrap();
+        assert_eq!(
+            repo.tags()
+                .await
+                .unwrap()
+                .iter()
+                .map(|tag| tag.name.as_ref())
+                .collect::<Vec<_>>(),
+            ["v1"]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_submodules(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        let submodule_dir = repo_dir.path().join("vendor/lib");
+        git2::Repository::init(repo_dir.path()).unwrap();
+        git2::Repository::init(&submodule_dir).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let submodule_repo = RealGitRepository::new(
+            &submodule_dir.join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        assert_eq!(repo.submodules().await.unwrap(), []);
+
+        let mut submodule_commits = Vec::new();
+        for contents in ["one", "two"] {
+            smol::fs::write(submodule_dir.join("file"), contents)
+                .await
+                .unwrap();
+            submodule_repo
+                .stage_paths(vec![repo_path("file")], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            submodule_repo
+                .commit(
+                    contents.to_string().into(),
+                    None,
+                    CommitOptions::default(),
+                    AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                    env.clone(),
+                )
+                .await
+                .unwrap();
+            submodule_commits
+                .push(Oid::from_str(&submodule_repo.head_sha().await.unwrap()).unwrap());
+        }
+
+        smol::fs::write(
+            repo_dir.path().join(GITMODULES),
+            "[submodule \"lib\"]\n\tpath = vendor/lib\n\turl = ../lib\n[submodule \"docs\"]\n\tpath = docs\n",
+        )
+        .await
+        .unwrap();
+        repo.stage_paths(vec![repo_path("vendor/lib")], Arc::new(HashMap::default()))
+            .await
+            .unwrap();
+
+        let submodules = repo.submodules().await.unwrap();
+        assert_eq!(
+            submodules,
+            [
+                Submodule {
+                    name: "docs".into(),
+                    path: repo_path("docs"),
+                    url: None,
+                    recorded_sha: None,
+                    checked_out_sha: None,
+                    status: SubmoduleStatus::Uninitialized,
+                },
+                Submodule {
+                    name: "lib".into(),
+                    path: repo_path("vendor/lib"),
+                    url: Some("../lib".into()),
+                    recorded_sha: Some(submodule_commits[1]),
+                    checked_out_sha: Some(submodule_commits[1]),
+                    status: SubmoduleStatus::Current,
+                },
+            ]
+        );
+
+        submodule_repo
+            .reset(
+                submodule_commits[0].to_string(),
+                ResetMode::Mixed,
+                Arc::new(HashMap::default()),
+            )
+            .await
+            .unwrap();
+        let submodule = repo.submodules().await.unwrap().pop().unwrap();
+        assert_eq!(submodule.recorded_sha, Some(submodule_commits[1]));
+        assert_eq!(submodule.checked_out_sha, Some(submodule_commits[0]));
+        assert_eq!(submodule.status, SubmoduleStatus::Modified);
+
+        // Staging the submodule records the commit that's checked out.
+        repo.stage_paths(vec![repo_path("vendor/lib")], Arc::new(HashMap::default()))
+            .await
+            .unwrap();
+        let submodule = repo.submodules().await.unwrap().pop().unwrap();
+        assert_eq!(submodule.recorded_sha, Some(submodule_commits[0]));
+        assert_eq!(submodule.status, SubmoduleStatus::Current);
+
+        // Submodules cloned by git point to their git directory in the parent's `.git/modules`.
+        let modules_dir = repo_dir.path().join(".git/modules");
+        smol::fs::create_dir_all(&modules_dir).await.unwrap();
+        smol::fs::rename(submodule_dir.join(".git"), modules_dir.join("lib"))
+            .await
+            .unwrap();
+        smol::fs::write(
+            submodule_dir.join(".git"),
+            "gitdir: ../../.git/modules/lib\n",
+        )
+        .await
+        .unwrap();
+        let submodule = repo.submodules().await.unwrap().pop().unwrap();
+        assert_eq!(submodule.checked_out_sha, Some(submodule_commits[0]));
+        assert_eq!(submodule.status, SubmoduleStatus::Current);
+    }
+
+    #[gpui::test]
+    async fn test_bisect(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let mut commits = Vec::new();
+        for version in 1..=6 {
+            smol::fs::write(repo_dir.path().join("version"), version.to_string())
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path("version")], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                format!("Version {version}").into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            commits.push(SharedString::from(repo.head_sha().await.unwrap()));
+        }
+        assert_eq!(repo.bisect_progress().await.unwrap(), None);
+
+        repo.bisect_start(None, Vec::new(), env.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.bisect_progress().await.unwrap(),
+            Some(BisectProgress::default())
+        );
+        repo.bisect_mark(BisectTerm::Bad, None, env.clone())
+            .await
+            .unwrap();
+        repo.bisect_mark(BisectTerm::Good, Some(commits[0].to_string()), env.clone())
+            .await
+            .unwrap();
+
+        let progress = repo.bisect_progress().await.unwrap().unwrap();
+        assert_eq!(progress.bad.as_ref(), Some(&commits[5]));
+        assert_eq!(progress.good, [commits[0].clone()]);
+        assert_eq!(
+            progress.remaining,
+            commits[1..].iter().rev().cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(progress.remaining_steps(), 2);
+
+        // Version 4 introduced the "bug".
+        let mut tested = 0;
+        let first_bad = loop {
+            let progress = repo.bisect_progress().await.unwrap().unwrap();
+            if let Some(first_bad) = progress.first_bad {
+                break first_bad;
+            }
+            let head = SharedString::from(repo.head_sha().await.unwrap());
+            assert!(progress.remaining.contains(&head));
+            let version: u32 = smol::fs::read_to_string(repo_dir.path().join("version"))
+                .await
+                .unwrap()
+                .parse()
+                .unwrap();
+            let term = if version >= 4 {
+                BisectTerm::Bad
+            } else {
+                BisectTerm::Good
+            };
+            repo.bisect_mark(term, None, env.clone()).await.unwrap();
+            tested += 1;
+        };
+        assert_eq!(first_bad, commits[3]);
+        assert!(tested <= 3);
+        assert_eq!(
+            repo.bisect_progress().await.unwrap().unwrap().remaining,
+            [commits[3].clone()]
+        );
+
+        repo.bisect_reset(env.clone()).await.unwrap();
+        assert_eq!(repo.bisect_progress().await.unwrap(), None);
+        assert_eq!(repo.head_sha().await.as_deref(), Some(commits[5].as_ref()));
+    }
+
+    #[test]
+    fn test_bisect_exit_codes() {
+        assert_eq!(BisectTerm::from_exit_code(0), Some(BisectTerm::Good));
+        assert_eq!(BisectTerm::from_exit_code(1), Some(BisectTerm::Bad));
+        assert_eq!(BisectTerm::from_exit_code(101), Some(BisectTerm::Bad));
+        assert_eq!(BisectTerm::from_exit_code(125), Some(BisectTerm::Skip));
+        assert_eq!(BisectTerm::from_exit_code(127), Some(BisectTerm::Bad));
+        assert_eq!(BisectTerm::from_exit_code(128), None);
+        assert_eq!(BisectTerm::from_exit_code(-1), None);
+    }
+
     impl RealGitRepository {
         /// Force a Git garbage collection on the repository.
         fn gc(&self) -> BoxFuture<'_, Result<()>> {
@@ -0,0 +1,327 @@
+use crate::repository::RepoPath;
+use crate::{DOT_GIT, Oid};
+use collections::HashMap;
+use gpui::SharedString;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SubmoduleStatus {
+    /// Listed in `.gitmodules`, but not cloned into the working tree.
+    Uninitialized,
+    /// The checked-out commit matches the one recorded in the index.
+    Current,
+    /// The checked-out commit differs from the one recorded in the index.
+    Modified,
+    /// The recorded commit has unresolved merge conflicts.
+    Conflicted,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Submodule {
+    pub name: SharedString,
+    pub path: RepoPath,
+    pub url: Option<SharedString>,
+    /// The commit recorded for the submodule in the parent repository's index.
+    pub recorded_sha: Option<Oid>,
+    /// The commit checked out in the submodule's working tree.
+    pub checked_out_sha: Option<Oid>,
+    pub status: SubmoduleStatus,
+}
+
+impl Submodule {
+    pub fn new(
+        entry: GitmodulesEntry,
+        path: RepoPath,
+        gitlink: Option<&Gitlink>,
+        checked_out_sha: Option<Oid>,
+    ) -> Self {
+        let recorded_sha = gitlink.and_then(|gitlink| gitlink.sha);
+        let status = if gitlink.is_some_and(|gitlink| gitlink.conflicted) {
+            SubmoduleStatus::Conflicted
+        } else if checked_out_sha.is_none() {
+            SubmoduleStatus::Uninitialized
+        } else if checked_out_sha != recorded_sha {
+            SubmoduleStatus::Modified
+        } else {
+            SubmoduleStatus::Current
+        };
+        Self {
+            name: entry.name.into(),
+            path,
+            url: entry.url.map(Into::into),
+            recorded_sha,
+            checked_out_sha,
+            status,
+        }
+    }
+}
+
+/// A `[submodule "name"]` section of a `.gitmodules` file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GitmodulesEntry {
+    pub name: String,
+    pub path: String,
+    pub url: Option<String>,
+}
+
+/// Parses the contents of a `.gitmodules` file, skipping sections without a `path`.
+pub fn parse_gitmodules(contents: &str) -> Vec<GitmodulesEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<(String, Option<String>, Option<String>)> = None;
+
+    let mut finish = |current: Option<(String, Option<String>, Option<String>)>| {
+        if let Some((name, Some(path), url)) = current {
+            entries.push(GitmodulesEntry { name, path, url });
+        }
+    };
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[') {
+            finish(current.take());
+            let Some(section) = section.strip_suffix(']') else {
+                continue;
+            };
+            let Some(name) = section.trim().strip_prefix("submodule") else {
+                continue;
+            };
+            current = Some((parse_value(name.trim()), None, None));
+            continue;
+        }
+
+        let Some((_, path, url)) = current.as_mut() else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = parse_value(value.trim());
+        match key.trim().to_ascii_lowercase().as_str() {
+            "path" => *path = Some(value.trim_end_matches('/').to_string()),
+            "url" => *url = Some(value),
+            _ => {}
+        }
+    }
+    finish(current);
+
+    entries
+}
+
+/// Unquotes a git config value, or strips a trailing comment from an unquoted one.
+fn parse_value(value: &str) -> String {
+    if let Some(quoted) = value.strip_prefix('"') {
+        let mut result = String::new();
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => result.extend(chars.next()),
+                c => result.push(c),
+            }
+        }
+        result
+    } else {
+        let end = value.find([' ', '\t']).map_or(value.len(), |ix| {
+            value[ix..]
+                .find(['#', ';'])
+                .map_or(value.len(), |comment| ix + comment)
+        });
+        value[..end].trim_end().to_string()
+    }
+}
+
+/// A submodule commit as recorded in the index.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Gitlink {
+    pub sha: Option<Oid>,
+    pub conflicted: bool,
+}
+
+/// Parses the output of `git ls-files --stage -z`, keeping only submodule entries.
+pub fn parse_gitlinks(output: &str) -> HashMap<String, Gitlink> {
+    let mut gitlinks = HashMap::<String, Gitlink>::default();
+    for record in output.split('\0') {
+        let Some((info, path)) = record.split_once('\t') else {
+            continue;
+        };
+        let mut info = info.split(' ');
+        let (Some(mode), Some(sha), Some(stage)) = (info.next(), info.next(), info.next()) else {
+            continue;
+        };
+        if mode != "160000" {
+            continue;
+        }
+        let gitlink = gitlinks.entry(path.to_string()).or_default();
+        match stage {
+            "0" => gitlink.sha = Oid::from_str(sha).ok(),
+            // During a conflict, show the commit from our side.
+            "2" => {
+                gitlink.sha = Oid::from_str(sha).ok();
+                gitlink.conflicted = true;
+            }
+            _ => gitlink.conflicted = true,
+        }
+    }
+    gitlinks
+}
+
+/// Reads the commit checked out in the submodule at `submodule_directory` from its git directory,
+/// so that listing submodules doesn't spawn git for each of them.
+/// Returns `None` if the submodule isn't initialized.
+pub async fn read_checked_out_sha(submodule_directory: &Path) -> Option<Oid> {
+    let dot_git = submodule_directory.join(DOT_GIT);
+    let git_dir = if smol::fs::metadata(&dot_git).await.ok()?.is_dir() {
+        dot_git
+    } else {
+        // Submodules cloned by git have a `.git` file pointing to a directory in the parent's `.git/modules`.
+        let contents = smol::fs::read_to_string(&dot_git).await.ok()?;
+        submodule_directory.join(parse_gitdir_file(&contents)?)
+    };
+
+    let head = smol::fs::read_to_string(git_dir.join("HEAD")).await.ok()?;
+    let Some(ref_name) = head.trim().strip_prefix("ref:") else {
+        return Oid::from_str(head.trim()).ok();
+    };
+    let ref_name = ref_name.trim();
+    // Linked worktrees keep their refs in the common git directory.
+    let common_dir = match smol::fs::read_to_string(git_dir.join("commondir")).await {
+        Ok(common_dir) => git_dir.join(common_dir.trim()),
+        Err(_) => git_dir,
+    };
+    if let Ok(sha) = smol::fs::read_to_string(common_dir.join(ref_name)).await {
+        return Oid::from_str(sha.trim()).ok();
+    }
+    let packed_refs = smol::fs::read_to_string(common_dir.join("packed-refs"))
+        .await
+        .ok()?;
+    find_packed_ref(&packed_refs, ref_name)
+}
+
+/// Parses a `.git` file, such as `gitdir: ../.git/modules/lib`.
+fn parse_gitdir_file(contents: &str) -> Option<PathBuf> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))
+        .map(|git_dir| PathBuf::from(git_dir.trim()))
+}
+
+/// Looks up a ref in the contents of a `packed-refs` file.
+fn find_packed_ref(packed_refs: &str, ref_name: &str) -> Option<Oid> {
+    packed_refs
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.starts_with('^'))
+        .find_map(|line| {
+            let (sha, name) = line.split_once(' ')?;
+            if name.trim() == ref_name {
+                Oid::from_str(sha).ok()
+            } else {
+                None
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unindent::Unindent as _;
+
+    #[test]
+    fn test_parse_gitmodules() {
+        let entries = parse_gitmodules(
+            &r#"
+            # Vendored dependencies
+            [submodule "vendor/lib"]
+            	path = vendor/lib
+            	url = https://example.com/lib.git
+            [core]
+            	path = ignored
+            [submodule "quoted name"]
+            	path = "third party/hal" ; the HAL
+            	url = ../hal  # relative to the superproject
+            	branch = main
+            [submodule "missing-path"]
+            	url = ../unused
+        "#
+            .unindent(),
+        );
+        assert_eq!(
+            entries,
+            vec![
+                GitmodulesEntry {
+                    name: "vendor/lib".into(),
+                    path: "vendor/lib".into(),
+                    url: Some("https://example.com/lib.git".into()),
+                },
+                GitmodulesEntry {
+                    name: "quoted name".into(),
+                    path: "third party/hal".into(),
+                    url: Some("../hal".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_gitlinks() {
+        let sha_a = "6ff077a41291f933143271bedb8dd351a4dd69a3";
+        let sha_b = "aef2fd53a022f55b2714c55d128c8465de5e6936";
+        let output = [
+            "100644 72ea43907c8c5d33169b1eab4b5b6e6d34f64140 0\t.gitmodules".to_string(),
+            format!("160000 {sha_a} 0\tother"),
+            format!("160000 {sha_a} 1\tvendor/lib"),
+            format!("160000 {sha_b} 2\tvendor/lib"),
+            format!("160000 {sha_a} 3\tvendor/lib"),
+            String::new(),
+        ]
+        .join("\0");
+
+        let gitlinks = parse_gitlinks(&output);
+        assert_eq!(gitlinks.len(), 2);
+        assert_eq!(
+            gitlinks["other"],
+            Gitlink {
+                sha: Some(Oid::from_str(sha_a).unwrap()),
+                conflicted: false,
+            }
+        );
+        assert_eq!(
+            gitlinks["vendor/lib"],
+            Gitlink {
+                sha: Some(Oid::from_str(sha_b).unwrap()),
+                conflicted: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_git_dir_files() {
+        assert_eq!(
+            parse_gitdir_file("gitdir: ../../.git/modules/vendor/lib\n"),
+            Some(PathBuf::from("../../.git/modules/vendor/lib"))
+        );
+        assert_eq!(parse_gitdir_file(""), None);
+
+        let sha_a = "6ff077a41291f933143271bedb8dd351a4dd69a3";
+        let sha_b = "aef2fd53a022f55b2714c55d128c8465de5e6936";
+        let packed_refs = format!(
+            "# pack-refs with: peeled fully-peeled sorted\n\
+             {sha_a} refs/heads/main\n\
+             {sha_b} refs/tags/v1.0\n\
+             ^{sha_a}\n"
+        );
+        assert_eq!(
+            find_packed_ref(&packed_refs, "refs/heads/main"),
+            Some(Oid::from_str(sha_a).unwrap())
+        );
+        assert_eq!(
+            find_packed_ref(&packed_refs, "refs/tags/v1.0"),
+            Some(Oid::from_str(sha_b).unwrap())
+        );
+        assert_eq!(find_packed_ref(&packed_refs, "refs/heads/other"), None);
+    }
+}
@@ -1,16 +1,25 @@
-use collections::{BTreeMap, HashMap};
+use collections::{BTreeMap, HashMap, HashSet};
 use feature_flags::{FeatureFlag, FeatureFlagAppExt as _};
 use git::{
     BuildCommitPermalinkParams, GitHostingProviderRegistry, GitRemote, Oid, ParsedGitRemote,
     parse_git_remote_url,
-    repository::{CommitDiff, InitialGraphCommitData, LogOrder, LogSource},
+    repository::{
+        BisectProgress, BisectTerm, CommitDiff, InitialGraphCommitData, LogOrder, LogSource,
+    },
+};
+use git_ui::{
+    bisect,
+    commit_tooltip::CommitAvatar,
+    commit_view::{self, CommitView},
+    rebase_editor::RebaseEditor,
+    tag_picker,
 };
-use git_ui::{commit_tooltip::CommitAvatar, commit_view::CommitView};
 use gpui::{
-    AnyElement, App, Bounds, ClipboardItem, Context, Corner, DefiniteLength, ElementId, Entity,
-    EventEmitter, FocusHandle, Focusable, FontWeight, Hsla, InteractiveElement, ParentElement,
-    PathBuilder, Pixels, Point, Render, ScrollStrategy, ScrollWheelEvent, SharedString, Styled,
-    Subscription, Task, WeakEntity, Window, actions, anchored, deferred, point, px,
+    AnyElement, App, Bounds, ClipboardItem, Context, Corner, DefiniteLength, DismissEvent,
+    ElementId, Entity, EventEmitter, FocusHandle, Focusable, FontWeight, Hsla, InteractiveElement,
+    MouseButton, ParentElement, PathBuilder, Pixels, Point, Render, ScrollStrategy,
+    ScrollWheelEvent, SharedString, Styled, Subscription, Task, WeakEntity, Window, actions,
+    anchored, deferred, point, px,
 };
 use menu::{SelectNext, SelectPrevious};
 use project::{
@@ -29,6 +38,7 @@ use ui::{
 use workspace::{
     Workspace,
     item::{Item, ItemEvent, SerializableItem},
+    notifications::DetachAndPromptErr,
 };
 
 const COMMIT_CIRCLE_RADIUS: Pixels = px(4.5);
@@ -42,6 +52,22 @@ actions!(
     [
         /// Opens the commit view for the selected commit.
         OpenCommitView,
+        /// Starts an interactive rebase of the selected commit and the commits after it.
+        InteractiveRebase,
+        /// Applies the selected commit onto the current branch.
+        CherryPickCommit,
+        /// Creates a new commit that undoes the selected commit.
+        RevertCommit,
+        /// Creates a tag at the selected commit.
+        CreateTag,
+        /// Starts a bisect with the selected commit as the bad commit.
+        StartBisectHere,
+        /// Marks the selected commit as good in the bisect in progress.
+        MarkCommitGood,
+        /// Marks the selected commit as bad in the bisect in progress.
+        MarkCommitBad,
+        /// Skips the selected commit in the bisect in progress.
+        SkipCommit,
     ]
 );
 
@@ -51,6 +77,22 @@ impl FeatureFlag for GitGraphFeatureFlag {
     const NAME: &'static str = "git-graph";
 }
 
+/// The badge shown on a commit that has been marked during a bisect.
+fn bisect_mark(bisect: &BisectProgress, sha: &str) -> Option<(SharedString, Color)> {
+    let is_sha = |candidate: &SharedString| *candidate == sha;
+    if bisect.first_bad.as_ref().is_some_and(is_sha) {
+        Some(("first bad".into(), Color::Error))
+    } else if bisect.bad.as_ref().is_some_and(is_sha) {
+        Some(("bad".into(), Color::Error))
+    } else if bisect.good.iter().any(is_sha) {
+        Some(("good".into(), Color::Success))
+    } else if bisect.skipped.iter().any(is_sha) {
+        Some(("skipped".into(), Color::Warning))
+    } else {
+        None
+    }
+}
+
 fn timestamp_format() -> &'static [BorrowedFormatItem<'static>] {
     static FORMAT: OnceLock<Vec<BorrowedFormatItem<'static>>> = OnceLock::new();
     FORMAT.get_or_init(|| {
@@ -754,6 +796,14 @@ impl GitGraph {
             .read_with(cx, |project, cx| project.active_repository(cx));
 
         let row_height = self.row_height;
+        let bisect = self.bisect_progress(cx);
+        // While a bisect is narrowing things down, commits that can no longer be
+        // the first bad commit are dimmed.
+        let bisect_remaining = bisect
+            .as_ref()
+            .filter(|bisect| bisect.first_bad.is_none())
+            .map(|bisect| bisect.remaining.iter().cloned().collect::<HashSet<_>>())
+            .filter(|remaining| !remaining.is_empty());
 
         // We fetch data outside the visible viewport to avoid loading entries when
         // users scroll through the git graph
@@ -808,8 +858,19 @@ impl GitGraph {
                     .copied()
                     .unwrap_or_else(|| accent_colors.0.first().copied().unwrap_or_default());
                 let is_selected = self.selected_entry_idx == Some(idx);
+                let sha = commit.data.sha.to_string();
+                let bisect_badge = bisect
+                    .as_ref()
+                    .and_then(|bisect| bisect_mark(bisect, &sha))
+                    .map(|(label, color)| self.render_badge(&label, color.color(cx)));
+                let is_outside_bisect = bisect_badge.is_none()
+                    && bisect_remaining
+                        .as_ref()
+                        .is_some_and(|remaining| !remaining.contains(sha.as_str()));
                 let text_color = if is_selected {
                     Color::Default
+                } else if is_outside_bisect {
+                    Color::Disabled
                 } else {
                     Color::Muted
                 };
@@ -824,6 +885,7 @@ impl GitGraph {
                                 .gap_1()
                                 .items_center()
                                 .overflow_hidden()
+                                .children(bisect_badge)
                                 .children((!commit.data.ref_names.is_empty()).then(|| {
                                     h_flex().flex_shrink().gap_2().items_center().children(
                                         commit
@@ -953,6 +1015,224 @@ impl GitGraph {
         );
     }
 
+    fn interactive_rebase_selected(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(selected_entry_index) = self.selected_entry_idx else {
+            return;
+        };
+        let Some(commit_entry) = self.graph_data.commits.get(selected_entry_index) else {
+            return;
+        };
+        let repository = self
+            .project
+            .read_with(cx, |project, cx| project.active_repository(cx));
+        let Some(repository) = repository else {
+            return;
+        };
+
+        // Rebasing onto the parent includes the selected commit; a root commit has no parent.
+        let base = commit_entry
+            .data
+            .parents
+            .first()
+            .map(|parent| parent.to_string());
+        RebaseEditor::open(self.workspace.clone(), repository, base, window, cx)
+            .detach_and_prompt_err(
+                "Failed to start interactive rebase",
+                window,
+                cx,
+                |e, _, _| Some(e.to_string()),
+            );
+    }
+
+    fn selected_commit(&self, cx: &App) -> Option<(Entity<Repository>, SharedString)> {
+        let commit_entry = self.graph_data.commits.get(self.selected_entry_idx?)?;
+        let repository = self.project.read(cx).active_repository(cx)?;
+        Some((repository, commit_entry.data.sha.to_string().into()))
+    }
+
+    fn cherry_pick_selected(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some((repository, sha)) = self.selected_commit(cx) {
+            commit_view::cherry_pick_commit(repository, sha, window, cx);
+        }
+    }
+
+    fn revert_selected(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some((repository, sha)) = self.selected_commit(cx) {
+            commit_view::revert_commit(repository, sha, window, cx);
+        }
+    }
+
+    fn create_tag_at_selected(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((repository, sha)) = self.selected_commit(cx) else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                tag_picker::create_tag_at(workspace, repository, sha, window, cx);
+            })
+            .ok();
+    }
+
+    fn start_bisect_at_selected(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some((repository, sha)) = self.selected_commit(cx) {
+            bisect::start_bisect(repository, Some(sha), Vec::new(), window, cx);
+        }
+    }
+
+    fn mark_selected(&mut self, term: BisectTerm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some((repository, sha)) = self.selected_commit(cx) {
+            bisect::mark_commit(repository, term, Some(sha), window, cx);
+        }
+    }
+
+    fn bisect_progress(&self, cx: &App) -> Option<BisectProgress> {
+        let repository = self.project.read(cx).active_repository(cx)?;
+        repository.read(cx).bisect.clone()
+    }
+
+    fn render_bisect_bar(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let bisect = self.bisect_progress(cx)?;
+        let message = if let Some(first_bad) = &bisect.first_bad {
+            let short_sha = first_bad
+                .chars()
+                .take(git::SHORT_SHA_LENGTH)
+                .collect::<String>();
+            format!("{short_sha} is the first bad commit")
+        } else if bisect.remaining.is_empty() {
+            "Bisecting: mark a good and a bad commit to narrow down the range".to_string()
+        } else {
+            format!(
+                "Bisecting: {} commits left to test (roughly {} steps)",
+                bisect.remaining.len(),
+                bisect.remaining_steps()
+            )
+        };
+        let is_finished = bisect.first_bad.is_some();
+
+        Some(
+            h_flex()
+                .w_full()
+                .px_2()
+                .py_1()
+                .gap_1()
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    Icon::new(IconName::Crosshair)
+                        .size(IconSize::Small)
+                        .color(Color::Muted),
+                )
+                .child(Label::new(message).color(Color::Muted).single_line())
+                .child(div().flex_1())
+                .when(!is_finished, |this| {
+                    this.child(
+                        Button::new("bisect-good", "Good")
+                            .label_size(LabelSize::Small)
+                            .tooltip(Tooltip::for_action_title(
+                                "Mark HEAD as Good",
+                                &git::BisectGood,
+                            ))
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(git::BisectGood.boxed_clone(), cx);
+                            }),
+                    )
+                    .child(
+                        Button::new("bisect-bad", "Bad")
+                            .label_size(LabelSize::Small)
+                            .tooltip(Tooltip::for_action_title(
+                                "Mark HEAD as Bad",
+                                &git::BisectBad,
+                            ))
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(git::BisectBad.boxed_clone(), cx);
+                            }),
+                    )
+                    .child(
+                        Button::new("bisect-skip", "Skip")
+                            .label_size(LabelSize::Small)
+                            .tooltip(Tooltip::for_action_title("Skip HEAD", &git::BisectSkip))
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(git::BisectSkip.boxed_clone(), cx);
+                            }),
+                    )
+                    .child(
+                        Button::new("bisect-run", "Run…")
+                            .label_size(LabelSize::Small)
+                            .tooltip(Tooltip::for_action_title(
+                                "Test Each Commit with a Command",
+                                &git::BisectRun,
+                            ))
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(git::BisectRun.boxed_clone(), cx);
+                            }),
+                    )
+                })
+                .child(
+                    Button::new("bisect-reset", "Reset")
+                        .label_size(LabelSize::Small)
+                        .tooltip(Tooltip::for_action_title("Reset Bisect", &git::ResetBisect))
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(git::ResetBisect.boxed_clone(), cx);
+                        }),
+                ),
+        )
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        entry_index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_entry(entry_index, cx);
+        let focus_handle = self.focus_handle.clone();
+        let is_bisecting = self.bisect_progress(cx).is_some();
+        let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
+            menu.context(focus_handle)
+                .action("Open Commit", OpenCommitView.boxed_clone())
+                .separator()
+                .separator()
+                .action(
+                    "Cherry-pick onto Current Branch",
+                    CherryPickCommit.boxed_clone(),
+                )
+                .action("Revert Commit", RevertCommit.boxed_clone())
+                .action("Create Tag Here…", CreateTag.boxed_clone())
+                .separator()
+                .action(
+                    "Interactive Rebase from Here",
+                    InteractiveRebase.boxed_clone(),
+                )
+                .separator()
+                .map(|menu| {
+                    if is_bisecting {
+                        menu.action("Mark as Good", MarkCommitGood.boxed_clone())
+                            .action("Mark as Bad", MarkCommitBad.boxed_clone())
+                            .action("Skip Commit", SkipCommit.boxed_clone())
+                            .action("Reset Bisect", git::ResetBisect.boxed_clone())
+                    } else {
+                        menu.action("Start Bisect Here", StartBisectHere.boxed_clone())
+                    }
+                })
+        });
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
     fn get_remote(
         &self,
         repository: &Repository,
@@ -1648,6 +1928,7 @@ impl Render for GitGraph {
                             .map_row(move |(index, row), _window, cx| {
                                 let is_selected = selected_entry_idx == Some(index);
                                 let weak = weak_self.clone();
+                                let weak_for_menu = weak_self.clone();
                                 row.h(row_height)
                                     .when(is_selected, |row| {
                                         row.bg(cx.theme().colors().element_selected)
@@ -1662,6 +1943,18 @@ impl Render for GitGraph {
                                         })
                                         .ok();
                                     })
+                                    .on_mouse_down(MouseButton::Right, move |event, window, cx| {
+                                        weak_for_menu
+                                            .update(cx, |this, cx| {
+                                                this.deploy_context_menu(
+                                                    event.position,
+                                                    index,
+                                                    window,
+                                                    cx,
+                                                );
+                                            })
+                                            .ok();
+                                    })
                                     .into_any_element()
                             })
                             .uniform_list(
@@ -1676,7 +1969,7 @@ impl Render for GitGraph {
                 })
         };
 
-        div()
+        v_flex()
             .size_full()
             .bg(cx.theme().colors().editor_background)
             .key_context("GitGraph")
@@ -1684,9 +1977,34 @@ impl Render for GitGraph {
             .on_action(cx.listener(|this, _: &OpenCommitView, window, cx| {
                 this.open_selected_commit_view(window, cx);
             }))
+            .on_action(cx.listener(|this, _: &InteractiveRebase, window, cx| {
+                this.interactive_rebase_selected(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &CherryPickCommit, window, cx| {
+                this.cherry_pick_selected(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &RevertCommit, window, cx| {
+                this.revert_selected(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &CreateTag, window, cx| {
+                this.create_tag_at_selected(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &StartBisectHere, window, cx| {
+                this.start_bisect_at_selected(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &MarkCommitGood, window, cx| {
+                this.mark_selected(BisectTerm::Good, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &MarkCommitBad, window, cx| {
+                this.mark_selected(BisectTerm::Bad, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &SkipCommit, window, cx| {
+                this.mark_selected(BisectTerm::Skip, window, cx);
+            }))
             .on_action(cx.listener(Self::select_prev))
             .on_action(cx.listener(Self::select_next))
-            .child(content)
+            .children(self.render_bisect_bar(cx))
+            .child(div().flex_1().min_h_0().child(content))
             .children(self.context_menu.as_ref().map(|(menu, position, _)| {
                 deferred(
                     anchored()
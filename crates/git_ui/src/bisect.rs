@@ -0,0 +1,760 @@
+use anyhow::{Context as _, Result, anyhow};
+use collections::HashSet;
+use futures::{StreamExt as _, channel::mpsc};
+use fuzzy::StringMatchCandidate;
+use git::repository::BisectTerm;
+use gpui::{
+    AnyElement, App, AsyncWindowContext, Context, DismissEvent, Entity, EventEmitter, FocusHandle,
+    Focusable, Render, SharedString, Subscription, Task, WeakEntity, Window,
+};
+use notifications::status_toast::{StatusToast, ToastIcon};
+use picker::{Picker, PickerDelegate};
+use project::{
+    Project, TaskSourceKind,
+    git_store::{GitStore, GitStoreEvent, Repository, RepositoryEvent, RepositoryId},
+};
+use std::sync::Arc;
+use task::{RevealStrategy, TaskContext, TaskTemplate, TaskVariables, VariableName};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
+
+use crate::{commit_view::CommitView, resolve_active_repository};
+
+pub(crate) fn register(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let bisect = cx.new(|cx| Bisect::new(workspace, window, cx));
+
+    workspace.register_action(|workspace, _: &git::StartBisect, window, cx| {
+        if let Some(repository) = resolve_active_repository(workspace, cx) {
+            let head = repository
+                .read(cx)
+                .head_commit
+                .as_ref()
+                .map(|commit| commit.sha.clone());
+            start_bisect(repository, head, Vec::new(), window, cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &git::BisectGood, window, cx| {
+        if let Some(repository) = resolve_active_repository(workspace, cx) {
+            mark_commit(repository, BisectTerm::Good, None, window, cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &git::BisectBad, window, cx| {
+        if let Some(repository) = resolve_active_repository(workspace, cx) {
+            mark_commit(repository, BisectTerm::Bad, None, window, cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &git::BisectSkip, window, cx| {
+        if let Some(repository) = resolve_active_repository(workspace, cx) {
+            mark_commit(repository, BisectTerm::Skip, None, window, cx);
+        }
+    });
+    workspace.register_action({
+        let bisect = bisect.clone();
+        move |workspace, _: &git::ResetBisect, window, cx| {
+            bisect.update(cx, |bisect, _| bisect.run.take());
+            if let Some(repository) = resolve_active_repository(workspace, cx) {
+                reset_bisect(repository, window, cx);
+            }
+        }
+    });
+
+    // Each step of a bisect run is spawned in a terminal, which guests can't open.
+    if workspace.project().read(cx).is_via_collab() {
+        return;
+    }
+    workspace.register_action(move |workspace, _: &git::BisectRun, window, cx| {
+        let Some(repository) = resolve_active_repository(workspace, cx) else {
+            return;
+        };
+        let bisect = bisect.clone();
+        let project = workspace.project().clone();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            BisectRunModal::new(bisect, repository, &project, window, cx)
+        });
+    });
+}
+
+/// Starts a bisect with `bad` (or HEAD) as the bad commit and `good` as known good commits.
+pub fn start_bisect(
+    repository: Entity<Repository>,
+    bad: Option<SharedString>,
+    good: Vec<SharedString>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let bad = bad.map(|sha| sha.to_string());
+    let good = good.into_iter().map(|sha| sha.to_string()).collect();
+    window
+        .spawn(cx, async move |cx| {
+            repository
+                .update(cx, |repo, cx| repo.bisect_start(bad, good, cx))
+                .await??;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to start bisect", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+}
+
+/// Marks `commit` (or HEAD) in the bisect in progress.
+pub fn mark_commit(
+    repository: Entity<Repository>,
+    term: BisectTerm,
+    commit: Option<SharedString>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let commit = commit.map(|sha| sha.to_string());
+    window
+        .spawn(cx, async move |cx| {
+            repository
+                .update(cx, |repo, cx| repo.bisect_mark(term, commit, cx))
+                .await??;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to mark commit", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+}
+
+pub fn reset_bisect(repository: Entity<Repository>, window: &mut Window, cx: &mut App) {
+    window
+        .spawn(cx, async move |cx| {
+            repository
+                .update(cx, |repo, cx| repo.bisect_reset(cx))
+                .await??;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to reset bisect", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+}
+
+/// Reports the first bad commit once a bisect finishes, and drives `git bisect run`.
+struct Bisect {
+    workspace: WeakEntity<Workspace>,
+    /// Repositories seen with an unfinished bisect, so that a bisect which had already
+    /// finished when the project was opened isn't reported again.
+    in_progress: HashSet<RepositoryId>,
+    run: Option<Task<()>>,
+    _subscription: Subscription,
+}
+
+impl Bisect {
+    fn new(workspace: &Workspace, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let git_store = workspace.project().read(cx).git_store().clone();
+        let in_progress = git_store
+            .read(cx)
+            .repositories()
+            .iter()
+            .filter(|(_, repository)| {
+                repository
+                    .read(cx)
+                    .bisect
+                    .as_ref()
+                    .is_some_and(|bisect| bisect.first_bad.is_none())
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        let subscription = cx.subscribe_in(&git_store, window, Self::handle_git_store_event);
+
+        Self {
+            workspace: workspace.weak_handle(),
+            in_progress,
+            run: None,
+            _subscription: subscription,
+        }
+    }
+
+    fn handle_git_store_event(
+        &mut self,
+        git_store: &Entity<GitStore>,
+        event: &GitStoreEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let GitStoreEvent::RepositoryUpdated(id, RepositoryEvent::BisectChanged, _) = event else {
+            return;
+        };
+        let Some(repository) = git_store.read(cx).repositories().get(id).cloned() else {
+            return;
+        };
+        let Some(bisect) = repository.read(cx).bisect.as_ref() else {
+            self.in_progress.remove(id);
+            return;
+        };
+        let Some(first_bad) = bisect.first_bad.clone() else {
+            self.in_progress.insert(*id);
+            return;
+        };
+        if self.in_progress.remove(id) {
+            self.report_first_bad(repository, first_bad, window, cx);
+        }
+    }
+
+    fn report_first_bad(
+        &mut self,
+        repository: Entity<Repository>,
+        first_bad: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let short_sha = first_bad
+            .chars()
+            .take(git::SHORT_SHA_LENGTH)
+            .collect::<String>();
+
+        CommitView::open(
+            first_bad.to_string(),
+            repository.downgrade(),
+            self.workspace.clone(),
+            None,
+            None,
+            window,
+            cx,
+        );
+        workspace.update(cx, |workspace, cx| {
+            let message = format!("{short_sha} is the first bad commit");
+            let toast = StatusToast::new(message, cx, |this, _cx| {
+                this.icon(ToastIcon::new(IconName::Crosshair).color(Color::Error))
+                    .action("Reset Bisect", |window, cx| {
+                        window.dispatch_action(Box::new(git::ResetBisect), cx);
+                    })
+                    .dismiss_button(true)
+            });
+            workspace.toggle_status_toast(toast, cx);
+        });
+    }
+
+    /// Runs `template` at each step of the bisect, marking the checked-out commit by its exit code.
+    fn run(
+        &mut self,
+        repository: Entity<Repository>,
+        template: TaskTemplate,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let workspace = self.workspace.clone();
+        self.run = Some(cx.spawn_in(window, async move |_, cx| {
+            let Err(error) = run_steps(workspace.clone(), repository, template, cx).await else {
+                return;
+            };
+            workspace
+                .update(cx, |workspace, cx| {
+                    let message = format!("Bisect run stopped: {error}");
+                    let toast = StatusToast::new(message, cx, |this, _cx| {
+                        this.icon(ToastIcon::new(IconName::XCircle).color(Color::Error))
+                            .dismiss_button(true)
+                    });
+                    workspace.toggle_status_toast(toast, cx);
+                })
+                .ok();
+        }));
+    }
+}
+
+async fn run_steps(
+    workspace: WeakEntity<Workspace>,
+    repository: Entity<Repository>,
+    template: TaskTemplate,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let (is_bisecting, work_directory) = repository.read_with(cx, |repository, _| {
+        (
+            repository.bisect.is_some(),
+            repository.work_directory_abs_path.to_path_buf(),
+        )
+    });
+    anyhow::ensure!(is_bisecting, "no bisect is in progress");
+
+    let task_context = TaskContext {
+        task_variables: TaskVariables::from_iter([(
+            VariableName::WorktreeRoot,
+            work_directory.to_string_lossy().into_owned(),
+        )]),
+        cwd: Some(work_directory),
+        ..TaskContext::default()
+    };
+    let spawn_in_terminal = template
+        .resolve_task("git-bisect-run", &task_context)
+        .with_context(|| format!("`{}` can't be run outside of a file", template.label))?
+        .resolved;
+
+    loop {
+        let status = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.spawn_in_terminal(spawn_in_terminal.clone(), window, cx)
+            })?
+            .await
+            .context("the command was cancelled")??;
+        let term = status
+            .code()
+            .and_then(BisectTerm::from_exit_code)
+            .ok_or_else(|| anyhow!("`{}` exited with {status}", template.command))?;
+
+        // Wait for the snapshot to reflect the mark before deciding whether to continue.
+        let (tx, mut rx) = mpsc::unbounded();
+        let _subscription = cx.update(|_, cx| {
+            cx.subscribe(&repository, move |_, event: &RepositoryEvent, _| {
+                if matches!(event, RepositoryEvent::BisectChanged) {
+                    tx.unbounded_send(()).ok();
+                }
+            })
+        })?;
+        repository
+            .update(cx, |repository, cx| repository.bisect_mark(term, None, cx))
+            .await??;
+        rx.next().await;
+
+        let finished = repository.read_with(cx, |repository, _| {
+            repository
+                .bisect
+                .as_ref()
+                .is_none_or(|bisect| bisect.first_bad.is_some())
+        });
+        if finished {
+            return Ok(());
+        }
+    }
+}
+
+/// Picks the command to test each commit of a bisect run with: a task from a tasks.json file,
+/// or the typed query run as a shell command.
+struct BisectRunModal {
+    picker: Entity<Picker<BisectRunDelegate>>,
+    _subscription: Subscription,
+}
+
+impl BisectRunModal {
+    fn new(
+        bisect: Entity<Bisect>,
+        repository: Entity<Repository>,
+        project: &Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let project = project.read(cx);
+        let worktree_id = project
+            .find_worktree(&repository.read(cx).work_directory_abs_path, cx)
+            .map(|(worktree, _)| worktree.read(cx).id());
+        let templates_request = project
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .map(|inventory| inventory.read(cx).list_tasks(None, None, worktree_id, cx));
+
+        cx.spawn_in(window, async move |this, cx| {
+            let Some(templates_request) = templates_request else {
+                return anyhow::Ok(());
+            };
+            let templates = templates_request
+                .await
+                .into_iter()
+                .filter(|(kind, _)| {
+                    matches!(
+                        kind,
+                        TaskSourceKind::Worktree { .. } | TaskSourceKind::AbsPath { .. }
+                    )
+                })
+                .map(|(_, template)| template)
+                .collect();
+
+            this.update_in(cx, |this, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.templates = templates;
+                    picker.refresh(window, cx);
+                })
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+
+        let delegate = BisectRunDelegate {
+            bisect,
+            repository,
+            templates: Vec::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx).modal(true));
+        let subscription = cx.subscribe(&picker, |_, _, _, cx| cx.emit(DismissEvent));
+
+        Self {
+            picker,
+            _subscription: subscription,
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for BisectRunModal {}
+impl ModalView for BisectRunModal {}
+impl Focusable for BisectRunModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for BisectRunModal {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("BisectRunModal")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum BisectRunEntry {
+    Task {
+        template: TaskTemplate,
+        positions: Vec<usize>,
+    },
+    Command {
+        command: String,
+    },
+}
+
+struct BisectRunDelegate {
+    bisect: Entity<Bisect>,
+    repository: Entity<Repository>,
+    templates: Vec<TaskTemplate>,
+    matches: Vec<BisectRunEntry>,
+    selected_index: usize,
+}
+
+impl BisectRunDelegate {
+    fn run(&self, template: TaskTemplate, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let repository = self.repository.clone();
+        self.bisect.update(cx, |bisect, cx| {
+            bisect.run(repository, template, window, cx);
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn run_command(&self, command: String, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let template = TaskTemplate {
+            label: format!("git bisect run {command}"),
+            command,
+            reveal: RevealStrategy::NoFocus,
+            ..TaskTemplate::default()
+        };
+        self.run(template, window, cx);
+    }
+}
+
+impl PickerDelegate for BisectRunDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a task or enter a command to test each commit with…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let templates = self.templates.clone();
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<BisectRunEntry> = if query.is_empty() {
+                templates
+                    .into_iter()
+                    .map(|template| BisectRunEntry::Task {
+                        template,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = templates
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, template)| StringMatchCandidate::new(ix, &template.label))
+                    .collect::<Vec<_>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    1000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| BisectRunEntry::Task {
+                    template: templates[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+
+            let command = query.trim();
+            if !command.is_empty() {
+                matches.push(BisectRunEntry::Command {
+                    command: command.to_string(),
+                });
+            }
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_index = 0;
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        // Secondary confirmation runs the query as a command, even when it matches a task.
+        let entry = if secondary {
+            self.matches
+                .iter()
+                .find(|entry| matches!(entry, BisectRunEntry::Command { .. }))
+        } else {
+            self.matches.get(self.selected_index)
+        };
+        match entry.cloned() {
+            Some(BisectRunEntry::Task { template, .. }) => self.run(template, window, cx),
+            Some(BisectRunEntry::Command { command }) => self.run_command(command, window, cx),
+            None => cx.emit(DismissEvent),
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let (icon, title) = match self.matches.get(ix)? {
+            BisectRunEntry::Task {
+                template,
+                positions,
+            } => (
+                IconName::PlayOutlined,
+                HighlightedLabel::new(template.label.clone(), positions.clone())
+                    .truncate()
+                    .into_any_element(),
+            ),
+            BisectRunEntry::Command { command } => (
+                IconName::Terminal,
+                Label::new(format!("Run: \"{command}\""))
+                    .truncate()
+                    .into_any_element(),
+            ),
+        };
+
+        Some(
+            ListItem::new(format!("bisect-run-{ix}"))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(icon).color(Color::Muted))
+                .child(title),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No tasks found".into())
+    }
+
+    fn render_footer(&self, _: &mut Window, cx: &mut Context<Picker<Self>>) -> Option<AnyElement> {
+        Some(
+            div()
+                .w_full()
+                .px_3()
+                .py_1p5()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Label::new(
+                        "Exit code 0 marks a commit good, 125 skips it, and 1 to 127 mark it bad.",
+                    )
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+                )
+                .into_any_element(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::{Oid, repository::InitialGraphCommitData};
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::{cell::RefCell, path::Path, process::ExitStatus, rc::Rc};
+    use task::SpawnInTerminal;
+    use util::path;
+    use workspace::{MultiWorkspace, TerminalProvider};
+
+    /// Exits with 1 at the commits in `bad` and with 0 elsewhere, recording each checked out commit.
+    struct FakeTerminalProvider {
+        fs: Arc<FakeFs>,
+        bad: Vec<String>,
+        tested: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl TerminalProvider for FakeTerminalProvider {
+        fn spawn(
+            &self,
+            task: SpawnInTerminal,
+            _window: &mut Window,
+            _cx: &mut App,
+        ) -> Task<Option<Result<ExitStatus>>> {
+            assert_eq!(task.command.as_deref(), Some("make test"));
+            let head = self
+                .fs
+                .with_git_state(Path::new(path!("/project/.git")), false, |state| {
+                    state.refs["HEAD"].clone()
+                })
+                .unwrap();
+            let code = if self.bad.contains(&head) { 1 } else { 0 };
+            self.tested.borrow_mut().push(head);
+            Task::ready(Some(Ok(exit_status(code))))
+        }
+    }
+
+    fn exit_status(code: i32) -> ExitStatus {
+        #[cfg(unix)]
+        {
+            std::os::unix::process::ExitStatusExt::from_raw(code << 8)
+        }
+        #[cfg(windows)]
+        {
+            std::os::windows::process::ExitStatusExt::from_raw(code as u32)
+        }
+    }
+
+    #[gpui::test]
+    async fn test_run_steps(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        // A linear history, newest first, which went bad at `shas[2]`.
+        let shas = (0..8)
+            .map(|ix| format!("{:040x}", 8 - ix).parse::<Oid>().unwrap())
+            .collect::<Vec<_>>();
+        let sha = |ix: usize| shas[ix].to_string();
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        fs.set_graph_commits(
+            Path::new(path!("/project/.git")),
+            shas.iter()
+                .enumerate()
+                .map(|(ix, sha)| {
+                    Arc::new(InitialGraphCommitData {
+                        sha: *sha,
+                        parents: shas.get(ix + 1).copied().into_iter().collect(),
+                        ref_names: Vec::new(),
+                    })
+                })
+                .collect(),
+        );
+        fs.with_git_state(Path::new(path!("/project/.git")), true, |state| {
+            state.refs.insert("HEAD".into(), sha(0));
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |multi_workspace, _| multi_workspace.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.run_until_parked();
+
+        let tested = Rc::default();
+        workspace.update(cx, |workspace, _| {
+            workspace.set_terminal_provider(FakeTerminalProvider {
+                fs: fs.clone(),
+                bad: (0..=2).map(sha).collect(),
+                tested: Rc::clone(&tested),
+            });
+        });
+        let repository = project.read_with(cx, |project, cx| {
+            project.repositories(cx).values().next().unwrap().clone()
+        });
+        repository
+            .update(cx, |repository, cx| {
+                repository.bisect_start(Some(sha(0)), vec![sha(7)], cx)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+
+        let template = TaskTemplate {
+            label: "Test".into(),
+            command: "make test".into(),
+            ..TaskTemplate::default()
+        };
+        let run = cx.update(|window, cx| {
+            let workspace = workspace.downgrade();
+            let repository = repository.clone();
+            window.spawn(cx, async move |cx| {
+                run_steps(workspace, repository, template, cx).await
+            })
+        });
+        cx.run_until_parked();
+        run.await.unwrap();
+
+        assert_eq!(*tested.borrow(), [sha(4), sha(2), sha(3)]);
+        assert_eq!(
+            repository.read_with(cx, |repository, _| repository
+                .bisect
+                .as_ref()
+                .and_then(|bisect| bisect.first_bad.clone())),
+            Some(sha(2).into())
+        );
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        zlog::init_test();
+
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        });
+    }
+}
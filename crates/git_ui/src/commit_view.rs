@@ -34,15 +34,29 @@ use workspace::{
     Item, ItemHandle, ItemNavHistory, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView,
     Workspace,
     item::{ItemEvent, TabContentParams},
-    notifications::NotifyTaskExt,
+    notifications::{DetachAndPromptErr, NotifyTaskExt},
     pane::SaveIntent,
     searchable::SearchableItemHandle,
 };
 
 use crate::commit_tooltip::CommitAvatar;
 use crate::git_panel::GitPanel;
-
-actions!(git, [ApplyCurrentStash, PopCurrentStash, DropCurrentStash,]);
+use crate::tag_picker;
+
+actions!(
+    git,
+    [
+        ApplyCurrentStash,
+        PopCurrentStash,
+        DropCurrentStash,
+        /// Applies the changes from the active commit view onto the current branch.
+        CherryPickCurrentCommit,
+        /// Creates a new commit that undoes the changes from the active commit view.
+        RevertCurrentCommit,
+        /// Creates a tag at the commit in the active commit view.
+        TagCurrentCommit,
+    ]
+);
 
 pub fn init(cx: &mut App) {
     cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
@@ -55,6 +69,21 @@ pub fn init(cx: &mut App) {
         workspace.register_action(|workspace, _: &PopCurrentStash, window, cx| {
             CommitView::pop_stash(workspace, window, cx);
         });
+        workspace.register_action(|workspace, _: &CherryPickCurrentCommit, window, cx| {
+            if let Some((repository, sha)) = CommitView::active_commit(workspace, cx) {
+                cherry_pick_commit(repository, sha, window, cx);
+            }
+        });
+        workspace.register_action(|workspace, _: &RevertCurrentCommit, window, cx| {
+            if let Some((repository, sha)) = CommitView::active_commit(workspace, cx) {
+                revert_commit(repository, sha, window, cx);
+            }
+        });
+        workspace.register_action(|workspace, _: &TagCurrentCommit, window, cx| {
+            if let Some((repository, sha)) = CommitView::active_commit(workspace, cx) {
+                tag_picker::create_tag_at(workspace, repository, sha, window, cx);
+            }
+        });
     })
     .detach();
 }
@@ -777,6 +806,22 @@ impl CommitView {
             .detach_and_notify_err(workspace.weak_handle(), window, cx);
     }
 
+    /// Returns the repository and SHA of the active commit view, unless it shows a stash.
+    fn active_commit(
+        workspace: &Workspace,
+        cx: &App,
+    ) -> Option<(Entity<Repository>, SharedString)> {
+        let commit_view = workspace.active_item_as::<CommitView>(cx)?;
+        let commit_view = commit_view.read(cx);
+        if commit_view.stash.is_some() {
+            return None;
+        }
+        Some((
+            commit_view.repository.clone(),
+            commit_view.commit.sha.clone(),
+        ))
+    }
+
     async fn close_commit_view(
         commit_view: Entity<CommitView>,
         workspace: WeakEntity<Workspace>,
@@ -1133,6 +1178,77 @@ impl ToolbarItemView for CommitViewToolbar {
     }
 }
 
+/// Asks for confirmation, then applies `sha` onto the current branch.
+pub fn cherry_pick_commit(
+    repository: Entity<Repository>,
+    sha: SharedString,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    replay_commit(ReplayKind::CherryPick, repository, sha, window, cx);
+}
+
+/// Asks for confirmation, then commits the inverse of `sha` on the current branch.
+pub fn revert_commit(
+    repository: Entity<Repository>,
+    sha: SharedString,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    replay_commit(ReplayKind::Revert, repository, sha, window, cx);
+}
+
+#[derive(Clone, Copy)]
+enum ReplayKind {
+    CherryPick,
+    Revert,
+}
+
+fn replay_commit(
+    kind: ReplayKind,
+    repository: Entity<Repository>,
+    sha: SharedString,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let short_sha = sha.chars().take(7).collect::<String>();
+    let branch = repository
+        .read(cx)
+        .branch
+        .as_ref()
+        .map(|branch| branch.name().to_owned())
+        .unwrap_or_else(|| "HEAD".to_owned());
+    let (message, confirm, error) = match kind {
+        ReplayKind::CherryPick => (
+            format!("Cherry-pick {short_sha} onto {branch}?"),
+            "Cherry-pick",
+            "Failed to cherry-pick commit",
+        ),
+        ReplayKind::Revert => (
+            format!("Revert {short_sha} on {branch}?"),
+            "Revert",
+            "Failed to revert commit",
+        ),
+    };
+    let answer = window.prompt(PromptLevel::Info, &message, None, &[confirm, "Cancel"], cx);
+
+    window
+        .spawn(cx, async move |cx| {
+            if answer.await != Ok(0) {
+                return anyhow::Ok(());
+            }
+            let sha = sha.to_string();
+            repository
+                .update(cx, |repo, cx| match kind {
+                    ReplayKind::CherryPick => repo.cherry_pick(sha, cx),
+                    ReplayKind::Revert => repo.revert(sha, cx),
+                })
+                .await??;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err(error, window, cx, |e, _, _| Some(e.to_string()));
+}
+
 fn stash_matches_index(sha: &str, stash_index: usize, repo: &Repository) -> bool {
     repo.stash_entries
         .entries
@@ -52,9 +52,12 @@ pub fn register_editor(editor: &mut Editor, buffer: Entity<MultiBuffer>, cx: &mu
         return;
     }
 
-    editor.register_addon(ConflictAddon {
-        buffers: Default::default(),
-    });
+    // Keep conflict sets that were registered while the editor was being built.
+    if editor.addon::<ConflictAddon>().is_none() {
+        editor.register_addon(ConflictAddon {
+            buffers: Default::default(),
+        });
+    }
 
     let buffers = buffer.read(cx).all_buffers();
     for buffer in buffers {
@@ -84,6 +87,48 @@ pub fn register_editor(editor: &mut Editor, buffer: Entity<MultiBuffer>, cx: &mu
     .detach();
 }
 
+/// Shows conflict controls for a buffer using a conflict set maintained by the caller, instead
+/// of the one the git store keeps for files that are unmerged in a repository.
+pub(crate) fn register_conflict_set(
+    editor: &mut Editor,
+    conflict_set: Entity<ConflictSet>,
+    cx: &mut Context<Editor>,
+) {
+    if editor.addon::<ConflictAddon>().is_none() {
+        editor.register_addon(ConflictAddon {
+            buffers: Default::default(),
+        });
+    }
+
+    let buffer_id = conflict_set.read(cx).snapshot().buffer_id;
+    let addon = editor.addon::<ConflictAddon>().unwrap();
+    if let Some(buffer_conflicts) = addon.buffers.get(&buffer_id) {
+        let previous_conflict_set = buffer_conflicts.conflict_set.clone();
+        let previous_conflicts_len = buffer_conflicts.block_ids.len();
+        conflicts_updated(
+            editor,
+            previous_conflict_set,
+            &ConflictSetUpdate {
+                buffer_range: None,
+                old_range: 0..previous_conflicts_len,
+                new_range: 0..0,
+            },
+            cx,
+        );
+    }
+
+    let subscription = cx.subscribe(&conflict_set, conflicts_updated);
+    editor.addon_mut::<ConflictAddon>().unwrap().buffers.insert(
+        buffer_id,
+        BufferConflicts {
+            block_ids: Vec::new(),
+            conflict_set: conflict_set.clone(),
+            _subscription: subscription,
+        },
+    );
+    excerpt_for_buffer_updated(editor, conflict_set, cx);
+}
+
 fn excerpt_for_buffer_updated(
     editor: &mut Editor,
     conflict_set: Entity<ConflictSet>,
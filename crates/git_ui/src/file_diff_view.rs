@@ -163,7 +163,7 @@ impl FileDiffView {
 }
 
 #[ztracing::instrument(skip_all)]
-async fn build_buffer_diff(
+pub(crate) async fn build_buffer_diff(
     old_buffer: &Entity<Buffer>,
     new_buffer: &Entity<Buffer>,
     language_registry: Arc<LanguageRegistry>,
@@ -7,7 +7,7 @@ use crate::remote_output::{self, RemoteAction, SuccessMessage};
 use crate::{branch_picker, picker_prompt, render_remote_button};
 use crate::{
     file_history_view::FileHistoryView, git_panel_settings::GitPanelSettings, git_status_icon,
-    repository_selector::RepositorySelector,
+    repository_selector::RepositorySelector, submodule_badge,
 };
 use agent_settings::AgentSettings;
 use anyhow::Context as _;
@@ -24,7 +24,7 @@ use futures::StreamExt as _;
 use git::commit::ParsedCommitMessage;
 use git::repository::{
     Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, FetchOptions, GitCommitter,
-    PushOptions, Remote, RemoteCommandOutput, ResetMode, Upstream, UpstreamTracking,
+    PushOptions, RebaseControl, Remote, RemoteCommandOutput, ResetMode, Upstream, UpstreamTracking,
     UpstreamTrackingStatus, get_git_committer,
 };
 use git::stash::GitStash;
@@ -148,6 +148,7 @@ struct GitMenuState {
     has_new_changes: bool,
     sort_by_path: bool,
     has_stash_items: bool,
+    has_submodules: bool,
     tree_view: bool,
 }
 
@@ -178,6 +179,12 @@ fn git_panel_context_menu(
             )
             .action_disabled_when(!state.has_stash_items, "Stash Pop", StashPop.boxed_clone())
             .action("View Stash", zed_actions::git::ViewStash.boxed_clone())
+            .action("View Tags", zed_actions::git::ViewTags.boxed_clone())
+            .action_disabled_when(
+                !state.has_submodules,
+                "Update Submodules",
+                git::UpdateSubmodules.boxed_clone(),
+            )
             .separator()
             .action("Open Diff", project_diff::Diff.boxed_clone())
             .separator()
@@ -737,7 +744,8 @@ impl GitPanel {
                         _,
                         RepositoryEvent::StatusesChanged
                         | RepositoryEvent::BranchChanged
-                        | RepositoryEvent::MergeHeadsChanged,
+                        | RepositoryEvent::MergeHeadsChanged
+                        | RepositoryEvent::SubmodulesChanged,
                         true,
                     )
                     | GitStoreEvent::RepositoryAdded
@@ -1968,6 +1976,34 @@ impl GitPanel {
         .detach();
     }
 
+    pub fn control_rebase(
+        &mut self,
+        control: RebaseControl,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+
+        cx.spawn({
+            async move |this, cx| {
+                let rebase_task = active_repository
+                    .update(cx, |repo, cx| repo.control_rebase(control, cx))
+                    .await;
+                this.update(cx, |this, cx| {
+                    rebase_task
+                        .map_err(|e| {
+                            this.show_error_toast(format!("rebase --{}", control.as_str()), e, cx);
+                        })
+                        .ok();
+                    cx.notify();
+                })
+            }
+        })
+        .detach();
+    }
+
     pub fn stash_apply(&mut self, _: &StashApply, _window: &mut Window, cx: &mut Context<Self>) {
         let Some(active_repository) = self.active_repository.clone() else {
             return;
@@ -3081,6 +3117,92 @@ impl GitPanel {
         .detach_and_log_err(cx);
     }
 
+    pub(crate) fn push_tag(
+        &mut self,
+        tag: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let remote = self.get_remote(true, true, window, cx);
+
+        cx.spawn_in(window, async move |this, cx| {
+            let remote = match remote.await {
+                Ok(Some(remote)) => remote,
+                Ok(None) => {
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Failed to get current remote: {}", e);
+                    this.update(cx, |this, cx| this.show_error_toast("push", e, cx))
+                        .ok();
+                    return Ok(());
+                }
+            };
+
+            let askpass_delegate = this.update_in(cx, |this, window, cx| {
+                this.askpass_delegate(format!("git push {}", remote.name), window, cx)
+            })?;
+
+            let push = repo.update(cx, |repo, cx| {
+                repo.push_tag(tag.clone(), remote.name.clone(), askpass_delegate, cx)
+            });
+
+            let remote_output = push.await?;
+
+            let action = RemoteAction::Push(tag, remote);
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while pushing tag {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Initializes and checks out the recorded commit of the given submodules of `repo`, or of
+    /// all of its submodules when `paths` is empty.
+    pub fn update_submodules(
+        &mut self,
+        repo: Entity<Repository>,
+        paths: Vec<RepoPath>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        telemetry::event!("Git Submodules Updated");
+        let askpass = self.askpass_delegate("git submodule update", window, cx);
+
+        cx.spawn_in(window, async move |this, cx| {
+            let update = repo.update(cx, |repo, cx| repo.update_submodules(paths, askpass, cx));
+
+            let remote_output = update.await?;
+
+            let action = RemoteAction::UpdateSubmodules;
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while updating submodules {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn create_pull_request(&self, window: &mut Window, cx: &mut Context<Self>) {
         let result = (|| -> anyhow::Result<()> {
             let repo = self
@@ -3743,6 +3865,12 @@ impl GitPanel {
         self.tracked_count > 0
     }
 
+    fn has_submodules(&self, cx: &App) -> bool {
+        self.active_repository
+            .as_ref()
+            .is_some_and(|repo| !repo.read(cx).submodules.is_empty())
+    }
+
     pub fn has_unstaged_conflicts(&self) -> bool {
         self.conflicted_count > 0 && self.conflicted_count != self.conflicted_staged_count
     }
@@ -3889,13 +4017,14 @@ impl GitPanel {
         path + file_name + depth * 2
     }
 
-    fn render_overflow_menu(&self, id: impl Into<ElementId>) -> impl IntoElement {
+    fn render_overflow_menu(&self, id: impl Into<ElementId>, cx: &App) -> impl IntoElement {
         let focus_handle = self.focus_handle.clone();
         let has_tracked_changes = self.has_tracked_changes();
         let has_staged_changes = self.has_staged_changes();
         let has_unstaged_changes = self.has_unstaged_changes();
         let has_new_changes = self.new_count > 0;
         let has_stash_items = self.stash_entries.entries.len() > 0;
+        let has_submodules = self.has_submodules(cx);
 
         PopoverMenu::new(id.into())
             .trigger(
@@ -3913,6 +4042,7 @@ impl GitPanel {
                         has_new_changes,
                         sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                         has_stash_items,
+                        has_submodules,
                         tree_view: GitPanelSettings::get_global(cx).tree_view,
                     },
                     window,
@@ -4183,7 +4313,7 @@ impl GitPanel {
                 .child(
                     h_flex()
                         .gap_1()
-                        .child(self.render_overflow_menu("overflow_menu"))
+                        .child(self.render_overflow_menu("overflow_menu", cx))
                         .child(
                             panel_filled_button(text)
                                 .tooltip(Tooltip::for_action_title_in(
@@ -4907,6 +5037,7 @@ impl GitPanel {
                 has_new_changes: self.new_count > 0,
                 sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                 has_stash_items: self.stash_entries.entries.len() > 0,
+                has_submodules: self.has_submodules(cx),
                 tree_view: GitPanelSettings::get_global(cx).tree_view,
             },
             window,
@@ -4992,6 +5123,8 @@ impl GitPanel {
             ElementId::Name(format!("entry_{}_{}_checkbox_wrapper", display_name, ix).into());
         let checkbox_id: ElementId =
             ElementId::Name(format!("entry_{}_{}_checkbox", display_name, ix).into());
+        let submodule_id: ElementId =
+            ElementId::Name(format!("entry_{}_{}_submodule", display_name, ix).into());
 
         let stage_status = GitPanel::stage_status_for_entry(entry, &repo);
         let mut is_staged: ToggleState = match stage_status {
@@ -5053,6 +5186,9 @@ impl GitPanel {
                         status.is_deleted(),
                     ))
                 }
+            })
+            .when_some(repo.submodule(&entry.repo_path), |this, submodule| {
+                this.child(submodule_badge(submodule_id, submodule))
             });
 
         h_flex()
@@ -14,13 +14,19 @@ use workspace::{ModalView, Workspace, pane};
 
 use crate::branch_picker::{self, BranchList, DeleteBranch, FilterRemotes};
 use crate::stash_picker::{self, DropStashItem, ShowStashItem, StashList};
+use crate::tag_picker::{self, DeleteTag, PushTag, TagList};
 use crate::worktree_picker::{
     self, WorktreeFromDefault, WorktreeFromDefaultOnWindow, WorktreeList,
 };
 
 actions!(
     git_picker,
-    [ActivateBranchesTab, ActivateWorktreesTab, ActivateStashTab,]
+    [
+        ActivateBranchesTab,
+        ActivateWorktreesTab,
+        ActivateStashTab,
+        ActivateTagsTab,
+    ]
 );
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -28,6 +34,7 @@ pub enum GitPickerTab {
     Branches,
     Worktrees,
     Stash,
+    Tags,
 }
 
 impl Display for GitPickerTab {
@@ -36,6 +43,7 @@ impl Display for GitPickerTab {
             GitPickerTab::Branches => "Branches",
             GitPickerTab::Worktrees => "Worktrees",
             GitPickerTab::Stash => "Stash",
+            GitPickerTab::Tags => "Tags",
         };
         write!(f, "{}", label)
     }
@@ -49,6 +57,7 @@ pub struct GitPicker {
     branch_list: Option<Entity<BranchList>>,
     worktree_list: Option<Entity<WorktreeList>>,
     stash_list: Option<Entity<StashList>>,
+    tag_list: Option<Entity<TagList>>,
     _subscriptions: Vec<Subscription>,
     popover_style: bool,
 }
@@ -82,6 +91,7 @@ impl GitPicker {
             branch_list: None,
             worktree_list: None,
             stash_list: None,
+            tag_list: None,
             _subscriptions: Vec::new(),
             popover_style,
         };
@@ -101,6 +111,9 @@ impl GitPicker {
             GitPickerTab::Stash => {
                 self.ensure_stash_list(window, cx);
             }
+            GitPickerTab::Tags => {
+                self.ensure_tag_list(window, cx);
+            }
         }
     }
 
@@ -188,11 +201,36 @@ impl GitPicker {
         self.stash_list.clone().unwrap()
     }
 
+    fn ensure_tag_list(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Entity<TagList> {
+        if self.tag_list.is_none() {
+            let tag_list = cx.new(|cx| {
+                tag_picker::create_embedded(
+                    self.repository.clone(),
+                    self.workspace.clone(),
+                    self.width,
+                    window,
+                    cx,
+                )
+            });
+
+            let subscription = cx.subscribe(&tag_list, |this, _, _: &DismissEvent, cx| {
+                if this.tab == GitPickerTab::Tags {
+                    cx.emit(DismissEvent);
+                }
+            });
+
+            self._subscriptions.push(subscription);
+            self.tag_list = Some(tag_list);
+        }
+        self.tag_list.clone().unwrap()
+    }
+
     fn activate_next_tab(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.tab = match self.tab {
             GitPickerTab::Branches => GitPickerTab::Worktrees,
             GitPickerTab::Worktrees => GitPickerTab::Stash,
-            GitPickerTab::Stash => GitPickerTab::Branches,
+            GitPickerTab::Stash => GitPickerTab::Tags,
+            GitPickerTab::Tags => GitPickerTab::Branches,
         };
         self.ensure_active_picker(window, cx);
         self.focus_active_picker(window, cx);
@@ -201,9 +239,10 @@ impl GitPicker {
 
     fn activate_previous_tab(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.tab = match self.tab {
-            GitPickerTab::Branches => GitPickerTab::Stash,
+            GitPickerTab::Branches => GitPickerTab::Tags,
             GitPickerTab::Worktrees => GitPickerTab::Branches,
             GitPickerTab::Stash => GitPickerTab::Worktrees,
+            GitPickerTab::Tags => GitPickerTab::Stash,
         };
         self.ensure_active_picker(window, cx);
         self.focus_active_picker(window, cx);
@@ -227,6 +266,11 @@ impl GitPicker {
                     stash_list.focus_handle(cx).focus(window, cx);
                 }
             }
+            GitPickerTab::Tags => {
+                if let Some(tag_list) = &self.tag_list {
+                    tag_list.focus_handle(cx).focus(window, cx);
+                }
+            }
         }
     }
 
@@ -234,7 +278,8 @@ impl GitPicker {
         let focus_handle = self.focus_handle(cx);
         let branches_focus_handle = focus_handle.clone();
         let worktrees_focus_handle = focus_handle.clone();
-        let stash_focus_handle = focus_handle;
+        let stash_focus_handle = focus_handle.clone();
+        let tags_focus_handle = focus_handle;
 
         h_flex().p_2().pb_0p5().w_full().child(
             ToggleButtonGroup::single_row(
@@ -291,6 +336,23 @@ impl GitPicker {
                             cx,
                         )
                     }),
+                    ToggleButtonSimple::new(
+                        GitPickerTab::Tags.to_string(),
+                        cx.listener(|this, _, window, cx| {
+                            this.tab = GitPickerTab::Tags;
+                            this.ensure_active_picker(window, cx);
+                            this.focus_active_picker(window, cx);
+                            cx.notify();
+                        }),
+                    )
+                    .tooltip(move |_, cx| {
+                        Tooltip::for_action_in(
+                            "Toggle Tag Picker",
+                            &ActivateTagsTab,
+                            &tags_focus_handle,
+                            cx,
+                        )
+                    }),
                 ],
             )
             .label_size(LabelSize::Default)
@@ -300,6 +362,7 @@ impl GitPicker {
                 GitPickerTab::Branches => 0,
                 GitPickerTab::Worktrees => 1,
                 GitPickerTab::Stash => 2,
+                GitPickerTab::Tags => 3,
             }),
         )
     }
@@ -322,6 +385,10 @@ impl GitPicker {
                 let stash_list = self.ensure_stash_list(window, cx);
                 stash_list.into_any_element()
             }
+            GitPickerTab::Tags => {
+                let tag_list = self.ensure_tag_list(window, cx);
+                tag_list.into_any_element()
+            }
         }
     }
 
@@ -353,6 +420,7 @@ impl GitPicker {
                     });
                 }
             }
+            GitPickerTab::Tags => {}
         }
     }
 
@@ -433,6 +501,22 @@ impl GitPicker {
             });
         }
     }
+
+    fn handle_delete_tag(&mut self, _: &DeleteTag, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(tag_list) = &self.tag_list {
+            tag_list.update(cx, |list, cx| {
+                list.handle_delete_tag(&DeleteTag, window, cx);
+            });
+        }
+    }
+
+    fn handle_push_tag(&mut self, _: &PushTag, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(tag_list) = &self.tag_list {
+            tag_list.update(cx, |list, cx| {
+                list.handle_push_tag(&PushTag, window, cx);
+            });
+        }
+    }
 }
 
 impl ModalView for GitPicker {}
@@ -456,6 +540,11 @@ impl Focusable for GitPicker {
                     return stash_list.focus_handle(cx);
                 }
             }
+            GitPickerTab::Tags => {
+                if let Some(tag_list) = &self.tag_list {
+                    return tag_list.focus_handle(cx);
+                }
+            }
         }
         cx.focus_handle()
     }
@@ -481,6 +570,7 @@ impl Render for GitPicker {
                     GitPickerTab::Branches => key_context.add("GitBranchSelector"),
                     GitPickerTab::Worktrees => key_context.add("GitWorktreeSelector"),
                     GitPickerTab::Stash => key_context.add("StashList"),
+                    GitPickerTab::Tags => key_context.add("TagList"),
                 }
                 key_context
             })
@@ -516,6 +606,12 @@ impl Render for GitPicker {
                 this.focus_active_picker(window, cx);
                 cx.notify();
             }))
+            .on_action(cx.listener(|this, _: &ActivateTagsTab, window, cx| {
+                this.tab = GitPickerTab::Tags;
+                this.ensure_active_picker(window, cx);
+                this.focus_active_picker(window, cx);
+                cx.notify();
+            }))
             .on_modifiers_changed(cx.listener(Self::handle_modifiers_changed))
             .when(self.tab == GitPickerTab::Branches, |el| {
                 el.on_action(cx.listener(Self::handle_delete_branch))
@@ -529,6 +625,10 @@ impl Render for GitPicker {
                 el.on_action(cx.listener(Self::handle_drop_stash))
                     .on_action(cx.listener(Self::handle_show_stash))
             })
+            .when(self.tab == GitPickerTab::Tags, |el| {
+                el.on_action(cx.listener(Self::handle_delete_tag))
+                    .on_action(cx.listener(Self::handle_push_tag))
+            })
             .child(self.render_tab_bar(cx))
             .child(self.render_active_picker(window, cx))
     }
@@ -561,6 +661,15 @@ pub fn open_stash(
     open_with_tab(workspace, GitPickerTab::Stash, window, cx);
 }
 
+pub fn open_tags(
+    workspace: &mut Workspace,
+    _: &zed_actions::git::ViewTags,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    open_with_tab(workspace, GitPickerTab::Tags, window, cx);
+}
+
 fn open_with_tab(
     workspace: &mut Workspace,
     tab: GitPickerTab,
@@ -609,4 +718,7 @@ pub fn register(workspace: &mut Workspace) {
     workspace.register_action(|workspace, _: &zed_actions::git::ViewStash, window, cx| {
         open_with_tab(workspace, GitPickerTab::Stash, window, cx);
     });
+    workspace.register_action(|workspace, _: &zed_actions::git::ViewTags, window, cx| {
+        open_with_tab(workspace, GitPickerTab::Tags, window, cx);
+    });
 }
@@ -8,15 +8,16 @@ use editor::{Editor, actions::DiffClipboardWithSelectionData};
 use project::ProjectPath;
 use ui::{
     Headline, HeadlineSize, Icon, IconName, IconSize, IntoElement, ParentElement, Render, Styled,
-    StyledExt, div, h_flex, rems, v_flex,
+    StyledExt, Tooltip, div, h_flex, rems, v_flex,
 };
 
 mod blame_ui;
 pub mod clone;
 
 use git::{
-    repository::{Branch, Upstream, UpstreamTracking, UpstreamTrackingStatus},
+    repository::{Branch, RebaseControl, Upstream, UpstreamTracking, UpstreamTrackingStatus},
     status::{FileStatus, StatusCode, UnmergedStatus, UnmergedStatusCode},
+    submodule::{Submodule, SubmoduleStatus},
 };
 use gpui::{
     Action, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString,
@@ -33,6 +34,7 @@ use zed_actions;
 use crate::{git_panel::GitPanel, text_diff_view::TextDiffView};
 
 mod askpass_modal;
+pub mod bisect;
 pub mod branch_picker;
 mod commit_modal;
 pub mod commit_tooltip;
@@ -43,13 +45,17 @@ pub mod file_history_view;
 pub mod git_panel;
 mod git_panel_settings;
 pub mod git_picker;
+pub mod local_history_view;
+pub mod merge_view;
 pub mod multi_diff_view;
 pub mod onboarding;
 pub mod picker_prompt;
 pub mod project_diff;
+pub mod rebase_editor;
 pub(crate) mod remote_output;
 pub mod repository_selector;
 pub mod stash_picker;
+pub mod tag_picker;
 pub mod text_diff_view;
 pub mod worktree_picker;
 
@@ -65,13 +71,14 @@ pub fn init(cx: &mut App) {
     editor::set_blame_renderer(blame_ui::GitBlameRenderer, cx);
     commit_view::init(cx);
     file_history_view::init(cx);
+    local_history_view::init(cx);
 
     cx.observe_new(|editor: &mut Editor, _, cx| {
         conflict_view::register_editor(editor, editor.buffer().clone(), cx);
     })
     .detach();
 
-    cx.observe_new(|workspace: &mut Workspace, _, cx| {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
         ProjectDiff::register(workspace, cx);
         CommitModal::register(workspace);
         git_panel::register(workspace);
@@ -108,6 +115,17 @@ pub fn init(cx: &mut App) {
                     panel.fetch(false, window, cx);
                 });
             });
+            workspace.register_action(|workspace, _: &git::UpdateSubmodules, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                let Some(repo) = resolve_active_repository(workspace, cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.update_submodules(repo, Vec::new(), window, cx);
+                });
+            });
             workspace.register_action(|workspace, _: &git::Push, window, cx| {
                 let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                     return;
@@ -165,6 +183,30 @@ pub fn init(cx: &mut App) {
                 panel.stash_pop(action, window, cx);
             });
         });
+        workspace.register_action(|workspace, _: &git::ContinueRebase, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.control_rebase(RebaseControl::Continue, window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::SkipRebase, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.control_rebase(RebaseControl::Skip, window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::AbortRebase, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.control_rebase(RebaseControl::Abort, window, cx);
+            });
+        });
         workspace.register_action(|workspace, action: &git::StashApply, window, cx| {
             let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                 return;
@@ -277,6 +319,9 @@ pub fn init(cx: &mut App) {
                 cx,
             );
         });
+        if let Some(window) = window {
+            bisect::register(workspace, window, cx);
+        }
     })
     .detach();
 }
@@ -339,6 +384,31 @@ pub fn git_status_icon(status: FileStatus) -> impl IntoElement {
     GitStatusIcon::new(status)
 }
 
+/// An icon marking a submodule, with a tooltip comparing its recorded and checked-out commits.
+pub fn submodule_badge(id: impl Into<ElementId>, submodule: &Submodule) -> impl IntoElement {
+    let (label, color) = match submodule.status {
+        SubmoduleStatus::Uninitialized => ("not initialized", Color::Muted),
+        SubmoduleStatus::Current => ("up to date", Color::Muted),
+        SubmoduleStatus::Modified => ("new commits", Color::VersionControlModified),
+        SubmoduleStatus::Conflicted => ("conflicted", Color::VersionControlConflict),
+    };
+    let title: SharedString = format!("Submodule {} ({label})", submodule.name).into();
+    let short_sha =
+        |sha: Option<git::Oid>| sha.map_or_else(|| "none".to_string(), |sha| sha.display_short());
+    let meta: SharedString = format!(
+        "Recorded {}, checked out {}",
+        short_sha(submodule.recorded_sha),
+        short_sha(submodule.checked_out_sha)
+    )
+    .into();
+
+    div()
+        .id(id)
+        .flex_none()
+        .tooltip(move |_window, cx| Tooltip::with_meta(title.clone(), None, meta.clone(), cx))
+        .child(Icon::new(IconName::Box).size(IconSize::Small).color(color))
+}
+
 struct RenameBranchModal {
     current_branch: SharedString,
     editor: Entity<Editor>,
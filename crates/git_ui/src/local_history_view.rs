@@ -0,0 +1,567 @@
+//! LocalHistoryView lists the snapshots kept in a file's local history, shows how the file
+//! changed since each of them, and restores them.
+
+use anyhow::Result;
+use buffer_diff::BufferDiff;
+use db::local_history::{LOCAL_HISTORY, LocalHistoryEntry};
+use editor::{Editor, MultiBuffer};
+use gpui::{
+    AnyElement, AnyEntity, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, IntoElement, Render, ScrollStrategy, Subscription, Task, UniformListScrollHandle,
+    Window, actions, uniform_list,
+};
+use language::{Buffer, BufferEvent};
+use project::{Project, ProjectPath, buffer_store::BufferStoreEvent};
+use std::{
+    any::{Any, TypeId},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+use time::OffsetDateTime;
+use ui::{ListItem, WithScrollbar, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, SaveOptions},
+};
+
+actions!(
+    local_history,
+    [
+        /// Shows the snapshots of the current file kept in its local history.
+        ViewFileHistory,
+        /// Replaces the file's contents with the selected local history snapshot.
+        RestoreVersion
+    ]
+);
+
+const RECALCULATE_DIFF_DEBOUNCE: Duration = Duration::from_millis(250);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
+        workspace.register_action(|workspace, _: &ViewFileHistory, window, cx| {
+            let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+                return;
+            };
+            let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+                return;
+            };
+            LocalHistoryView::open(buffer, workspace, window, cx);
+        });
+    })
+    .detach();
+}
+
+pub struct LocalHistoryView {
+    abs_path: PathBuf,
+    buffer: Entity<Buffer>,
+    diff: Entity<BufferDiff>,
+    editor: Entity<Editor>,
+    entries: Vec<LocalHistoryEntry>,
+    selected_entry: Option<usize>,
+    /// The contents of the selected snapshot, which the buffer is compared with.
+    base_text: Option<Arc<str>>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    _load_entries: Task<()>,
+    _load_version: Task<()>,
+    _update_diff: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl LocalHistoryView {
+    pub fn open(
+        buffer: Entity<Buffer>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(abs_path) = buffer
+            .read(cx)
+            .file()
+            .and_then(|file| Some(file.as_local()?.abs_path(cx)))
+        else {
+            return;
+        };
+
+        let existing = workspace
+            .items_of_type::<LocalHistoryView>(cx)
+            .find(|view| view.read(cx).abs_path == abs_path);
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let project = workspace.project().clone();
+        let view = cx.new(|cx| LocalHistoryView::new(abs_path, buffer, project, window, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+    }
+
+    fn new(
+        abs_path: PathBuf,
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let buffer_store = project.read(cx).buffer_store().clone();
+        let diff = cx.new(|cx| BufferDiff::new(&buffer.read(cx).text_snapshot(), cx));
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::singleton(buffer.clone(), cx);
+            multibuffer.add_diff(diff.clone(), cx);
+            multibuffer
+        });
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::for_multibuffer(multibuffer, Some(project), window, cx);
+            editor.start_temporary_diff_override();
+            editor.disable_diagnostics(cx);
+            editor.set_expand_all_diff_hunks(cx);
+            editor.set_render_diff_hunk_controls(
+                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                cx,
+            );
+            editor
+        });
+        let subscriptions = vec![
+            cx.subscribe(&buffer, |this, _, event, cx| {
+                if let BufferEvent::Edited = event {
+                    this.update_diff(true, cx);
+                }
+            }),
+            cx.subscribe(&buffer_store, |this, _, event, cx| {
+                if let BufferStoreEvent::LocalHistoryRecorded { abs_path } = event
+                    && *abs_path == this.abs_path
+                {
+                    this.reload_entries(cx);
+                }
+            }),
+        ];
+
+        let mut this = Self {
+            abs_path,
+            buffer,
+            diff,
+            editor,
+            entries: Vec::new(),
+            selected_entry: None,
+            base_text: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+            _load_entries: Task::ready(()),
+            _load_version: Task::ready(()),
+            _update_diff: Task::ready(()),
+            _subscriptions: subscriptions,
+        };
+        this.reload_entries(cx);
+        this
+    }
+
+    fn reload_entries(&mut self, cx: &mut Context<Self>) {
+        let abs_path = self.abs_path.clone();
+        let entries = cx.background_spawn(async move { LOCAL_HISTORY.entries(&abs_path) });
+        self._load_entries = cx.spawn(async move |this, cx| {
+            let Some(entries) = entries.await.log_err() else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                let selected_id = this.selected_entry().map(|entry| entry.id);
+                this.entries = entries;
+                let ix = match selected_id {
+                    Some(id) => this.entries.iter().position(|entry| entry.id == id),
+                    None => None,
+                };
+                let ix = ix.or_else(|| (!this.entries.is_empty()).then_some(0));
+                this.select_ix(ix, cx);
+            })
+            .ok();
+        });
+    }
+
+    fn selected_entry(&self) -> Option<&LocalHistoryEntry> {
+        self.entries.get(self.selected_entry?)
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let entry_count = self.entries.len();
+        let ix = match self.selected_entry {
+            _ if entry_count == 0 => None,
+            None => Some(0),
+            Some(ix) => Some((ix + 1) % entry_count),
+        };
+        self.select_ix(ix, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let entry_count = self.entries.len();
+        let ix = match self.selected_entry {
+            _ if entry_count == 0 => None,
+            None | Some(0) => Some(entry_count - 1),
+            Some(ix) => Some(ix - 1),
+        };
+        self.select_ix(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &menu::SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = (!self.entries.is_empty()).then_some(0);
+        self.select_ix(ix, cx);
+    }
+
+    fn select_last(&mut self, _: &menu::SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.entries.len().checked_sub(1);
+        self.select_ix(ix, cx);
+    }
+
+    fn select_ix(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        self.selected_entry = ix;
+        if let Some(ix) = ix {
+            self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        }
+        self.load_selected_version(cx);
+        cx.notify();
+    }
+
+    fn load_selected_version(&mut self, cx: &mut Context<Self>) {
+        let Some(entry_id) = self.selected_entry().map(|entry| entry.id) else {
+            self.base_text = None;
+            self._load_version = Task::ready(());
+            return;
+        };
+        let content = cx.background_spawn(async move { LOCAL_HISTORY.content(entry_id) });
+        self._load_version = cx.spawn(async move |this, cx| {
+            let content = content.await.log_err().flatten();
+            this.update(cx, |this, cx| {
+                this.base_text = content.map(Arc::from);
+                this.update_diff(false, cx);
+            })
+            .ok();
+        });
+    }
+
+    fn update_diff(&mut self, debounce: bool, cx: &mut Context<Self>) {
+        let Some(base_text) = self.base_text.clone() else {
+            return;
+        };
+        let buffer = self.buffer.clone();
+        let diff = self.diff.clone();
+        self._update_diff = cx.spawn(async move |_, cx| {
+            if debounce {
+                cx.background_executor()
+                    .timer(RECALCULATE_DIFF_DEBOUNCE)
+                    .await;
+            }
+            let (snapshot, language) = buffer.read_with(cx, |buffer, _| {
+                (buffer.text_snapshot(), buffer.language().cloned())
+            });
+            diff.update(cx, |diff, cx| {
+                diff.set_base_text(Some(base_text), language, snapshot, cx)
+            })
+            .await
+            .ok();
+        });
+    }
+
+    fn restore_version(&mut self, _: &RestoreVersion, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(base_text) = self.base_text.clone() else {
+            return;
+        };
+        let buffer = self.buffer.clone();
+        let diff = buffer.read(cx).diff(base_text, cx);
+        cx.spawn(async move |_, cx| {
+            let diff = diff.await;
+            buffer.update(cx, |buffer, cx| buffer.apply_diff(diff, cx));
+        })
+        .detach();
+    }
+
+    fn file_name(&self) -> String {
+        self.abs_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "File".to_string())
+    }
+
+    fn render_entry(
+        &self,
+        ix: usize,
+        entry: &LocalHistoryEntry,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let timestamp = OffsetDateTime::from_unix_timestamp(entry.timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::UNIX_EPOCH);
+        let relative_timestamp = time_format::format_localized_timestamp(
+            timestamp,
+            OffsetDateTime::now_utc(),
+            time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC),
+            time_format::TimestampFormat::Relative,
+        );
+
+        ListItem::new(("local-history-entry", ix))
+            .toggle_state(Some(ix) == self.selected_entry)
+            .child(
+                h_flex()
+                    .h_8()
+                    .w_full()
+                    .px_2()
+                    .gap_2()
+                    .justify_between()
+                    .child(
+                        Label::new(relative_timestamp)
+                            .size(LabelSize::Small)
+                            .truncate(),
+                    )
+                    .child(
+                        Label::new(entry.source.label())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.select_ix(Some(ix), cx);
+            }))
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<ItemEvent> for LocalHistoryView {}
+
+impl Focusable for LocalHistoryView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for LocalHistoryView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry_count = self.entries.len();
+
+        v_flex()
+            .id("local_history_view")
+            .key_context("LocalHistoryView")
+            .on_action(cx.listener(Self::restore_version))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .h(rems_from_px(41.))
+                    .pl_3()
+                    .pr_2()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new(self.abs_path.to_string_lossy().into_owned())
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_1p5()
+                            .child(
+                                Label::new(format!("{} versions", entry_count))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(
+                                Button::new("restore-version", "Restore")
+                                    .disabled(self.base_text.is_none())
+                                    .label_size(LabelSize::Small)
+                                    .icon(IconName::HistoryRerun)
+                                    .icon_size(IconSize::Small)
+                                    .icon_color(Color::Muted)
+                                    .icon_position(IconPosition::Start)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.restore_version(&RestoreVersion, window, cx);
+                                    })),
+                            ),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .child(
+                        v_flex()
+                            .track_focus(&self.focus_handle)
+                            .on_action(cx.listener(Self::select_next))
+                            .on_action(cx.listener(Self::select_previous))
+                            .on_action(cx.listener(Self::select_first))
+                            .on_action(cx.listener(Self::select_last))
+                            .w(rems_from_px(280.))
+                            .h_full()
+                            .flex_none()
+                            .border_r_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .child({
+                                let view = cx.weak_entity();
+                                uniform_list(
+                                    "local-history-list",
+                                    entry_count,
+                                    move |range, _window, cx| {
+                                        let Some(view) = view.upgrade() else {
+                                            return Vec::new();
+                                        };
+                                        view.update(cx, |this, cx| {
+                                            range
+                                                .filter_map(|ix| {
+                                                    let entry = this.entries.get(ix)?;
+                                                    Some(this.render_entry(ix, entry, cx))
+                                                })
+                                                .collect()
+                                        })
+                                    },
+                                )
+                                .flex_1()
+                                .size_full()
+                                .track_scroll(&self.scroll_handle)
+                            })
+                            .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+                    )
+                    .child(if entry_count == 0 {
+                        h_flex()
+                            .size_full()
+                            .justify_center()
+                            .child(
+                                Label::new("No versions of this file have been recorded yet")
+                                    .color(Color::Muted),
+                            )
+                            .into_any_element()
+                    } else {
+                        div()
+                            .size_full()
+                            .child(self.editor.clone())
+                            .into_any_element()
+                    }),
+            )
+    }
+}
+
+impl Item for LocalHistoryView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        format!("Local History: {}", self.file_name()).into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(format!("Local history for {}", self.abs_path.to_string_lossy()).into())
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("local history")
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<workspace::WorkspaceId>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Task<Option<Entity<Self>>> {
+        Task::ready(None)
+    }
+
+    fn navigate(
+        &mut self,
+        _: Arc<dyn Any + Send>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> bool {
+        false
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        false
+    }
+
+    fn save(
+        &mut self,
+        _options: SaveOptions,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn save_as(
+        &mut self,
+        _project: Entity<Project>,
+        _path: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn reload(
+        &mut self,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn is_dirty(&self, _: &App) -> bool {
+        false
+    }
+
+    fn has_conflict(&self, _: &App) -> bool {
+        false
+    }
+
+    fn breadcrumbs(&self, _cx: &App) -> Option<Vec<workspace::item::BreadcrumbText>> {
+        None
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+        window.focus(&self.focus_handle, cx);
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn pixel_position_of_cursor(&self, _: &App) -> Option<gpui::Point<gpui::Pixels>> {
+        None
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else {
+            None
+        }
+    }
+}
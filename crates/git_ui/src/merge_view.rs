@@ -0,0 +1,780 @@
+//! MergeView provides a three-way merge UI, used when Zed acts as `git mergetool`.
+
+use crate::{conflict_view, file_diff_view::build_buffer_diff};
+use anyhow::Result;
+use buffer_diff::BufferDiff;
+use collections::HashMap;
+use editor::{Anchor, Editor, EditorEvent, MultiBuffer, ToPoint as _, scroll::ScrollAnchor};
+use gpui::{
+    AnyElement, App, AppContext as _, Context, Entity, EntityId, EventEmitter, FocusHandle,
+    Focusable, IntoElement, Render, Task, WeakEntity, Window,
+};
+use language::{Buffer, Point};
+use project::{ConflictSet, Project};
+use std::{
+    any::{Any, TypeId},
+    path::PathBuf,
+    sync::Arc,
+};
+use ui::{prelude::*, vertical_divider};
+use util::paths::PathExt as _;
+use workspace::{
+    Item, ItemHandle as _, ItemNavHistory, ToolbarItemLocation, Workspace,
+    item::{BreadcrumbText, ItemEvent, SaveOptions, TabContentParams},
+    searchable::SearchableItemHandle,
+};
+
+/// Shows the `LOCAL` and `REMOTE` sides of a merge, each diffed against `BASE`, on either side
+/// of an editor for the `MERGED` file, in which conflicts can be resolved hunk by hunk.
+///
+/// Scrolling any of the editors scrolls the others to the same place, by mapping rows through
+/// each file's diff against `BASE`, so that corresponding hunks line up.
+pub struct MergeView {
+    local_editor: Entity<Editor>,
+    merged_editor: Entity<Editor>,
+    remote_editor: Entity<Editor>,
+    local_diff: Entity<BufferDiff>,
+    merged_diff: Entity<BufferDiff>,
+    remote_diff: Entity<BufferDiff>,
+    merged_buffer: Entity<Buffer>,
+    conflict_set: Entity<ConflictSet>,
+    /// The scroll anchors set on editors that follow another editor's scrolling, so that the
+    /// events of following aren't followed in turn.
+    following_scroll_anchors: HashMap<EntityId, ScrollAnchor>,
+    _reparse_conflicts_task: Task<()>,
+}
+
+impl MergeView {
+    #[ztracing::instrument(skip_all)]
+    pub fn open(
+        base_path: PathBuf,
+        local_path: PathBuf,
+        remote_path: PathBuf,
+        merged_path: PathBuf,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        window.spawn(cx, async move |cx| {
+            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+            let mut buffers = Vec::new();
+            for path in [&base_path, &local_path, &remote_path, &merged_path] {
+                let buffer = project
+                    .update(cx, |project, cx| project.open_local_buffer(path, cx))
+                    .await?;
+                buffers.push(buffer);
+            }
+            let [base_buffer, local_buffer, remote_buffer, merged_buffer] =
+                <[_; 4]>::try_from(buffers).unwrap();
+            let languages = project.update(cx, |project, _| project.languages().clone());
+
+            let local_diff =
+                build_buffer_diff(&base_buffer, &local_buffer, languages.clone(), cx).await?;
+            let remote_diff =
+                build_buffer_diff(&base_buffer, &remote_buffer, languages.clone(), cx).await?;
+            let merged_diff =
+                build_buffer_diff(&base_buffer, &merged_buffer, languages, cx).await?;
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let merge_view = cx.new(|cx| {
+                    MergeView::new(
+                        (local_buffer, local_diff),
+                        (remote_buffer, remote_diff),
+                        (merged_buffer, merged_diff),
+                        project.clone(),
+                        window,
+                        cx,
+                    )
+                });
+
+                let pane = workspace.active_pane();
+                pane.update(cx, |pane, cx| {
+                    pane.add_item(Box::new(merge_view.clone()), true, true, None, window, cx);
+                });
+
+                merge_view
+            })
+        })
+    }
+
+    /// Creates a merge view from the `LOCAL`, `REMOTE` and `MERGED` buffers, each with its diff
+    /// against `BASE`. The diff of `MERGED` is only used to line up scrolling, and isn't shown.
+    pub fn new(
+        (local_buffer, local_diff): (Entity<Buffer>, Entity<BufferDiff>),
+        (remote_buffer, remote_diff): (Entity<Buffer>, Entity<BufferDiff>),
+        (merged_buffer, merged_diff): (Entity<Buffer>, Entity<BufferDiff>),
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let local_editor = side_editor(
+            local_buffer,
+            local_diff.clone(),
+            project.clone(),
+            window,
+            cx,
+        );
+        let remote_editor = side_editor(
+            remote_buffer,
+            remote_diff.clone(),
+            project.clone(),
+            window,
+            cx,
+        );
+
+        let buffer_id = merged_buffer.read(cx).remote_id();
+        let conflict_set = cx.new(|cx| {
+            let mut conflict_set = ConflictSet::new(buffer_id, true, cx);
+            let buffer_snapshot = merged_buffer.read(cx).text_snapshot();
+            let snapshot = ConflictSet::parse(&buffer_snapshot);
+            let update = conflict_set.snapshot().compare(&snapshot, &buffer_snapshot);
+            conflict_set.set_snapshot(snapshot, update, cx);
+            conflict_set
+        });
+        let merged_editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_buffer(merged_buffer.clone(), Some(project.clone()), window, cx);
+            conflict_view::register_conflict_set(&mut editor, conflict_set.clone(), cx);
+            editor
+        });
+
+        cx.subscribe(&merged_editor, |_, _, event: &EditorEvent, cx| {
+            cx.emit(event.clone())
+        })
+        .detach();
+        cx.subscribe(&merged_buffer, |this, _, event, cx| {
+            if let language::BufferEvent::Edited = event {
+                this.reparse_conflicts(cx);
+            }
+        })
+        .detach();
+        cx.observe(&conflict_set, |_, _, cx| cx.notify()).detach();
+        for editor in [&local_editor, &merged_editor, &remote_editor] {
+            cx.subscribe_in(
+                editor,
+                window,
+                |this, editor, event: &EditorEvent, window, cx| {
+                    if let EditorEvent::ScrollPositionChanged { .. } = event {
+                        this.follow_scroll(editor, window, cx);
+                    }
+                },
+            )
+            .detach();
+        }
+
+        Self {
+            local_editor,
+            merged_editor,
+            remote_editor,
+            local_diff,
+            merged_diff,
+            remote_diff,
+            merged_buffer,
+            conflict_set,
+            following_scroll_anchors: HashMap::default(),
+            _reparse_conflicts_task: Task::ready(()),
+        }
+    }
+
+    /// Whether the merged file has been saved without any remaining conflict markers.
+    pub fn is_resolved(&self, cx: &App) -> bool {
+        let buffer = self.merged_buffer.read(cx);
+        !buffer.is_dirty()
+            && ConflictSet::parse(&buffer.text_snapshot())
+                .conflicts
+                .is_empty()
+    }
+
+    fn reparse_conflicts(&mut self, cx: &mut Context<Self>) {
+        let buffer_snapshot = self.merged_buffer.read(cx).text_snapshot();
+        let old_snapshot = self.conflict_set.read(cx).snapshot();
+        let conflict_set = self.conflict_set.clone();
+        self._reparse_conflicts_task = cx.spawn(async move |_, cx| {
+            let (snapshot, update) = cx
+                .background_spawn(async move {
+                    let snapshot = ConflictSet::parse(&buffer_snapshot);
+                    let update = old_snapshot.compare(&snapshot, &buffer_snapshot);
+                    (snapshot, update)
+                })
+                .await;
+            conflict_set.update(cx, |conflict_set, cx| {
+                conflict_set.set_snapshot(snapshot, update, cx)
+            });
+        });
+    }
+
+    /// Scrolls the other editors to the rows that `leader`'s top row maps to through `BASE`.
+    fn follow_scroll(
+        &mut self,
+        leader: &Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let scroll_anchor = leader
+            .read(cx)
+            .scroll_manager
+            .shared_scroll_anchor(cx)
+            .scroll_anchor;
+        if self.following_scroll_anchors.remove(&leader.entity_id()) == Some(scroll_anchor) {
+            return;
+        }
+
+        let panes = [
+            (self.local_editor.clone(), self.local_diff.clone()),
+            (self.merged_editor.clone(), self.merged_diff.clone()),
+            (self.remote_editor.clone(), self.remote_diff.clone()),
+        ];
+        let Some(base_point) =
+            panes
+                .iter()
+                .find(|(editor, _)| editor == leader)
+                .and_then(|(editor, diff)| {
+                    base_point_for_anchor(editor, diff, scroll_anchor.anchor, cx)
+                })
+        else {
+            return;
+        };
+
+        for (editor, diff) in panes.iter().filter(|(editor, _)| editor != leader) {
+            let Some(anchor) = anchor_for_base_point(editor, diff, base_point, cx) else {
+                continue;
+            };
+            let current = editor
+                .read(cx)
+                .scroll_manager
+                .shared_scroll_anchor(cx)
+                .scroll_anchor;
+            let scroll_anchor = ScrollAnchor {
+                anchor,
+                offset: gpui::Point::new(current.offset.x, scroll_anchor.offset.y),
+            };
+            if current == scroll_anchor {
+                continue;
+            }
+            self.following_scroll_anchors
+                .insert(editor.entity_id(), scroll_anchor);
+            editor.update(cx, |editor, cx| {
+                editor.set_scroll_anchor(scroll_anchor, window, cx)
+            });
+        }
+    }
+
+    fn render_pane(
+        &self,
+        title: &'static str,
+        detail: SharedString,
+        editor: &Entity<Editor>,
+        cx: &App,
+    ) -> impl IntoElement {
+        v_flex()
+            .flex_1()
+            .min_w_0()
+            .h_full()
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new(title).size(LabelSize::Small))
+                    .child(
+                        Label::new(detail)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(),
+                    ),
+            )
+            .child(div().flex_1().min_h_0().child(editor.clone()))
+    }
+}
+
+fn side_editor(
+    buffer: Entity<Buffer>,
+    diff: Entity<BufferDiff>,
+    project: Entity<Project>,
+    window: &mut Window,
+    cx: &mut Context<MergeView>,
+) -> Entity<Editor> {
+    let multibuffer = cx.new(|cx| {
+        let mut multibuffer = MultiBuffer::singleton(buffer, cx);
+        multibuffer.add_diff(diff, cx);
+        multibuffer
+    });
+    cx.new(|cx| {
+        let mut editor = Editor::for_multibuffer(multibuffer, Some(project), window, cx);
+        editor.set_read_only(true);
+        editor.start_temporary_diff_override();
+        editor.disable_diagnostics(cx);
+        editor.set_expand_all_diff_hunks(cx);
+        editor.set_render_diff_hunk_controls(
+            Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+            cx,
+        );
+        editor
+    })
+}
+
+/// Maps the row of `anchor` in an editor to a point in `BASE`, through the diff of its buffer.
+fn base_point_for_anchor(
+    editor: &Entity<Editor>,
+    diff: &Entity<BufferDiff>,
+    anchor: Anchor,
+    cx: &App,
+) -> Option<Point> {
+    let snapshot = editor.read(cx).buffer().read(cx).snapshot(cx);
+    let row = anchor.to_point(&snapshot).row;
+    let (buffer, point, _) = snapshot.point_to_buffer_point(Point::new(row, 0))?;
+    let diff = diff.read(cx).snapshot(cx);
+    // Deleted lines, which the side editors show expanded, are already in `BASE`.
+    if buffer.remote_id() == diff.base_text().remote_id() {
+        return Some(point);
+    }
+    Some(diff.buffer_point_to_base_text_point(point, buffer))
+}
+
+/// Anchors the start of the row that `base_point` maps to in an editor, through the diff of
+/// its buffer.
+fn anchor_for_base_point(
+    editor: &Entity<Editor>,
+    diff: &Entity<BufferDiff>,
+    base_point: Point,
+    cx: &App,
+) -> Option<Anchor> {
+    let snapshot = editor.read(cx).buffer().read(cx).snapshot(cx);
+    let (_, _, buffer) = snapshot.as_singleton()?;
+    let point = diff
+        .read(cx)
+        .snapshot(cx)
+        .base_text_point_to_buffer_point(base_point, buffer);
+    snapshot.as_singleton_anchor(buffer.anchor_before(Point::new(point.row, 0)))
+}
+
+fn buffer_path(editor: &Entity<Editor>, cx: &App) -> SharedString {
+    editor
+        .read(cx)
+        .buffer()
+        .read(cx)
+        .as_singleton()
+        .and_then(|buffer| buffer.read(cx).file().cloned())
+        .map(|file| file.full_path(cx).compact().to_string_lossy().into_owned())
+        .unwrap_or_else(|| "untitled".into())
+        .into()
+}
+
+impl EventEmitter<EditorEvent> for MergeView {}
+
+impl Focusable for MergeView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.merged_editor.focus_handle(cx)
+    }
+}
+
+impl Item for MergeView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
+        let filename = self
+            .merged_buffer
+            .read(cx)
+            .file()
+            .and_then(|file| {
+                Some(
+                    file.full_path(cx)
+                        .file_name()?
+                        .to_string_lossy()
+                        .to_string(),
+                )
+            })
+            .unwrap_or_else(|| "untitled".into());
+
+        format!("{filename} (Merge)").into()
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        Some(buffer_path(&self.merged_editor, cx))
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Merge View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.merged_editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.merged_editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.merged_editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.merged_editor.for_each_project_item(cx, f)
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.merged_buffer.read(cx).is_dirty()
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.merged_buffer.read(cx).has_conflict()
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.merged_editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Arc<dyn Any + Send>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.merged_editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.merged_editor.breadcrumbs(cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        for editor in [&self.local_editor, &self.merged_editor, &self.remote_editor] {
+            editor.update(cx, |editor, cx| {
+                editor.added_to_workspace(workspace, window, cx)
+            });
+        }
+    }
+
+    fn can_save(&self, cx: &App) -> bool {
+        self.merged_editor.read(cx).can_save(cx)
+    }
+
+    fn save(
+        &mut self,
+        options: SaveOptions,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.merged_editor
+            .update(cx, |editor, cx| editor.save(options, project, window, cx))
+    }
+}
+
+impl Render for MergeView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let conflicts_len = self.conflict_set.read(cx).snapshot().conflicts.len();
+        let merged_detail = match conflicts_len {
+            0 => "No conflicts remaining".into(),
+            1 => "1 conflict remaining".into(),
+            len => format!("{len} conflicts remaining").into(),
+        };
+
+        h_flex()
+            .size_full()
+            .child(self.render_pane(
+                "Local",
+                buffer_path(&self.local_editor, cx),
+                &self.local_editor,
+                cx,
+            ))
+            .child(vertical_divider())
+            .child(self.render_pane("Merged", merged_detail, &self.merged_editor, cx))
+            .child(vertical_divider())
+            .child(self.render_pane(
+                "Remote",
+                buffer_path(&self.remote_editor, cx),
+                &self.remote_editor,
+                cx,
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::test::editor_test_context::assert_state_with_diff;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Fs, Project};
+    use settings::SettingsStore;
+    use unindent::unindent;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_merge_view(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "file_BASE.txt": "line 1\nline 2\nline 3\n",
+                "file_LOCAL.txt": "line 1\nours\nline 3\n",
+                "file_REMOTE.txt": "line 1\ntheirs\nline 3\n",
+                "file.txt": "line 1\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> feature\nline 3\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let merge_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                MergeView::open(
+                    path!("/test/file_BASE.txt").into(),
+                    path!("/test/file_LOCAL.txt").into(),
+                    path!("/test/file_REMOTE.txt").into(),
+                    path!("/test/file.txt").into(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        assert_state_with_diff(
+            &merge_view.read_with(cx, |merge_view, _| merge_view.local_editor.clone()),
+            cx,
+            &unindent(
+                "
+                  ˇline 1
+                - line 2
+                + ours
+                  line 3
+                ",
+            ),
+        );
+        assert_state_with_diff(
+            &merge_view.read_with(cx, |merge_view, _| merge_view.remote_editor.clone()),
+            cx,
+            &unindent(
+                "
+                  ˇline 1
+                - line 2
+                + theirs
+                  line 3
+                ",
+            ),
+        );
+
+        let conflict = merge_view.read_with(cx, |merge_view, cx| {
+            assert_eq!(merge_view.tab_content_text(0, cx), "file.txt (Merge)");
+            assert!(!merge_view.is_resolved(cx));
+            let conflicts = merge_view.conflict_set.read(cx).snapshot().conflicts;
+            assert_eq!(conflicts.len(), 1);
+            assert_eq!(conflicts[0].ours_branch_name, "HEAD");
+            assert_eq!(conflicts[0].theirs_branch_name, "feature");
+            conflicts[0].clone()
+        });
+
+        // Take both sides of the conflict, as the "Use Both" button does.
+        merge_view.update(cx, |merge_view, cx| {
+            let ranges = [conflict.ours.clone(), conflict.theirs.clone()];
+            conflict.resolve(merge_view.merged_buffer.clone(), &ranges, cx);
+        });
+        cx.run_until_parked();
+
+        merge_view.read_with(cx, |merge_view, cx| {
+            assert_eq!(
+                merge_view.merged_buffer.read(cx).text(),
+                "line 1\nours\ntheirs\nline 3\n"
+            );
+            assert!(
+                merge_view
+                    .conflict_set
+                    .read(cx)
+                    .snapshot()
+                    .conflicts
+                    .is_empty()
+            );
+            assert!(
+                !merge_view.is_resolved(cx),
+                "Merge should not be resolved until it is saved"
+            );
+        });
+
+        let save_task = merge_view.update_in(cx, |merge_view, window, cx| {
+            workspace::Item::save(
+                merge_view,
+                workspace::item::SaveOptions::default(),
+                project.clone(),
+                window,
+                cx,
+            )
+        });
+        save_task.await.expect("Save should succeed");
+
+        assert_eq!(
+            fs.load(path!("/test/file.txt").as_ref()).await.unwrap(),
+            "line 1\nours\ntheirs\nline 3\n"
+        );
+        merge_view.read_with(cx, |merge_view, cx| assert!(merge_view.is_resolved(cx)));
+    }
+
+    #[gpui::test]
+    async fn test_merge_view_scrolling(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        // `LOCAL` adds lines after line 0, and `REMOTE` deletes lines 5 to 9, which both merge
+        // cleanly into `MERGED`.
+        let base = (0..30).map(|ix| format!("line {ix}\n")).collect::<String>();
+        let ours = (0..10).map(|ix| format!("ours {ix}\n")).collect::<String>();
+        let local = base.replacen("line 1\n", &format!("{ours}line 1\n"), 1);
+        let remote = (0..30)
+            .filter(|ix| !(5..10).contains(ix))
+            .map(|ix| format!("line {ix}\n"))
+            .collect::<String>();
+        let merged = remote.replacen("line 1\n", &format!("{ours}line 1\n"), 1);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "file_BASE.txt": base,
+                "file_LOCAL.txt": local,
+                "file_REMOTE.txt": remote,
+                "file.txt": merged,
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let merge_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                MergeView::open(
+                    path!("/test/file_BASE.txt").into(),
+                    path!("/test/file_LOCAL.txt").into(),
+                    path!("/test/file_REMOTE.txt").into(),
+                    path!("/test/file.txt").into(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        let (local_editor, merged_editor, remote_editor) =
+            merge_view.read_with(cx, |merge_view, _| {
+                (
+                    merge_view.local_editor.clone(),
+                    merge_view.merged_editor.clone(),
+                    merge_view.remote_editor.clone(),
+                )
+            });
+        let top_line = |editor: &Entity<Editor>, cx: &mut VisualTestContext| {
+            editor.read_with(cx, |editor, cx| {
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let row = editor
+                    .scroll_manager
+                    .shared_scroll_anchor(cx)
+                    .scroll_anchor
+                    .top_row(&snapshot);
+                snapshot
+                    .text()
+                    .lines()
+                    .nth(row as usize)
+                    .unwrap()
+                    .to_string()
+            })
+        };
+
+        merged_editor.update_in(cx, |editor, window, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let scroll_anchor = ScrollAnchor {
+                anchor: snapshot.anchor_before(Point::new(25, 0)),
+                offset: Default::default(),
+            };
+            editor.set_scroll_anchor(scroll_anchor, window, cx);
+        });
+        cx.run_until_parked();
+        for editor in [&local_editor, &merged_editor, &remote_editor] {
+            assert_eq!(top_line(editor, cx), "line 20");
+        }
+
+        // A line deleted in `MERGED` lines up with where its hunk was there.
+        remote_editor.update_in(cx, |editor, window, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let scroll_anchor = ScrollAnchor {
+                anchor: snapshot.anchor_before(Point::new(7, 0)),
+                offset: Default::default(),
+            };
+            editor.set_scroll_anchor(scroll_anchor, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(top_line(&remote_editor, cx), "line 7");
+        assert_eq!(top_line(&local_editor, cx), "line 7");
+        assert_eq!(top_line(&merged_editor, cx), "line 10");
+    }
+}
@@ -16,8 +16,11 @@ use editor::{
 };
 
 use git::{
-    Commit, StageAll, StageAndNext, ToggleStaged, UnstageAll, UnstageAndNext,
-    repository::{Branch, RepoPath, Upstream, UpstreamTracking, UpstreamTrackingStatus},
+    AbortRebase, Commit, ContinueRebase, SkipRebase, StageAll, StageAndNext, ToggleStaged,
+    UnstageAll, UnstageAndNext,
+    repository::{
+        Branch, RebaseProgress, RepoPath, Upstream, UpstreamTracking, UpstreamTrackingStatus,
+    },
     status::FileStatus,
 };
 use gpui::{
@@ -564,6 +567,13 @@ impl ProjectDiff {
         }
     }
 
+    /// Returns the progress of the rebase in progress, and whether it still has unresolved conflicts.
+    fn rebase_state(&self, cx: &App) -> Option<(RebaseProgress, bool)> {
+        let repo = self.branch_diff.read(cx).repo()?.read(cx);
+        let progress = repo.rebase.clone()?;
+        Some((progress, repo.status_summary().conflict > 0))
+    }
+
     fn handle_editor_event(
         &mut self,
         editor: &Entity<SplittableEditor>,
@@ -1323,6 +1333,7 @@ impl Render for ProjectDiffToolbar {
         let focus_handle = project_diff.focus_handle(cx);
         let button_states = project_diff.read(cx).button_states(cx);
         let review_count = project_diff.read(cx).total_review_comment_count();
+        let rebase_state = project_diff.read(cx).rebase_state(cx);
 
         h_group_xl()
             .my_neg_1()
@@ -1330,6 +1341,62 @@ impl Render for ProjectDiffToolbar {
             .items_center()
             .flex_wrap()
             .justify_between()
+            .when_some(rebase_state, |el, (progress, has_conflicts)| {
+                let continue_focus_handle = focus_handle.clone();
+                el.child(
+                    h_group_sm()
+                        .child(
+                            Label::new(format!(
+                                "Rebasing {}/{}",
+                                progress.step, progress.total_steps
+                            ))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                        )
+                        .child(
+                            Button::new("continue-rebase", "Continue")
+                                .tooltip(move |_window, cx| {
+                                    if has_conflicts {
+                                        Tooltip::simple("Resolve and stage all conflicts first", cx)
+                                    } else {
+                                        Tooltip::for_action_in(
+                                            "Continue rebase",
+                                            &ContinueRebase,
+                                            &continue_focus_handle,
+                                            cx,
+                                        )
+                                    }
+                                })
+                                .disabled(has_conflicts)
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.dispatch_action(&ContinueRebase, window, cx)
+                                })),
+                        )
+                        .child(
+                            Button::new("skip-rebase", "Skip")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "Skip this commit",
+                                    &SkipRebase,
+                                    &focus_handle,
+                                ))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.dispatch_action(&SkipRebase, window, cx)
+                                })),
+                        )
+                        .child(
+                            Button::new("abort-rebase", "Abort")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "Abort rebase",
+                                    &AbortRebase,
+                                    &focus_handle,
+                                ))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.dispatch_action(&AbortRebase, window, cx)
+                                })),
+                        ),
+                )
+                .child(vertical_divider())
+            })
             .child(
                 h_group_sm()
                     .when(button_states.selection, |el| {
@@ -0,0 +1,557 @@
+use anyhow::{Result, anyhow};
+use editor::{Editor, EditorEvent};
+use git::repository::{RebaseTodoAction, RebaseTodoEntry};
+use gpui::{
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ScrollStrategy, Subscription,
+    Task, UniformListScrollHandle, WeakEntity, actions, uniform_list,
+};
+use menu::{Cancel, SelectNext, SelectPrevious};
+use project::git_store::Repository;
+use ui::{Chip, Divider, KeyBinding, ListItem, WithScrollbar, prelude::*};
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
+
+actions!(
+    rebase_editor,
+    [
+        /// Applies the selected commit as-is.
+        Pick,
+        /// Applies the selected commit with a new message.
+        Reword,
+        /// Melds the selected commit into the previous one, combining their messages.
+        Squash,
+        /// Melds the selected commit into the previous one, discarding its message.
+        Fixup,
+        /// Leaves the selected commit out of the rebase.
+        Drop,
+        /// Moves the selected commit earlier in the rebase.
+        MoveUp,
+        /// Moves the selected commit later in the rebase.
+        MoveDown,
+        /// Starts the rebase with the edited todo list.
+        StartRebase,
+    ]
+);
+
+/// A modal for editing the todo list of an interactive rebase before starting it.
+pub struct RebaseEditor {
+    repository: Entity<Repository>,
+    base: Option<String>,
+    entries: Vec<RebaseTodoEntry>,
+    selected_index: usize,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    message_editor: Entity<Editor>,
+    _message_editor_subscription: Subscription,
+}
+
+impl RebaseEditor {
+    /// Lists the commits after `base` and opens the editor for them. When `base` is `None`, every
+    /// commit on the current branch is rebased, including the root commit.
+    pub fn open(
+        workspace: WeakEntity<Workspace>,
+        repository: Entity<Repository>,
+        base: Option<String>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<()>> {
+        let entries = repository.update(cx, |repository, _| repository.rebase_todo(base.clone()));
+        window.spawn(cx, async move |cx| {
+            let entries = entries.await??;
+            anyhow::ensure!(!entries.is_empty(), "There are no commits to rebase");
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    Self::new(repository, base, entries, window, cx)
+                });
+            })
+        })
+    }
+
+    pub fn new(
+        repository: Entity<Repository>,
+        base: Option<String>,
+        entries: Vec<RebaseTodoEntry>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let message_editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(3, 8, window, cx);
+            editor.set_placeholder_text("New commit message", window, cx);
+            editor
+        });
+        let subscription = cx.subscribe(&message_editor, |this, editor, event, cx| {
+            if let EditorEvent::BufferEdited = event
+                && let Some(entry) = this.entries.get_mut(this.selected_index)
+                && entry.action == RebaseTodoAction::Reword
+            {
+                entry.message = editor.read(cx).text(cx).into();
+            }
+        });
+        Self {
+            repository,
+            base,
+            entries,
+            selected_index: 0,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            message_editor,
+            _message_editor_subscription: subscription,
+        }
+    }
+
+    pub fn entries(&self) -> &[RebaseTodoEntry] {
+        &self.entries
+    }
+
+    fn select_index(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if index >= self.entries.len() {
+            return;
+        }
+        self.selected_index = index;
+        self.scroll_handle
+            .scroll_to_item(index, ScrollStrategy::Nearest);
+        self.sync_message_editor(window, cx);
+        cx.notify();
+    }
+
+    fn sync_message_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(self.selected_index) else {
+            return;
+        };
+        if entry.action == RebaseTodoAction::Reword {
+            let message = entry.message.clone();
+            self.message_editor.update(cx, |editor, cx| {
+                if editor.text(cx) != message.as_ref() {
+                    editor.set_text(message, window, cx);
+                }
+            });
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        self.select_index(self.selected_index + 1, window, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(index) = self.selected_index.checked_sub(1) {
+            self.select_index(index, window, cx);
+        }
+    }
+
+    pub fn set_action(
+        &mut self,
+        action: RebaseTodoAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.entries.get_mut(self.selected_index) else {
+            return;
+        };
+        entry.action = action;
+        self.error = None;
+        self.sync_message_editor(window, cx);
+        if action == RebaseTodoAction::Reword {
+            self.message_editor.focus_handle(cx).focus(window, cx);
+        }
+        cx.notify();
+    }
+
+    fn pick(&mut self, _: &Pick, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseTodoAction::Pick, window, cx);
+    }
+
+    fn reword(&mut self, _: &Reword, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseTodoAction::Reword, window, cx);
+    }
+
+    fn squash(&mut self, _: &Squash, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseTodoAction::Squash, window, cx);
+    }
+
+    fn fixup(&mut self, _: &Fixup, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseTodoAction::Fixup, window, cx);
+    }
+
+    fn drop_commit(&mut self, _: &Drop, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseTodoAction::Drop, window, cx);
+    }
+
+    pub fn move_selected(&mut self, down: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let target = if down {
+            self.selected_index + 1
+        } else if let Some(target) = self.selected_index.checked_sub(1) {
+            target
+        } else {
+            return;
+        };
+        if target >= self.entries.len() {
+            return;
+        }
+        self.entries.swap(self.selected_index, target);
+        self.error = None;
+        self.select_index(target, window, cx);
+    }
+
+    fn move_up(&mut self, _: &MoveUp, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_selected(false, window, cx);
+    }
+
+    fn move_down(&mut self, _: &MoveDown, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_selected(true, window, cx);
+    }
+
+    /// Returns why the todo list can't be applied, if it can't.
+    pub fn validate(&self) -> Option<SharedString> {
+        let first = self
+            .entries
+            .iter()
+            .find(|entry| entry.action != RebaseTodoAction::Drop)?;
+        if first.action.melds_into_previous() {
+            return Some(
+                format!(
+                    "Can't {} the first commit, as there is no previous commit to meld it into",
+                    first.action.as_str()
+                )
+                .into(),
+            );
+        }
+        self.entries
+            .iter()
+            .find(|entry| {
+                entry.action == RebaseTodoAction::Reword && entry.message.trim().is_empty()
+            })
+            .map(|entry| {
+                format!(
+                    "The new message for {} is empty",
+                    &entry.sha[..entry.sha.len().min(7)]
+                )
+                .into()
+            })
+    }
+
+    fn start_rebase(&mut self, _: &StartRebase, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(error) = self.validate() {
+            self.error = Some(error);
+            cx.notify();
+            return;
+        }
+
+        let rebase = self.repository.update(cx, |repository, cx| {
+            repository.rebase_interactive(self.base.clone(), self.entries.clone(), cx)
+        });
+        cx.spawn(async move |_, _| {
+            rebase
+                .await
+                .map_err(|_| anyhow!("Operation was canceled"))?
+        })
+        .detach_and_prompt_err("Failed to rebase", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry = &self.entries[ix];
+        let action_color = match entry.action {
+            RebaseTodoAction::Pick => Color::Default,
+            RebaseTodoAction::Reword => Color::Accent,
+            RebaseTodoAction::Squash | RebaseTodoAction::Fixup => Color::Warning,
+            RebaseTodoAction::Drop => Color::Deleted,
+        };
+        let subject = if entry.action == RebaseTodoAction::Reword {
+            entry
+                .message
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string()
+                .into()
+        } else {
+            entry.subject.clone()
+        };
+
+        ListItem::new(("rebase-entry", ix))
+            .toggle_state(ix == self.selected_index)
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.select_index(ix, window, cx);
+            }))
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(
+                        div().w(rems_from_px(52.)).flex_none().child(
+                            Label::new(entry.action.as_str())
+                                .size(LabelSize::Small)
+                                .color(action_color),
+                        ),
+                    )
+                    .child(
+                        div()
+                            .w(rems_from_px(64.))
+                            .flex_none()
+                            .child(Chip::new(entry.sha[..entry.sha.len().min(7)].to_string())),
+                    )
+                    .child(
+                        Label::new(subject)
+                            .size(LabelSize::Small)
+                            .when(entry.action == RebaseTodoAction::Drop, |label| {
+                                label.strikethrough().color(Color::Muted)
+                            })
+                            .truncate(),
+                    ),
+            )
+    }
+
+    fn render_action_button(
+        &self,
+        id: &'static str,
+        label: &'static str,
+        action: &dyn gpui::Action,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let action = action.boxed_clone();
+        Button::new(id, label)
+            .label_size(LabelSize::Small)
+            .key_binding(
+                KeyBinding::for_action_in(action.as_ref(), &self.focus_handle, cx)
+                    .map(|kb| kb.size(rems_from_px(12.))),
+            )
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.focus_handle.focus(window, cx);
+                window.dispatch_action(action.boxed_clone(), cx);
+            }))
+    }
+}
+
+impl EventEmitter<DismissEvent> for RebaseEditor {}
+impl ModalView for RebaseEditor {}
+impl Focusable for RebaseEditor {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for RebaseEditor {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let onto = match &self.base {
+            Some(base) => format!("Interactive Rebase onto {}", &base[..base.len().min(7)]),
+            None => "Interactive Rebase from Root".to_string(),
+        };
+        let is_rewording = self
+            .entries
+            .get(self.selected_index)
+            .is_some_and(|entry| entry.action == RebaseTodoAction::Reword);
+
+        v_flex()
+            .key_context("RebaseEditor")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::start_rebase))
+            .elevation_2(cx)
+            .w(rems(40.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitBranch).size(IconSize::XSmall))
+                    .child(Headline::new(onto).size(HeadlineSize::XSmall)),
+            )
+            .child(
+                v_flex()
+                    .key_context("RebaseTodoList")
+                    .track_focus(&self.focus_handle)
+                    .on_action(cx.listener(Self::select_next))
+                    .on_action(cx.listener(Self::select_previous))
+                    .on_action(cx.listener(Self::pick))
+                    .on_action(cx.listener(Self::reword))
+                    .on_action(cx.listener(Self::squash))
+                    .on_action(cx.listener(Self::fixup))
+                    .on_action(cx.listener(Self::drop_commit))
+                    .on_action(cx.listener(Self::move_up))
+                    .on_action(cx.listener(Self::move_down))
+                    .h(rems(20.))
+                    .px_1()
+                    .child(
+                        uniform_list(
+                            "rebase-todo-list",
+                            self.entries.len(),
+                            cx.processor(|this, range: std::ops::Range<usize>, _, cx| {
+                                range.map(|ix| this.render_entry(ix, cx)).collect()
+                            }),
+                        )
+                        .size_full()
+                        .track_scroll(&self.scroll_handle),
+                    )
+                    .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+            )
+            .when(is_rewording, |el| {
+                el.child(Divider::horizontal()).child(
+                    div()
+                        .px_3()
+                        .py_2()
+                        .w_full()
+                        .child(self.message_editor.clone()),
+                )
+            })
+            .when_some(self.error.clone(), |el, error| {
+                el.child(
+                    h_flex()
+                        .px_3()
+                        .py_1()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::Warning)
+                                .size(IconSize::XSmall)
+                                .color(Color::Warning),
+                        )
+                        .child(Label::new(error).size(LabelSize::Small)),
+                )
+            })
+            .child(Divider::horizontal())
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_1()
+                    .flex_wrap()
+                    .child(self.render_action_button("pick", "Pick", &Pick, cx))
+                    .child(self.render_action_button("reword", "Reword", &Reword, cx))
+                    .child(self.render_action_button("squash", "Squash", &Squash, cx))
+                    .child(self.render_action_button("fixup", "Fixup", &Fixup, cx))
+                    .child(self.render_action_button("drop", "Drop", &Drop, cx))
+                    .child(self.render_action_button("move-up", "Move Up", &MoveUp, cx))
+                    .child(self.render_action_button("move-down", "Move Down", &MoveDown, cx)),
+            )
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_1()
+                    .justify_end()
+                    .child(
+                        Button::new("cancel", "Cancel")
+                            .key_binding(
+                                KeyBinding::for_action_in(&Cancel, &self.focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.cancel(&Cancel, window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("start-rebase", "Start Rebase")
+                            .key_binding(
+                                KeyBinding::for_action_in(&StartRebase, &self.focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.start_rebase(&StartRebase, window, cx);
+                            })),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+        });
+    }
+
+    fn todo_entry(sha: &str, subject: &str) -> RebaseTodoEntry {
+        RebaseTodoEntry {
+            action: RebaseTodoAction::Pick,
+            sha: sha.to_string().into(),
+            subject: subject.to_string().into(),
+            message: format!("{subject}\n\nBody").into(),
+        }
+    }
+
+    #[gpui::test]
+    async fn test_rebase_editor(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+
+        let (rebase_editor, cx) = cx.add_window_view(|window, cx| {
+            RebaseEditor::new(
+                repository,
+                Some("base".into()),
+                vec![
+                    todo_entry("aaaaaaaaaa", "First"),
+                    todo_entry("bbbbbbbbbb", "Second"),
+                ],
+                window,
+                cx,
+            )
+        });
+
+        // The first commit has nothing to be squashed into until it is moved down.
+        rebase_editor.update_in(cx, |rebase_editor, window, cx| {
+            rebase_editor.set_action(RebaseTodoAction::Squash, window, cx);
+            assert!(rebase_editor.validate().is_some());
+
+            rebase_editor.move_selected(true, window, cx);
+            assert_eq!(rebase_editor.validate(), None);
+            assert_eq!(
+                rebase_editor
+                    .entries()
+                    .iter()
+                    .map(|entry| (entry.subject.as_ref(), entry.action))
+                    .collect::<Vec<_>>(),
+                [
+                    ("Second", RebaseTodoAction::Pick),
+                    ("First", RebaseTodoAction::Squash),
+                ]
+            );
+
+            rebase_editor.select_index(0, window, cx);
+            rebase_editor.set_action(RebaseTodoAction::Reword, window, cx);
+            assert_eq!(
+                rebase_editor.message_editor.read(cx).text(cx),
+                "Second\n\nBody"
+            );
+            rebase_editor.message_editor.update(cx, |editor, cx| {
+                editor.set_text("Reworded", window, cx);
+            });
+        });
+        cx.run_until_parked();
+
+        rebase_editor.update_in(cx, |rebase_editor, window, cx| {
+            assert_eq!(rebase_editor.entries()[0].message.as_ref(), "Reworded");
+            assert_eq!(rebase_editor.validate(), None);
+
+            rebase_editor.message_editor.update(cx, |editor, cx| {
+                editor.set_text("  ", window, cx);
+            });
+        });
+        cx.run_until_parked();
+
+        rebase_editor.read_with(cx, |rebase_editor, _| {
+            assert!(rebase_editor.validate().is_some());
+        });
+    }
+}
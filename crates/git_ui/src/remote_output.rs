@@ -10,6 +10,7 @@ pub enum RemoteAction {
     Fetch(Option<Remote>),
     Pull(Remote),
     Push(SharedString, Remote),
+    UpdateSubmodules,
 }
 
 impl RemoteAction {
@@ -18,6 +19,7 @@ impl RemoteAction {
             RemoteAction::Fetch(_) => "fetch",
             RemoteAction::Pull(_) => "pull",
             RemoteAction::Push(_, _) => "push",
+            RemoteAction::UpdateSubmodules => "submodule update",
         }
     }
 }
@@ -157,6 +159,28 @@ pub fn format_output(action: &RemoteAction, output: RemoteCommandOutput) -> Succ
                 style: style.unwrap_or(SuccessStyle::ToastWithLog { output }),
             }
         }
+        RemoteAction::UpdateSubmodules => {
+            let updated = output
+                .stdout
+                .lines()
+                .filter(|line| line.starts_with("Submodule path"))
+                .count();
+            if updated == 0 {
+                SuccessMessage {
+                    message: "Submodules: Already up to date".into(),
+                    style: SuccessStyle::Toast,
+                }
+            } else {
+                SuccessMessage {
+                    message: format!(
+                        "Updated {} submodule{}",
+                        updated,
+                        if updated == 1 { "" } else { "s" }
+                    ),
+                    style: SuccessStyle::ToastWithLog { output },
+                }
+            }
+        }
     }
 }
 
@@ -297,4 +321,28 @@ mod tests {
             panic!("Expected ToastWithLog variant");
         }
     }
+
+    #[test]
+    fn test_update_submodules() {
+        let output = RemoteCommandOutput {
+            stdout: indoc! {"
+                Submodule path 'vendor/lib': checked out '6ff077a41291f933143271bedb8dd351a4dd69a3'
+                Submodule path 'vendor/hal': checked out 'aef2fd53a022f55b2714c55d128c8465de5e6936'
+                "}
+            .to_string(),
+            stderr: "Cloning into '/repo/vendor/hal'...\n".to_string(),
+        };
+
+        let msg = format_output(&RemoteAction::UpdateSubmodules, output);
+        assert_eq!(msg.message, "Updated 2 submodules");
+        assert!(matches!(msg.style, SuccessStyle::ToastWithLog { .. }));
+
+        let output = RemoteCommandOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+        };
+        let msg = format_output(&RemoteAction::UpdateSubmodules, output);
+        assert_eq!(msg.message, "Submodules: Already up to date");
+        assert!(matches!(msg.style, SuccessStyle::Toast));
+    }
 }
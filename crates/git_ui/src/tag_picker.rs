@@ -0,0 +1,658 @@
+use fuzzy::StringMatchCandidate;
+
+use git::repository::Tag;
+use gpui::{
+    Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, Render, SharedString, Styled, Subscription,
+    Task, WeakEntity, Window, actions, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, Workspace};
+
+use crate::commit_view::CommitView;
+use crate::git_panel::{GitPanel, show_error_toast};
+use crate::tag_picker;
+
+actions!(
+    tag_picker,
+    [
+        /// Deletes the selected tag.
+        DeleteTag,
+        /// Pushes the selected tag to a remote.
+        PushTag,
+    ]
+);
+
+/// Opens a tag picker in which new tags are created at `commit` rather than at HEAD.
+pub fn create_tag_at(
+    workspace: &mut Workspace,
+    repository: Entity<Repository>,
+    commit: SharedString,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let weak_workspace = workspace.weak_handle();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        TagList::new(
+            Some(repository),
+            weak_workspace,
+            Some(commit),
+            rems(34.),
+            window,
+            cx,
+        )
+    })
+}
+
+pub fn create_embedded(
+    repository: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    width: Rems,
+    window: &mut Window,
+    cx: &mut Context<TagList>,
+) -> TagList {
+    TagList::new_embedded(repository, workspace, width, window, cx)
+}
+
+pub struct TagList {
+    width: Rems,
+    pub picker: Entity<Picker<TagListDelegate>>,
+    picker_focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl TagList {
+    fn new(
+        repository: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        target: Option<SharedString>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self::new_inner(repository, workspace, target, width, false, window, cx);
+        this._subscriptions
+            .push(cx.subscribe(&this.picker, |_, _, _, cx| {
+                cx.emit(DismissEvent);
+            }));
+        this
+    }
+
+    fn new_inner(
+        repository: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        target: Option<SharedString>,
+        width: Rems,
+        embedded: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let tags_request = repository
+            .clone()
+            .map(|repository| repository.update(cx, |repo, _| repo.tags()));
+
+        cx.spawn_in(window, async move |this, cx| {
+            let tags = match tags_request {
+                Some(tags_request) => tags_request.await??,
+                None => Vec::new(),
+            };
+
+            this.update_in(cx, |this, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.all_tags = Some(tags);
+                    picker.refresh(window, cx);
+                })
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+
+        let delegate = TagListDelegate::new(repository, workspace, target, window, cx);
+        let picker = cx.new(|cx| {
+            Picker::uniform_list(delegate, window, cx)
+                .show_scrollbar(true)
+                .modal(!embedded)
+        });
+        let picker_focus_handle = picker.focus_handle(cx);
+        picker.update(cx, |picker, _| {
+            picker.delegate.focus_handle = picker_focus_handle.clone();
+        });
+
+        Self {
+            picker,
+            picker_focus_handle,
+            width,
+            _subscriptions: Vec::new(),
+        }
+    }
+
+    fn new_embedded(
+        repository: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self::new_inner(repository, workspace, None, width, true, window, cx);
+        this._subscriptions
+            .push(cx.subscribe(&this.picker, |_, _, _, cx| {
+                cx.emit(DismissEvent);
+            }));
+        this
+    }
+
+    pub fn handle_delete_tag(
+        &mut self,
+        _: &DeleteTag,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .delete_tag_at(picker.delegate.selected_index(), window, cx);
+        });
+        cx.notify();
+    }
+
+    pub fn handle_push_tag(&mut self, _: &PushTag, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .push_tag_at(picker.delegate.selected_index(), window, cx);
+        });
+    }
+}
+
+impl ModalView for TagList {}
+impl EventEmitter<DismissEvent> for TagList {}
+impl Focusable for TagList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.picker_focus_handle.clone()
+    }
+}
+
+impl Render for TagList {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("TagList")
+            .w(self.width)
+            .on_action(cx.listener(Self::handle_delete_tag))
+            .on_action(cx.listener(Self::handle_push_tag))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TagEntry {
+    Tag { tag: Tag, positions: Vec<usize> },
+    NewTag { name: String },
+}
+
+pub struct TagListDelegate {
+    matches: Vec<TagEntry>,
+    all_tags: Option<Vec<Tag>>,
+    repo: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    /// The commit that new tags are created at, or `None` for HEAD.
+    target: Option<SharedString>,
+    selected_index: usize,
+    focus_handle: FocusHandle,
+}
+
+impl TagListDelegate {
+    fn new(
+        repo: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        target: Option<SharedString>,
+        _window: &mut Window,
+        cx: &mut Context<TagList>,
+    ) -> Self {
+        Self {
+            matches: vec![],
+            all_tags: None,
+            repo,
+            workspace,
+            target,
+            selected_index: 0,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn short_target(&self) -> SharedString {
+        match &self.target {
+            Some(sha) => sha.chars().take(7).collect::<String>().into(),
+            None => "HEAD".into(),
+        }
+    }
+
+    fn create_tag(&self, name: String, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let commit = self.target.as_ref().map(|sha| sha.to_string());
+
+        cx.spawn(async move |_, cx| {
+            repo.update(cx, |repo, cx| repo.create_tag(name, commit, None, cx))
+                .await??;
+            Ok(())
+        })
+        .detach_and_prompt_err("Failed to create tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn show_tag(&self, tag: &Tag, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        CommitView::open(
+            tag.sha.to_string(),
+            repo.downgrade(),
+            self.workspace.clone(),
+            None,
+            None,
+            window,
+            cx,
+        );
+        cx.emit(DismissEvent);
+    }
+
+    fn delete_tag_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(TagEntry::Tag { tag, .. }) = self.matches.get(ix).cloned() else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let workspace = self.workspace.clone();
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let result = repo
+                .update(cx, |repo, _| repo.delete_tag(tag.name.to_string()))
+                .await?;
+
+            if let Err(e) = result {
+                log::error!("Failed to delete tag: {}", e);
+                if let Some(workspace) = workspace.upgrade() {
+                    cx.update(|_window, cx| {
+                        show_error_toast(workspace, format!("tag -d {}", tag.name), e, cx)
+                    })?;
+                }
+                return Ok(());
+            }
+
+            picker.update_in(cx, |picker, _, cx| {
+                let delegate = &mut picker.delegate;
+                delegate.matches.retain(|entry| {
+                    !matches!(entry, TagEntry::Tag { tag: existing, .. } if existing.name == tag.name)
+                });
+                if let Some(all_tags) = &mut delegate.all_tags {
+                    all_tags.retain(|existing| existing.name != tag.name);
+                }
+                if delegate.matches.is_empty() {
+                    delegate.selected_index = 0;
+                } else if delegate.selected_index >= delegate.matches.len() {
+                    delegate.selected_index = delegate.matches.len() - 1;
+                }
+                cx.notify();
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach();
+    }
+
+    fn push_tag_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(TagEntry::Tag { tag, .. }) = self.matches.get(ix) else {
+            return;
+        };
+        let Some(panel) = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| workspace.read(cx).panel::<GitPanel>(cx))
+        else {
+            return;
+        };
+        let name = tag.name.clone();
+        panel.update(cx, |panel, cx| panel.push_tag(name, window, cx));
+        cx.emit(DismissEvent);
+    }
+}
+
+impl PickerDelegate for TagListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        if self.target.is_some() {
+            format!("Enter a name to tag {}…", self.short_target()).into()
+        } else {
+            "Select or create a tag…".into()
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_tags) = self.all_tags.clone() else {
+            return Task::ready(());
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<TagEntry> = if query.is_empty() {
+                all_tags
+                    .into_iter()
+                    .map(|tag| TagEntry::Tag {
+                        tag,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = all_tags
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, tag)| StringMatchCandidate::new(ix, &tag.name))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| TagEntry::Tag {
+                    tag: all_tags[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+
+            // Tag names can't contain spaces, so offer to create one with dashes instead.
+            let name = query.trim().replace(' ', "-");
+            if !name.is_empty()
+                && !matches
+                    .iter()
+                    .any(|entry| matches!(entry, TagEntry::Tag { tag, .. } if tag.name == name))
+            {
+                matches.insert(0, TagEntry::NewTag { name });
+            }
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.matches.get(self.selected_index()).cloned() else {
+            return;
+        };
+        match entry {
+            TagEntry::Tag { tag, .. } => self.show_tag(&tag, window, cx),
+            TagEntry::NewTag { name } => self.create_tag(name, window, cx),
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = self.matches.get(ix)?;
+
+        let (icon, title, detail) = match entry {
+            TagEntry::Tag { tag, positions } => {
+                let short_sha = tag.sha.chars().take(7).collect::<String>();
+                let detail = match &tag.message {
+                    Some(message) => format!("{short_sha} • {message}"),
+                    None => short_sha,
+                };
+                (
+                    IconName::Hash,
+                    HighlightedLabel::new(tag.name.clone(), positions.clone())
+                        .truncate()
+                        .into_any_element(),
+                    detail,
+                )
+            }
+            TagEntry::NewTag { name } => (
+                IconName::Plus,
+                Label::new(format!("Create Tag: \"{name}\""))
+                    .truncate()
+                    .into_any_element(),
+                format!("At {}", self.short_target()),
+            ),
+        };
+
+        Some(
+            ListItem::new(format!("tag-{ix}"))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_3()
+                        .child(Icon::new(icon).color(Color::Muted))
+                        .child(
+                            v_flex().w_full().child(title).child(
+                                Label::new(detail)
+                                    .truncate()
+                                    .color(Color::Muted)
+                                    .size(LabelSize::Small),
+                            ),
+                        ),
+                ),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No tags found".into())
+    }
+
+    fn render_footer(&self, _: &mut Window, cx: &mut Context<Picker<Self>>) -> Option<AnyElement> {
+        let focus_handle = self.focus_handle.clone();
+        let is_new_tag = matches!(
+            self.matches.get(self.selected_index),
+            Some(TagEntry::NewTag { .. })
+        );
+
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_0p5()
+                .justify_end()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .when(!is_new_tag, |this| {
+                    this.child(
+                        Button::new("delete-tag", "Delete")
+                            .key_binding(
+                                KeyBinding::for_action_in(
+                                    &tag_picker::DeleteTag,
+                                    &focus_handle,
+                                    cx,
+                                )
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(tag_picker::DeleteTag.boxed_clone(), cx)
+                            }),
+                    )
+                    .child(
+                        Button::new("push-tag", "Push")
+                            .key_binding(
+                                KeyBinding::for_action_in(&tag_picker::PushTag, &focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(tag_picker::PushTag.boxed_clone(), cx)
+                            }),
+                    )
+                })
+                .child(
+                    Button::new("confirm-tag", if is_new_tag { "Create" } else { "View" })
+                        .key_binding(
+                            KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                        }),
+                )
+                .into_any(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext, rems};
+    use picker::PickerDelegate;
+    use project::{FakeFs, Project};
+    use settings::SettingsStore;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        })
+    }
+
+    fn tag(name: &str, message: Option<&str>) -> Tag {
+        Tag {
+            name: name.to_string().into(),
+            sha: format!("{:0>40}", name.len()).into(),
+            message: message.map(|message| message.to_string().into()),
+        }
+    }
+
+    #[gpui::test]
+    async fn test_tag_matches(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project, window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+
+        let tag_list = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                TagList::new(None, weak_workspace, None, rems(34.), window, cx)
+            });
+            workspace.active_modal::<TagList>(cx).unwrap()
+        });
+
+        cx.run_until_parked();
+        tag_list.update(cx, |tag_list, cx| {
+            tag_list.picker.update(cx, |picker, _| {
+                picker.delegate.all_tags = Some(vec![
+                    tag("v1.0.0", Some("First release")),
+                    tag("v1.1.0", None),
+                ]);
+            });
+        });
+
+        let matches = |query: &str, cx: &mut VisualTestContext| {
+            tag_list.update_in(cx, |tag_list, window, cx| {
+                tag_list.picker.update(cx, |picker, cx| {
+                    picker
+                        .delegate
+                        .update_matches(query.to_string(), window, cx)
+                })
+            })
+        };
+
+        matches("", cx).await;
+        tag_list.update(cx, |tag_list, cx| {
+            let delegate = &tag_list.picker.read(cx).delegate;
+            assert_eq!(delegate.matches.len(), 2);
+            assert!(
+                delegate
+                    .matches
+                    .iter()
+                    .all(|entry| matches!(entry, TagEntry::Tag { .. }))
+            );
+        });
+
+        // An exact match doesn't offer to create the tag again.
+        matches("v1.1.0", cx).await;
+        tag_list.update(cx, |tag_list, cx| {
+            let delegate = &tag_list.picker.read(cx).delegate;
+            assert!(
+                !delegate
+                    .matches
+                    .iter()
+                    .any(|entry| matches!(entry, TagEntry::NewTag { .. }))
+            );
+        });
+
+        matches("v2 beta", cx).await;
+        tag_list.update(cx, |tag_list, cx| {
+            let delegate = &tag_list.picker.read(cx).delegate;
+            assert_eq!(
+                delegate.matches.first(),
+                Some(&TagEntry::NewTag {
+                    name: "v2-beta".into()
+                })
+            );
+        });
+    }
+}
@@ -0,0 +1,280 @@
+use std::ops::Range;
+
+use crate::{Bounds, FocusHandle, FocusId, Pixels, SharedString};
+
+/// The role an element plays, as reported to assistive technologies such as screen readers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessibilityRole {
+    /// The root of a window's accessibility tree.
+    Window,
+    /// A container that groups related elements.
+    Group,
+    /// An element that performs an action when clicked.
+    Button,
+    /// An element that navigates somewhere when clicked.
+    Link,
+    /// A non-editable run of text.
+    StaticText,
+    /// An editable text field, such as an editor.
+    TextInput,
+    /// A list of items.
+    List,
+    /// An item in a [`AccessibilityRole::List`].
+    ListItem,
+}
+
+/// A node in a window's accessibility tree, describing an element to assistive technologies.
+///
+/// Elements publish nodes while painting with [`crate::Window::with_accessibility_node`]. The tree
+/// for the last rendered frame is sent to the platform window, and can be inspected in tests with
+/// [`crate::VisualTestContext::accessibility_tree`]. Only the Linux platform publishes the tree to
+/// assistive technologies so far; macOS and Windows don't support them yet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityNode {
+    /// The role of the element.
+    pub role: AccessibilityRole,
+    /// The name announced for the element, such as a button's label.
+    pub label: Option<SharedString>,
+    /// The element's current value, such as the contents of a text input.
+    pub value: Option<SharedString>,
+    /// Whether the element holds keyboard focus.
+    pub focused: bool,
+    /// The bounds of the element in window coordinates.
+    pub bounds: Bounds<Pixels>,
+    /// The selected byte range within [`Self::value`]. An empty range is the cursor position.
+    pub text_selection: Option<Range<usize>>,
+    /// The nodes of the elements painted within this element.
+    pub children: Vec<AccessibilityNode>,
+    focus_id: Option<FocusId>,
+}
+
+impl AccessibilityNode {
+    /// Create a node with the given role and no label, value or children.
+    pub fn new(role: AccessibilityRole) -> Self {
+        Self {
+            role,
+            label: None,
+            value: None,
+            focused: false,
+            bounds: Bounds::default(),
+            text_selection: None,
+            children: Vec::new(),
+            focus_id: None,
+        }
+    }
+
+    /// Set the name announced for this node.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the current value of this node.
+    pub fn value(mut self, value: impl Into<SharedString>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Set the bounds of this node in window coordinates.
+    pub fn bounds(mut self, bounds: Bounds<Pixels>) -> Self {
+        self.bounds = bounds;
+        self
+    }
+
+    /// Set the selected byte range within this node's value.
+    pub fn text_selection(mut self, selection: Range<usize>) -> Self {
+        self.text_selection = Some(selection);
+        self
+    }
+
+    /// Mark this node as focused whenever the given focus handle is focused.
+    pub fn focus_handle(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_id = Some(focus_handle.id);
+        self
+    }
+
+    /// Returns the first node in this subtree, in depth-first order, that matches the predicate.
+    pub fn find(&self, predicate: impl Fn(&AccessibilityNode) -> bool) -> Option<&Self> {
+        self.find_inner(&predicate)
+    }
+
+    fn find_inner(&self, predicate: &dyn Fn(&AccessibilityNode) -> bool) -> Option<&Self> {
+        if predicate(self) {
+            return Some(self);
+        }
+        self.children
+            .iter()
+            .find_map(|child| child.find_inner(predicate))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum AccessibilityOperation {
+    Push(AccessibilityNode),
+    Pop,
+}
+
+/// Records the accessibility nodes published while painting a frame, so that they can be
+/// replayed when views are reused from the previous frame.
+#[derive(Debug, Default)]
+pub(crate) struct AccessibilityTreeBuilder {
+    pub(crate) operations: Vec<AccessibilityOperation>,
+}
+
+impl AccessibilityTreeBuilder {
+    pub fn push(&mut self, node: AccessibilityNode) {
+        self.operations.push(AccessibilityOperation::Push(node));
+    }
+
+    pub fn pop(&mut self) {
+        self.operations.push(AccessibilityOperation::Pop);
+    }
+
+    pub fn clear(&mut self) {
+        self.operations.clear();
+    }
+
+    pub fn replay(&mut self, operations: &[AccessibilityOperation]) {
+        self.operations.extend_from_slice(operations);
+    }
+
+    pub fn paint_index(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Assembles the recorded nodes into a tree rooted at a [`AccessibilityRole::Window`] node.
+    pub fn build(&self, bounds: Bounds<Pixels>, focus: Option<FocusId>) -> AccessibilityNode {
+        let mut stack = vec![AccessibilityNode::new(AccessibilityRole::Window).bounds(bounds)];
+        for operation in &self.operations {
+            match operation {
+                AccessibilityOperation::Push(node) => {
+                    let mut node = node.clone();
+                    node.focused = node.focus_id.is_some() && node.focus_id == focus;
+                    stack.push(node);
+                }
+                AccessibilityOperation::Pop => {
+                    if stack.len() > 1 {
+                        let node = stack.pop().unwrap();
+                        stack.last_mut().unwrap().children.push(node);
+                    }
+                }
+            }
+        }
+        while stack.len() > 1 {
+            let node = stack.pop().unwrap();
+            stack.last_mut().unwrap().children.push(node);
+        }
+        stack.pop().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Context, IntoElement, Render, TestAppContext, Window, div, point, prelude::*, px, size,
+        uniform_list,
+    };
+
+    #[test]
+    fn test_build_accessibility_tree() {
+        let mut builder = AccessibilityTreeBuilder::default();
+        builder.push(AccessibilityNode::new(AccessibilityRole::List));
+        builder.push(AccessibilityNode::new(AccessibilityRole::ListItem).label("one"));
+        builder.pop();
+        builder.push(AccessibilityNode::new(AccessibilityRole::ListItem).label("two"));
+        builder.pop();
+        builder.pop();
+        builder.push(AccessibilityNode::new(AccessibilityRole::Button).label("OK"));
+        builder.pop();
+
+        let bounds = Bounds::new(point(px(0.), px(0.)), size(px(100.), px(100.)));
+        let tree = builder.build(bounds, None);
+        assert_eq!(tree.role, AccessibilityRole::Window);
+        assert_eq!(tree.bounds, bounds);
+        assert_eq!(
+            tree.children
+                .iter()
+                .map(|child| child.role)
+                .collect::<Vec<_>>(),
+            [AccessibilityRole::List, AccessibilityRole::Button]
+        );
+        assert_eq!(
+            tree.children[0]
+                .children
+                .iter()
+                .map(|child| child.label.as_deref().unwrap())
+                .collect::<Vec<_>>(),
+            ["one", "two"]
+        );
+        assert_eq!(
+            tree.find(|node| node.role == AccessibilityRole::Button)
+                .and_then(|node| node.label.as_deref()),
+            Some("OK")
+        );
+    }
+
+    #[crate::test]
+    fn test_window_accessibility_tree(cx: &mut TestAppContext) {
+        struct TestView {
+            focus_handle: FocusHandle,
+        }
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .size_full()
+                    .child(
+                        div()
+                            .id("save")
+                            .track_focus(&self.focus_handle)
+                            .accessibility_role(AccessibilityRole::Button)
+                            .accessibility_label("Save")
+                            .child("Save"),
+                    )
+                    .child(
+                        uniform_list("entries", 3, |range, _, _| {
+                            range
+                                .map(|ix| div().h(px(20.)).child(format!("Item {ix}")))
+                                .collect()
+                        })
+                        .h(px(100.)),
+                    )
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|window, cx| {
+            let focus_handle = cx.focus_handle();
+            window.focus(&focus_handle, cx);
+            TestView { focus_handle }
+        });
+        cx.run_until_parked();
+
+        let tree = cx.accessibility_tree().unwrap();
+        assert_eq!(tree.role, AccessibilityRole::Window);
+
+        let button = tree
+            .find(|node| node.role == AccessibilityRole::Button)
+            .unwrap();
+        assert_eq!(button.label.as_deref(), Some("Save"));
+        assert!(button.focused);
+        assert_eq!(button.children.len(), 1);
+        assert_eq!(button.children[0].role, AccessibilityRole::StaticText);
+
+        let list = tree
+            .find(|node| node.role == AccessibilityRole::List)
+            .unwrap();
+        assert_eq!(
+            list.children
+                .iter()
+                .map(|item| {
+                    assert_eq!(item.role, AccessibilityRole::ListItem);
+                    item.find(|node| node.role == AccessibilityRole::StaticText)
+                        .and_then(|node| node.label.clone())
+                        .unwrap()
+                })
+                .collect::<Vec<_>>(),
+            ["Item 0", "Item 1", "Item 2"]
+        );
+    }
+}
@@ -16,14 +16,14 @@
 //! constructed by combining these two systems into an all-in-one element.
 
 use crate::{
-    AbsoluteLength, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, App, Bounds, ClickEvent,
-    DispatchPhase, Display, Element, ElementId, Entity, FocusHandle, Global, GlobalElementId,
-    Hitbox, HitboxBehavior, HitboxId, InspectorElementId, IntoElement, IsZero, KeyContext,
-    KeyDownEvent, KeyUpEvent, KeyboardButton, KeyboardClickEvent, LayoutId, ModifiersChangedEvent,
-    MouseButton, MouseClickEvent, MouseDownEvent, MouseMoveEvent, MousePressureEvent, MouseUpEvent,
-    Overflow, ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size, Style,
-    StyleRefinement, Styled, Task, TooltipId, Visibility, Window, WindowControlArea, point, px,
-    size,
+    AbsoluteLength, AccessibilityNode, AccessibilityRole, Action, AnyDrag, AnyElement, AnyTooltip,
+    AnyView, App, Bounds, ClickEvent, DispatchPhase, Display, Element, ElementId, Entity,
+    FocusHandle, Global, GlobalElementId, Hitbox, HitboxBehavior, HitboxId, InspectorElementId,
+    IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, KeyboardButton, KeyboardClickEvent,
+    LayoutId, ModifiersChangedEvent, MouseButton, MouseClickEvent, MouseDownEvent, MouseMoveEvent,
+    MousePressureEvent, MouseUpEvent, Overflow, ParentElement, Pixels, Point, Render,
+    ScrollWheelEvent, SharedString, Size, Style, StyleRefinement, Styled, Task, TooltipId,
+    Visibility, Window, WindowControlArea, point, px, size,
 };
 use collections::HashMap;
 use refineable::Refineable;
@@ -699,6 +699,26 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Publish this element to the window's accessibility tree with the given role.
+    /// Accessibility nodes published by this element's children become children of its node.
+    fn accessibility_role(mut self, role: AccessibilityRole) -> Self {
+        self.interactivity().accessibility_role = Some(role);
+        self
+    }
+
+    /// Set the name assistive technologies announce for this element, such as a button's label.
+    /// Elements with a label but no role are published as a [`AccessibilityRole::Group`].
+    fn accessibility_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.interactivity().accessibility_label = Some(label.into());
+        self
+    }
+
+    /// Set the value assistive technologies report for this element.
+    fn accessibility_value(mut self, value: impl Into<SharedString>) -> Self {
+        self.interactivity().accessibility_value = Some(value.into());
+        self
+    }
+
     /// Set the keymap context for this element. This will be used to determine
     /// which action to dispatch from the keymap.
     fn key_context<C, E>(mut self, key_context: C) -> Self
@@ -1660,6 +1680,9 @@ pub struct Interactivity {
     pub(crate) tab_index: Option<isize>,
     pub(crate) tab_group: bool,
     pub(crate) tab_stop: bool,
+    pub(crate) accessibility_role: Option<AccessibilityRole>,
+    pub(crate) accessibility_label: Option<SharedString>,
+    pub(crate) accessibility_value: Option<SharedString>,
 
     #[cfg(any(feature = "inspector", debug_assertions))]
     pub(crate) source_location: Option<&'static core::panic::Location<'static>>,
@@ -1960,6 +1983,15 @@ impl Interactivity {
                     window.next_frame.tab_stops.insert(focus_handle);
                 }
 
+                let accessibility_node = window
+                    .is_accessibility_enabled()
+                    .then(|| self.accessibility_node(bounds))
+                    .flatten();
+                let has_accessibility_node = accessibility_node.is_some();
+                if let Some(node) = accessibility_node {
+                    window.next_frame.accessibility.push(node);
+                }
+
                 window.with_element_opacity(style.opacity, |window| {
                     style.paint(bounds, window, cx, |window: &mut Window, cx: &mut App| {
                         window.with_text_style(style.text_style().cloned(), |window| {
@@ -2025,11 +2057,30 @@ impl Interactivity {
                     });
                 });
 
+                if has_accessibility_node {
+                    window.next_frame.accessibility.pop();
+                }
+
                 ((), element_state)
             },
         );
     }
 
+    fn accessibility_node(&self, bounds: Bounds<Pixels>) -> Option<AccessibilityNode> {
+        let role = self.accessibility_role.or_else(|| {
+            self.accessibility_label
+                .is_some()
+                .then_some(AccessibilityRole::Group)
+        })?;
+        let mut node = AccessibilityNode::new(role).bounds(bounds);
+        node.label = self.accessibility_label.clone();
+        node.value = self.accessibility_value.clone();
+        if let Some(focus_handle) = &self.tracked_focus_handle {
+            node = node.focus_handle(focus_handle);
+        }
+        Some(node)
+    }
+
     #[cfg(debug_assertions)]
     fn paint_debug_info(
         &self,
@@ -1,9 +1,10 @@
 use crate::{
-    ActiveTooltip, AnyView, App, Bounds, DispatchPhase, Element, ElementId, GlobalElementId,
-    HighlightStyle, Hitbox, HitboxBehavior, InspectorElementId, IntoElement, LayoutId,
-    MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, SharedString, Size, TextOverflow,
-    TextRun, TextStyle, TooltipId, TruncateFrom, WhiteSpace, Window, WrappedLine,
-    WrappedLineLayout, register_tooltip_mouse_handlers, set_tooltip_on_window,
+    AccessibilityNode, AccessibilityRole, ActiveTooltip, AnyView, App, Bounds, DispatchPhase,
+    Element, ElementId, GlobalElementId, HighlightStyle, Hitbox, HitboxBehavior,
+    InspectorElementId, IntoElement, LayoutId, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    Pixels, Point, SharedString, Size, TextOverflow, TextRun, TextStyle, TooltipId, TruncateFrom,
+    WhiteSpace, Window, WrappedLine, WrappedLineLayout, register_tooltip_mouse_handlers,
+    set_tooltip_on_window,
 };
 use anyhow::Context as _;
 use itertools::Itertools;
@@ -64,7 +65,7 @@ impl Element for &'static str {
         window: &mut Window,
         cx: &mut App,
     ) {
-        text_layout.paint(self, window, cx)
+        text_layout.paint(&SharedString::new_static(self), window, cx)
     }
 }
 
@@ -138,7 +139,7 @@ impl Element for SharedString {
         window: &mut Window,
         cx: &mut App,
     ) {
-        text_layout.paint(self.as_ref(), window, cx)
+        text_layout.paint(self, window, cx)
     }
 }
 
@@ -459,7 +460,7 @@ impl TextLayout {
         element_state.bounds = Some(bounds);
     }
 
-    fn paint(&self, text: &str, window: &mut Window, cx: &mut App) {
+    fn paint(&self, text: &SharedString, window: &mut Window, cx: &mut App) {
         let element_state = self.0.borrow();
         let element_state = element_state
             .as_ref()
@@ -470,6 +471,14 @@ impl TextLayout {
             .with_context(|| format!("prepaint has not been performed on {text}"))
             .unwrap();
 
+        if window.is_accessibility_enabled() {
+            window.insert_accessibility_node(
+                AccessibilityNode::new(AccessibilityRole::StaticText)
+                    .label(text.clone())
+                    .bounds(bounds),
+            );
+        }
+
         let line_height = element_state.line_height;
         let mut line_origin = bounds.origin;
         let text_style = window.text_style();
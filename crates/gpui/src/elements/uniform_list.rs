@@ -5,10 +5,10 @@
 //! elements with uniform height.
 
 use crate::{
-    AnyElement, App, AvailableSpace, Bounds, ContentMask, Element, ElementId, Entity,
-    GlobalElementId, Hitbox, InspectorElementId, InteractiveElement, Interactivity, IntoElement,
-    IsZero, LayoutId, ListSizingBehavior, Overflow, Pixels, Point, ScrollHandle, Size,
-    StyleRefinement, Styled, Window, point, size,
+    AccessibilityNode, AccessibilityRole, AnyElement, App, AvailableSpace, Bounds, ContentMask,
+    Element, ElementId, Entity, GlobalElementId, Hitbox, InspectorElementId, InteractiveElement,
+    Interactivity, IntoElement, IsZero, LayoutId, ListSizingBehavior, Overflow, Pixels, Point,
+    ScrollHandle, Size, StyleRefinement, Styled, Window, point, size,
 };
 use smallvec::SmallVec;
 use std::{cell::RefCell, cmp, ops::Range, rc::Rc, usize};
@@ -46,6 +46,7 @@ where
         interactivity: Interactivity {
             element_id: Some(id),
             base_style: Box::new(base_style),
+            accessibility_role: Some(AccessibilityRole::List),
             ..Interactivity::new()
         },
         scroll_handle: None,
@@ -71,6 +72,7 @@ pub struct UniformList {
 /// Frame state used by the [UniformList].
 pub struct UniformListFrameState {
     items: SmallVec<[AnyElement; 32]>,
+    item_bounds: SmallVec<[Bounds<Pixels>; 32]>,
     decorations: SmallVec<[AnyElement; 2]>,
 }
 
@@ -311,6 +313,7 @@ impl Element for UniformList {
             layout_id,
             UniformListFrameState {
                 items: SmallVec::new(),
+                item_bounds: SmallVec::new(),
                 decorations: SmallVec::new(),
             },
         )
@@ -496,6 +499,9 @@ impl Element for UniformList {
                             item.layout_as_root(available_space, window, cx);
                             item.prepaint_at(item_origin, window, cx);
                             frame_state.items.push(item);
+                            frame_state
+                                .item_bounds
+                                .push(Bounds::new(item_origin, size(available_width, item_height)));
                         }
 
                         let bounds =
@@ -544,8 +550,17 @@ impl Element for UniformList {
             window,
             cx,
             |_, window, cx| {
-                for item in &mut request_layout.items {
-                    item.paint(window, cx);
+                for (item, bounds) in request_layout
+                    .items
+                    .iter_mut()
+                    .zip(request_layout.item_bounds.iter().copied())
+                {
+                    let accessibility_node = window.is_accessibility_enabled().then(|| {
+                        AccessibilityNode::new(AccessibilityRole::ListItem).bounds(bounds)
+                    });
+                    window.with_accessibility_node(accessibility_node, |window| {
+                        item.paint(window, cx)
+                    });
                 }
                 for decoration in &mut request_layout.decorations {
                     decoration.paint(window, cx);
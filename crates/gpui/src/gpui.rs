@@ -6,6 +6,7 @@
 
 extern crate self as gpui;
 
+mod accessibility;
 #[macro_use]
 mod action;
 mod app;
@@ -70,6 +71,7 @@ mod seal {
     pub trait Sealed {}
 }
 
+pub use accessibility::*;
 pub use action::*;
 pub use anyhow::Result;
 pub use app::*;
@@ -0,0 +1,218 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use accesskit::{
+    ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler, Node, NodeId, Rect, Role,
+    Tree, TreeUpdate,
+};
+
+use crate::{AccessibilityNode, AccessibilityRole, Bounds, Pixels};
+
+const ROOT_ID: NodeId = NodeId(0);
+
+/// Publishes a window's accessibility tree over AT-SPI, where screen readers such as Orca read it.
+///
+/// The adapter only becomes enabled once an assistive technology connects, so windows don't
+/// build accessibility trees while nobody is listening.
+pub(crate) struct LinuxAccessibility {
+    adapter: accesskit_unix::Adapter,
+    status: Arc<AdapterStatus>,
+    last_update: Option<(AccessibilityNode, Bounds<Pixels>, f32)>,
+}
+
+/// Shared with the handlers, which the adapter calls from its own thread.
+#[derive(Default)]
+struct AdapterStatus {
+    enabled: AtomicBool,
+    needs_initial_tree: AtomicBool,
+}
+
+impl LinuxAccessibility {
+    pub fn new() -> Self {
+        let status = Arc::new(AdapterStatus::default());
+        let adapter = accesskit_unix::Adapter::new(
+            Activation(status.clone()),
+            Actions,
+            Deactivation(status.clone()),
+        );
+        Self {
+            adapter,
+            status,
+            last_update: None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.status.enabled.load(Ordering::Acquire)
+    }
+
+    pub fn set_window_focused(&mut self, focused: bool) {
+        self.adapter.update_window_focus_state(focused);
+    }
+
+    /// Sends the tree to the assistive technology, unless it's unchanged since the last update.
+    pub fn update(&mut self, tree: AccessibilityNode, window_bounds: Bounds<Pixels>, scale: f32) {
+        let update = (tree, window_bounds, scale);
+        let needs_initial_tree = self.status.needs_initial_tree.swap(false, Ordering::AcqRel);
+        if !needs_initial_tree && self.last_update.as_ref() == Some(&update) {
+            return;
+        }
+        let (tree, window_bounds, scale) = &update;
+
+        let window_rect = to_rect(*window_bounds, *scale);
+        self.adapter
+            .set_root_window_bounds(window_rect, window_rect);
+        self.adapter.update_if_active(|| tree_update(tree, *scale));
+        self.last_update = Some(update);
+    }
+}
+
+struct Activation(Arc<AdapterStatus>);
+
+impl ActivationHandler for Activation {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        // The tree is built while the window draws its next frame, and then sent as the initial
+        // tree by `LinuxAccessibility::update`.
+        self.0.needs_initial_tree.store(true, Ordering::Release);
+        self.0.enabled.store(true, Ordering::Release);
+        None
+    }
+}
+
+struct Deactivation(Arc<AdapterStatus>);
+
+impl DeactivationHandler for Deactivation {
+    fn deactivate_accessibility(&mut self) {
+        self.0.enabled.store(false, Ordering::Release);
+    }
+}
+
+struct Actions;
+
+impl ActionHandler for Actions {
+    fn do_action(&mut self, request: ActionRequest) {
+        log::debug!("ignoring accessibility action request {request:?}");
+    }
+}
+
+fn tree_update(root: &AccessibilityNode, scale: f32) -> TreeUpdate {
+    let mut nodes = Vec::new();
+    let mut focus = ROOT_ID;
+    push_node(root, ROOT_ID, scale, &mut nodes, &mut focus);
+    let mut tree = Tree::new(ROOT_ID);
+    tree.toolkit_name = Some("GPUI".into());
+    TreeUpdate {
+        nodes,
+        tree: Some(tree),
+        focus,
+    }
+}
+
+fn push_node(
+    node: &AccessibilityNode,
+    id: NodeId,
+    scale: f32,
+    nodes: &mut Vec<(NodeId, Node)>,
+    focus: &mut NodeId,
+) {
+    let child_ids = node
+        .children
+        .iter()
+        .enumerate()
+        .map(|(ix, child)| child_id(id, ix, child.role))
+        .collect::<Vec<_>>();
+    for (child, child_id) in node.children.iter().zip(&child_ids) {
+        push_node(child, *child_id, scale, nodes, focus);
+    }
+
+    let mut platform_node = Node::new(role(node.role));
+    if let Some(label) = &node.label {
+        platform_node.set_label(label.as_ref());
+    }
+    // Text selections aren't exposed yet, since AccessKit expects them to refer to text run
+    // nodes with per-character positions.
+    if let Some(value) = &node.value {
+        platform_node.set_value(value.as_ref());
+    }
+    platform_node.set_bounds(to_rect(node.bounds, scale));
+    platform_node.set_children(child_ids);
+    if node.focused {
+        *focus = id;
+    }
+    nodes.push((id, platform_node));
+}
+
+/// Derives a node's ID from its position in the tree, so that IDs are stable across frames as
+/// long as the surrounding elements don't change.
+fn child_id(parent: NodeId, index: usize, role: AccessibilityRole) -> NodeId {
+    let mut hasher = DefaultHasher::new();
+    (parent.0, index, role).hash(&mut hasher);
+    NodeId(hasher.finish())
+}
+
+fn role(role: AccessibilityRole) -> Role {
+    match role {
+        AccessibilityRole::Window => Role::Window,
+        AccessibilityRole::Group => Role::Group,
+        AccessibilityRole::Button => Role::Button,
+        AccessibilityRole::Link => Role::Link,
+        AccessibilityRole::StaticText => Role::Label,
+        AccessibilityRole::TextInput => Role::MultilineTextInput,
+        AccessibilityRole::List => Role::List,
+        AccessibilityRole::ListItem => Role::ListItem,
+    }
+}
+
+fn to_rect(bounds: Bounds<Pixels>, scale: f32) -> Rect {
+    let bounds = bounds.scale(scale);
+    let origin = bounds.origin;
+    let bottom_right = bounds.bottom_right();
+    Rect::new(
+        origin.x.0 as f64,
+        origin.y.0 as f64,
+        bottom_right.x.0 as f64,
+        bottom_right.y.0 as f64,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{point, px, size};
+
+    #[test]
+    fn test_tree_update() {
+        let bounds = Bounds::new(point(px(10.), px(20.)), size(px(100.), px(50.)));
+        let mut root = AccessibilityNode::new(AccessibilityRole::Window).bounds(bounds);
+        let mut button = AccessibilityNode::new(AccessibilityRole::Button)
+            .label("Save")
+            .bounds(bounds);
+        button.focused = true;
+        root.children.push(button);
+        root.children
+            .push(AccessibilityNode::new(AccessibilityRole::StaticText).label("Ready"));
+
+        let update = tree_update(&root, 2.);
+        assert_eq!(update.tree.unwrap().root, ROOT_ID);
+        assert_eq!(update.nodes.len(), 3);
+
+        let (root_id, root_node) = update.nodes.last().unwrap();
+        assert_eq!(*root_id, ROOT_ID);
+        assert_eq!(root_node.role(), Role::Window);
+        assert_eq!(root_node.children().len(), 2);
+
+        let button_id = root_node.children()[0];
+        assert_eq!(update.focus, button_id);
+        let (_, button_node) = update
+            .nodes
+            .iter()
+            .find(|(id, _)| *id == button_id)
+            .unwrap();
+        assert_eq!(button_node.label(), Some("Save"));
+        assert_eq!(button_node.bounds(), Some(Rect::new(20., 40., 220., 140.)));
+
+        // IDs only depend on the position in the tree, so they are the same in the next frame.
+        assert_eq!(tree_update(&root, 2.).focus, button_id);
+    }
+}
@@ -25,10 +25,11 @@ use wayland_protocols_plasma::blur::client::org_kde_kwin_blur;
 use wayland_protocols_wlr::layer_shell::v1::client::zwlr_layer_surface_v1;
 
 use crate::{
-    AnyWindowHandle, Bounds, Decorations, DevicePixels, Globals, GpuSpecs, Modifiers, Output,
-    Pixels, PlatformDisplay, PlatformInput, Point, PromptButton, PromptLevel, RequestFrameOptions,
-    ResizeEdge, Size, Tiling, WaylandClientStatePtr, WindowAppearance, WindowBackgroundAppearance,
-    WindowBounds, WindowControlArea, WindowControls, WindowDecorations, WindowParams, get_window,
+    AccessibilityNode, AnyWindowHandle, Bounds, Decorations, DevicePixels, Globals, GpuSpecs,
+    LinuxAccessibility, Modifiers, Output, Pixels, PlatformDisplay, PlatformInput, Point,
+    PromptButton, PromptLevel, RequestFrameOptions, ResizeEdge, Size, Tiling,
+    WaylandClientStatePtr, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    WindowControlArea, WindowControls, WindowDecorations, WindowParams, get_window,
     layer_shell::LayerShellNotSupportedError, px, size,
 };
 use crate::{
@@ -121,6 +122,7 @@ pub struct WaylandWindowState {
     in_progress_window_controls: Option<WindowControls>,
     window_controls: WindowControls,
     client_inset: Option<Pixels>,
+    accessibility: LinuxAccessibility,
 }
 
 pub enum WaylandSurfaceState {
@@ -381,6 +383,7 @@ impl WaylandWindowState {
             in_progress_window_controls: None,
             window_controls: WindowControls::default(),
             client_inset: None,
+            accessibility: LinuxAccessibility::new(),
         })
     }
 
@@ -991,7 +994,10 @@ impl WaylandWindowStatePtr {
     }
 
     pub fn set_focused(&self, focus: bool) {
-        self.state.borrow_mut().active = focus;
+        let mut state = self.state.borrow_mut();
+        state.active = focus;
+        state.accessibility.set_window_focused(focus);
+        drop(state);
         if let Some(ref mut fun) = self.callbacks.borrow_mut().active_status_change {
             fun(focus);
         }
@@ -1399,6 +1405,16 @@ impl PlatformWindow for WaylandWindow {
         state.client.update_ime_position(bounds);
     }
 
+    fn is_accessibility_enabled(&self) -> bool {
+        self.borrow().accessibility.is_enabled()
+    }
+
+    fn update_accessibility_tree(&self, tree: AccessibilityNode) {
+        let mut state = self.borrow_mut();
+        let (bounds, scale) = (state.bounds, state.scale);
+        state.accessibility.update(tree, bounds, scale);
+    }
+
     fn gpu_specs(&self) -> Option<GpuSpecs> {
         self.borrow().renderer.gpu_specs().into()
     }
@@ -3,11 +3,12 @@ use x11rb::connection::RequestConnection;
 
 use crate::platform::wgpu::{WgpuContext, WgpuRenderer, WgpuSurfaceConfig};
 use crate::{
-    AnyWindowHandle, Bounds, Decorations, DevicePixels, ForegroundExecutor, GpuSpecs, Modifiers,
-    Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow,
-    Point, PromptButton, PromptLevel, RequestFrameOptions, ResizeEdge, ScaledPixels, Scene, Size,
-    Tiling, WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControlArea,
-    WindowDecorations, WindowKind, WindowParams, X11ClientStatePtr, px,
+    AccessibilityNode, AnyWindowHandle, Bounds, Decorations, DevicePixels, ForegroundExecutor,
+    GpuSpecs, LinuxAccessibility, Modifiers, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
+    PlatformInputHandler, PlatformWindow, Point, PromptButton, PromptLevel, RequestFrameOptions,
+    ResizeEdge, ScaledPixels, Scene, Size, Tiling, WindowAppearance, WindowBackgroundAppearance,
+    WindowBounds, WindowControlArea, WindowDecorations, WindowKind, WindowParams,
+    X11ClientStatePtr, px,
 };
 
 use collections::FxHashSet;
@@ -281,6 +282,7 @@ pub struct X11WindowState {
     edge_constraints: Option<EdgeConstraints>,
     pub handle: AnyWindowHandle,
     last_insets: [u32; 4],
+    accessibility: LinuxAccessibility,
 }
 
 impl X11WindowState {
@@ -729,6 +731,7 @@ impl X11WindowState {
                 edge_constraints: None,
                 counter_id: sync_request_counter,
                 last_sync_counter: None,
+                accessibility: LinuxAccessibility::new(),
             })
         });
 
@@ -1193,6 +1196,10 @@ impl X11WindowStatePtr {
     }
 
     pub fn set_active(&self, focus: bool) {
+        self.state
+            .borrow_mut()
+            .accessibility
+            .set_window_focused(focus);
         if let Some(ref mut fun) = self.callbacks.borrow_mut().active_status_change {
             fun(focus);
         }
@@ -1754,6 +1761,16 @@ impl PlatformWindow for X11Window {
         client.update_ime_position(bounds);
     }
 
+    fn is_accessibility_enabled(&self) -> bool {
+        self.0.state.borrow().accessibility.is_enabled()
+    }
+
+    fn update_accessibility_tree(&self, tree: AccessibilityNode) {
+        let mut state = self.0.state.borrow_mut();
+        let (bounds, scale_factor) = (state.bounds, state.scale_factor);
+        state.accessibility.update(tree, bounds, scale_factor);
+    }
+
     fn gpu_specs(&self) -> Option<GpuSpecs> {
         self.0.state.borrow().renderer.gpu_specs().into()
     }
@@ -1,23 +1,24 @@
 #[cfg(any(feature = "inspector", debug_assertions))]
 use crate::Inspector;
 use crate::{
-    Action, AnyDrag, AnyElement, AnyImageCache, AnyTooltip, AnyView, App, AppContext, Arena, Asset,
-    AsyncWindowContext, AvailableSpace, Background, BorderStyle, Bounds, BoxShadow, Capslock,
-    Context, Corners, CursorStyle, Decorations, DevicePixels, DispatchActionListener,
-    DispatchNodeId, DispatchTree, DisplayId, Edges, Effect, Entity, EntityId, EventEmitter,
-    FileDropEvent, FontId, Global, GlobalElementId, GlyphId, GpuSpecs, Hsla, InputHandler, IsZero,
-    KeyBinding, KeyContext, KeyDownEvent, KeyEvent, Keystroke, KeystrokeEvent, LayoutId,
-    LineLayoutIndex, Modifiers, ModifiersChangedEvent, MonochromeSprite, MouseButton, MouseEvent,
-    MouseMoveEvent, MouseUpEvent, Path, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
-    PlatformInputHandler, PlatformWindow, Point, PolychromeSprite, Priority, PromptButton,
-    PromptLevel, Quad, Render, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams,
-    Replay, ResizeEdge, SMOOTH_SVG_SCALE_FACTOR, SUBPIXEL_VARIANTS_X, SUBPIXEL_VARIANTS_Y,
-    ScaledPixels, Scene, Shadow, SharedString, Size, StrikethroughStyle, Style, SubpixelSprite,
-    SubscriberSet, Subscription, SystemWindowTab, SystemWindowTabController, TabStopMap,
-    TaffyLayoutEngine, Task, TextRenderingMode, TextStyle, TextStyleRefinement, ThermalState,
-    TransformationMatrix, Underline, UnderlineStyle, WindowAppearance, WindowBackgroundAppearance,
-    WindowBounds, WindowControls, WindowDecorations, WindowOptions, WindowParams, WindowTextSystem,
-    point, prelude::*, px, rems, size, transparent_black,
+    AccessibilityNode, AccessibilityTreeBuilder, Action, AnyDrag, AnyElement, AnyImageCache,
+    AnyTooltip, AnyView, App, AppContext, Arena, Asset, AsyncWindowContext, AvailableSpace,
+    Background, BorderStyle, Bounds, BoxShadow, Capslock, Context, Corners, CursorStyle,
+    Decorations, DevicePixels, DispatchActionListener, DispatchNodeId, DispatchTree, DisplayId,
+    Edges, Effect, Entity, EntityId, EventEmitter, FileDropEvent, FontId, Global, GlobalElementId,
+    GlyphId, GpuSpecs, Hsla, InputHandler, IsZero, KeyBinding, KeyContext, KeyDownEvent, KeyEvent,
+    Keystroke, KeystrokeEvent, LayoutId, LineLayoutIndex, Modifiers, ModifiersChangedEvent,
+    MonochromeSprite, MouseButton, MouseEvent, MouseMoveEvent, MouseUpEvent, Path, Pixels,
+    PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow, Point,
+    PolychromeSprite, Priority, PromptButton, PromptLevel, Quad, Render, RenderGlyphParams,
+    RenderImage, RenderImageParams, RenderSvgParams, Replay, ResizeEdge, SMOOTH_SVG_SCALE_FACTOR,
+    SUBPIXEL_VARIANTS_X, SUBPIXEL_VARIANTS_Y, ScaledPixels, Scene, Shadow, SharedString, Size,
+    StrikethroughStyle, Style, SubpixelSprite, SubscriberSet, Subscription, SystemWindowTab,
+    SystemWindowTabController, TabStopMap, TaffyLayoutEngine, Task, TextRenderingMode, TextStyle,
+    TextStyleRefinement, ThermalState, TransformationMatrix, Underline, UnderlineStyle,
+    WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControls, WindowDecorations,
+    WindowOptions, WindowParams, WindowTextSystem, point, prelude::*, px, rems, size,
+    transparent_black,
 };
 use anyhow::{Context as _, Result, anyhow};
 use collections::{FxHashMap, FxHashSet};
@@ -752,6 +753,7 @@ pub(crate) struct Frame {
     #[cfg(any(feature = "inspector", debug_assertions))]
     pub(crate) inspector_hitboxes: FxHashMap<HitboxId, crate::InspectorElementId>,
     pub(crate) tab_stops: TabStopMap,
+    pub(crate) accessibility: AccessibilityTreeBuilder,
 }
 
 #[derive(Clone, Default)]
@@ -772,6 +774,7 @@ pub(crate) struct PaintIndex {
     cursor_styles_index: usize,
     accessed_element_states_index: usize,
     tab_handle_index: usize,
+    accessibility_index: usize,
     line_layout_index: LineLayoutIndex,
 }
 
@@ -801,6 +804,7 @@ impl Frame {
             #[cfg(any(feature = "inspector", debug_assertions))]
             inspector_hitboxes: FxHashMap::default(),
             tab_stops: TabStopMap::default(),
+            accessibility: AccessibilityTreeBuilder::default(),
         }
     }
 
@@ -817,6 +821,7 @@ impl Frame {
         self.window_control_hitboxes.clear();
         self.deferred_draws.clear();
         self.tab_stops.clear();
+        self.accessibility.clear();
         self.focus = None;
 
         #[cfg(any(feature = "inspector", debug_assertions))]
@@ -941,6 +946,7 @@ pub struct Window {
     pub(crate) input_rate_tracker: Rc<RefCell<InputRateTracker>>,
     last_input_modality: InputModality,
     pub(crate) refreshing: bool,
+    accessibility_enabled: bool,
     pub(crate) activation_observers: SubscriberSet<(), AnyObserver>,
     pub(crate) focus: Option<FocusId>,
     focus_enabled: bool,
@@ -1236,6 +1242,13 @@ impl Window {
                 handle
                     .update(&mut cx, |_, window, _| {
                         window.complete_frame();
+                        // Assistive technologies can connect at any time, and need a tree as
+                        // soon as they do.
+                        if window.platform_window.is_accessibility_enabled()
+                            != window.accessibility_enabled
+                        {
+                            window.refresh();
+                        }
                     })
                     .log_err();
             }
@@ -1424,6 +1437,7 @@ impl Window {
             input_rate_tracker,
             last_input_modality: InputModality::Mouse,
             refreshing: false,
+            accessibility_enabled: false,
             activation_observers: SubscriberSet::new(),
             focus: None,
             focus_enabled: true,
@@ -2171,6 +2185,13 @@ impl Window {
         self.invalidator.set_dirty(false);
         self.requested_autoscroll = None;
 
+        let accessibility_enabled = self.platform_window.is_accessibility_enabled();
+        if accessibility_enabled && !self.accessibility_enabled {
+            // Views reused from the previous frame didn't publish any accessibility nodes.
+            self.refreshing = true;
+        }
+        self.accessibility_enabled = accessibility_enabled;
+
         // Restore the previously-used input handler.
         if let Some(input_handler) = self.platform_window.take_input_handler() {
             self.rendered_frame.input_handlers.push(Some(input_handler));
@@ -2225,6 +2246,11 @@ impl Window {
                 .retain(&(), |listener| listener(&event, self, cx));
         }
 
+        if self.accessibility_enabled {
+            self.platform_window
+                .update_accessibility_tree(self.accessibility_tree());
+        }
+
         debug_assert!(self.rendered_entity_stack.is_empty());
         self.record_entities_accessed(cx);
         self.reset_cursor_style(cx);
@@ -2549,6 +2575,7 @@ impl Window {
             cursor_styles_index: self.next_frame.cursor_styles.len(),
             accessed_element_states_index: self.next_frame.accessed_element_states.len(),
             tab_handle_index: self.next_frame.tab_stops.paint_index(),
+            accessibility_index: self.next_frame.accessibility.paint_index(),
             line_layout_index: self.text_system.layout_index(),
         }
     }
@@ -2582,6 +2609,10 @@ impl Window {
             &self.rendered_frame.tab_stops.insertion_history
                 [range.start.tab_handle_index..range.end.tab_handle_index],
         );
+        self.next_frame.accessibility.replay(
+            &self.rendered_frame.accessibility.operations
+                [range.start.accessibility_index..range.end.accessibility_index],
+        );
 
         self.text_system
             .reuse_layouts(range.start.line_layout_index..range.end.line_layout_index);
@@ -3010,6 +3041,46 @@ impl Window {
         }
     }
 
+    /// Returns whether an assistive technology is listening to this window's accessibility tree.
+    /// Elements should skip building accessibility nodes when it isn't.
+    pub fn is_accessibility_enabled(&self) -> bool {
+        self.accessibility_enabled
+    }
+
+    /// Executes the given closure with the given node as the parent of any accessibility nodes
+    /// published within it. If no node is given, the closure is executed as-is.
+    /// This method should only be called as part of the paint phase of element drawing.
+    #[inline]
+    pub fn with_accessibility_node<R>(
+        &mut self,
+        node: Option<AccessibilityNode>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        if let Some(node) = node {
+            self.invalidator.debug_assert_paint();
+            self.next_frame.accessibility.push(node);
+            let result = f(self);
+            self.next_frame.accessibility.pop();
+            result
+        } else {
+            f(self)
+        }
+    }
+
+    /// Publishes a node without children to the window's accessibility tree.
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn insert_accessibility_node(&mut self, node: AccessibilityNode) {
+        self.with_accessibility_node(Some(node), |_| {});
+    }
+
+    /// Returns the accessibility tree of the last rendered frame.
+    pub fn accessibility_tree(&self) -> AccessibilityNode {
+        self.rendered_frame.accessibility.build(
+            Bounds::new(Point::default(), self.viewport_size),
+            self.focus,
+        )
+    }
+
     /// Defers the drawing of the given element, scheduling it to be painted on top of the currently-drawn tree
     /// at a later time. The `priority` parameter determines the drawing order relative to other deferred elements,
     /// with higher values being drawn on top.
@@ -0,0 +1,581 @@
+use std::ops::Range;
+
+use anyhow::Result;
+use editor::{Editor, SelectionEffects, scroll::Autoscroll};
+use gpui::{
+    Entity, EventEmitter, FocusHandle, Focusable, ScrollStrategy, Task, UniformListScrollHandle,
+    WeakEntity, uniform_list,
+};
+use language::{Anchor, Buffer, ToPoint as _};
+use project::{CallHierarchyItem, Location, Project, TypeHierarchyItem};
+use ui::{ListItem, ListItemSpacing, WithScrollbar as _, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    SplitDirection, Toast, Workspace,
+    item::{Item, ItemEvent},
+    notifications::NotificationId,
+};
+use zed_actions::{
+    call_hierarchy::{ShowIncomingCalls, ShowOutgoingCalls},
+    type_hierarchy::{ShowSubtypes, ShowSupertypes},
+};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ShowIncomingCalls, window, cx| {
+            show_hierarchy(workspace, HierarchyKind::IncomingCalls, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowOutgoingCalls, window, cx| {
+            show_hierarchy(workspace, HierarchyKind::OutgoingCalls, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowSupertypes, window, cx| {
+            show_hierarchy(workspace, HierarchyKind::Supertypes, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowSubtypes, window, cx| {
+            show_hierarchy(workspace, HierarchyKind::Subtypes, window, cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyKind {
+    /// Expanding an entry shows the functions calling it.
+    IncomingCalls,
+    /// Expanding an entry shows the functions it calls.
+    OutgoingCalls,
+    /// Expanding an entry shows the types it extends or implements.
+    Supertypes,
+    /// Expanding an entry shows the types extending or implementing it.
+    Subtypes,
+}
+
+impl HierarchyKind {
+    fn is_call_hierarchy(self) -> bool {
+        matches!(self, Self::IncomingCalls | Self::OutgoingCalls)
+    }
+
+    fn prepare(
+        self,
+        project: &mut Project,
+        buffer: &Entity<Buffer>,
+        position: Anchor,
+        cx: &mut Context<Project>,
+    ) -> Task<Result<Vec<HierarchySymbol>>> {
+        if self.is_call_hierarchy() {
+            let items = project.prepare_call_hierarchy(buffer, position, cx);
+            cx.background_spawn(async move {
+                Ok(items
+                    .await?
+                    .into_iter()
+                    .map(HierarchySymbol::Call)
+                    .collect())
+            })
+        } else {
+            let items = project.prepare_type_hierarchy(buffer, position, cx);
+            cx.background_spawn(async move {
+                Ok(items
+                    .await?
+                    .into_iter()
+                    .map(HierarchySymbol::Type)
+                    .collect())
+            })
+        }
+    }
+
+    /// Queries the next level of the hierarchy, along with the call sites for call hierarchies.
+    fn children(
+        self,
+        symbol: &HierarchySymbol,
+        project: &mut Project,
+        cx: &mut Context<Project>,
+    ) -> Task<Result<Vec<(HierarchySymbol, Vec<Location>)>>> {
+        match (self, symbol) {
+            (Self::IncomingCalls | Self::OutgoingCalls, HierarchySymbol::Call(item)) => {
+                let calls = if self == Self::IncomingCalls {
+                    project.incoming_calls(item, cx)
+                } else {
+                    project.outgoing_calls(item, cx)
+                };
+                cx.background_spawn(async move {
+                    Ok(calls
+                        .await?
+                        .into_iter()
+                        .map(|call| (HierarchySymbol::Call(call.item), call.call_sites))
+                        .collect())
+                })
+            }
+            (Self::Supertypes | Self::Subtypes, HierarchySymbol::Type(item)) => {
+                let types = if self == Self::Supertypes {
+                    project.supertypes(item, cx)
+                } else {
+                    project.subtypes(item, cx)
+                };
+                cx.background_spawn(async move {
+                    Ok(types
+                        .await?
+                        .into_iter()
+                        .map(|item| (HierarchySymbol::Type(item), Vec::new()))
+                        .collect())
+                })
+            }
+            _ => Task::ready(Ok(Vec::new())),
+        }
+    }
+}
+
+fn show_hierarchy(
+    workspace: &mut Workspace,
+    kind: HierarchyKind,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some((buffer, position)) = editor.update(cx, |editor, cx| {
+        let head = editor.selections.newest_anchor().head();
+        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    }) else {
+        return;
+    };
+
+    let project = workspace.project().clone();
+    let prepare = project.update(cx, |project, cx| {
+        kind.prepare(project, &buffer, position, cx)
+    });
+    cx.spawn_in(window, async move |workspace, cx| {
+        let items = prepare.await?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            if items.is_empty() {
+                struct NoHierarchy;
+                let message = if kind.is_call_hierarchy() {
+                    "No call hierarchy available for the symbol under the cursor"
+                } else {
+                    "No type hierarchy available for the symbol under the cursor"
+                };
+                workspace.show_toast(
+                    Toast::new(NotificationId::unique::<NoHierarchy>(), message).autohide(),
+                    cx,
+                );
+                return;
+            }
+
+            let workspace_handle = workspace.weak_handle();
+            let view =
+                cx.new(|cx| HierarchyView::new(workspace_handle, project, kind, items, window, cx));
+            workspace.split_item(SplitDirection::Right, Box::new(view), window, cx);
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+/// A tree of callers or callees of a symbol, or of the supertypes or subtypes of a type,
+/// whose levels are loaded from the language server as they are expanded.
+pub struct HierarchyView {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    kind: HierarchyKind,
+    entries: Vec<HierarchyEntry>,
+    roots: Vec<usize>,
+    /// Indices into `entries`, in display order.
+    visible_entries: Vec<usize>,
+    selected_index: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+}
+
+enum HierarchySymbol {
+    Call(CallHierarchyItem),
+    Type(TypeHierarchyItem),
+}
+
+impl HierarchySymbol {
+    fn name(&self) -> &str {
+        match self {
+            Self::Call(item) => &item.name,
+            Self::Type(item) => &item.name,
+        }
+    }
+
+    fn detail(&self) -> Option<&String> {
+        match self {
+            Self::Call(item) => item.detail.as_ref(),
+            Self::Type(item) => item.detail.as_ref(),
+        }
+    }
+
+    fn location(&self) -> &Location {
+        match self {
+            Self::Call(item) => &item.location,
+            Self::Type(item) => &item.location,
+        }
+    }
+
+    fn selection_range(&self) -> &Range<Anchor> {
+        match self {
+            Self::Call(item) => &item.selection_range,
+            Self::Type(item) => &item.selection_range,
+        }
+    }
+}
+
+struct HierarchyEntry {
+    symbol: HierarchySymbol,
+    call_sites: Vec<Location>,
+    depth: usize,
+    expanded: bool,
+    children: Children,
+}
+
+enum Children {
+    Unresolved,
+    Loading(Task<()>),
+    Resolved(Vec<usize>),
+}
+
+impl HierarchyView {
+    pub(crate) fn new(
+        workspace: WeakEntity<Workspace>,
+        project: Entity<Project>,
+        kind: HierarchyKind,
+        items: Vec<HierarchySymbol>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            workspace,
+            project,
+            kind,
+            entries: Vec::new(),
+            roots: Vec::new(),
+            visible_entries: Vec::new(),
+            selected_index: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+        };
+        for symbol in items {
+            this.roots.push(this.entries.len());
+            this.entries.push(HierarchyEntry {
+                symbol,
+                call_sites: Vec::new(),
+                depth: 0,
+                expanded: false,
+                children: Children::Unresolved,
+            });
+        }
+        // Expand the roots right away, since showing the symbol on its own is not useful.
+        for root in this.roots.clone() {
+            this.toggle_expanded(root, cx);
+        }
+        this.selected_index = (!this.visible_entries.is_empty()).then_some(0);
+        this
+    }
+
+    fn toggle_expanded(&mut self, entry_ix: usize, cx: &mut Context<Self>) {
+        let entry = &mut self.entries[entry_ix];
+        entry.expanded = !entry.expanded;
+        if entry.expanded && matches!(entry.children, Children::Unresolved) {
+            let kind = self.kind;
+            let children = self.project.update(cx, |project, cx| {
+                kind.children(&self.entries[entry_ix].symbol, project, cx)
+            });
+            let task = cx.spawn(async move |this, cx| {
+                let children = children.await.log_err().unwrap_or_default();
+                this.update(cx, |this, cx| {
+                    this.insert_children(entry_ix, children);
+                    cx.notify();
+                })
+                .ok();
+            });
+            self.entries[entry_ix].children = Children::Loading(task);
+        }
+        self.update_visible_entries();
+        cx.notify();
+    }
+
+    fn insert_children(
+        &mut self,
+        parent_ix: usize,
+        symbols: Vec<(HierarchySymbol, Vec<Location>)>,
+    ) {
+        let depth = self.entries[parent_ix].depth + 1;
+        let mut children = Vec::with_capacity(symbols.len());
+        for (symbol, call_sites) in symbols {
+            children.push(self.entries.len());
+            self.entries.push(HierarchyEntry {
+                symbol,
+                call_sites,
+                depth,
+                expanded: false,
+                children: Children::Unresolved,
+            });
+        }
+        self.entries[parent_ix].children = Children::Resolved(children);
+        self.update_visible_entries();
+    }
+
+    fn update_visible_entries(&mut self) {
+        let selected_entry = self
+            .selected_index
+            .and_then(|ix| self.visible_entries.get(ix).copied());
+
+        self.visible_entries.clear();
+        let mut stack = self.roots.iter().rev().copied().collect::<Vec<_>>();
+        while let Some(entry_ix) = stack.pop() {
+            self.visible_entries.push(entry_ix);
+            let entry = &self.entries[entry_ix];
+            if entry.expanded
+                && let Children::Resolved(children) = &entry.children
+            {
+                stack.extend(children.iter().rev().copied());
+            }
+        }
+
+        self.selected_index = selected_entry
+            .and_then(|entry_ix| self.visible_entries.iter().position(|ix| *ix == entry_ix))
+            .or_else(|| (!self.visible_entries.is_empty()).then_some(0));
+    }
+
+    fn select_index(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.selected_index = Some(index);
+        self.scroll_handle
+            .scroll_to_item(index, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let next = self.selected_index.map_or(0, |ix| {
+            (ix + 1).min(self.visible_entries.len().saturating_sub(1))
+        });
+        self.select_index(next, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let previous = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_index(previous, cx);
+    }
+
+    fn expand_selected(
+        &mut self,
+        _: &menu::SelectChild,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry_ix) = self.selected_entry() else {
+            return;
+        };
+        if self.entries[entry_ix].expanded {
+            self.select_next(&menu::SelectNext, window, cx);
+        } else {
+            self.toggle_expanded(entry_ix, cx);
+        }
+    }
+
+    fn collapse_selected(
+        &mut self,
+        _: &menu::SelectParent,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry_ix) = self.selected_entry() else {
+            return;
+        };
+        if self.entries[entry_ix].expanded {
+            self.toggle_expanded(entry_ix, cx);
+        } else if let Some(parent_index) = self.parent_index(entry_ix) {
+            self.select_index(parent_index, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(entry_ix) = self.selected_entry() {
+            self.open_entry(entry_ix, window, cx);
+        }
+    }
+
+    fn selected_entry(&self) -> Option<usize> {
+        self.visible_entries.get(self.selected_index?).copied()
+    }
+
+    fn parent_index(&self, entry_ix: usize) -> Option<usize> {
+        let visible_ix = self.visible_entries.iter().position(|ix| *ix == entry_ix)?;
+        let depth = self.entries[entry_ix].depth;
+        self.visible_entries[..visible_ix]
+            .iter()
+            .rposition(|ix| self.entries[*ix].depth < depth)
+    }
+
+    /// Opens the entry's symbol, or the first call site when the entry is a call.
+    fn open_entry(&mut self, entry_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let entry = &self.entries[entry_ix];
+        let (buffer, position) = match (self.kind, entry.call_sites.first()) {
+            // Incoming call sites are inside the caller, so they're more useful to show than
+            // the caller's definition.
+            (HierarchyKind::IncomingCalls, Some(call_site)) => {
+                (call_site.buffer.clone(), call_site.range.start)
+            }
+            _ => (
+                entry.symbol.location().buffer.clone(),
+                entry.symbol.selection_range().start,
+            ),
+        };
+        let position = position.to_point(buffer.read(cx));
+
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let pane = workspace.adjacent_pane(window, cx);
+                let editor = workspace
+                    .open_project_item::<Editor>(pane, buffer, true, true, true, true, window, cx);
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(
+                        SelectionEffects::scroll(Autoscroll::center()),
+                        window,
+                        cx,
+                        |selections| selections.select_ranges([position..position]),
+                    );
+                });
+            })
+            .log_err();
+    }
+
+    fn render_entries(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<ListItem> {
+        range
+            .filter_map(|visible_ix| {
+                let entry_ix = *self.visible_entries.get(visible_ix)?;
+                let entry = &self.entries[entry_ix];
+                let is_leaf =
+                    matches!(&entry.children, Children::Resolved(children) if children.is_empty());
+                let is_loading = entry.expanded && matches!(entry.children, Children::Loading(_));
+                let location = entry.symbol.location();
+                let file_name = location
+                    .buffer
+                    .read(cx)
+                    .file()
+                    .map(|file| SharedString::from(file.file_name(cx).to_string()));
+                let row = entry
+                    .symbol
+                    .selection_range()
+                    .start
+                    .to_point(location.buffer.read(cx))
+                    .row;
+
+                Some(
+                    ListItem::new(("hierarchy-entry", entry_ix))
+                        .spacing(ListItemSpacing::Sparse)
+                        .indent_level(entry.depth)
+                        .indent_step_size(px(12.))
+                        .toggle_state(self.selected_index == Some(visible_ix))
+                        .toggle((!is_leaf).then_some(entry.expanded))
+                        .on_toggle(cx.listener(move |this, _, _, cx| {
+                            this.toggle_expanded(entry_ix, cx);
+                        }))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.select_index(visible_ix, cx);
+                            this.open_entry(entry_ix, window, cx);
+                        }))
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(Label::new(entry.symbol.name().to_string()))
+                                .when_some(entry.symbol.detail().cloned(), |this, detail| {
+                                    this.child(
+                                        Label::new(detail)
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted)
+                                            .truncate(),
+                                    )
+                                })
+                                .when_some(file_name, |this, file_name| {
+                                    this.child(
+                                        Label::new(format!("{file_name}:{}", row + 1))
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                    )
+                                })
+                                .when(entry.call_sites.len() > 1, |this| {
+                                    this.child(
+                                        Label::new(format!("({} calls)", entry.call_sites.len()))
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                    )
+                                })
+                                .when(is_loading, |this| {
+                                    this.child(
+                                        Label::new("Loading…")
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                    )
+                                }),
+                        ),
+                )
+            })
+            .collect()
+    }
+
+    fn title(&self) -> SharedString {
+        let name = self
+            .roots
+            .first()
+            .map(|root| self.entries[*root].symbol.name())
+            .unwrap_or_default();
+        match self.kind {
+            HierarchyKind::IncomingCalls => format!("Callers of {name}").into(),
+            HierarchyKind::OutgoingCalls => format!("Callees of {name}").into(),
+            HierarchyKind::Supertypes => format!("Supertypes of {name}").into(),
+            HierarchyKind::Subtypes => format!("Subtypes of {name}").into(),
+        }
+    }
+}
+
+impl Render for HierarchyView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("HierarchyView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::expand_selected))
+            .on_action(cx.listener(Self::collapse_selected))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .bg(cx.theme().colors().panel_background)
+            .child(
+                uniform_list(
+                    "hierarchy-entries",
+                    self.visible_entries.len(),
+                    cx.processor(|this, range: Range<usize>, _, cx| this.render_entries(range, cx)),
+                )
+                .size_full()
+                .track_scroll(&self.scroll_handle),
+            )
+            .vertical_scrollbar_for(&self.scroll_handle, window, cx)
+    }
+}
+
+impl EventEmitter<()> for HierarchyView {}
+
+impl Focusable for HierarchyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for HierarchyView {
+    type Event = ();
+
+    fn to_item_events(_: &Self::Event, _: impl FnMut(ItemEvent)) {}
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        self.title()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+}
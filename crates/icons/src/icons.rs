@@ -88,6 +88,8 @@ pub enum IconName {
     DebugIgnoreBreakpoints,
     DebugLogBreakpoint,
     DebugPause,
+    DebugReverseContinue,
+    DebugStepBack,
     DebugStepInto,
     DebugStepOut,
     DebugStepOver,
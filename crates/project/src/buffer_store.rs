@@ -1,6 +1,7 @@
 use crate::{
     ProjectPath,
     lsp_store::OpenLspBufferHandle,
+    project_settings::ProjectSettings,
     worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
 use anyhow::{Context as _, Result, anyhow};
@@ -22,9 +23,15 @@ use rpc::{
     proto::{self, PeerId},
 };
 
-use settings::Settings;
-use std::{io, sync::Arc, time::Instant};
-use text::{BufferId, ReplicaId};
+use db::local_history::{LOCAL_HISTORY, LocalHistoryRetention, LocalHistorySource};
+use settings::{Settings, SettingsLocation};
+use std::{
+    io,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use text::{BufferId, ReplicaId, Rope};
 use util::{ResultExt as _, TryFutureExt, debug_panic, maybe, rel_path::RelPath};
 use worktree::{File, PathChange, ProjectEntryId, Worktree, WorktreeId, WorktreeSettings};
 
@@ -97,6 +104,21 @@ pub enum BufferStoreEvent {
         buffer: Entity<Buffer>,
         old_file: Option<Arc<dyn language::File>>,
     },
+    /// A snapshot of a local file was recorded in its local history.
+    LocalHistoryRecorded {
+        abs_path: PathBuf,
+    },
+}
+
+/// Files larger than this are left out of the local history.
+const MAX_LOCAL_HISTORY_FILE_SIZE: usize = 1024 * 1024;
+
+/// A local file's contents, to be recorded in its local history.
+struct LocalHistorySnapshot {
+    abs_path: PathBuf,
+    text: Rope,
+    source: LocalHistorySource,
+    retention: LocalHistoryRetention,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -409,6 +431,7 @@ impl LocalBufferStore {
             has_changed_file = true;
         }
 
+        let saved_text = text.clone();
         let save = worktree.update(cx, |worktree, cx| {
             worktree.write_file(path, text, line_ending, encoding, has_bom, cx)
         });
@@ -443,6 +466,14 @@ impl LocalBufferStore {
                 }
                 buffer.did_save(version.clone(), mtime, cx);
             });
+            this.update(cx, |this, cx| {
+                let buffer = buffer_handle.read(cx);
+                let snapshot =
+                    this.local_history_snapshot(buffer, saved_text, LocalHistorySource::Save, cx);
+                if let Some(snapshot) = snapshot {
+                    BufferStore::record_local_history(snapshot, cx);
+                }
+            })?;
             Ok(())
         })
     }
@@ -517,6 +548,7 @@ impl LocalBufferStore {
             return None;
         };
 
+        let mut history_snapshot = None;
         let events = buffer.update(cx, |buffer, cx| {
             let file = buffer.file()?;
             let old_file = File::from_dyn(Some(file))?;
@@ -557,6 +589,21 @@ impl LocalBufferStore {
             }
 
             let mut events = Vec::new();
+            // The buffer is about to be reloaded with the file's new contents, so keep the
+            // contents it had before they changed on disk.
+            if !buffer.is_dirty()
+                && old_file.disk_state.mtime().is_some()
+                && new_file.disk_state.mtime().is_some()
+                && new_file.disk_state.mtime() != old_file.disk_state.mtime()
+                && new_file.disk_state.mtime() != buffer.saved_mtime()
+            {
+                history_snapshot = this.local_history_snapshot(
+                    buffer,
+                    buffer.as_rope().clone(),
+                    LocalHistorySource::BeforeExternalChange,
+                    cx,
+                );
+            }
             if new_file.path != old_file.path {
                 this.path_to_buffer_id.remove(&ProjectPath {
                     path: old_file.path.clone(),
@@ -600,6 +647,9 @@ impl LocalBufferStore {
             Some(events)
         })?;
 
+        if let Some(snapshot) = history_snapshot {
+            BufferStore::record_local_history(snapshot, cx);
+        }
         for event in events {
             cx.emit(event);
         }
@@ -825,6 +875,73 @@ impl BufferStore {
         }
     }
 
+    /// A snapshot of a local file's contents for its local history, unless the local history
+    /// is disabled for the file or leaves it out.
+    fn local_history_snapshot(
+        &self,
+        buffer: &Buffer,
+        text: Rope,
+        source: LocalHistorySource,
+        cx: &App,
+    ) -> Option<LocalHistorySnapshot> {
+        if !matches!(self.state, BufferStoreState::Local(_)) {
+            return None;
+        }
+        let file = File::from_dyn(buffer.file())?;
+        if file.is_private || text.len() > MAX_LOCAL_HISTORY_FILE_SIZE {
+            return None;
+        }
+        let settings = ProjectSettings::get(
+            Some(SettingsLocation {
+                worktree_id: file.worktree_id(cx),
+                path: &file.path,
+            }),
+            cx,
+        )
+        .local_history;
+        if !settings.enabled {
+            return None;
+        }
+        Some(LocalHistorySnapshot {
+            abs_path: file.worktree.read(cx).absolutize(&file.path),
+            text,
+            source,
+            retention: LocalHistoryRetention {
+                max_entries: settings.max_entries,
+                max_age: Duration::from_secs(settings.max_age_days * 24 * 60 * 60),
+            },
+        })
+    }
+
+    /// Records a snapshot in the local history, which is kept by absolute path and shared
+    /// between projects.
+    fn record_local_history(snapshot: LocalHistorySnapshot, cx: &mut Context<Self>) {
+        let LocalHistorySnapshot {
+            abs_path,
+            text,
+            source,
+            retention,
+        } = snapshot;
+        let timestamp = SystemTime::now();
+        let record = cx.background_spawn({
+            let abs_path = abs_path.clone();
+            async move {
+                LOCAL_HISTORY
+                    .record(&abs_path, text.to_string(), source, timestamp, retention)
+                    .await
+            }
+        });
+        cx.spawn(async move |this, cx| {
+            if record.await.log_err() == Some(true) {
+                this.update(cx, |_, cx| {
+                    cx.emit(BufferStoreEvent::LocalHistoryRecorded { abs_path })
+                })
+                .ok();
+            }
+        })
+        .detach();
+    }
+
     fn as_local_mut(&mut self) -> Option<&mut LocalBufferStore> {
         match &mut self.state {
             BufferStoreState::Local(state) => Some(state),
@@ -1162,6 +1279,20 @@ impl BufferStore {
                 self.buffer_changed_file(buffer, cx);
             }
             BufferEvent::Reloaded => {
+                let snapshot = {
+                    let buffer = buffer.read(cx);
+                    let text = buffer.as_rope().clone();
+                    self.local_history_snapshot(
+                        buffer,
+                        text,
+                        LocalHistorySource::ExternalChange,
+                        cx,
+                    )
+                };
+                if let Some(snapshot) = snapshot {
+                    Self::record_local_history(snapshot, cx);
+                }
+
                 let Some((downstream_client, project_id)) = self.downstream_client.as_ref() else {
                     return;
                 };
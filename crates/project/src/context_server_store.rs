@@ -7,20 +7,27 @@ use std::time::Duration;
 
 use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet};
+use context_server::client::NotificationSubscription;
+use context_server::protocol::{InitializedContextServerProtocol, ServerCapability};
+use context_server::types::{self, Notification as _, notifications, requests};
 use context_server::{ContextServer, ContextServerCommand, ContextServerId};
-use futures::{FutureExt as _, future::join_all};
+use futures::{
+    FutureExt as _,
+    future::{Shared, join_all},
+};
 use gpui::{App, AsyncApp, Context, Entity, EventEmitter, Subscription, Task, WeakEntity, actions};
 use itertools::Itertools;
 use registry::ContextServerDescriptorRegistry;
 use remote::RemoteClient;
 use rpc::{AnyProtoClient, TypedEnvelope, proto};
 use settings::{Settings as _, SettingsStore};
+use url::Url;
 use util::{ResultExt as _, rel_path::RelPath};
 
 use crate::{
     DisableAiSettings, Project,
     project_settings::{ContextServerSettings, ProjectSettings},
-    worktree_store::WorktreeStore,
+    worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
 
 /// Maximum timeout for context server requests
@@ -205,6 +212,7 @@ pub struct ContextServerStore {
     context_server_settings: HashMap<Arc<str>, ContextServerSettings>,
     servers: HashMap<ContextServerId, ContextServerState>,
     server_ids: Vec<ContextServerId>,
+    resource_lists: HashMap<ContextServerId, ResourceList>,
     worktree_store: Entity<WorktreeStore>,
     project: Option<WeakEntity<Project>>,
     registry: Entity<ContextServerDescriptorRegistry>,
@@ -214,6 +222,18 @@ pub struct ContextServerStore {
     _subscriptions: Vec<Subscription>,
 }
 
+/// The resources and resource templates listed by a context server.
+#[derive(Debug, Default)]
+pub struct ContextServerResources {
+    pub resources: Vec<types::Resource>,
+    pub resource_templates: Vec<types::ResourceTemplate>,
+}
+
+struct ResourceList {
+    resources: Shared<Task<Arc<ContextServerResources>>>,
+    _list_changed_subscription: NotificationSubscription,
+}
+
 pub struct ServerStatusChangedEvent {
     pub server_id: ContextServerId,
     pub status: ContextServerStatus,
@@ -221,6 +241,43 @@ pub struct ServerStatusChangedEvent {
 
 impl EventEmitter<ServerStatusChangedEvent> for ContextServerStore {}
 
+/// A request sent by a context server that needs to be answered by the user.
+#[derive(Clone, Debug)]
+pub enum ContextServerRequestEvent {
+    Sampling(SamplingRequest),
+    Elicitation(ElicitationRequest),
+}
+
+impl EventEmitter<ContextServerRequestEvent> for ContextServerStore {}
+
+/// A `sampling/createMessage` request, asking the client to run a completion on the server's behalf.
+#[derive(Clone, Debug)]
+pub struct SamplingRequest {
+    pub server_id: ContextServerId,
+    pub params: types::CreateMessageRequest,
+    response_channel: smol::channel::Sender<Result<types::CreateMessageResult>>,
+}
+
+impl SamplingRequest {
+    pub async fn respond(self, response: Result<types::CreateMessageResult>) -> Option<()> {
+        self.response_channel.send(response).await.ok()
+    }
+}
+
+/// An `elicitation/create` request, asking the user to fill in a form described by the server.
+#[derive(Clone, Debug)]
+pub struct ElicitationRequest {
+    pub server_id: ContextServerId,
+    pub params: types::ElicitParams,
+    response_channel: smol::channel::Sender<types::ElicitResult>,
+}
+
+impl ElicitationRequest {
+    pub async fn respond(self, response: types::ElicitResult) -> Option<()> {
+        self.response_channel.send(response).await.ok()
+    }
+}
+
 impl ContextServerStore {
     pub fn local(
         worktree_store: Entity<WorktreeStore>,
@@ -383,6 +440,15 @@ impl ContextServerStore {
             }));
         }
 
+        subscriptions.push(
+            cx.subscribe(&worktree_store, |this, _, event, _| match event {
+                WorktreeStoreEvent::WorktreeAdded(_)
+                | WorktreeStoreEvent::WorktreeRemoved(..)
+                | WorktreeStoreEvent::WorktreeOrderChanged => this.roots_changed(),
+                _ => {}
+            }),
+        );
+
         let mut this = Self {
             state,
             _subscriptions: subscriptions,
@@ -395,6 +461,7 @@ impl ContextServerStore {
             needs_server_update: false,
             servers: HashMap::default(),
             server_ids: Default::default(),
+            resource_lists: HashMap::default(),
             update_servers_task: None,
             context_server_factory,
         };
@@ -416,6 +483,48 @@ impl ContextServerStore {
         }
     }
 
+    /// Returns the resources and resource templates of a running server, loading every page
+    /// of both lists on first use. The lists are cached until the server sends
+    /// `notifications/resources/list_changed` or its status changes.
+    pub fn resources(
+        &mut self,
+        id: &ContextServerId,
+        cx: &mut Context<Self>,
+    ) -> Option<Shared<Task<Arc<ContextServerResources>>>> {
+        if let Some(list) = self.resource_lists.get(id) {
+            return Some(list.resources.clone());
+        }
+        let client = self.get_running_server(id)?.client()?;
+        if !client.capable(ServerCapability::Resources) {
+            return None;
+        }
+
+        let this = cx.weak_entity();
+        let server_id = id.clone();
+        let list_changed_subscription = client.on_notification(
+            notifications::ResourcesListChanged::METHOD,
+            Box::new(move |_, cx| {
+                let server_id = server_id.clone();
+                let this = this.clone();
+                cx.spawn(async move |cx| {
+                    this.update(cx, |this, _| this.resource_lists.remove(&server_id))
+                })
+                .detach();
+            }),
+        );
+        let resources = cx
+            .spawn(async move |_, _| Arc::new(load_resources(&client).await))
+            .shared();
+        self.resource_lists.insert(
+            id.clone(),
+            ResourceList {
+                resources: resources.clone(),
+                _list_changed_subscription: list_changed_subscription,
+            },
+        );
+        Some(resources)
+    }
+
     pub fn status_for_server(&self, id: &ContextServerId) -> Option<ContextServerStatus> {
         self.servers.get(id).map(ContextServerStatus::from_state)
     }
@@ -469,6 +578,93 @@ impl ContextServerStore {
             .collect();
     }
 
+    /// The roots exposed to context servers: the project's visible worktree directories.
+    pub fn roots(&self, cx: &App) -> Vec<types::Root> {
+        self.worktree_store
+            .read(cx)
+            .visible_worktrees(cx)
+            .filter_map(|worktree| {
+                let worktree = worktree.read(cx);
+                if worktree.is_single_file() {
+                    return None;
+                }
+                Some(types::Root {
+                    uri: Url::from_directory_path(worktree.abs_path()).ok()?,
+                    name: Some(worktree.root_name_str().to_string()),
+                })
+            })
+            .collect()
+    }
+
+    fn roots_changed(&self) {
+        for server in self.running_servers() {
+            if let Some(client) = server.client() {
+                client
+                    .notify::<notifications::RootsListChanged>(())
+                    .log_err();
+            }
+        }
+    }
+
+    /// Answers the requests a context server can send to the client: roots are
+    /// served from the project, while sampling and elicitation are forwarded to
+    /// the user via [`ContextServerRequestEvent`].
+    fn register_request_handlers(&self, server: &ContextServer, cx: &mut Context<Self>) {
+        let this = cx.weak_entity();
+        let server_id = server.id();
+
+        server.on_request::<requests::ListRoots>({
+            let this = this.clone();
+            move |_, cx| {
+                let roots = this.read_with(&cx, |this, cx| this.roots(cx));
+                Task::ready(roots.map(|roots| types::ListRootsResponse { roots, meta: None }))
+            }
+        });
+
+        server.on_request::<requests::CreateMessage>({
+            let this = this.clone();
+            let server_id = server_id.clone();
+            move |params, mut cx| {
+                let (response_channel, response) = smol::channel::bounded(1);
+                let request = SamplingRequest {
+                    server_id: server_id.clone(),
+                    params,
+                    response_channel,
+                };
+                let emitted = this.update(&mut cx, |_, cx| {
+                    cx.emit(ContextServerRequestEvent::Sampling(request))
+                });
+                cx.background_spawn(async move {
+                    emitted?;
+                    response
+                        .recv()
+                        .await
+                        .context("sampling request was dismissed")?
+                })
+            }
+        });
+
+        server.on_request::<requests::Elicit>(move |params, mut cx| {
+            let (response_channel, response) = smol::channel::bounded(1);
+            let request = ElicitationRequest {
+                server_id: server_id.clone(),
+                params,
+                response_channel,
+            };
+            let emitted = this.update(&mut cx, |_, cx| {
+                cx.emit(ContextServerRequestEvent::Elicitation(request))
+            });
+            cx.background_spawn(async move {
+                emitted?;
+                Ok(response.recv().await.unwrap_or(types::ElicitResult {
+                    action: types::ElicitAction::Cancel,
+                    content: None,
+                    meta: None,
+                }))
+            })
+        });
+    }
+
     pub fn running_servers(&self) -> Vec<Arc<ContextServer>> {
         self.servers
             .values()
@@ -562,6 +758,7 @@ impl ContextServerStore {
         ) {
             self.stop_server(&id, cx).log_err();
         }
+        self.register_request_handlers(&server, cx);
         let task = cx.spawn({
             let id = server.id();
             let server = server.clone();
@@ -620,6 +817,7 @@ impl ContextServerStore {
             .remove(id)
             .context("Context server not found")?;
         drop(state);
+        self.resource_lists.remove(id);
         cx.emit(ServerStatusChangedEvent {
             server_id: id.clone(),
             status: ContextServerStatus::Stopped,
@@ -837,6 +1035,7 @@ impl ContextServerStore {
     ) {
         let status = ContextServerStatus::from_state(&state);
         self.servers.insert(id.clone(), state);
+        self.resource_lists.remove(&id);
         cx.emit(ServerStatusChangedEvent {
             server_id: id,
             status,
@@ -969,3 +1168,47 @@ impl ContextServerStore {
         Ok(())
     }
 }
+
+/// Loads every page of a server's resources and resource templates. A list that fails to
+/// load is left empty, since servers often don't implement templates.
+async fn load_resources(client: &InitializedContextServerProtocol) -> ContextServerResources {
+    let mut resources = ContextServerResources::default();
+
+    let mut cursor = None;
+    loop {
+        let params = types::PaginatedParams { cursor, meta: None };
+        let Some(response) = client
+            .request::<requests::ResourcesList>(params)
+            .await
+            .log_err()
+        else {
+            break;
+        };
+        resources.resources.extend(response.resources);
+        cursor = response.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    let mut cursor = None;
+    loop {
+        let params = types::PaginatedParams { cursor, meta: None };
+        let Some(response) = client
+            .request::<requests::ListResourceTemplates>(params)
+            .await
+            .log_err()
+        else {
+            break;
+        };
+        resources
+            .resource_templates
+            .extend(response.resource_templates);
+        cursor = response.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    resources
+}
@@ -152,6 +152,8 @@ pub struct BreakpointStore {
     buffer_store: Entity<BufferStore>,
     worktree_store: Entity<WorktreeStore>,
     breakpoints: BTreeMap<Arc<Path>, BreakpointsInFile>,
+    function_breakpoints: Vec<FunctionBreakpoint>,
+    data_breakpoints: Vec<DataBreakpoint>,
     downstream_client: Option<(AnyProtoClient, u64)>,
     active_stack_frame: Option<ActiveStackFrame>,
     // E.g ssh
@@ -162,10 +164,14 @@ impl BreakpointStore {
     pub fn init(client: &AnyProtoClient) {
         client.add_entity_request_handler(Self::handle_toggle_breakpoint);
         client.add_entity_message_handler(Self::handle_breakpoints_for_file);
+        client.add_entity_request_handler(Self::handle_set_function_breakpoints);
+        client.add_entity_message_handler(Self::handle_function_breakpoints);
     }
     pub fn local(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            function_breakpoints: Vec::new(),
+            data_breakpoints: Vec::new(),
             mode: BreakpointStoreMode::Local,
             buffer_store,
             worktree_store,
@@ -182,6 +188,8 @@ impl BreakpointStore {
     ) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            function_breakpoints: Vec::new(),
+            data_breakpoints: Vec::new(),
             mode: BreakpointStoreMode::Remote(RemoteBreakpointStore {
                 upstream_client,
                 upstream_project_id,
@@ -311,8 +319,80 @@ impl BreakpointStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_function_breakpoints(
+        this: Entity<Self>,
+        message: TypedEnvelope<proto::FunctionBreakpoints>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            this.function_breakpoints = message
+                .payload
+                .breakpoints
+                .into_iter()
+                .map(FunctionBreakpoint::from_proto)
+                .collect();
+            cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+            cx.notify();
+        });
+        Ok(())
+    }
+
+    async fn handle_set_function_breakpoints(
+        this: Entity<Self>,
+        message: TypedEnvelope<proto::SetFunctionBreakpoints>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        this.update(&mut cx, |this, cx| {
+            this.function_breakpoints = message
+                .payload
+                .breakpoints
+                .into_iter()
+                .map(FunctionBreakpoint::from_proto)
+                .collect();
+            this.function_breakpoints_changed(cx);
+        });
+        Ok(proto::Ack {})
+    }
+
+    /// Sends the function breakpoints to the host when this is a remote project, or to the guests
+    /// when it's shared.
+    fn function_breakpoints_changed(&mut self, cx: &mut Context<Self>) {
+        let breakpoints = self
+            .function_breakpoints
+            .iter()
+            .map(FunctionBreakpoint::to_proto)
+            .collect();
+        if let BreakpointStoreMode::Remote(remote) = &self.mode {
+            cx.background_spawn(
+                remote
+                    .upstream_client
+                    .request(proto::SetFunctionBreakpoints {
+                        project_id: remote.upstream_project_id,
+                        breakpoints,
+                    }),
+            )
+            .detach();
+        } else if let Some((client, project_id)) = &self.downstream_client {
+            let _ = client.send(proto::FunctionBreakpoints {
+                project_id: *project_id,
+                breakpoints,
+            });
+        }
+
+        cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        cx.notify();
+    }
+
     pub(crate) fn broadcast(&self) {
         if let Some((client, project_id)) = &self.downstream_client {
+            let _ = client.send(proto::FunctionBreakpoints {
+                project_id: *project_id,
+                breakpoints: self
+                    .function_breakpoints
+                    .iter()
+                    .map(FunctionBreakpoint::to_proto)
+                    .collect(),
+            });
             for (path, breakpoint_set) in &self.breakpoints {
                 let _ = client.send(proto::BreakpointsForFile {
                     project_id: *project_id,
@@ -606,6 +686,144 @@ impl BreakpointStore {
         let breakpoint_paths = self.breakpoints.keys().cloned().collect();
         self.breakpoints.clear();
         cx.emit(BreakpointStoreEvent::BreakpointsCleared(breakpoint_paths));
+        if !self.function_breakpoints.is_empty() {
+            self.function_breakpoints.clear();
+            self.function_breakpoints_changed(cx);
+        }
+        if !self.data_breakpoints.is_empty() {
+            self.data_breakpoints.clear();
+            cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+        }
+    }
+
+    pub fn function_breakpoints(&self) -> &[FunctionBreakpoint] {
+        &self.function_breakpoints
+    }
+
+    /// Adds an enabled breakpoint on the function called `name`, unless there already is one.
+    pub fn add_function_breakpoint(&mut self, name: Arc<str>, cx: &mut Context<Self>) {
+        if name.is_empty()
+            || self
+                .function_breakpoints
+                .iter()
+                .any(|breakpoint| breakpoint.name == name)
+        {
+            return;
+        }
+        self.function_breakpoints.push(FunctionBreakpoint {
+            name,
+            condition: None,
+            hit_condition: None,
+            state: BreakpointState::Enabled,
+        });
+        self.function_breakpoints_changed(cx);
+    }
+
+    pub fn edit_function_breakpoint(
+        &mut self,
+        name: &str,
+        edit_action: BreakpointEditAction,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self
+            .function_breakpoints
+            .iter()
+            .position(|breakpoint| breakpoint.name.as_ref() == name)
+        else {
+            return;
+        };
+        let breakpoint = &mut self.function_breakpoints[ix];
+        match edit_action {
+            BreakpointEditAction::Toggle => {
+                self.function_breakpoints.remove(ix);
+            }
+            BreakpointEditAction::InvertState => {
+                breakpoint.state = if breakpoint.state.is_enabled() {
+                    BreakpointState::Disabled
+                } else {
+                    BreakpointState::Enabled
+                };
+            }
+            // Function breakpoints can't be turned into log points.
+            BreakpointEditAction::EditLogMessage(_) => return,
+            BreakpointEditAction::EditCondition(condition) => {
+                breakpoint.condition = (!condition.is_empty()).then_some(condition);
+            }
+            BreakpointEditAction::EditHitCondition(hit_condition) => {
+                breakpoint.hit_condition = (!hit_condition.is_empty()).then_some(hit_condition);
+            }
+        }
+        self.function_breakpoints_changed(cx);
+    }
+
+    pub fn with_serialized_function_breakpoints(
+        &mut self,
+        function_breakpoints: Vec<FunctionBreakpoint>,
+        cx: &mut Context<Self>,
+    ) {
+        if let BreakpointStoreMode::Local = &self.mode {
+            self.function_breakpoints = function_breakpoints;
+            cx.notify();
+        }
+    }
+
+    /// The data breakpoints that are set again when a debug session starts.
+    pub fn data_breakpoints(&self) -> &[DataBreakpoint] {
+        &self.data_breakpoints
+    }
+
+    /// Keeps a data breakpoint for later sessions, replacing the one with the same data ID.
+    pub fn persist_data_breakpoint(&mut self, breakpoint: DataBreakpoint, cx: &mut Context<Self>) {
+        if let Some(existing) = self
+            .data_breakpoints
+            .iter_mut()
+            .find(|existing| existing.data_id == breakpoint.data_id)
+        {
+            *existing = breakpoint;
+        } else {
+            self.data_breakpoints.push(breakpoint);
+        }
+        cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+        cx.notify();
+    }
+
+    pub fn set_data_breakpoint_state(
+        &mut self,
+        data_id: &str,
+        state: BreakpointState,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(breakpoint) = self
+            .data_breakpoints
+            .iter_mut()
+            .find(|breakpoint| breakpoint.data_id.as_ref() == data_id)
+            && breakpoint.state != state
+        {
+            breakpoint.state = state;
+            cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+            cx.notify();
+        }
+    }
+
+    pub fn remove_data_breakpoint(&mut self, data_id: &str, cx: &mut Context<Self>) {
+        let len = self.data_breakpoints.len();
+        self.data_breakpoints
+            .retain(|breakpoint| breakpoint.data_id.as_ref() != data_id);
+        if self.data_breakpoints.len() != len {
+            cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+            cx.notify();
+        }
+    }
+
+    pub fn with_serialized_data_breakpoints(
+        &mut self,
+        data_breakpoints: Vec<DataBreakpoint>,
+        cx: &mut Context<Self>,
+    ) {
+        if let BreakpointStoreMode::Local = &self.mode {
+            self.data_breakpoints = data_breakpoints;
+            cx.notify();
+        }
     }
 
     pub fn breakpoints<'a>(
@@ -875,6 +1093,8 @@ pub enum BreakpointStoreEvent {
     ClearDebugLines,
     BreakpointsUpdated(Arc<Path>, BreakpointUpdatedReason),
     BreakpointsCleared(Vec<Arc<Path>>),
+    FunctionBreakpointsUpdated,
+    DataBreakpointsUpdated,
 }
 
 impl EventEmitter<BreakpointStoreEvent> for BreakpointStore {}
@@ -1036,3 +1256,82 @@ impl From<SourceBreakpoint> for dap::SourceBreakpoint {
         }
     }
 }
+
+/// Breakpoint on entry to a function, identified by its name.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FunctionBreakpoint {
+    pub name: Arc<str>,
+    pub condition: Option<Arc<str>>,
+    pub hit_condition: Option<Arc<str>>,
+    pub state: BreakpointState,
+}
+
+impl FunctionBreakpoint {
+    fn to_proto(&self) -> proto::FunctionBreakpoint {
+        proto::FunctionBreakpoint {
+            name: String::from(self.name.as_ref()),
+            state: match self.state {
+                BreakpointState::Enabled => proto::BreakpointState::Enabled.into(),
+                BreakpointState::Disabled => proto::BreakpointState::Disabled.into(),
+            },
+            condition: self.condition.as_ref().map(|s| String::from(s.as_ref())),
+            hit_condition: self
+                .hit_condition
+                .as_ref()
+                .map(|s| String::from(s.as_ref())),
+        }
+    }
+
+    fn from_proto(breakpoint: proto::FunctionBreakpoint) -> Self {
+        Self {
+            name: breakpoint.name.into(),
+            state: match proto::BreakpointState::from_i32(breakpoint.state) {
+                Some(proto::BreakpointState::Disabled) => BreakpointState::Disabled,
+                None | Some(proto::BreakpointState::Enabled) => BreakpointState::Enabled,
+            },
+            condition: breakpoint.condition.map(Into::into),
+            hit_condition: breakpoint.hit_condition.map(Into::into),
+        }
+    }
+}
+
+impl From<FunctionBreakpoint> for dap::FunctionBreakpoint {
+    fn from(bp: FunctionBreakpoint) -> Self {
+        Self {
+            name: String::from(bp.name.as_ref()),
+            condition: bp
+                .condition
+                .map(|condition| String::from(condition.as_ref())),
+            hit_condition: bp
+                .hit_condition
+                .map(|hit_condition| String::from(hit_condition.as_ref())),
+        }
+    }
+}
+
+/// Data breakpoint that the debug adapter reported it can persist across sessions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataBreakpoint {
+    pub data_id: Arc<str>,
+    /// Describes the data, since the data ID is only meaningful to the debug adapter.
+    pub label: Arc<str>,
+    pub access_type: Option<dap::DataBreakpointAccessType>,
+    pub condition: Option<Arc<str>>,
+    pub hit_condition: Option<Arc<str>>,
+    pub state: BreakpointState,
+}
+
+impl From<DataBreakpoint> for dap::DataBreakpoint {
+    fn from(bp: DataBreakpoint) -> Self {
+        Self {
+            data_id: String::from(bp.data_id.as_ref()),
+            access_type: bp.access_type,
+            condition: bp
+                .condition
+                .map(|condition| String::from(condition.as_ref())),
+            hit_condition: bp
+                .hit_condition
+                .map(|hit_condition| String::from(hit_condition.as_ref())),
+        }
+    }
+}
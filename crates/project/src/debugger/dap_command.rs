@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use anyhow::{Context as _, Ok, Result};
 use base64::Engine;
@@ -198,6 +198,8 @@ impl DapCommand for NextCommand {
 #[derive(Debug, Hash, PartialEq, Eq)]
 pub(crate) struct StepInCommand {
     pub inner: StepCommand,
+    /// The step-in target returned by `stepInTargets` to step into, if any.
+    pub target_id: Option<u64>,
 }
 
 impl LocalDapCommand for StepInCommand {
@@ -208,7 +210,7 @@ impl LocalDapCommand for StepInCommand {
         StepInArguments {
             thread_id: self.inner.thread_id,
             single_thread: self.inner.single_thread,
-            target_id: None,
+            target_id: self.target_id,
             granularity: self.inner.granularity,
         }
     }
@@ -238,6 +240,7 @@ impl DapCommand for StepInCommand {
                 single_thread: request.single_thread,
                 granularity: request.granularity,
             }),
+            target_id: request.target_id,
         }
     }
 
@@ -259,7 +262,7 @@ impl DapCommand for StepInCommand {
             thread_id: self.inner.thread_id,
             single_thread: self.inner.single_thread,
             granularity: self.inner.granularity.map(|gran| gran.to_proto() as i32),
-            target_id: None,
+            target_id: self.target_id,
         }
     }
 
@@ -413,6 +416,35 @@ impl DapCommand for StepBackCommand {
     }
 }
 
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub(crate) struct ReverseContinueCommand {
+    pub thread_id: i64,
+    pub single_thread: Option<bool>,
+}
+
+impl LocalDapCommand for ReverseContinueCommand {
+    type Response = <dap::requests::ReverseContinue as dap::requests::Request>::Response;
+    type DapRequest = dap::requests::ReverseContinue;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities.supports_step_back.unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::ReverseContinueArguments {
+            thread_id: self.thread_id,
+            single_thread: self.single_thread,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        _message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(())
+    }
+}
+
 #[derive(Debug, Hash, PartialEq, Eq)]
 pub(crate) struct ContinueCommand {
     pub args: ContinueArguments,
@@ -1792,6 +1824,33 @@ impl LocalDapCommand for SetDataBreakpointsCommand {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct SetFunctionBreakpointsCommand {
+    pub breakpoints: Vec<dap::FunctionBreakpoint>,
+}
+
+impl LocalDapCommand for SetFunctionBreakpointsCommand {
+    type Response = Vec<dap::Breakpoint>;
+    type DapRequest = dap::requests::SetFunctionBreakpoints;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities.supports_function_breakpoints.unwrap_or(false)
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::SetFunctionBreakpointsArguments {
+            breakpoints: self.breakpoints.clone(),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.breakpoints)
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub(super) enum SetExceptionBreakpoints {
     Plain {
@@ -1954,6 +2013,164 @@ impl LocalDapCommand for ReadMemory {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct DisassembleCommand {
+    pub(crate) memory_reference: String,
+    pub(crate) instruction_offset: i64,
+    pub(crate) instruction_count: u64,
+}
+
+impl LocalDapCommand for DisassembleCommand {
+    type Response = Vec<dap::DisassembledInstruction>;
+    type DapRequest = dap::requests::Disassemble;
+    const CACHEABLE: bool = true;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_disassemble_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::DisassembleArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: None,
+            instruction_offset: Some(self.instruction_offset),
+            instruction_count: self.instruction_count,
+            resolve_symbols: Some(true),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.instructions)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct SetInstructionBreakpointsCommand {
+    pub breakpoints: Vec<dap::InstructionBreakpoint>,
+}
+
+impl LocalDapCommand for SetInstructionBreakpointsCommand {
+    type Response = Vec<dap::Breakpoint>;
+    type DapRequest = dap::requests::SetInstructionBreakpoints;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_instruction_breakpoints
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::SetInstructionBreakpointsArguments {
+            breakpoints: self.breakpoints.clone(),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.breakpoints)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct StepInTargetsCommand {
+    pub frame_id: u64,
+}
+
+impl LocalDapCommand for StepInTargetsCommand {
+    type Response = Vec<dap::StepInTarget>;
+    type DapRequest = dap::requests::StepInTargets;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_step_in_targets_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::StepInTargetsArguments {
+            frame_id: self.frame_id,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.targets)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct GotoTargetsCommand {
+    pub path: Arc<Path>,
+    /// Zero-based row in the source file.
+    pub row: u32,
+}
+
+impl LocalDapCommand for GotoTargetsCommand {
+    type Response = Vec<dap::GotoTarget>;
+    type DapRequest = dap::requests::GotoTargets;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_goto_targets_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::GotoTargetsArguments {
+            source: super::session::client_source(&self.path),
+            line: self.row as u64 + 1,
+            column: None,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.targets)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct GotoCommand {
+    pub thread_id: i64,
+    pub target_id: u64,
+}
+
+impl LocalDapCommand for GotoCommand {
+    type Response = ();
+    type DapRequest = dap::requests::Goto;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_goto_targets_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::GotoArguments {
+            thread_id: self.thread_id,
+            target_id: self.target_id,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        _message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(())
+    }
+}
+
 impl LocalDapCommand for dap::WriteMemoryArguments {
     type Response = dap::WriteMemoryResponse;
     type DapRequest = dap::requests::WriteMemory;
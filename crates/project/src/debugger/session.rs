@@ -1,13 +1,16 @@
 use super::breakpoint_store::{
-    BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason, SourceBreakpoint,
+    BreakpointState, BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason,
+    DataBreakpoint, SourceBreakpoint,
 };
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetDataBreakpointsCommand, SetExceptionBreakpoints, SetVariableValueCommand,
-    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
-    TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand,
+    DisassembleCommand, DisconnectCommand, EvaluateCommand, GotoCommand, GotoTargetsCommand,
+    Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand, ModulesCommand,
+    NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand, ReverseContinueCommand,
+    ScopesCommand, SetDataBreakpointsCommand, SetExceptionBreakpoints,
+    SetFunctionBreakpointsCommand, SetInstructionBreakpointsCommand, SetVariableValueCommand,
+    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepInTargetsCommand,
+    StepOutCommand, TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
 };
 use super::dap_store::DapStore;
 use crate::debugger::breakpoint_store::BreakpointSessionState;
@@ -149,7 +152,7 @@ pub struct Watcher {
 pub struct DataBreakpointState {
     pub dap: dap::DataBreakpoint,
     pub is_enabled: bool,
-    pub context: Arc<DataBreakpointContext>,
+    pub label: SharedString,
 }
 
 pub enum SessionState {
@@ -178,7 +181,7 @@ pub struct SessionQuirks {
     pub prefer_thread_name: bool,
 }
 
-fn client_source(abs_path: &Path) -> dap::Source {
+pub(super) fn client_source(abs_path: &Path) -> dap::Source {
     dap::Source {
         name: abs_path
             .file_name()
@@ -415,6 +418,27 @@ impl RunningMode {
         })
     }
 
+    fn send_function_breakpoints(
+        &self,
+        ignore_breakpoints: bool,
+        breakpoint_store: &Entity<BreakpointStore>,
+        cx: &App,
+    ) -> Task<Result<Vec<dap::Breakpoint>>> {
+        let breakpoints = if ignore_breakpoints {
+            vec![]
+        } else {
+            breakpoint_store
+                .read(cx)
+                .function_breakpoints()
+                .iter()
+                .filter(|bp| bp.state.is_enabled())
+                .cloned()
+                .map(Into::into)
+                .collect()
+        };
+        self.request(SetFunctionBreakpointsCommand { breakpoints })
+    }
+
     fn initialize_sequence(
         &self,
         capabilities: &Capabilities,
@@ -435,6 +459,9 @@ impl RunningMode {
         };
 
         let configuration_done_supported = ConfigurationDone::is_supported(capabilities);
+        let supports_function_breakpoints =
+            SetFunctionBreakpointsCommand::is_supported(capabilities);
+        let supports_data_breakpoints = SetDataBreakpointsCommand::is_supported(capabilities);
         // From spec (on initialization sequence):
         // client sends a setExceptionBreakpoints request if one or more exceptionBreakpointFilters have been defined (or if supportsConfigurationDoneRequest is not true)
         //
@@ -495,6 +522,19 @@ impl RunningMode {
                     }
                 })?;
 
+                if supports_function_breakpoints {
+                    cx.update(|cx| this.send_function_breakpoints(false, &breakpoint_store, cx))
+                        .await
+                        .context("failed to set function breakpoints")
+                        .log_err();
+                }
+
+                if supports_data_breakpoints {
+                    session.update(cx, |session, cx| {
+                        session.restore_data_breakpoints(&breakpoint_store, cx)
+                    })?;
+                }
+
                 if should_send_exception_breakpoints {
                     _ = session.update(cx, |this, _| {
                         filters.retain(|filter| {
@@ -680,6 +720,7 @@ pub struct SessionSnapshot {
     variables: HashMap<VariableReference, Vec<dap::Variable>>,
     stack_frames: IndexMap<StackFrameId, StackFrame>,
     locations: HashMap<u64, dap::LocationsResponse>,
+    disassembly: HashMap<(String, i64, u64), Arc<[dap::DisassembledInstruction]>>,
     modules: Vec<dap::Module>,
     loaded_sources: Vec<dap::Source>,
 }
@@ -709,6 +750,7 @@ pub struct Session {
     ignore_breakpoints: bool,
     exception_breakpoints: BTreeMap<String, (ExceptionBreakpointsFilter, IsEnabled)>,
     data_breakpoints: BTreeMap<String, DataBreakpointState>,
+    instruction_breakpoints: BTreeMap<String, dap::InstructionBreakpoint>,
     background_tasks: Vec<Task<()>>,
     restart_task: Option<Task<()>>,
     task_context: SharedTaskContext,
@@ -860,7 +902,19 @@ impl Session {
                         local.unset_breakpoints_from_paths(paths, cx).detach();
                     }
                 }
-                BreakpointStoreEvent::SetDebugLine | BreakpointStoreEvent::ClearDebugLines => {}
+                BreakpointStoreEvent::FunctionBreakpointsUpdated => {
+                    if !this.ignore_breakpoints
+                        && SetFunctionBreakpointsCommand::is_supported(&this.capabilities)
+                        && let Some(local) = this.as_running()
+                    {
+                        local
+                            .send_function_breakpoints(false, &store, cx)
+                            .detach_and_log_err(cx);
+                    }
+                }
+                BreakpointStoreEvent::DataBreakpointsUpdated
+                | BreakpointStoreEvent::SetDebugLine
+                | BreakpointStoreEvent::ClearDebugLines => {}
             })
             .detach();
 
@@ -883,6 +937,7 @@ impl Session {
                 ignore_breakpoints: false,
                 breakpoint_store,
                 data_breakpoints: Default::default(),
+                instruction_breakpoints: Default::default(),
                 exception_breakpoints: Default::default(),
                 label,
                 adapter,
@@ -1367,6 +1422,48 @@ impl Session {
         }
     }
 
+    /// Moves the next statement to execute on a stopped thread to the given row, without running
+    /// the code in between. The adapter reports the new position with a `goto` stopped event.
+    pub fn jump_to_position(
+        &mut self,
+        path: Arc<Path>,
+        row: u32,
+        thread_id: ThreadId,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if !matches!(
+            self.active_snapshot.thread_states.thread_state(thread_id),
+            Some(ThreadStatus::Stopped)
+        ) {
+            return Task::ready(Err(anyhow!("thread must be stopped to jump")));
+        }
+        self.select_historic_snapshot(None, cx);
+
+        let targets = self.request(
+            GotoTargetsCommand { path, row },
+            |_, response, _| response.log_err(),
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            let target = targets
+                .await
+                .and_then(|targets| targets.into_iter().next())
+                .context("no location to jump to on this line")?;
+            this.update(cx, |this, cx| {
+                this.request(
+                    GotoCommand {
+                        thread_id: thread_id.0,
+                        target_id: target.id,
+                    },
+                    |_, response, _| response.log_err(),
+                    cx,
+                )
+            })?
+            .await
+            .context("failed to jump to line")
+        })
+    }
+
     pub fn has_new_output(&self, last_update: OutputToken) -> bool {
         self.output_token.0.checked_sub(last_update.0).unwrap_or(0) != 0
     }
@@ -1771,6 +1868,7 @@ impl Session {
         self.invalidate_command_type::<ThreadsCommand>();
         self.invalidate_command_type::<DataBreakpointInfoCommand>();
         self.invalidate_command_type::<ReadMemory>();
+        self.invalidate_command_type::<DisassembleCommand>();
         let executor = self.as_running().map(|running| running.executor.clone());
         if let Some(executor) = executor {
             self.memory.clear(&executor);
@@ -2003,6 +2101,11 @@ impl Session {
         self.ignore_breakpoints = ignore;
 
         if let Some(local) = self.as_running() {
+            if SetFunctionBreakpointsCommand::is_supported(&self.capabilities) {
+                local
+                    .send_function_breakpoints(ignore, &self.breakpoint_store, cx)
+                    .detach_and_log_err(cx);
+            }
             local.send_source_breakpoints(ignore, &self.breakpoint_store, cx)
         } else {
             // todo(debugger): We need to propagate this change to downstream sessions and send a message to upstream sessions
@@ -2050,7 +2153,15 @@ impl Session {
     pub fn toggle_data_breakpoint(&mut self, id: &str, cx: &mut Context<'_, Session>) {
         if let Some(state) = self.data_breakpoints.get_mut(id) {
             state.is_enabled = !state.is_enabled;
-            self.send_exception_breakpoints(cx);
+            let state = if state.is_enabled {
+                BreakpointState::Enabled
+            } else {
+                BreakpointState::Disabled
+            };
+            self.breakpoint_store.update(cx, |store, cx| {
+                store.set_data_breakpoint_state(id, state, cx)
+            });
+            self.send_data_breakpoints(cx);
         }
     }
 
@@ -2066,26 +2177,114 @@ impl Session {
         }
     }
 
+    pub fn remove_data_breakpoint(&mut self, id: &str, cx: &mut Context<Self>) {
+        if self.data_breakpoints.remove(id).is_some() {
+            self.breakpoint_store
+                .update(cx, |store, cx| store.remove_data_breakpoint(id, cx));
+            self.send_data_breakpoints(cx);
+        }
+    }
+
+    /// Toggles a data breakpoint. It's kept for later sessions when the adapter reported that it
+    /// `can_persist`.
     pub fn create_data_breakpoint(
         &mut self,
         context: Arc<DataBreakpointContext>,
         data_id: String,
         dap: dap::DataBreakpoint,
+        can_persist: bool,
         cx: &mut Context<Self>,
     ) {
-        if self.data_breakpoints.remove(&data_id).is_none() {
+        if self.data_breakpoints.remove(&data_id).is_some() {
+            self.breakpoint_store
+                .update(cx, |store, cx| store.remove_data_breakpoint(&data_id, cx));
+        } else {
+            let label = SharedString::from(context.human_readable_label());
+            if can_persist {
+                let breakpoint = DataBreakpoint {
+                    data_id: data_id.as_str().into(),
+                    label: label.as_ref().into(),
+                    access_type: dap.access_type,
+                    condition: dap.condition.as_deref().map(Into::into),
+                    hit_condition: dap.hit_condition.as_deref().map(Into::into),
+                    state: BreakpointState::Enabled,
+                };
+                self.breakpoint_store.update(cx, |store, cx| {
+                    store.persist_data_breakpoint(breakpoint, cx)
+                });
+            }
             self.data_breakpoints.insert(
                 data_id,
                 DataBreakpointState {
                     dap,
                     is_enabled: true,
-                    context,
+                    label,
                 },
             );
         }
         self.send_data_breakpoints(cx);
     }
 
+    /// Sets the data breakpoints persisted by earlier sessions.
+    fn restore_data_breakpoints(
+        &mut self,
+        breakpoint_store: &Entity<BreakpointStore>,
+        cx: &mut Context<Self>,
+    ) {
+        let breakpoints = breakpoint_store.read(cx).data_breakpoints();
+        if breakpoints.is_empty() {
+            return;
+        }
+        for breakpoint in breakpoints {
+            self.data_breakpoints
+                .entry(breakpoint.data_id.to_string())
+                .or_insert_with(|| DataBreakpointState {
+                    dap: breakpoint.clone().into(),
+                    is_enabled: breakpoint.state.is_enabled(),
+                    label: breakpoint.label.clone().into(),
+                });
+        }
+        self.send_data_breakpoints(cx);
+    }
+
+    pub fn instruction_breakpoints(&self) -> impl Iterator<Item = &dap::InstructionBreakpoint> {
+        self.instruction_breakpoints.values()
+    }
+
+    /// Sets a breakpoint on the instruction at `instruction_reference`, or removes the one already there.
+    pub fn toggle_instruction_breakpoint(
+        &mut self,
+        instruction_reference: String,
+        cx: &mut Context<Self>,
+    ) {
+        if self
+            .instruction_breakpoints
+            .remove(&instruction_reference)
+            .is_none()
+        {
+            self.instruction_breakpoints.insert(
+                instruction_reference.clone(),
+                dap::InstructionBreakpoint {
+                    instruction_reference,
+                    offset: None,
+                    condition: None,
+                    hit_condition: None,
+                    mode: None,
+                },
+            );
+        }
+        self.send_instruction_breakpoints(cx);
+        cx.notify();
+    }
+
+    fn send_instruction_breakpoints(&mut self, cx: &mut Context<Self>) {
+        if let Some(mode) = self.as_running() {
+            let breakpoints = self.instruction_breakpoints.values().cloned().collect();
+            let command = SetInstructionBreakpointsCommand { breakpoints };
+            mode.request(command).detach_and_log_err(cx);
+        }
+    }
+
     pub fn breakpoints_enabled(&self) -> bool {
         self.ignore_breakpoints
     }
@@ -2295,6 +2494,26 @@ impl Session {
         .detach();
     }
 
+    /// Runs the thread backwards until it hits a breakpoint or the start of the recording.
+    pub fn reverse_continue(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        self.select_historic_snapshot(None, cx);
+
+        let supports_single_thread_execution_requests =
+            self.capabilities.supports_single_thread_execution_requests;
+        self.active_snapshot
+            .thread_states
+            .continue_thread(thread_id);
+        self.request(
+            ReverseContinueCommand {
+                thread_id: thread_id.0,
+                single_thread: supports_single_thread_execution_requests,
+            },
+            Self::on_step_response::<ReverseContinueCommand>(thread_id),
+            cx,
+        )
+        .detach();
+    }
+
     pub fn adapter_client(&self) -> Option<Arc<DebugAdapterClient>> {
         match self.state {
             SessionState::Running(ref local) => Some(local.client.clone()),
@@ -2343,6 +2562,42 @@ impl Session {
         thread_id: ThreadId,
         granularity: SteppingGranularity,
         cx: &mut Context<Self>,
+    ) {
+        self.step_in_inner(thread_id, granularity, None, cx);
+    }
+
+    /// Steps into one of the targets returned by [`Self::step_in_targets`], e.g. `baz` in `foo(bar(), baz())`.
+    pub fn step_in_target(
+        &mut self,
+        thread_id: ThreadId,
+        target_id: u64,
+        granularity: SteppingGranularity,
+        cx: &mut Context<Self>,
+    ) {
+        self.step_in_inner(thread_id, granularity, Some(target_id), cx);
+    }
+
+    /// Returns the calls that can be stepped into from the current line of the given stack frame.
+    pub fn step_in_targets(
+        &mut self,
+        stack_frame_id: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Vec<dap::StepInTarget>>> {
+        self.request(
+            StepInTargetsCommand {
+                frame_id: stack_frame_id,
+            },
+            |_, response, _| response.log_err(),
+            cx,
+        )
+    }
+
+    fn step_in_inner(
+        &mut self,
+        thread_id: ThreadId,
+        granularity: SteppingGranularity,
+        target_id: Option<u64>,
+        cx: &mut Context<Self>,
     ) {
         self.select_historic_snapshot(None, cx);
 
@@ -2359,6 +2614,7 @@ impl Session {
                 granularity: supports_stepping_granularity.then(|| granularity),
                 single_thread: supports_single_thread_execution_requests,
             },
+            target_id,
         };
 
         self.active_snapshot.thread_states.process_step(thread_id);
@@ -2558,6 +2814,13 @@ impl Session {
             .unwrap_or_default()
     }
 
+    pub fn stack_frame(&self, stack_frame_id: StackFrameId) -> Option<&dap::StackFrame> {
+        self.session_state()
+            .stack_frames
+            .get(&stack_frame_id)
+            .map(|stack_frame| &stack_frame.dap)
+    }
+
     pub fn variables_by_stack_frame_id(
         &self,
         stack_frame_id: StackFrameId,
@@ -2794,6 +3057,43 @@ impl Session {
         self.session_state().locations.get(&reference).cloned()
     }
 
+    /// Disassembles `instruction_count` instructions, starting `instruction_offset` instructions
+    /// away from `memory_reference`. Returns `None` until the adapter has responded.
+    pub fn disassemble(
+        &mut self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+        cx: &mut Context<Self>,
+    ) -> Option<Arc<[dap::DisassembledInstruction]>> {
+        let key = (
+            memory_reference.clone(),
+            instruction_offset,
+            instruction_count,
+        );
+        self.fetch(
+            DisassembleCommand {
+                memory_reference,
+                instruction_offset,
+                instruction_count,
+            },
+            {
+                let key = key.clone();
+                move |this, response, cx| {
+                    let Some(instructions) = response.log_err() else {
+                        return;
+                    };
+                    this.active_snapshot
+                        .disassembly
+                        .insert(key, instructions.into());
+                    cx.notify();
+                }
+            },
+            cx,
+        );
+        self.session_state().disassembly.get(&key).cloned()
+    }
+
     pub fn is_attached(&self) -> bool {
         let SessionState::Running(local_mode) = &self.state else {
             return false;
@@ -29,9 +29,10 @@ use git::{
     blame::Blame,
     parse_git_remote_url,
     repository::{
-        Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, FetchOptions,
-        GitRepository, GitRepositoryCheckpoint, GraphCommitData, InitialGraphCommitData, LogOrder,
-        LogSource, PushOptions, Remote, RemoteCommandOutput, RepoPath, ResetMode,
+        BisectProgress, BisectTerm, Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions,
+        DiffType, FetchOptions, GitRepository, GitRepositoryCheckpoint, GraphCommitData,
+        InitialGraphCommitData, LogOrder, LogSource, PushOptions, RebaseControl, RebaseProgress,
+        RebaseTodoAction, RebaseTodoEntry, Remote, RemoteCommandOutput, RepoPath, ResetMode, Tag,
         UpstreamTrackingStatus, Worktree as GitWorktree,
     },
     stash::{GitStash, StashEntry},
@@ -39,6 +40,7 @@ use git::{
         DiffTreeType, FileStatus, GitSummary, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus,
         UnmergedStatus, UnmergedStatusCode,
     },
+    submodule::{Submodule, SubmoduleStatus},
 };
 use gpui::{
     App, AppContext, AsyncApp, Context, Entity, EventEmitter, SharedString, Subscription, Task,
@@ -53,7 +55,7 @@ use pending_op::{PendingOp, PendingOpId, PendingOps, PendingOpsSummary};
 use postage::stream::Stream as _;
 use rpc::{
     AnyProtoClient, TypedEnvelope,
-    proto::{self, git_reset, split_repository_update},
+    proto::{self, git_bisect_mark, git_control_rebase, git_reset, split_repository_update},
 };
 use serde::Deserialize;
 use settings::WorktreeId;
@@ -275,6 +277,10 @@ pub struct RepositorySnapshot {
     pub remote_origin_url: Option<String>,
     pub remote_upstream_url: Option<String>,
     pub stash_entries: GitStash,
+    pub rebase: Option<RebaseProgress>,
+    /// Submodules declared in `.gitmodules`, sorted by path.
+    pub submodules: Arc<[Submodule]>,
+    pub bisect: Option<BisectProgress>,
 }
 
 type JobId = u64;
@@ -396,6 +402,8 @@ pub enum RepositoryEvent {
     MergeHeadsChanged,
     BranchChanged,
     StashEntriesChanged,
+    SubmodulesChanged,
+    BisectChanged,
     PendingOpsChanged { pending_ops: SumTree<PendingOps> },
     GitGraphCountUpdated((LogOrder, LogSource), usize),
 }
@@ -517,6 +525,19 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_stash_pop);
         client.add_entity_request_handler(Self::handle_stash_apply);
         client.add_entity_request_handler(Self::handle_stash_drop);
+        client.add_entity_request_handler(Self::handle_rebase_todo);
+        client.add_entity_request_handler(Self::handle_rebase_interactive);
+        client.add_entity_request_handler(Self::handle_control_rebase);
+        client.add_entity_request_handler(Self::handle_bisect_start);
+        client.add_entity_request_handler(Self::handle_bisect_mark);
+        client.add_entity_request_handler(Self::handle_bisect_reset);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_get_tags);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
+        client.add_entity_request_handler(Self::handle_push_tag);
+        client.add_entity_request_handler(Self::handle_update_submodules);
         client.add_entity_request_handler(Self::handle_commit);
         client.add_entity_request_handler(Self::handle_run_hook);
         client.add_entity_request_handler(Self::handle_reset);
@@ -1742,6 +1763,60 @@ impl GitStore {
             .max_by_key(|(repo, _)| repo.read(cx).work_directory_abs_path.clone())
     }
 
+    /// Returns the repository that declares a submodule at the given project path, along with
+    /// that submodule.
+    pub fn submodule_for_project_path(
+        &self,
+        path: &ProjectPath,
+        cx: &App,
+    ) -> Option<(Entity<Repository>, Submodule)> {
+        let abs_path = self.worktree_store.read(cx).absolutize(path, cx)?;
+        self.submodule_for_abs_path(&abs_path, cx)
+    }
+
+    /// Returns the parent of a repository that is checked out as a submodule, along with the
+    /// parent's entry for it.
+    pub fn parent_repository(
+        &self,
+        id: RepositoryId,
+        cx: &App,
+    ) -> Option<(Entity<Repository>, Submodule)> {
+        let abs_path = self
+            .repositories
+            .get(&id)?
+            .read(cx)
+            .work_directory_abs_path
+            .clone();
+        self.submodule_for_abs_path(&abs_path, cx)
+    }
+
+    /// Returns the repository checked out for a submodule of `parent`, if it has been initialized.
+    pub fn submodule_repository(
+        &self,
+        parent: &RepositorySnapshot,
+        submodule: &Submodule,
+        cx: &App,
+    ) -> Option<Entity<Repository>> {
+        let abs_path = parent.repo_path_to_abs_path(&submodule.path);
+        self.repositories
+            .values()
+            .find(|repo| *repo.read(cx).work_directory_abs_path == *abs_path)
+            .cloned()
+    }
+
+    fn submodule_for_abs_path(
+        &self,
+        abs_path: &Path,
+        cx: &App,
+    ) -> Option<(Entity<Repository>, Submodule)> {
+        self.repositories.values().find_map(|repo| {
+            let snapshot = repo.read(cx);
+            let repo_path = snapshot.abs_path_to_repo_path(abs_path)?;
+            let submodule = snapshot.submodule(&repo_path)?.clone();
+            Some((repo.clone(), submodule))
+        })
+    }
+
     pub fn git_init(
         &self,
         path: Arc<Path>,
@@ -2152,6 +2227,275 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_rebase_todo(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseTodo>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebaseTodoResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_todo(envelope.payload.base)
+            })
+            .await??;
+        Ok(proto::GitRebaseTodoResponse {
+            entries: entries.iter().map(rebase_todo_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_rebase_interactive(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseInteractive>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let todo = envelope
+            .payload
+            .todo
+            .iter()
+            .map(proto_to_rebase_todo_entry)
+            .collect();
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_interactive(envelope.payload.base, todo, cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_control_rebase(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitControlRebase>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let control = match envelope.payload.control() {
+            git_control_rebase::Control::Continue => RebaseControl::Continue,
+            git_control_rebase::Control::Skip => RebaseControl::Skip,
+            git_control_rebase::Control::Abort => RebaseControl::Abort,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.control_rebase(control, cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_start(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectStart>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect_start(envelope.payload.bad, envelope.payload.good, cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_mark(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectMark>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let term = match envelope.payload.term() {
+            git_bisect_mark::Term::Good => BisectTerm::Good,
+            git_bisect_mark::Term::Bad => BisectTerm::Bad,
+            git_bisect_mark::Term::Skip => BisectTerm::Skip,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect_mark(term, envelope.payload.commit, cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect_reset(cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.cherry_pick(envelope.payload.commit, cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_revert(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevert>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.revert(envelope.payload.commit, cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_tags(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetTags>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitTagsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| repository_handle.tags())
+            .await??;
+        Ok(proto::GitTagsResponse {
+            tags: tags.iter().map(tag_to_proto).collect(),
+        })
+    }
+
+    async fn handle_create_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let proto::GitCreateTag {
+            name,
+            commit,
+            message,
+            ..
+        } = envelope.payload;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.create_tag(name, commit, message, cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_delete_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.delete_tag(envelope.payload.name)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_push_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitPushTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let askpass_id = envelope.payload.askpass_id;
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.push_tag(
+                    envelope.payload.name.into(),
+                    envelope.payload.remote_name.into(),
+                    askpass,
+                    cx,
+                )
+            })
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
+    async fn handle_update_submodules(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitUpdateSubmodules>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|path| RepoPath::from_proto(path))
+            .collect::<Result<Vec<_>>>()?;
+
+        let askpass_id = envelope.payload.askpass_id;
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.update_submodules(paths, askpass, cx)
+            })
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
     async fn handle_set_index_text(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::SetIndexText>,
@@ -3433,6 +3777,9 @@ impl RepositorySnapshot {
             remote_origin_url: None,
             remote_upstream_url: None,
             stash_entries: Default::default(),
+            rebase: None,
+            submodules: Arc::default(),
+            bisect: None,
             path_style,
         }
     }
@@ -3468,6 +3815,9 @@ impl RepositorySnapshot {
                 .collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
+            rebase: self.rebase.as_ref().map(rebase_progress_to_proto),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
+            bisect: self.bisect.as_ref().map(bisect_progress_to_proto),
         }
     }
 
@@ -3539,6 +3889,9 @@ impl RepositorySnapshot {
                 .collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
+            rebase: self.rebase.as_ref().map(rebase_progress_to_proto),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
+            bisect: self.bisect.as_ref().map(bisect_progress_to_proto),
         }
     }
 
@@ -3556,6 +3909,14 @@ impl RepositorySnapshot {
             .cloned()
     }
 
+    pub fn submodule(&self, path: &RepoPath) -> Option<&Submodule> {
+        let ix = self
+            .submodules
+            .binary_search_by(|submodule| submodule.path.cmp(path))
+            .ok()?;
+        self.submodules.get(ix)
+    }
+
     pub fn abs_path_to_repo_path(&self, abs_path: &Path) -> Option<RepoPath> {
         Self::abs_path_to_repo_path_inner(&self.work_directory_abs_path, abs_path, self.path_style)
     }
@@ -4998,7 +5359,457 @@ impl Repository {
         })
     }
 
-    pub fn run_hook(&mut self, hook: RunHook, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+    pub fn rebase_todo(
+        &mut self,
+        base: Option<String>,
+    ) -> oneshot::Receiver<Result<Vec<RebaseTodoEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.rebase_todo(base).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitRebaseTodo {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                        })
+                        .await?;
+
+                    Ok(response
+                        .entries
+                        .iter()
+                        .map(proto_to_rebase_todo_entry)
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn rebase_interactive(
+        &mut self,
+        base: Option<String>,
+        todo: Vec<RebaseTodoEntry>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git rebase --interactive".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase_interactive(base, todo, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebaseInteractive {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                base,
+                                todo: todo.iter().map(rebase_todo_entry_to_proto).collect(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn control_rebase(
+        &mut self,
+        control: RebaseControl,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git rebase --{}", control.as_str()).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.control_rebase(control, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitControlRebase {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                control: match control {
+                                    RebaseControl::Continue => {
+                                        git_control_rebase::Control::Continue.into()
+                                    }
+                                    RebaseControl::Skip => git_control_rebase::Control::Skip.into(),
+                                    RebaseControl::Abort => {
+                                        git_control_rebase::Control::Abort.into()
+                                    }
+                                },
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Starts a bisect, marking `bad` and `good` if given.
+    pub fn bisect_start(
+        &mut self,
+        bad: Option<String>,
+        good: Vec<String>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git bisect start".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_start(bad, good, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectStart {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                bad,
+                                good,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Marks `commit`, or HEAD when `commit` is `None`, and checks out the next commit to test.
+    pub fn bisect_mark(
+        &mut self,
+        term: BisectTerm,
+        commit: Option<String>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git bisect {}", term.as_str()).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_mark(term, commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectMark {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                term: match term {
+                                    BisectTerm::Good => git_bisect_mark::Term::Good.into(),
+                                    BisectTerm::Bad => git_bisect_mark::Term::Bad.into(),
+                                    BisectTerm::Skip => git_bisect_mark::Term::Skip.into(),
+                                },
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_reset(&mut self, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git bisect reset".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_reset(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectReset {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn cherry_pick(&mut self, commit: String, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git cherry-pick {commit}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.cherry_pick(commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn revert(&mut self, commit: String, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git revert {commit}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.revert(commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRevert {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn tags(&mut self) -> oneshot::Receiver<Result<Vec<Tag>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.tags().await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitGetTags {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.tags.iter().map(proto_to_tag).collect())
+                }
+            }
+        })
+    }
+
+    pub fn create_tag(
+        &mut self,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status_msg = match &commit {
+            Some(commit) => format!("git tag {name} {commit}"),
+            None => format!("git tag {name}"),
+        };
+        self.send_job(Some(status_msg.into()), move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.create_tag(name, commit, message, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitCreateTag {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            name,
+                            commit,
+                            message,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn delete_tag(&mut self, name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag -d {name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(state) => state.backend.delete_tag(name).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitDeleteTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn push_tag(
+        &mut self,
+        name: SharedString,
+        remote: SharedString,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git push {remote} refs/tags/{name}").into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .push_tag(
+                                name.to_string(),
+                                remote.to_string(),
+                                askpass,
+                                environment,
+                                cx,
+                            )
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let response = client
+                            .request(proto::GitPushTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name: name.to_string(),
+                                remote_name: remote.to_string(),
+                                askpass_id,
+                            })
+                            .await
+                            .context("sending push tag request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
+    /// Initializes the submodules at `paths` if needed and checks out their recorded commits,
+    /// or updates every submodule when `paths` is empty.
+    pub fn update_submodules(
+        &mut self,
+        paths: Vec<RepoPath>,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+
+        let status = if paths.is_empty() {
+            "git submodule update --init".to_string()
+        } else {
+            format!(
+                "git submodule update --init -- {}",
+                paths
+                    .iter()
+                    .map(|path| path.as_unix_str())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+        };
+        self.send_job(Some(status.into()), move |git_repo, cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => {
+                    backend
+                        .update_submodules(paths, askpass, environment, cx)
+                        .await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    askpass_delegates.lock().insert(askpass_id, askpass);
+                    let _defer = util::defer(|| {
+                        let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                        debug_assert!(askpass_delegate.is_some());
+                    });
+                    let response = client
+                        .request(proto::GitUpdateSubmodules {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            paths: paths.iter().map(|path| path.to_proto()).collect(),
+                            askpass_id,
+                        })
+                        .await
+                        .context("sending update submodules request")?;
+
+                    Ok(RemoteCommandOutput {
+                        stdout: response.stdout,
+                        stderr: response.stderr,
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn run_hook(&mut self, hook: RunHook, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
         self.send_job(
             Some(format!("git hook {}", hook.as_str()).into()),
@@ -5855,6 +6666,16 @@ impl Repository {
 
         self.snapshot.merge.conflicted_paths = conflicted_paths;
         self.snapshot.merge.message = update.merge_message.map(SharedString::from);
+        let new_rebase = update.rebase.as_ref().map(proto_to_rebase_progress);
+        if self.snapshot.rebase != new_rebase {
+            cx.emit(RepositoryEvent::MergeHeadsChanged)
+        }
+        self.snapshot.rebase = new_rebase;
+        let new_bisect = update.bisect.as_ref().map(proto_to_bisect_progress);
+        if self.snapshot.bisect != new_bisect {
+            cx.emit(RepositoryEvent::BisectChanged)
+        }
+        self.snapshot.bisect = new_bisect;
         let new_stash_entries = GitStash {
             entries: update
                 .stash_entries
@@ -5866,6 +6687,15 @@ impl Repository {
             cx.emit(RepositoryEvent::StashEntriesChanged)
         }
         self.snapshot.stash_entries = new_stash_entries;
+        let new_submodules = update
+            .submodules
+            .iter()
+            .filter_map(|submodule| proto_to_submodule(submodule).log_err())
+            .collect::<Arc<[_]>>();
+        if self.snapshot.submodules != new_submodules {
+            cx.emit(RepositoryEvent::SubmodulesChanged)
+        }
+        self.snapshot.submodules = new_submodules;
         self.snapshot.remote_upstream_url = update.remote_upstream_url;
         self.snapshot.remote_origin_url = update.remote_origin_url;
 
@@ -6503,6 +7333,144 @@ fn branch_to_proto(branch: &git::repository::Branch) -> proto::Branch {
     }
 }
 
+fn rebase_progress_to_proto(progress: &RebaseProgress) -> proto::RebaseProgress {
+    proto::RebaseProgress {
+        head_name: progress.head_name.as_ref().map(|name| name.to_string()),
+        onto: progress.onto.to_string(),
+        step: progress.step as u64,
+        total_steps: progress.total_steps as u64,
+    }
+}
+
+fn proto_to_rebase_progress(proto: &proto::RebaseProgress) -> RebaseProgress {
+    RebaseProgress {
+        head_name: proto.head_name.clone().map(SharedString::from),
+        onto: proto.onto.clone().into(),
+        step: proto.step as usize,
+        total_steps: proto.total_steps as usize,
+    }
+}
+
+fn bisect_progress_to_proto(progress: &BisectProgress) -> proto::BisectProgress {
+    let to_strings = |shas: &[SharedString]| shas.iter().map(|sha| sha.to_string()).collect();
+    proto::BisectProgress {
+        bad: progress.bad.as_ref().map(|sha| sha.to_string()),
+        good: to_strings(&progress.good),
+        skipped: to_strings(&progress.skipped),
+        remaining: to_strings(&progress.remaining),
+        first_bad: progress.first_bad.as_ref().map(|sha| sha.to_string()),
+    }
+}
+
+fn proto_to_bisect_progress(proto: &proto::BisectProgress) -> BisectProgress {
+    let to_shas = |strings: &[String]| strings.iter().cloned().map(SharedString::from).collect();
+    BisectProgress {
+        bad: proto.bad.clone().map(SharedString::from),
+        good: to_shas(&proto.good),
+        skipped: to_shas(&proto.skipped),
+        remaining: to_shas(&proto.remaining),
+        first_bad: proto.first_bad.clone().map(SharedString::from),
+    }
+}
+
+fn rebase_todo_entry_to_proto(entry: &RebaseTodoEntry) -> proto::RebaseTodoEntry {
+    use proto::rebase_todo_entry::Action;
+
+    let action = match entry.action {
+        RebaseTodoAction::Pick => Action::Pick,
+        RebaseTodoAction::Reword => Action::Reword,
+        RebaseTodoAction::Squash => Action::Squash,
+        RebaseTodoAction::Fixup => Action::Fixup,
+        RebaseTodoAction::Drop => Action::Drop,
+    };
+    proto::RebaseTodoEntry {
+        action: action.into(),
+        sha: entry.sha.to_string(),
+        subject: entry.subject.to_string(),
+        message: entry.message.to_string(),
+    }
+}
+
+fn proto_to_rebase_todo_entry(proto: &proto::RebaseTodoEntry) -> RebaseTodoEntry {
+    use proto::rebase_todo_entry::Action;
+
+    let action = match proto.action() {
+        Action::Pick => RebaseTodoAction::Pick,
+        Action::Reword => RebaseTodoAction::Reword,
+        Action::Squash => RebaseTodoAction::Squash,
+        Action::Fixup => RebaseTodoAction::Fixup,
+        Action::Drop => RebaseTodoAction::Drop,
+    };
+    RebaseTodoEntry {
+        action,
+        sha: proto.sha.clone().into(),
+        subject: proto.subject.clone().into(),
+        message: proto.message.clone().into(),
+    }
+}
+
+fn tag_to_proto(tag: &Tag) -> proto::Tag {
+    proto::Tag {
+        name: tag.name.to_string(),
+        sha: tag.sha.to_string(),
+        message: tag.message.as_ref().map(|message| message.to_string()),
+    }
+}
+
+fn proto_to_tag(proto: &proto::Tag) -> Tag {
+    Tag {
+        name: proto.name.clone().into(),
+        sha: proto.sha.clone().into(),
+        message: proto.message.clone().map(Into::into),
+    }
+}
+
+fn submodule_to_proto(submodule: &Submodule) -> proto::Submodule {
+    use proto::submodule::Status;
+
+    let status = match submodule.status {
+        SubmoduleStatus::Uninitialized => Status::Uninitialized,
+        SubmoduleStatus::Current => Status::Current,
+        SubmoduleStatus::Modified => Status::Modified,
+        SubmoduleStatus::Conflicted => Status::Conflicted,
+    };
+    proto::Submodule {
+        name: submodule.name.to_string(),
+        path: submodule.path.to_proto(),
+        url: submodule.url.as_ref().map(|url| url.to_string()),
+        recorded_sha: submodule.recorded_sha.map(|sha| sha.to_string()),
+        checked_out_sha: submodule.checked_out_sha.map(|sha| sha.to_string()),
+        status: status.into(),
+    }
+}
+
+fn proto_to_submodule(proto: &proto::Submodule) -> Result<Submodule> {
+    use proto::submodule::Status;
+
+    let status = match proto.status() {
+        Status::Uninitialized => SubmoduleStatus::Uninitialized,
+        Status::Current => SubmoduleStatus::Current,
+        Status::Modified => SubmoduleStatus::Modified,
+        Status::Conflicted => SubmoduleStatus::Conflicted,
+    };
+    Ok(Submodule {
+        name: proto.name.clone().into(),
+        path: RepoPath::from_proto(&proto.path)?,
+        url: proto.url.clone().map(Into::into),
+        recorded_sha: proto
+            .recorded_sha
+            .as_deref()
+            .map(Oid::from_str)
+            .transpose()?,
+        checked_out_sha: proto
+            .checked_out_sha
+            .as_deref()
+            .map(Oid::from_str)
+            .transpose()?,
+        status,
+    })
+}
+
 fn worktree_to_proto(worktree: &git::repository::Worktree) -> proto::Worktree {
     proto::Worktree {
         path: worktree.path.to_string_lossy().to_string(),
@@ -6586,6 +7554,15 @@ async fn compute_snapshot(
         )])
         .await?;
     let stash_entries = backend.stash_entries().await?;
+    let submodules: Arc<[Submodule]> = backend
+        .submodules()
+        .await
+        .log_err()
+        .unwrap_or_default()
+        .into();
+    if submodules != prev_snapshot.submodules {
+        events.push(RepositoryEvent::SubmodulesChanged);
+    }
     let statuses_by_path = SumTree::from_iter(
         statuses
             .entries
@@ -6599,11 +7576,17 @@ async fn compute_snapshot(
     let (merge_details, merge_heads_changed) =
         MergeDetails::load(&backend, &statuses_by_path, &prev_snapshot).await?;
     log::debug!("new merge details (changed={merge_heads_changed:?}): {merge_details:?}");
+    let rebase = backend.rebase_progress().await;
 
-    if merge_heads_changed {
+    if merge_heads_changed || rebase != prev_snapshot.rebase {
         events.push(RepositoryEvent::MergeHeadsChanged);
     }
 
+    let bisect = backend.bisect_progress().await.log_err().flatten();
+    if bisect != prev_snapshot.bisect {
+        events.push(RepositoryEvent::BisectChanged);
+    }
+
     if statuses_by_path != prev_snapshot.statuses_by_path {
         events.push(RepositoryEvent::StatusesChanged)
     }
@@ -6633,6 +7616,9 @@ async fn compute_snapshot(
         remote_origin_url,
         remote_upstream_url,
         stash_entries,
+        rebase,
+        submodules,
+        bisect,
     };
 
     Ok((snapshot, events))
@@ -198,7 +198,7 @@ impl ConflictSet {
 
                 let branch_name = branch_name.trim();
                 if !branch_name.is_empty() {
-                    ours_branch_name = Some(SharedString::new(branch_name));
+                    ours_branch_name = Some(conflict_marker_label(branch_name));
                 }
             } else if line.starts_with("||||||| ")
                 && conflict_start.is_some()
@@ -225,7 +225,7 @@ impl ConflictSet {
             {
                 let branch_name = branch_name.trim();
                 if !branch_name.is_empty() {
-                    theirs_branch_name = Some(SharedString::new(branch_name));
+                    theirs_branch_name = Some(conflict_marker_label(branch_name));
                 }
 
                 let theirs_end = line_pos;
@@ -273,4 +273,19 @@ impl ConflictSet {
     }
 }
 
+/// Cherry-picks, reverts and rebases label their side of a conflict with the commit being
+/// applied, followed by its subject (e.g. `1a2b3c4 (Fix typo)` or `parent of 1a2b3c4 (Fix typo)`).
+/// The subject is dropped so that the label stays short.
+fn conflict_marker_label(label: &str) -> SharedString {
+    if let Some((commit, _subject)) = label.strip_suffix(')').and_then(|l| l.split_once(" ("))
+        && commit
+            .rsplit(' ')
+            .next()
+            .is_some_and(|sha| sha.len() >= 4 && sha.chars().all(|c| c.is_ascii_hexdigit()))
+    {
+        return SharedString::new(commit);
+    }
+    SharedString::new(label)
+}
+
 impl EventEmitter<ConflictSetUpdate> for ConflictSet {}
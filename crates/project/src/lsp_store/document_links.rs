@@ -0,0 +1,413 @@
+use std::ops::Range;
+use std::str::FromStr as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+use clock::Global;
+use collections::HashMap;
+use futures::FutureExt as _;
+use futures::future::{Shared, join_all};
+use gpui::{AppContext as _, AsyncApp, Context, Entity, Task};
+use itertools::Itertools;
+use language::{
+    Bias, Buffer, BufferSnapshot, ToPointUtf16 as _,
+    proto::{deserialize_anchor_range, serialize_anchor_range},
+    range_from_lsp, range_to_lsp,
+};
+use lsp::LanguageServerId;
+use rpc::{TypedEnvelope, proto};
+use settings::Settings as _;
+use text::{Anchor, BufferId};
+use util::ResultExt as _;
+
+use crate::lsp_command::{GetDocumentLinks, LspCommand as _};
+use crate::lsp_store::LspStore;
+use crate::project_settings::ProjectSettings;
+
+/// A range in a buffer that links to another document or a web site, as reported by a language server.
+#[derive(Clone, Debug)]
+pub struct DocumentLink {
+    pub range: Range<Anchor>,
+    /// The resource the link points to.
+    /// Language servers may omit it and provide it only when the link is resolved.
+    pub target: Option<lsp::Uri>,
+    pub tooltip: Option<String>,
+    pub server_id: LanguageServerId,
+    data: Option<serde_json::Value>,
+}
+
+impl DocumentLink {
+    pub(crate) fn from_lsp(
+        link: lsp::DocumentLink,
+        server_id: LanguageServerId,
+        snapshot: &BufferSnapshot,
+    ) -> Self {
+        let range = range_from_lsp(link.range);
+        let start = snapshot.clip_point_utf16(range.start, Bias::Left);
+        let end = snapshot.clip_point_utf16(range.end, Bias::Left);
+        Self {
+            range: snapshot.anchor_after(start)..snapshot.anchor_before(end),
+            target: link.target,
+            tooltip: link.tooltip,
+            server_id,
+            data: link.data,
+        }
+    }
+
+    fn to_lsp(&self, snapshot: &BufferSnapshot) -> Result<lsp::DocumentLink> {
+        Ok(lsp::DocumentLink {
+            range: range_to_lsp(
+                self.range.start.to_point_utf16(snapshot)..self.range.end.to_point_utf16(snapshot),
+            )?,
+            target: self.target.clone(),
+            tooltip: self.tooltip.clone(),
+            data: self.data.clone(),
+        })
+    }
+
+    pub(crate) fn to_proto(&self) -> proto::DocumentLink {
+        proto::DocumentLink {
+            range: Some(serialize_anchor_range(self.range.clone())),
+            target: self.target.as_ref().map(|target| target.to_string()),
+            tooltip: self.tooltip.clone(),
+            data: self
+                .data
+                .as_ref()
+                .and_then(|data| serde_json::to_string(data).log_err()),
+            server_id: self.server_id.to_proto(),
+        }
+    }
+
+    pub(crate) fn from_proto(link: proto::DocumentLink) -> Result<Self> {
+        Ok(Self {
+            range: deserialize_anchor_range(link.range.context("missing document link range")?)?,
+            target: link
+                .target
+                .map(|target| lsp::Uri::from_str(&target))
+                .transpose()
+                .context("invalid document link target")?,
+            tooltip: link.tooltip,
+            server_id: LanguageServerId::from_proto(link.server_id),
+            data: link
+                .data
+                .map(|data| serde_json::from_str(&data))
+                .transpose()
+                .context("invalid document link data")?,
+        })
+    }
+}
+
+pub(super) type DocumentLinkTask =
+    Shared<Task<std::result::Result<Vec<DocumentLink>, Arc<anyhow::Error>>>>;
+
+#[derive(Debug, Default)]
+pub(super) struct DocumentLinkData {
+    pub(super) links: HashMap<LanguageServerId, Vec<DocumentLink>>,
+    links_update: Option<(Global, DocumentLinkTask)>,
+}
+
+impl LspStore {
+    /// Returns a task that resolves to the document links for the given buffer, sorted by their start.
+    ///
+    /// Caches results per buffer version so repeated calls for the same version
+    /// return immediately. Deduplicates concurrent in-flight requests.
+    pub fn fetch_document_links(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Vec<DocumentLink>> {
+        let version_queried_for = buffer.read(cx).version();
+        let buffer_id = buffer.read(cx).remote_id();
+
+        let current_language_servers = self.as_local().map(|local| {
+            local
+                .buffers_opened_in_servers
+                .get(&buffer_id)
+                .cloned()
+                .unwrap_or_default()
+        });
+
+        if let Some(lsp_data) = self.current_lsp_data(buffer_id) {
+            if let Some(cached) = &lsp_data.document_links {
+                if !version_queried_for.changed_since(&lsp_data.buffer_version) {
+                    let has_different_servers =
+                        current_language_servers.is_some_and(|current_language_servers| {
+                            current_language_servers != cached.links.keys().copied().collect()
+                        });
+                    if !has_different_servers {
+                        let snapshot = buffer.read(cx).snapshot();
+                        return Task::ready(
+                            cached
+                                .links
+                                .values()
+                                .flatten()
+                                .cloned()
+                                .sorted_by(|a, b| a.range.start.cmp(&b.range.start, &snapshot))
+                                .collect(),
+                        );
+                    }
+                }
+            }
+        }
+
+        let links_lsp_data = self
+            .latest_lsp_data(buffer, cx)
+            .document_links
+            .get_or_insert_default();
+        if let Some((updating_for, running_update)) = &links_lsp_data.links_update {
+            if !version_queried_for.changed_since(updating_for) {
+                let running = running_update.clone();
+                return cx.background_spawn(async move { running.await.unwrap_or_default() });
+            }
+        }
+
+        let buffer = buffer.clone();
+        let query_version = version_queried_for.clone();
+        let new_task = cx
+            .spawn(async move |lsp_store, cx| {
+                cx.background_executor()
+                    .timer(Duration::from_millis(30))
+                    .await;
+
+                let fetched = lsp_store
+                    .update(cx, |lsp_store, cx| {
+                        lsp_store.fetch_document_links_for_buffer(&buffer, cx)
+                    })
+                    .map_err(Arc::new)?
+                    .await
+                    .context("fetching document links")
+                    .map_err(Arc::new);
+
+                let fetched = match fetched {
+                    Ok(fetched) => fetched,
+                    Err(e) => {
+                        lsp_store
+                            .update(cx, |lsp_store, _| {
+                                if let Some(lsp_data) = lsp_store.lsp_data.get_mut(&buffer_id) {
+                                    if let Some(document_links) = &mut lsp_data.document_links {
+                                        document_links.links_update = None;
+                                    }
+                                }
+                            })
+                            .ok();
+                        return Err(e);
+                    }
+                };
+
+                lsp_store
+                    .update(cx, |lsp_store, cx| {
+                        let lsp_data = lsp_store.latest_lsp_data(&buffer, cx);
+                        let document_links = lsp_data.document_links.get_or_insert_default();
+
+                        if let Some(fetched_links) = fetched {
+                            if lsp_data.buffer_version == query_version {
+                                document_links.links.extend(fetched_links);
+                            } else if !lsp_data.buffer_version.changed_since(&query_version) {
+                                lsp_data.buffer_version = query_version;
+                                document_links.links = fetched_links;
+                            }
+                        }
+                        document_links.links_update = None;
+                        let snapshot = buffer.read(cx).snapshot();
+                        document_links
+                            .links
+                            .values()
+                            .flatten()
+                            .cloned()
+                            .sorted_by(|a, b| a.range.start.cmp(&b.range.start, &snapshot))
+                            .collect()
+                    })
+                    .map_err(Arc::new)
+            })
+            .shared();
+
+        links_lsp_data.links_update = Some((version_queried_for, new_task.clone()));
+
+        cx.background_spawn(async move { new_task.await.unwrap_or_default() })
+    }
+
+    fn fetch_document_links_for_buffer(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<Option<HashMap<LanguageServerId, Vec<DocumentLink>>>>> {
+        if let Some((client, project_id)) = self.upstream_client() {
+            let request = GetDocumentLinks;
+            if !self.is_capable_for_proto_request(buffer, &request, cx) {
+                return Task::ready(Ok(None));
+            }
+
+            let request_timeout = ProjectSettings::get_global(cx)
+                .global_lsp_settings
+                .get_request_timeout();
+            let request_task = client.request_lsp(
+                project_id,
+                None,
+                request_timeout,
+                cx.background_executor().clone(),
+                request.to_proto(project_id, buffer.read(cx)),
+            );
+            let buffer = buffer.clone();
+            cx.spawn(async move |weak_lsp_store, cx| {
+                let Some(lsp_store) = weak_lsp_store.upgrade() else {
+                    return Ok(None);
+                };
+                let Some(responses) = request_task.await? else {
+                    return Ok(None);
+                };
+
+                let document_links = join_all(responses.payload.into_iter().map(|response| {
+                    let lsp_store = lsp_store.clone();
+                    let buffer = buffer.clone();
+                    let cx = cx.clone();
+                    async move {
+                        (
+                            LanguageServerId::from_proto(response.server_id),
+                            GetDocumentLinks
+                                .response_from_proto(response.response, lsp_store, buffer, cx)
+                                .await,
+                        )
+                    }
+                }))
+                .await;
+
+                let mut has_errors = false;
+                let result = document_links
+                    .into_iter()
+                    .filter_map(|(server_id, links)| match links {
+                        Ok(links) => Some((server_id, links)),
+                        Err(e) => {
+                            has_errors = true;
+                            log::error!("Failed to fetch document links: {e:#}");
+                            None
+                        }
+                    })
+                    .collect::<HashMap<_, _>>();
+                anyhow::ensure!(
+                    !has_errors || !result.is_empty(),
+                    "Failed to fetch document links"
+                );
+                Ok(Some(result))
+            })
+        } else {
+            let links_task =
+                self.request_multiple_lsp_locally(buffer, None::<usize>, GetDocumentLinks, cx);
+            cx.background_spawn(async move { Ok(Some(links_task.await.into_iter().collect())) })
+        }
+    }
+
+    /// Fills in the target of a document link that was reported without one,
+    /// using the `documentLink/resolve` request of the language server that produced it.
+    pub fn resolve_document_link(
+        &mut self,
+        link: DocumentLink,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DocumentLink>> {
+        if link.target.is_some() {
+            return Task::ready(Ok(link));
+        }
+
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            let request = proto::ResolveDocumentLink {
+                project_id,
+                buffer_id: buffer.read(cx).remote_id().into(),
+                link: Some(link.to_proto()),
+            };
+            cx.background_spawn(async move {
+                let response = upstream_client
+                    .request(request)
+                    .await
+                    .context("document link resolve proto request")?;
+                DocumentLink::from_proto(response.link.context("missing resolved document link")?)
+            })
+        } else {
+            let Some(lang_server) = buffer.update(cx, |buffer, cx| {
+                self.language_server_for_local_buffer(buffer, link.server_id, cx)
+                    .map(|(_, server)| server.clone())
+            }) else {
+                return Task::ready(Ok(link));
+            };
+            let supports_resolve = lang_server
+                .capabilities()
+                .document_link_provider
+                .and_then(|options| options.resolve_provider)
+                .unwrap_or(false);
+            if !supports_resolve {
+                return Task::ready(Ok(link));
+            }
+
+            let snapshot = buffer.read(cx).snapshot();
+            let lsp_link = match link.to_lsp(&snapshot) {
+                Ok(lsp_link) => lsp_link,
+                Err(e) => return Task::ready(Err(e)),
+            };
+            let request_timeout = ProjectSettings::get_global(cx)
+                .global_lsp_settings
+                .get_request_timeout();
+            cx.spawn(async move |lsp_store, cx| {
+                let resolved_link = lang_server
+                    .request::<lsp::request::DocumentLinkResolve>(lsp_link, request_timeout)
+                    .await
+                    .into_response()
+                    .context("document link resolve LSP request")?;
+                let resolved_link = DocumentLink {
+                    range: link.range,
+                    target: resolved_link.target,
+                    tooltip: resolved_link.tooltip.or(link.tooltip),
+                    server_id: link.server_id,
+                    data: resolved_link.data,
+                };
+                lsp_store
+                    .update(cx, |lsp_store, _| {
+                        let cached_link = lsp_store
+                            .lsp_data
+                            .get_mut(&snapshot.remote_id())
+                            .filter(|lsp_data| lsp_data.buffer_version == *snapshot.version())
+                            .and_then(|lsp_data| lsp_data.document_links.as_mut())
+                            .and_then(|document_links| {
+                                document_links.links.get_mut(&resolved_link.server_id)
+                            })
+                            .and_then(|links| {
+                                links.iter_mut().find(|cached_link| {
+                                    cached_link.target.is_none()
+                                        && cached_link.range == resolved_link.range
+                                })
+                            });
+                        if let Some(cached_link) = cached_link {
+                            *cached_link = resolved_link.clone();
+                        }
+                    })
+                    .ok();
+                Ok(resolved_link)
+            })
+        }
+    }
+
+    pub(super) async fn handle_resolve_document_link(
+        lsp_store: Entity<Self>,
+        envelope: TypedEnvelope<proto::ResolveDocumentLink>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::ResolveDocumentLinkResponse> {
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let buffer = lsp_store.update(&mut cx, |lsp_store, cx| {
+            lsp_store.buffer_store.read(cx).get_existing(buffer_id)
+        })?;
+        let link = DocumentLink::from_proto(
+            envelope
+                .payload
+                .link
+                .context("invalid document link resolve request")?,
+        )?;
+        let resolved_link = lsp_store
+            .update(&mut cx, |lsp_store, cx| {
+                lsp_store.resolve_document_link(link, buffer, cx)
+            })
+            .await
+            .context("resolving document link")?;
+        Ok(proto::ResolveDocumentLinkResponse {
+            link: Some(resolved_link.to_proto()),
+        })
+    }
+}
@@ -0,0 +1,195 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Result};
+use collections::{HashMap, HashSet};
+use gpui::{AsyncApp, Context, Entity, Task};
+use language::{Diagnostic, DiagnosticEntry, DiagnosticSourceKind, PointUtf16, Unclipped};
+use lsp::{DiagnosticSeverity, LanguageServerId, NumberOrString};
+use rpc::{TypedEnvelope, proto};
+use task::{Problem, ProblemSeverity, TaskId};
+use util::{ResultExt as _, paths::normalize_lexically, post_inc};
+
+use crate::lsp_store::{DocumentDiagnostics, DocumentDiagnosticsUpdate, LspStore};
+
+/// Diagnostics reported by a task's problem matchers.
+/// Each task gets its own diagnostics "server", keyed by the task's [`task::SpawnInTerminal::diagnostics_id`],
+/// so that its diagnostics are replaced on every rerun, without affecting the diagnostics of the language servers
+/// or other tasks.
+#[derive(Debug)]
+pub(super) struct TaskDiagnostics {
+    server_id: LanguageServerId,
+    abs_paths: HashSet<PathBuf>,
+}
+
+impl LspStore {
+    /// Replaces the diagnostics reported by the task with the given diagnostics id with the problems found
+    /// in its latest output. In remote projects, the problems are reported to the host, which runs the task.
+    ///
+    /// Relative problem paths are resolved against the task's working directory, or the first visible worktree,
+    /// and problems in files outside of the project's worktrees are ignored.
+    pub fn update_task_diagnostics(
+        &mut self,
+        diagnostics_id: TaskId,
+        cwd: Option<PathBuf>,
+        problems: Vec<Problem>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if let Some((client, project_id)) = self.upstream_client() {
+            let request = client.request(proto::UpdateTaskDiagnostics {
+                project_id,
+                diagnostics_id: diagnostics_id.0,
+                cwd: cwd.map(|cwd| cwd.to_string_lossy().into_owned()),
+                problems: problems.iter().map(Problem::to_proto).collect(),
+            });
+            return cx.background_spawn(async move {
+                request.await?;
+                Ok(())
+            });
+        }
+        Task::ready(self.update_local_task_diagnostics(
+            diagnostics_id,
+            cwd.as_deref(),
+            problems,
+            cx,
+        ))
+    }
+
+    pub(super) async fn handle_update_task_diagnostics(
+        lsp_store: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateTaskDiagnostics>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        lsp_store.update(&mut cx, |lsp_store, cx| {
+            lsp_store.update_local_task_diagnostics(
+                TaskId(envelope.payload.diagnostics_id),
+                envelope.payload.cwd.as_deref().map(Path::new),
+                envelope
+                    .payload
+                    .problems
+                    .into_iter()
+                    .map(Problem::from_proto)
+                    .collect(),
+                cx,
+            )
+        })?;
+        Ok(proto::Ack {})
+    }
+
+    fn update_local_task_diagnostics(
+        &mut self,
+        diagnostics_id: TaskId,
+        cwd: Option<&Path>,
+        problems: Vec<Problem>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let root = cwd.map(Path::to_path_buf).or_else(|| {
+            self.worktree_store
+                .read(cx)
+                .visible_worktrees(cx)
+                .next()
+                .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+        });
+        let worktree_store = self.worktree_store.clone();
+        let languages = self.languages.clone();
+        let local = self
+            .as_local_mut()
+            .context("task diagnostics can only be reported for local projects")?;
+
+        let mut diagnostics_by_path =
+            HashMap::<PathBuf, Vec<DiagnosticEntry<Unclipped<PointUtf16>>>>::default();
+        for problem in problems {
+            let abs_path = match &root {
+                Some(root) if problem.file.is_relative() => root.join(&problem.file),
+                _ => problem.file.clone(),
+            };
+            let Some(abs_path) = normalize_lexically(&abs_path).log_err() else {
+                continue;
+            };
+            if worktree_store
+                .read(cx)
+                .find_worktree(&abs_path, cx)
+                .is_none()
+            {
+                log::debug!("skipping task problem in {abs_path:?}, no worktree found for it");
+                continue;
+            }
+            let group_id = post_inc(&mut local.next_diagnostic_group_id);
+            diagnostics_by_path
+                .entry(abs_path)
+                .or_default()
+                .push(diagnostic_entry(problem, group_id));
+        }
+
+        let task_diagnostics = local
+            .task_diagnostics
+            .entry(diagnostics_id)
+            .or_insert_with(|| TaskDiagnostics {
+                server_id: languages.next_language_server_id(),
+                abs_paths: HashSet::default(),
+            });
+        let server_id = task_diagnostics.server_id;
+        let stale_abs_paths = std::mem::replace(
+            &mut task_diagnostics.abs_paths,
+            diagnostics_by_path.keys().cloned().collect(),
+        );
+        for stale_abs_path in stale_abs_paths {
+            diagnostics_by_path.entry(stale_abs_path).or_default();
+        }
+
+        let updates = diagnostics_by_path
+            .into_iter()
+            .map(
+                |(document_abs_path, diagnostics)| DocumentDiagnosticsUpdate {
+                    diagnostics: DocumentDiagnostics {
+                        diagnostics,
+                        document_abs_path,
+                        version: None,
+                    },
+                    result_id: None,
+                    registration_id: None,
+                    server_id,
+                    disk_based_sources: Cow::Borrowed(&[]),
+                },
+            )
+            .collect();
+        self.merge_diagnostic_entries(updates, |_, _, _| false, cx)
+    }
+}
+
+fn diagnostic_entry(problem: Problem, group_id: usize) -> DiagnosticEntry<Unclipped<PointUtf16>> {
+    let start = PointUtf16::new(
+        problem.line.saturating_sub(1),
+        problem.column.unwrap_or(1).saturating_sub(1),
+    );
+    let end = match (problem.end_line, problem.end_column) {
+        (Some(end_line), Some(end_column)) => {
+            PointUtf16::new(end_line.saturating_sub(1), end_column.saturating_sub(1))
+        }
+        (Some(end_line), None) => PointUtf16::new(end_line.saturating_sub(1), u32::MAX),
+        (None, Some(end_column)) => PointUtf16::new(start.row, end_column.saturating_sub(1)),
+        // Without a column, mark the whole line.
+        (None, None) if problem.column.is_none() => PointUtf16::new(start.row, u32::MAX),
+        (None, None) => start,
+    };
+    let severity = match problem.severity {
+        ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+        ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+        ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+        ProblemSeverity::Hint => DiagnosticSeverity::HINT,
+    };
+    DiagnosticEntry {
+        range: Unclipped(start)..Unclipped(end.max(start)),
+        diagnostic: Diagnostic {
+            source: problem.source,
+            code: problem.code.map(NumberOrString::String),
+            severity,
+            message: problem.message,
+            group_id,
+            is_primary: true,
+            is_disk_based: true,
+            source_kind: DiagnosticSourceKind::Other,
+            ..Diagnostic::default()
+        },
+    }
+}
@@ -30,6 +30,7 @@ pub use environment::ProjectEnvironmentEvent;
 use git::repository::get_git_committer;
 use git_store::{Repository, RepositoryId};
 pub mod search_history;
+pub mod search_index;
 pub mod yarn;
 
 use dap::inline_value::{InlineValueLocation, VariableLookupKind, VariableScope};
@@ -113,6 +114,7 @@ use rpc::{
 };
 use search::{SearchInputKind, SearchQuery, SearchResult};
 use search_history::SearchHistory;
+use search_index::SearchIndex;
 use settings::{InvalidSettingsError, RegisterSetting, Settings, SettingsLocation, SettingsStore};
 use snippet::Snippet;
 pub use snippet_provider;
@@ -151,7 +153,7 @@ pub use language::Location;
 pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
 pub use task_inventory::{
     BasicContextProvider, ContextProviderWithTasks, DebugScenarioContext, Inventory, TaskContexts,
-    TaskSourceKind,
+    TaskDependencies, TaskDependency, TaskSourceKind,
 };
 
 pub use buffer_store::ProjectTransaction;
@@ -226,6 +228,7 @@ pub struct Project {
     buffer_store: Entity<BufferStore>,
     context_server_store: Entity<ContextServerStore>,
     image_store: Entity<ImageStore>,
+    search_index: Option<Entity<SearchIndex>>,
     lsp_store: Entity<LspStore>,
     _subscriptions: Vec<gpui::Subscription>,
     buffers_needing_diff: HashSet<WeakEntity<Buffer>>,
@@ -845,6 +848,46 @@ pub struct DocumentSymbol {
     pub children: Vec<DocumentSymbol>,
 }
 
+/// A symbol that takes part in a call hierarchy, as returned by the language server.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The full range of the symbol, e.g. the whole function including its body.
+    pub location: Location,
+    /// The range that should be revealed when navigating to the symbol, e.g. the function name.
+    pub selection_range: Range<language::Anchor>,
+    pub server_id: LanguageServerId,
+    /// The original item, which has to be sent back to the server to query further calls.
+    pub lsp_item: lsp::CallHierarchyItem,
+}
+
+/// An incoming or outgoing call of a [`CallHierarchyItem`].
+#[derive(Clone, Debug)]
+pub struct CallHierarchyCall {
+    /// The caller for incoming calls, or the callee for outgoing calls.
+    pub item: CallHierarchyItem,
+    /// The call expressions. For incoming calls these are in the caller's buffer,
+    /// for outgoing calls they are in the buffer of the item whose calls were requested.
+    pub call_sites: Vec<Location>,
+}
+
+/// A type that takes part in a type hierarchy, as returned by the language server.
+#[derive(Clone, Debug)]
+pub struct TypeHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The full range of the type, e.g. the whole class including its body.
+    pub location: Location,
+    /// The range that should be revealed when navigating to the type, e.g. the class name.
+    pub selection_range: Range<language::Anchor>,
+    pub server_id: LanguageServerId,
+    /// The original item, which has to be sent back to the server to query related types.
+    pub lsp_item: lsp::TypeHierarchyItem,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HoverBlock {
     pub text: String,
@@ -1216,6 +1259,9 @@ impl Project {
             cx.subscribe(&image_store, Self::on_image_store_event)
                 .detach();
 
+            let search_index =
+                cx.new(|cx| SearchIndex::new(fs.clone(), worktree_store.clone(), cx));
+
             let prettier_store = cx.new(|cx| {
                 PrettierStore::new(
                     node.clone(),
@@ -1295,6 +1341,7 @@ impl Project {
                 worktree_store,
                 buffer_store,
                 image_store,
+                search_index: Some(search_index),
                 lsp_store,
                 context_server_store,
                 join_project_response_message_id: 0,
@@ -1510,6 +1557,7 @@ impl Project {
                 worktree_store,
                 buffer_store,
                 image_store,
+                search_index: None,
                 lsp_store,
                 context_server_store,
                 breakpoint_store,
@@ -1799,6 +1847,7 @@ impl Project {
                 buffer_ordered_messages_tx: tx,
                 buffer_store: buffer_store.clone(),
                 image_store,
+                search_index: None,
                 worktree_store: worktree_store.clone(),
                 lsp_store: lsp_store.clone(),
                 context_server_store,
@@ -4091,6 +4140,118 @@ impl Project {
         )
     }
 
+    /// Resolves the call hierarchy items for the symbol at the given position,
+    /// which can then be passed to [`Self::incoming_calls`] and [`Self::outgoing_calls`].
+    pub fn prepare_call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareCallHierarchy { position },
+            cx,
+        )
+    }
+
+    /// Returns the callers of the given call hierarchy item.
+    pub fn incoming_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            GetIncomingCalls {
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    /// Returns the callees of the given call hierarchy item.
+    pub fn outgoing_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            GetOutgoingCalls {
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    pub fn prepare_type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareTypeHierarchy { position },
+            cx,
+        )
+    }
+
+    /// Returns the direct supertypes of the given type hierarchy item.
+    pub fn supertypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            GetSupertypes {
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    /// Returns the direct subtypes of the given type hierarchy item.
+    pub fn subtypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            GetSubtypes {
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    /// Returns, for each of the given positions, the ranges the language server considers
+    /// meaningful selections around it, from the innermost to the outermost one.
+    pub fn selection_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Vec<Range<text::Anchor>>>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetSelectionRanges { positions },
+            cx,
+        )
+    }
+
     pub fn symbols(&self, query: &str, cx: &mut Context<Self>) -> Task<Result<Vec<Symbol>>> {
         self.lsp_store
             .update(cx, |lsp_store, cx| lsp_store.symbols(query, cx))
@@ -4373,6 +4534,7 @@ impl Project {
                     self.fs.clone(),
                     self.buffer_store.clone(),
                     self.worktree_store.clone(),
+                    self.search_index.clone(),
                     project_search::Search::MAX_SEARCH_RESULT_FILES + 1,
                     cx,
                 ),
@@ -5770,6 +5932,35 @@ impl Project {
             .any(|capabilities| capabilities.semantic_tokens_provider.is_some())
     }
 
+    /// Whether any language server for the buffer's language has a capability, as checked by
+    /// `supports`.
+    pub fn any_language_server_supports(
+        &self,
+        buffer: &Buffer,
+        supports: impl Fn(&lsp::ServerCapabilities) -> bool,
+        cx: &App,
+    ) -> bool {
+        let Some(language) = buffer.language().cloned() else {
+            return false;
+        };
+        let lsp_store = self.lsp_store.read(cx);
+        let relevant_language_servers = lsp_store
+            .languages
+            .lsp_adapters(&language.name())
+            .into_iter()
+            .map(|lsp_adapter| lsp_adapter.name())
+            .collect::<HashSet<_>>();
+        lsp_store
+            .language_server_statuses()
+            .filter_map(|(server_id, server_status)| {
+                relevant_language_servers
+                    .contains(&server_status.name)
+                    .then_some(server_id)
+            })
+            .filter_map(|server_id| lsp_store.lsp_server_capabilities.get(&server_id))
+            .any(supports)
+    }
+
     pub fn language_server_id_for_name(
         &self,
         buffer: &Buffer,
@@ -28,6 +28,7 @@ use crate::{
     Project, ProjectItem, ProjectPath, RemotelyCreatedModels,
     buffer_store::BufferStore,
     search::{SearchQuery, SearchResult},
+    search_index::{IndexQuery, SearchIndex},
     worktree_store::WorktreeStore,
 };
 
@@ -44,6 +45,7 @@ enum SearchKind {
     Local {
         fs: Arc<dyn Fs>,
         worktrees: Vec<Entity<Worktree>>,
+        search_index: Option<Entity<SearchIndex>>,
     },
     /// Query remote host for candidates. As of writing, the host runs a local search in "buffers with matches only" mode.
     Remote {
@@ -99,6 +101,8 @@ enum FindSearchCandidates {
         /// based on disk contents of a buffer. This step is not performed for buffers we already have in memory.
         confirm_contents_will_match_tx: Sender<MatchingEntry>,
         confirm_contents_will_match_rx: Receiver<MatchingEntry>,
+        /// Rules out files whose contents can't match before they're read, when indexed.
+        index_query: Option<Arc<IndexQuery>>,
     },
     Remote,
     OpenBuffersOnly,
@@ -109,12 +113,17 @@ impl Search {
         fs: Arc<dyn Fs>,
         buffer_store: Entity<BufferStore>,
         worktree_store: Entity<WorktreeStore>,
+        search_index: Option<Entity<SearchIndex>>,
         limit: usize,
         cx: &mut App,
     ) -> Self {
         let worktrees = worktree_store.read(cx).visible_worktrees(cx).collect();
         Self {
-            kind: SearchKind::Local { fs, worktrees },
+            kind: SearchKind::Local {
+                fs,
+                worktrees,
+                search_index,
+            },
             buffer_store,
             worktree_store,
             limit,
@@ -202,7 +211,11 @@ impl Search {
                     SearchKind::Local {
                         fs,
                         ref mut worktrees,
+                        ref search_index,
                     } => {
+                        let index_query = search_index.as_ref().and_then(|search_index| {
+                            search_index.read_with(cx, |search_index, _| search_index.query(&query))
+                        });
                         let (get_buffer_for_full_scan_tx, get_buffer_for_full_scan_rx) =
                             unbounded();
                         let (confirm_contents_will_match_tx, confirm_contents_will_match_rx) =
@@ -238,6 +251,7 @@ impl Search {
                                 confirm_contents_will_match_tx,
                                 confirm_contents_will_match_rx,
                                 input_paths_rx,
+                                index_query,
                             },
                             tasks,
                         )
@@ -637,20 +651,23 @@ impl Worker {
             confirm_contents_will_match_rx,
             mut confirm_contents_will_match_tx,
             fs,
+            index_query,
         ) = match self.candidates {
             FindSearchCandidates::Local {
                 fs,
                 input_paths_rx,
                 confirm_contents_will_match_rx,
                 confirm_contents_will_match_tx,
+                index_query,
             } => (
                 input_paths_rx,
                 confirm_contents_will_match_rx,
                 confirm_contents_will_match_tx,
                 Some(fs),
+                index_query,
             ),
             FindSearchCandidates::Remote | FindSearchCandidates::OpenBuffersOnly => {
-                (unbounded().1, unbounded().1, unbounded().0, None)
+                (unbounded().1, unbounded().1, unbounded().0, None, None)
             }
         };
         // WorkerA: grabs a request for "find all matches in file/a" <- takes 5 minutes
@@ -664,6 +681,7 @@ impl Worker {
                 query: &self.query,
                 open_entries: &self.open_buffers,
                 fs: fs.as_deref(),
+                index_query: index_query.as_deref(),
                 confirm_contents_will_match_tx: &confirm_contents_will_match_tx,
             };
             // Whenever we notice that some step of a pipeline is closed, we don't want to close subsequent
@@ -703,6 +721,7 @@ impl Worker {
 struct RequestHandler<'worker> {
     query: &'worker SearchQuery,
     fs: Option<&'worker dyn Fs>,
+    index_query: Option<&'worker IndexQuery>,
     open_entries: &'worker HashSet<ProjectEntryId>,
     confirm_contents_will_match_tx: &'worker Sender<MatchingEntry>,
 }
@@ -790,6 +809,10 @@ impl RequestHandler<'_> {
                         path: entry.path.clone(),
                     })
                     .await?;
+            } else if self.index_query.is_some_and(|index_query| {
+                index_query.rules_out(snapshot.id(), &entry.path, entry.mtime)
+            }) {
+                return Ok(());
             } else {
                 self.confirm_contents_will_match_tx
                     .send(MatchingEntry {
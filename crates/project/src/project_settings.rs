@@ -78,6 +78,30 @@ pub struct ProjectSettings {
 
     /// Configuration for session-related features
     pub session: SessionSettings,
+
+    /// Configuration for the local history of files
+    pub local_history: LocalHistorySettings,
+
+    /// Whether to keep an on-disk trigram index of project files for project search.
+    ///
+    /// Default: false
+    pub search_index: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LocalHistorySettings {
+    /// Whether to snapshot files as they are saved or changed on disk.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// The maximum number of snapshots to keep for each file.
+    ///
+    /// Default: 50
+    pub max_entries: usize,
+    /// The number of days after which snapshots are removed.
+    ///
+    /// Default: 30
+    pub max_age_days: u64,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -718,6 +742,15 @@ impl Settings for ProjectSettings {
                 restore_unsaved_buffers: content.session.unwrap().restore_unsaved_buffers.unwrap(),
                 trust_all_worktrees: content.session.unwrap().trust_all_worktrees.unwrap(),
             },
+            local_history: {
+                let local_history = project.local_history.unwrap();
+                LocalHistorySettings {
+                    enabled: local_history.enabled.unwrap(),
+                    max_entries: local_history.max_entries.unwrap(),
+                    max_age_days: local_history.max_age_days.unwrap(),
+                }
+            },
+            search_index: content.editor.search.as_ref().unwrap().index.unwrap(),
         }
     }
 }
@@ -4,7 +4,7 @@ use client::proto;
 use fancy_regex::{Captures, Regex, RegexBuilder};
 use gpui::Entity;
 use itertools::Itertools as _;
-use language::{Buffer, BufferSnapshot, CharKind};
+use language::{Buffer, BufferSnapshot, CharKind, Language};
 use smol::future::yield_now;
 use std::{
     borrow::Cow,
@@ -18,6 +18,10 @@ use util::{
     rel_path::RelPath,
 };
 
+mod structural;
+
+pub use structural::StructuralPattern;
+
 #[derive(Debug)]
 pub enum SearchResult {
     Buffer {
@@ -77,6 +81,13 @@ pub enum SearchQuery {
         one_match_per_line: bool,
         inner: SearchInputs,
     },
+    /// Matches syntax trees against a code pattern with metavariables, see [`StructuralPattern`].
+    Structural {
+        pattern: Arc<StructuralPattern>,
+        replacement: Option<String>,
+        include_ignored: bool,
+        inner: SearchInputs,
+    },
 }
 
 static WORD_MATCH_TEST: LazyLock<Regex> = LazyLock::new(|| {
@@ -206,6 +217,34 @@ impl SearchQuery {
         })
     }
 
+    /// Create a structural query
+    ///
+    /// `match_full_paths` has the same meaning as in [`SearchQuery::text`].
+    pub fn structural(
+        query: impl ToString,
+        include_ignored: bool,
+        files_to_include: PathMatcher,
+        files_to_exclude: PathMatcher,
+        match_full_paths: bool,
+        buffers: Option<Vec<Entity<Buffer>>>,
+    ) -> Result<Self> {
+        let query = query.to_string();
+        let pattern = StructuralPattern::new(&query)?;
+        let inner = SearchInputs {
+            query: query.into(),
+            files_to_exclude,
+            files_to_include,
+            match_full_paths,
+            buffers,
+        };
+        Ok(Self::Structural {
+            pattern: Arc::new(pattern),
+            replacement: None,
+            include_ignored,
+            inner,
+        })
+    }
+
     /// Extracts case sensitivity settings from pattern items in the provided
     /// query and returns the same query, with the pattern items removed.
     ///
@@ -270,7 +309,16 @@ impl SearchQuery {
             message.files_to_exclude
         };
 
-        if message.regex {
+        if message.structural {
+            Self::structural(
+                message.query,
+                message.include_ignored,
+                PathMatcher::new(files_to_include, path_style)?,
+                PathMatcher::new(files_to_exclude, path_style)?,
+                message.match_full_paths,
+                None, // search opened only don't need search remote
+            )
+        } else if message.regex {
             Self::regex(
                 message.query,
                 message.whole_word,
@@ -305,6 +353,10 @@ impl SearchQuery {
             | Self::Regex {
                 ref mut replacement,
                 ..
+            }
+            | Self::Structural {
+                ref mut replacement,
+                ..
             } => {
                 *replacement = Some(new_replacement);
                 self
@@ -318,6 +370,7 @@ impl SearchQuery {
         proto::SearchQuery {
             query: self.as_str().to_string(),
             regex: self.is_regex(),
+            structural: self.is_structural(),
             whole_word: self.whole_word(),
             case_sensitive: self.case_sensitive(),
             include_ignored: self.include_ignored(),
@@ -387,18 +440,28 @@ impl SearchQuery {
                     Ok(false)
                 }
             }
+            Self::Structural { pattern, .. } => {
+                let mut text = String::new();
+                reader.read_to_string(&mut text)?;
+                Ok(pattern.may_match(&text))
+            }
         }
     }
     /// Returns the replacement text for this `SearchQuery`.
     pub fn replacement(&self) -> Option<&str> {
         match self {
-            SearchQuery::Text { replacement, .. } | SearchQuery::Regex { replacement, .. } => {
-                replacement.as_deref()
-            }
+            SearchQuery::Text { replacement, .. }
+            | SearchQuery::Regex { replacement, .. }
+            | SearchQuery::Structural { replacement, .. } => replacement.as_deref(),
         }
     }
     /// Replaces search hits if replacement is set. `text` is assumed to be a string that matches this `SearchQuery` exactly, without any leftovers on either side.
-    pub fn replacement_for<'a>(&self, text: &'a str) -> Option<Cow<'a, str>> {
+    /// Structural queries also need the `language` of the buffer the match is in.
+    pub fn replacement_for<'a>(
+        &self,
+        text: &'a str,
+        language: Option<&Arc<Language>>,
+    ) -> Option<Cow<'a, str>> {
         match self {
             SearchQuery::Text { replacement, .. } => replacement.clone().map(Cow::from),
             SearchQuery::Regex {
@@ -421,6 +484,13 @@ impl SearchQuery {
                     None
                 }
             }
+            SearchQuery::Structural {
+                pattern,
+                replacement,
+                ..
+            } => pattern
+                .replacement_for(language?, text, replacement.as_ref()?)
+                .map(Cow::Owned),
         }
     }
 
@@ -519,6 +589,13 @@ impl SearchQuery {
                     }
                 }
             }
+
+            Self::Structural { pattern, .. } => {
+                let subrange = range_offset..range_offset + rope.len();
+                for range in pattern.search(buffer, subrange).await {
+                    matches.push(range.start - range_offset..range.end - range_offset);
+                }
+            }
         }
 
         matches
@@ -536,6 +613,7 @@ impl SearchQuery {
         match self {
             Self::Text { whole_word, .. } => *whole_word,
             Self::Regex { whole_word, .. } => *whole_word,
+            Self::Structural { .. } => false,
         }
     }
 
@@ -543,6 +621,7 @@ impl SearchQuery {
         match self {
             Self::Text { case_sensitive, .. } => *case_sensitive,
             Self::Regex { case_sensitive, .. } => *case_sensitive,
+            Self::Structural { .. } => true,
         }
     }
 
@@ -554,6 +633,9 @@ impl SearchQuery {
             Self::Regex {
                 include_ignored, ..
             } => *include_ignored,
+            Self::Structural {
+                include_ignored, ..
+            } => *include_ignored,
         }
     }
 
@@ -561,6 +643,16 @@ impl SearchQuery {
         matches!(self, Self::Regex { .. })
     }
 
+    pub fn is_structural(&self) -> bool {
+        matches!(self, Self::Structural { .. })
+    }
+
+    /// Whether each match gets its own replacement text, rather than all of them getting
+    /// [`SearchQuery::replacement`].
+    pub fn has_per_match_replacements(&self) -> bool {
+        matches!(self, Self::Regex { .. } | Self::Structural { .. })
+    }
+
     pub fn files_to_include(&self) -> &PathMatcher {
         self.as_inner().files_to_include()
     }
@@ -604,7 +696,9 @@ impl SearchQuery {
     }
     pub fn as_inner(&self) -> &SearchInputs {
         match self {
-            Self::Regex { inner, .. } | Self::Text { inner, .. } => inner,
+            Self::Regex { inner, .. }
+            | Self::Text { inner, .. }
+            | Self::Structural { inner, .. } => inner,
         }
     }
 
@@ -617,7 +711,7 @@ impl SearchQuery {
             Self::Regex {
                 one_match_per_line, ..
             } => Some(*one_match_per_line),
-            Self::Text { .. } => None,
+            Self::Text { .. } | Self::Structural { .. } => None,
         }
     }
 }
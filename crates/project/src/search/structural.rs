@@ -0,0 +1,428 @@
+//! Syntax-aware search, matching code patterns against the syntax trees of the searched files.
+//!
+//! A pattern is a snippet of code in which `$NAME` stands for any single syntax node, and
+//! `$$$NAME` for any number of sibling nodes, e.g. `$X.lock().unwrap()` or `foo($$$ARGS)`.
+//! Metavariables used more than once must match the same text each time, except for `$_`,
+//! which matches anything without being captured. Replacement templates can refer to the
+//! metavariables the pattern captured.
+
+use std::{fmt, ops::Range, sync::Arc};
+
+use anyhow::{Result, bail};
+use collections::HashMap;
+use language::{BufferSnapshot, Grammar, Language, LanguageName, Node, Tree};
+use parking_lot::Mutex;
+use smol::future::yield_now;
+
+/// Metavariables are swapped for identifiers with these prefixes before the pattern is parsed,
+/// since `$` doesn't make for a valid identifier in most languages.
+const METAVARIABLE_PREFIX: &str = "zed_metavar_";
+const MULTI_METAVARIABLE_PREFIX: &str = "zed_metavars_";
+const ANONYMOUS_METAVARIABLE: &str = "_";
+
+/// Code that patterns are wrapped in before they're parsed, as a prefix and a suffix, since
+/// many languages don't allow statements or expressions at the top level. The first context in
+/// which a pattern parses without errors is used, falling back to parsing it on its own.
+const PATTERN_CONTEXTS: &[(&str, &str)] = &[
+    ("fn zed_context() {\n", "\n}"),
+    ("func zed_context() {\n", "\n}"),
+    ("function zed_context() {\n", "\n}"),
+    ("void zed_context() {\n", "\n}"),
+    ("void zed_context() {\n", ";\n}"),
+    ("class ZedContext {\nvoid zedContext() {\n", ";\n}\n}"),
+    ("<?php\n", ";"),
+    ("", ""),
+];
+
+pub struct StructuralPattern {
+    /// The pattern, with its metavariables replaced by placeholder identifiers.
+    source: String,
+    /// The words outside of the metavariables, all of which appear in a matching file.
+    words: Vec<String>,
+    /// The pattern parsed with the grammar of each language it was searched in.
+    compiled: Mutex<HashMap<LanguageName, Option<Arc<CompiledPattern>>>>,
+}
+
+impl fmt::Debug for StructuralPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StructuralPattern")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Metavariable<'a> {
+    Single(&'a str),
+    Multi(&'a str),
+}
+
+impl<'a> Metavariable<'a> {
+    fn from_placeholder(text: &'a str) -> Option<Self> {
+        if let Some(name) = text.strip_prefix(MULTI_METAVARIABLE_PREFIX) {
+            is_metavariable_name(name).then_some(Self::Multi(name))
+        } else if let Some(name) = text.strip_prefix(METAVARIABLE_PREFIX) {
+            is_metavariable_name(name).then_some(Self::Single(name))
+        } else {
+            None
+        }
+    }
+}
+
+fn is_metavariable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_uppercase() || c == '_')
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Splits `$NAME` and `$$$NAME` off the start of `text`, returning the metavariable and the
+/// number of bytes it takes.
+fn metavariable_at(text: &str) -> Option<(Metavariable<'_>, usize)> {
+    let (rest, multi) = if let Some(rest) = text.strip_prefix("$$$") {
+        (rest, true)
+    } else {
+        (text.strip_prefix('$')?, false)
+    };
+    let name_len = rest
+        .find(|c: char| !(c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'))
+        .unwrap_or(rest.len());
+    let name = &rest[..name_len];
+    if !is_metavariable_name(name) {
+        return None;
+    }
+    let len = text.len() - rest.len() + name_len;
+    if multi {
+        Some((Metavariable::Multi(name), len))
+    } else {
+        Some((Metavariable::Single(name), len))
+    }
+}
+
+impl StructuralPattern {
+    pub fn new(pattern: &str) -> Result<Self> {
+        let mut source = String::with_capacity(pattern.len());
+        let mut literal = String::new();
+        let mut metavariables = 0;
+        let mut rest = pattern;
+        while let Some(c) = rest.chars().next() {
+            if let Some((metavariable, len)) = metavariable_at(rest) {
+                match metavariable {
+                    Metavariable::Single(name) => {
+                        source.push_str(METAVARIABLE_PREFIX);
+                        source.push_str(name);
+                    }
+                    Metavariable::Multi(name) => {
+                        source.push_str(MULTI_METAVARIABLE_PREFIX);
+                        source.push_str(name);
+                    }
+                }
+                literal.push(' ');
+                metavariables += 1;
+                rest = &rest[len..];
+            } else {
+                source.push(c);
+                literal.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+
+        if source.trim().is_empty() {
+            bail!("Structural pattern is empty");
+        }
+        if metavariables == 1 && literal.trim().is_empty() {
+            bail!("Structural pattern must contain code besides a metavariable");
+        }
+
+        let words = literal
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|word| !word.is_empty())
+            .map(ToOwned::to_owned)
+            .collect();
+        Ok(Self {
+            source,
+            words,
+            compiled: Mutex::default(),
+        })
+    }
+
+    /// The words outside of the pattern's metavariables, which every match contains.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Whether a file with this text might contain a match, checked before parsing it.
+    pub fn may_match(&self, text: &str) -> bool {
+        self.words.iter().all(|word| text.contains(word.as_str()))
+    }
+
+    fn compiled(&self, language: &Arc<Language>) -> Option<Arc<CompiledPattern>> {
+        self.compiled
+            .lock()
+            .entry(language.name())
+            .or_insert_with(|| CompiledPattern::new(&self.source, language).map(Arc::new))
+            .clone()
+    }
+
+    /// Finds the ranges of the outermost syntax nodes matching this pattern within `range`,
+    /// walking the buffer's syntax trees, including those of injected languages.
+    pub async fn search(&self, buffer: &BufferSnapshot, range: Range<usize>) -> Vec<Range<usize>> {
+        const YIELD_INTERVAL: usize = 20000;
+
+        let mut matches = Vec::new();
+        let layers = buffer
+            .syntax_layers_for_range(range.clone(), true)
+            .filter_map(|layer| Some((self.compiled(layer.language)?, layer.node())))
+            .collect::<Vec<_>>();
+        if layers.is_empty() {
+            return matches;
+        }
+        let text = buffer.text();
+
+        let mut visited_nodes = 0;
+        for (pattern, layer_root) in layers {
+            let pattern_root = pattern.root();
+            let mut cursor = layer_root.walk();
+            'walk: loop {
+                visited_nodes += 1;
+                if visited_nodes % YIELD_INTERVAL == 0 {
+                    yield_now().await;
+                }
+
+                let node = cursor.node();
+                let node_range = node.byte_range();
+                let mut matched = false;
+                let mut descend = node_range.start < range.end && range.start < node_range.end;
+                if descend
+                    && node.kind_id() == pattern_root.kind_id()
+                    && range.start <= node_range.start
+                    && node_range.end <= range.end
+                {
+                    let mut matcher = Matcher::new(&pattern.source, &text);
+                    if matcher.match_node(pattern_root, node) {
+                        matches.push(node_range);
+                        matched = true;
+                    }
+                }
+                descend &= !matched;
+
+                if descend && cursor.goto_first_child() {
+                    continue;
+                }
+                while !cursor.goto_next_sibling() {
+                    if !cursor.goto_parent() {
+                        break 'walk;
+                    }
+                }
+            }
+        }
+        matches.sort_by_key(|range| range.start);
+        matches
+    }
+
+    /// Renders the replacement `template` for `text`, a match of this pattern in a buffer of the
+    /// given language.
+    pub fn replacement_for(
+        &self,
+        language: &Arc<Language>,
+        text: &str,
+        template: &str,
+    ) -> Option<String> {
+        let pattern = self.compiled(language)?;
+        // Parse the match in the same context as the pattern, so that it parses the same way.
+        let (prefix, suffix) = pattern.context;
+        let source = format!("{prefix}{text}{suffix}");
+        let tree = parse(&pattern.grammar, &source)?;
+        let range = prefix.len()..prefix.len() + text.len();
+        let mut node = tree
+            .root_node()
+            .descendant_for_byte_range(range.start, range.end)?;
+        while node.byte_range() == range {
+            let mut matcher = Matcher::new(&pattern.source, &source);
+            if matcher.match_node(pattern.root(), node) {
+                return Some(matcher.render(template));
+            }
+            node = node.parent()?;
+        }
+        None
+    }
+}
+
+/// A pattern parsed with the grammar of one language.
+struct CompiledPattern {
+    grammar: Arc<Grammar>,
+    /// The pattern wrapped in its context.
+    source: String,
+    context: (&'static str, &'static str),
+    tree: Tree,
+    /// The range of the syntax node the pattern consists of.
+    range: Range<usize>,
+}
+
+impl CompiledPattern {
+    fn new(pattern: &str, language: &Arc<Language>) -> Option<Self> {
+        let grammar = language.grammar()?.clone();
+        let compiled = PATTERN_CONTEXTS.iter().find_map(|&(prefix, suffix)| {
+            let source = format!("{prefix}{pattern}{suffix}");
+            let tree = parse(&grammar, &source)?;
+            let range = prefix.len() + pattern.len() - pattern.trim_start().len()
+                ..prefix.len() + pattern.trim_end().len();
+            let node = tree
+                .root_node()
+                .descendant_for_byte_range(range.start, range.end)?;
+            // Without a context, the pattern only needs to parse as a single node, so that
+            // snippets that are incomplete on their own (like a statement without its `;`)
+            // still work.
+            let has_error = if prefix.is_empty() && suffix.is_empty() {
+                node.has_error()
+            } else {
+                tree.root_node().has_error()
+            };
+            if node.byte_range() != range || has_error {
+                return None;
+            }
+            Some(Self {
+                grammar: grammar.clone(),
+                source,
+                context: (prefix, suffix),
+                tree,
+                range,
+            })
+        });
+        if compiled.is_none() {
+            log::debug!(
+                "structural pattern {pattern:?} is not a single {} syntax node",
+                language.name()
+            );
+        }
+        compiled
+    }
+
+    fn root(&self) -> Node<'_> {
+        self.tree
+            .root_node()
+            .descendant_for_byte_range(self.range.start, self.range.end)
+            .expect("pattern range was checked when compiling")
+    }
+}
+
+fn parse(grammar: &Grammar, text: &str) -> Option<Tree> {
+    language::with_parser(|parser| {
+        parser.set_language(&grammar.ts_language).ok()?;
+        parser.parse(text, None)
+    })
+}
+
+/// The children that take part in matching, leaving out comments and nodes the parser
+/// inserted to recover from errors.
+fn significant_children(node: Node<'_>) -> Vec<Node<'_>> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|child| !child.is_extra() && !child.is_missing())
+        .collect()
+}
+
+struct Matcher<'a> {
+    pattern_source: &'a str,
+    source: &'a str,
+    captures: HashMap<&'a str, Range<usize>>,
+}
+
+impl<'a> Matcher<'a> {
+    fn new(pattern_source: &'a str, source: &'a str) -> Self {
+        Self {
+            pattern_source,
+            source,
+            captures: HashMap::default(),
+        }
+    }
+
+    fn match_node(&mut self, pattern: Node<'_>, node: Node<'_>) -> bool {
+        let pattern_source = self.pattern_source;
+        let pattern_text = &pattern_source[pattern.byte_range()];
+        if let Some(Metavariable::Single(name) | Metavariable::Multi(name)) =
+            Metavariable::from_placeholder(pattern_text)
+        {
+            return self.capture(name, node.byte_range());
+        }
+        if pattern.kind_id() != node.kind_id() {
+            return false;
+        }
+
+        let pattern_children = significant_children(pattern);
+        if pattern_children.is_empty() {
+            return pattern_text == &self.source[node.byte_range()];
+        }
+        self.match_children(&pattern_children, &significant_children(node))
+    }
+
+    fn match_children(&mut self, patterns: &[Node<'_>], nodes: &[Node<'_>]) -> bool {
+        let Some((pattern, remaining_patterns)) = patterns.split_first() else {
+            return nodes.is_empty();
+        };
+
+        let pattern_source = self.pattern_source;
+        let pattern_text = &pattern_source[pattern.byte_range()];
+        if let Some(Metavariable::Multi(name)) = Metavariable::from_placeholder(pattern_text) {
+            for count in 0..=nodes.len() {
+                let captures = self.captures.clone();
+                let range = match &nodes[..count] {
+                    [] => 0..0,
+                    [first, .., last] => first.start_byte()..last.end_byte(),
+                    [only] => only.byte_range(),
+                };
+                if self.capture(name, range)
+                    && self.match_children(remaining_patterns, &nodes[count..])
+                {
+                    return true;
+                }
+                self.captures = captures;
+            }
+            return false;
+        }
+
+        let Some((node, remaining_nodes)) = nodes.split_first() else {
+            return false;
+        };
+        let captures = self.captures.clone();
+        if self.match_node(*pattern, *node)
+            && self.match_children(remaining_patterns, remaining_nodes)
+        {
+            return true;
+        }
+        self.captures = captures;
+        false
+    }
+
+    fn capture(&mut self, name: &'a str, range: Range<usize>) -> bool {
+        if name == ANONYMOUS_METAVARIABLE {
+            return true;
+        }
+        match self.captures.get(name) {
+            Some(captured) => self.source[captured.clone()] == self.source[range],
+            None => {
+                self.captures.insert(name, range);
+                true
+            }
+        }
+    }
+
+    fn render(&self, template: &str) -> String {
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(c) = rest.chars().next() {
+            if let Some((Metavariable::Single(name) | Metavariable::Multi(name), len)) =
+                metavariable_at(rest)
+                && let Some(range) = self.captures.get(name)
+            {
+                rendered.push_str(&self.source[range.clone()]);
+                rest = &rest[len..];
+            } else {
+                rendered.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        rendered
+    }
+}
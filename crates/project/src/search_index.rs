@@ -0,0 +1,784 @@
+//! An on-disk trigram index of the files in local worktrees, used by project search to skip
+//! files that can't contain a match without reading them.
+//!
+//! For every indexed file we keep a fixed-size bloom filter of the (ASCII-lowercased) trigrams
+//! it contains, along with the file's mtime at the time it was indexed. A query is turned into
+//! the trigrams any match has to contain, and a file whose filter lacks one of them is ruled
+//! out. Files the index doesn't know about, or whose mtime differs from the one in the latest
+//! worktree snapshot, are searched as if there were no index.
+//!
+//! On disk, the index of a worktree is a directory of segments. Each save appends a segment
+//! with the files that changed since the previous one, and the segments are compacted into one
+//! once they pile up.
+
+use std::{
+    path::{Path, PathBuf},
+    pin::pin,
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Result, bail};
+use collections::{HashMap, HashSet};
+use fs::{Fs, MTime, RemoveOptions, RenameOptions};
+use futures::{StreamExt as _, future::Either};
+use gpui::{AsyncApp, Context, Entity, Subscription, Task, WeakEntity};
+use parking_lot::RwLock;
+use regex_syntax::hir::{Hir, HirKind};
+use settings::{Settings as _, SettingsStore};
+use sha2::{Digest as _, Sha256};
+use smol::channel::{Receiver, Sender, unbounded};
+use util::{ResultExt as _, rel_path::RelPath};
+use worktree::{Snapshot, Worktree, WorktreeId};
+
+use crate::{
+    project_settings::ProjectSettings,
+    search::SearchQuery,
+    worktree_store::{WorktreeStore, WorktreeStoreEvent},
+};
+
+const INDEX_FILE_MAGIC: &[u8; 4] = b"ZTRI";
+const INDEX_FILE_VERSION: u32 = 2;
+const SEGMENT_EXTENSION: &str = "segment";
+/// Larger files are left out of the index, and always searched.
+const MAX_INDEXED_FILE_SIZE: u64 = 16 * 1024 * 1024;
+const MAX_CONCURRENT_FILE_READS: usize = 16;
+/// How long to wait after the last change before appending the changed files to the index.
+const SAVE_DEBOUNCE: Duration = Duration::from_secs(2);
+/// Indexing at least this many files at once saves them right away, so that an interrupted
+/// initial indexing doesn't have to start over.
+const SAVE_IMMEDIATELY_THRESHOLD: usize = 1000;
+/// The segments are compacted once there are more of them than this, or once they hold more
+/// than twice as many records as there are indexed files.
+const MAX_SEGMENTS: usize = 16;
+
+/// The size of every file's filter. Files with many distinct trigrams saturate it, and are
+/// then never ruled out.
+const FILTER_BITS: usize = 4096;
+const FILTER_WORDS: usize = FILTER_BITS / 64;
+const HASHES_PER_TRIGRAM: usize = 2;
+
+type Trigram = u32;
+
+pub struct SearchIndex {
+    fs: Arc<dyn Fs>,
+    worktree_store: Entity<WorktreeStore>,
+    worktrees: HashMap<WorktreeId, WorktreeIndex>,
+    enabled: bool,
+    _subscriptions: [Subscription; 2],
+}
+
+struct WorktreeIndex {
+    files: Arc<RwLock<HashMap<Arc<RelPath>, FileTrigrams>>>,
+    changed_paths_tx: Sender<Vec<Arc<RelPath>>>,
+    _maintain_index: Task<()>,
+}
+
+impl SearchIndex {
+    pub fn new(
+        fs: Arc<dyn Fs>,
+        worktree_store: Entity<WorktreeStore>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let subscriptions = [
+            cx.subscribe(&worktree_store, Self::on_worktree_store_event),
+            cx.observe_global::<SettingsStore>(Self::on_settings_changed),
+        ];
+        let mut this = Self {
+            fs,
+            worktree_store,
+            worktrees: HashMap::default(),
+            enabled: false,
+            _subscriptions: subscriptions,
+        };
+        this.on_settings_changed(cx);
+        this
+    }
+
+    fn on_settings_changed(&mut self, cx: &mut Context<Self>) {
+        let enabled = ProjectSettings::get_global(cx).search_index;
+        if enabled == self.enabled {
+            return;
+        }
+        self.enabled = enabled;
+        if enabled {
+            let worktrees = self.worktree_store.read(cx).worktrees().collect::<Vec<_>>();
+            for worktree in worktrees {
+                self.add_worktree(worktree, cx);
+            }
+        } else {
+            self.worktrees.clear();
+        }
+    }
+
+    fn on_worktree_store_event(
+        &mut self,
+        _: Entity<WorktreeStore>,
+        event: &WorktreeStoreEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        match event {
+            WorktreeStoreEvent::WorktreeAdded(worktree) => self.add_worktree(worktree.clone(), cx),
+            WorktreeStoreEvent::WorktreeRemoved(_, worktree_id) => {
+                self.worktrees.remove(worktree_id);
+            }
+            WorktreeStoreEvent::WorktreeUpdatedEntries(worktree_id, changes) => {
+                if let Some(index) = self.worktrees.get(worktree_id) {
+                    let paths = changes.iter().map(|(path, _, _)| path.clone()).collect();
+                    index.changed_paths_tx.try_send(paths).ok();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_worktree(&mut self, worktree: Entity<Worktree>, cx: &mut Context<Self>) {
+        let worktree_id = worktree.read(cx).id();
+        if !worktree.read(cx).is_local()
+            || !worktree.read(cx).is_visible()
+            || self.worktrees.contains_key(&worktree_id)
+        {
+            return;
+        }
+
+        let files = Arc::<RwLock<HashMap<_, _>>>::default();
+        let (changed_paths_tx, changed_paths_rx) = unbounded();
+        let maintain_index = cx.spawn({
+            let fs = self.fs.clone();
+            let worktree = worktree.downgrade();
+            let files = files.clone();
+            async move |_, cx| {
+                maintain_index(fs, worktree, files, changed_paths_rx, cx)
+                    .await
+                    .log_err();
+            }
+        });
+        self.worktrees.insert(
+            worktree_id,
+            WorktreeIndex {
+                files,
+                changed_paths_tx,
+                _maintain_index: maintain_index,
+            },
+        );
+    }
+
+    /// Prepares `query` to be checked against the index, or returns `None` if the index can't
+    /// rule out any file for it.
+    pub(crate) fn query(&self, query: &SearchQuery) -> Option<Arc<IndexQuery>> {
+        if self.worktrees.is_empty() {
+            return None;
+        }
+        let trigrams = query_trigrams(query);
+        if trigrams.is_empty() {
+            return None;
+        }
+        Some(Arc::new(IndexQuery {
+            trigrams,
+            worktrees: self
+                .worktrees
+                .iter()
+                .map(|(worktree_id, index)| (*worktree_id, index.files.clone()))
+                .collect(),
+        }))
+    }
+}
+
+/// The trigrams a search query requires, along with the index of each worktree at the time
+/// the search started.
+pub(crate) struct IndexQuery {
+    trigrams: Vec<Trigram>,
+    worktrees: HashMap<WorktreeId, Arc<RwLock<HashMap<Arc<RelPath>, FileTrigrams>>>>,
+}
+
+impl IndexQuery {
+    /// Whether the index is certain that the file at `path`, last modified at `mtime`, contains
+    /// no match.
+    pub(crate) fn rules_out(
+        &self,
+        worktree_id: WorktreeId,
+        path: &RelPath,
+        mtime: Option<MTime>,
+    ) -> bool {
+        let Some(files) = self.worktrees.get(&worktree_id) else {
+            return false;
+        };
+        let files = files.read();
+        files
+            .get(path)
+            .is_some_and(|file| Some(file.mtime) == mtime && !file.may_contain_all(&self.trigrams))
+    }
+}
+
+/// A bloom filter of the trigrams in a file.
+struct FileTrigrams {
+    mtime: MTime,
+    bits: Box<[u64; FILTER_WORDS]>,
+}
+
+impl FileTrigrams {
+    fn new(mtime: MTime, content: &[u8]) -> Self {
+        let mut bits = Box::new([0; FILTER_WORDS]);
+        for window in content.windows(3) {
+            for bit in filter_bits(trigram(window)) {
+                bits[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+        Self { mtime, bits }
+    }
+
+    fn may_contain_all(&self, trigrams: &[Trigram]) -> bool {
+        trigrams.iter().all(|trigram| {
+            filter_bits(*trigram).all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+        })
+    }
+}
+
+fn trigram(bytes: &[u8]) -> Trigram {
+    bytes.iter().take(3).fold(0, |trigram, byte| {
+        trigram << 8 | byte.to_ascii_lowercase() as Trigram
+    })
+}
+
+fn filter_bits(trigram: Trigram) -> impl Iterator<Item = usize> {
+    // The finalizer of splitmix64, to spread the trigram's bits over the whole hash.
+    let mut hash = trigram as u64;
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^= hash >> 31;
+    let first = hash as u32 as usize;
+    let step = (hash >> 32) as usize | 1;
+    (0..HASHES_PER_TRIGRAM).map(move |ix| first.wrapping_add(ix.wrapping_mul(step)) % FILTER_BITS)
+}
+
+/// Returns the trigrams that every match of `query` contains.
+fn query_trigrams(query: &SearchQuery) -> Vec<Trigram> {
+    let (literals, case_insensitive) = match query {
+        SearchQuery::Text { case_sensitive, .. } => {
+            (vec![query.as_str().to_string()], !case_sensitive)
+        }
+        // Parts of the pattern made case-insensitive with inline flags are parsed into
+        // classes, which don't contribute any literals.
+        SearchQuery::Regex { case_sensitive, .. } => (required_literals(query), !case_sensitive),
+        SearchQuery::Structural { .. } => (required_literals(query), false),
+    };
+
+    let mut trigrams = Vec::new();
+    for literal in literals {
+        for window in literal.as_bytes().windows(3) {
+            // Unicode case folding lets the query match non-ASCII text (e.g. `K` matches the
+            // Kelvin sign), which the index doesn't know about, so only rely on parts of the
+            // literal that are matched as-is.
+            let usable = !case_insensitive
+                || window.iter().all(|byte| {
+                    byte.is_ascii() && !matches!(byte.to_ascii_lowercase(), b'k' | b's')
+                });
+            if usable && !window.iter().any(|byte| matches!(byte, b'\n' | b'\r')) {
+                trigrams.push(trigram(window));
+            }
+        }
+    }
+    trigrams.sort_unstable();
+    trigrams.dedup();
+    trigrams
+}
+
+/// Returns strings that every match of `query` contains. For regular expressions, only the
+/// literals that certainly take part in a match are returned, so the result may be empty.
+pub fn required_literals(query: &SearchQuery) -> Vec<String> {
+    match query {
+        SearchQuery::Text { .. } => vec![query.as_str().to_string()],
+        // Patterns using features only supported by fancy-regex, like lookaround and
+        // backreferences, can't be parsed here.
+        SearchQuery::Regex { regex, .. } => match regex_syntax::parse(regex.as_str()) {
+            Ok(hir) => match regex_literals(&hir) {
+                RequiredLiterals::Exact(literal) if literal.is_empty() => Vec::new(),
+                RequiredLiterals::Exact(literal) => vec![literal],
+                RequiredLiterals::Contains(literals) => literals,
+            },
+            Err(_) => Vec::new(),
+        },
+        SearchQuery::Structural { pattern, .. } => pattern.words().to_vec(),
+    }
+}
+
+/// What every match of a part of a regular expression contains.
+enum RequiredLiterals {
+    /// The part only matches this string.
+    Exact(String),
+    /// Every match of the part contains these strings.
+    Contains(Vec<String>),
+}
+
+fn regex_literals(hir: &Hir) -> RequiredLiterals {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => RequiredLiterals::Exact(String::new()),
+        HirKind::Literal(literal) => match std::str::from_utf8(&literal.0) {
+            Ok(literal) => RequiredLiterals::Exact(literal.to_string()),
+            Err(_) => RequiredLiterals::Contains(Vec::new()),
+        },
+        HirKind::Class(_) => RequiredLiterals::Contains(Vec::new()),
+        HirKind::Alternation(alternatives) => {
+            let mut literals = Vec::with_capacity(alternatives.len());
+            for alternative in alternatives {
+                match regex_literals(alternative) {
+                    RequiredLiterals::Exact(literal) => literals.push(literal),
+                    RequiredLiterals::Contains(_) => return RequiredLiterals::Contains(Vec::new()),
+                }
+            }
+            common_affixes(&literals)
+        }
+        HirKind::Capture(capture) => regex_literals(&capture.sub),
+        HirKind::Repetition(repetition) if repetition.min > 0 => {
+            match regex_literals(&repetition.sub) {
+                RequiredLiterals::Exact(literal) if literal.is_empty() => {
+                    RequiredLiterals::Contains(Vec::new())
+                }
+                RequiredLiterals::Exact(literal) => RequiredLiterals::Contains(vec![literal]),
+                contains => contains,
+            }
+        }
+        HirKind::Repetition(_) => RequiredLiterals::Contains(Vec::new()),
+        HirKind::Concat(subs) => {
+            let mut literals = Vec::new();
+            let mut run = String::new();
+            let mut exact = true;
+            for sub in subs {
+                match regex_literals(sub) {
+                    RequiredLiterals::Exact(literal) => run.push_str(&literal),
+                    RequiredLiterals::Contains(sub_literals) => {
+                        exact = false;
+                        if !run.is_empty() {
+                            literals.push(std::mem::take(&mut run));
+                        }
+                        literals.extend(sub_literals);
+                    }
+                }
+            }
+            if exact {
+                return RequiredLiterals::Exact(run);
+            }
+            if !run.is_empty() {
+                literals.push(run);
+            }
+            RequiredLiterals::Contains(literals)
+        }
+    }
+}
+
+/// What every one of `literals` contains: the prefix and suffix they share, such as `ba` in
+/// `bar|baz`.
+fn common_affixes(literals: &[String]) -> RequiredLiterals {
+    let Some((first, rest)) = literals.split_first() else {
+        return RequiredLiterals::Contains(Vec::new());
+    };
+    if rest.iter().all(|literal| literal == first) {
+        return RequiredLiterals::Exact(first.clone());
+    }
+
+    let mut prefix = first.as_str();
+    let mut suffix = first.as_str();
+    for literal in rest {
+        let prefix_len = prefix
+            .chars()
+            .zip(literal.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(c, _)| c.len_utf8())
+            .sum::<usize>();
+        prefix = &prefix[..prefix_len];
+        let suffix_len = suffix
+            .chars()
+            .rev()
+            .zip(literal.chars().rev())
+            .take_while(|(a, b)| a == b)
+            .map(|(c, _)| c.len_utf8())
+            .sum::<usize>();
+        suffix = &suffix[suffix.len() - suffix_len..];
+    }
+    let mut affixes = Vec::new();
+    if !prefix.is_empty() {
+        affixes.push(prefix.to_string());
+    }
+    if !suffix.is_empty() && suffix != prefix {
+        affixes.push(suffix.to_string());
+    }
+    RequiredLiterals::Contains(affixes)
+}
+
+async fn maintain_index(
+    fs: Arc<dyn Fs>,
+    worktree: WeakEntity<Worktree>,
+    files: Arc<RwLock<HashMap<Arc<RelPath>, FileTrigrams>>>,
+    changed_paths_rx: Receiver<Vec<Arc<RelPath>>>,
+    cx: &mut AsyncApp,
+) -> Result<()> {
+    let mut snapshot = worktree.read_with(cx, |worktree, _| worktree.snapshot())?;
+    let (mut store, persisted_files) =
+        IndexStore::open(fs.as_ref(), index_dir(snapshot.abs_path())).await;
+    let mut unsaved_paths = HashSet::default();
+    {
+        let mut files = files.write();
+        files.extend(persisted_files);
+        files.retain(|path, _| {
+            let exists = snapshot.entry_for_path(path).is_some();
+            if !exists {
+                unsaved_paths.insert(path.clone());
+            }
+            exists
+        });
+    }
+
+    let mut paths = snapshot
+        .files(false, 0)
+        .map(|entry| entry.path.clone())
+        .collect::<Vec<_>>();
+    loop {
+        let changed_paths = cx
+            .background_spawn(index_paths(
+                fs.clone(),
+                snapshot.clone(),
+                files.clone(),
+                paths,
+            ))
+            .await;
+        unsaved_paths.extend(changed_paths);
+        if unsaved_paths.len() >= SAVE_IMMEDIATELY_THRESHOLD {
+            let paths = std::mem::take(&mut unsaved_paths);
+            store.append(fs.as_ref(), &files, paths).await.log_err();
+        }
+
+        let next_paths = if unsaved_paths.is_empty() {
+            changed_paths_rx.recv().await
+        } else {
+            let next_paths = pin!(changed_paths_rx.recv());
+            let save_timer = pin!(cx.background_executor().timer(SAVE_DEBOUNCE));
+            match futures::future::select(next_paths, save_timer).await {
+                Either::Left((next_paths, _)) => next_paths,
+                Either::Right((_, next_paths)) => {
+                    let paths = std::mem::take(&mut unsaved_paths);
+                    store.append(fs.as_ref(), &files, paths).await.log_err();
+                    next_paths.await
+                }
+            }
+        };
+        let Ok(mut next_paths) = next_paths else {
+            return Ok(());
+        };
+        while let Ok(more_paths) = changed_paths_rx.try_recv() {
+            next_paths.extend(more_paths);
+        }
+        paths = next_paths;
+        snapshot = worktree.read_with(cx, |worktree, _| worktree.snapshot())?;
+    }
+}
+
+/// Brings the index of `paths` up to date with `snapshot`, returning the paths whose index
+/// changed.
+async fn index_paths(
+    fs: Arc<dyn Fs>,
+    snapshot: Snapshot,
+    files: Arc<RwLock<HashMap<Arc<RelPath>, FileTrigrams>>>,
+    paths: Vec<Arc<RelPath>>,
+) -> Vec<Arc<RelPath>> {
+    let mut changed_paths = Vec::new();
+    let mut stale_files = Vec::new();
+    {
+        let mut files = files.write();
+        for path in paths {
+            match snapshot.entry_for_path(&path) {
+                Some(entry)
+                    if entry.is_file()
+                        && !entry.is_fifo
+                        && !entry.is_ignored
+                        && entry.size <= MAX_INDEXED_FILE_SIZE =>
+                {
+                    let Some(mtime) = entry.mtime else {
+                        continue;
+                    };
+                    if files.get(&path).is_none_or(|file| file.mtime != mtime) {
+                        stale_files.push((path, mtime));
+                    }
+                }
+                _ => {
+                    if files.remove(&path).is_some() {
+                        changed_paths.push(path);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut indexed_files = futures::stream::iter(stale_files)
+        .map(|(path, mtime)| {
+            let abs_path = snapshot.absolutize(&path);
+            let fs = fs.clone();
+            async move {
+                let content = fs.load_bytes(&abs_path).await.ok()?;
+                Some((path, FileTrigrams::new(mtime, &content)))
+            }
+        })
+        .buffer_unordered(MAX_CONCURRENT_FILE_READS);
+    while let Some(indexed_file) = indexed_files.next().await {
+        if let Some((path, file)) = indexed_file {
+            files.write().insert(path.clone(), file);
+            changed_paths.push(path);
+        }
+    }
+    changed_paths
+}
+
+fn index_dir(worktree_abs_path: &Path) -> PathBuf {
+    let digest = Sha256::digest(worktree_abs_path.to_string_lossy().as_bytes());
+    let dir_name = digest
+        .iter()
+        .take(16)
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    paths::search_index_dir().join(dir_name)
+}
+
+/// The segments a worktree's index is stored in, numbered in the order they were written.
+///
+/// A segment either holds every indexed file, replacing the segments before it, or the files
+/// that were indexed or removed since the previous segment.
+struct IndexStore {
+    dir: PathBuf,
+    segments: Vec<u64>,
+    /// How many files the segments hold in total, including ones that were since replaced.
+    record_count: usize,
+}
+
+impl IndexStore {
+    /// Loads the index in `dir`, discarding it if it can't be read.
+    async fn open(fs: &dyn Fs, dir: PathBuf) -> (Self, HashMap<Arc<RelPath>, FileTrigrams>) {
+        let mut store = Self {
+            dir,
+            segments: Vec::new(),
+            record_count: 0,
+        };
+        match store.load(fs).await {
+            Ok(files) => (store, files),
+            Err(error) => {
+                log::info!("discarding search index {:?}: {error:#}", store.dir);
+                // Indexes from before segments were introduced are a single file at `dir`.
+                if fs.is_file(&store.dir).await {
+                    fs.remove_file(&store.dir, RemoveOptions::default())
+                        .await
+                        .log_err();
+                } else {
+                    fs.remove_dir(
+                        &store.dir,
+                        RemoveOptions {
+                            recursive: true,
+                            ignore_if_not_exists: true,
+                        },
+                    )
+                    .await
+                    .log_err();
+                }
+                store.segments.clear();
+                store.record_count = 0;
+                (store, HashMap::default())
+            }
+        }
+    }
+
+    async fn load(&mut self, fs: &dyn Fs) -> Result<HashMap<Arc<RelPath>, FileTrigrams>> {
+        let mut files = HashMap::default();
+        if !fs.is_dir(&self.dir).await {
+            if fs.is_file(&self.dir).await {
+                bail!("search index has an unknown format");
+            }
+            return Ok(files);
+        }
+
+        let mut entries = fs.read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next().await {
+            let entry = entry?;
+            if entry
+                .extension()
+                .is_some_and(|ext| ext == SEGMENT_EXTENSION)
+                && let Some(id) = entry
+                    .file_stem()
+                    .and_then(|stem| u64::from_str_radix(stem.to_str()?, 16).ok())
+            {
+                self.segments.push(id);
+            }
+        }
+        self.segments.sort_unstable();
+
+        for id in &self.segments {
+            let content = fs.load_bytes(&self.segment_path(*id)).await?;
+            let mut reader = IndexReader { content: &content };
+            if reader.bytes(4)? != INDEX_FILE_MAGIC || reader.u32()? != INDEX_FILE_VERSION {
+                bail!("search index has an unknown format");
+            }
+            if reader.u8()? == SegmentKind::Full as u8 {
+                files.clear();
+                self.record_count = 0;
+            }
+            let record_count = reader.u64()? as usize;
+            for _ in 0..record_count {
+                let path_len = reader.u32()? as usize;
+                let path = std::str::from_utf8(reader.bytes(path_len)?)?;
+                let path = RelPath::unix(path)?.into_arc();
+                if reader.u8()? == 0 {
+                    files.remove(&path);
+                    continue;
+                }
+                let mtime = MTime::from_seconds_and_nanos(reader.u64()?, reader.u32()?);
+                let mut bits = Box::new([0; FILTER_WORDS]);
+                for word in bits.iter_mut() {
+                    *word = reader.u64()?;
+                }
+                files.insert(path, FileTrigrams { mtime, bits });
+            }
+            self.record_count += record_count;
+        }
+        Ok(files)
+    }
+
+    /// Writes the current index of `paths` as a new segment, compacting the segments if they
+    /// have grown too large.
+    async fn append(
+        &mut self,
+        fs: &dyn Fs,
+        files: &RwLock<HashMap<Arc<RelPath>, FileTrigrams>>,
+        paths: HashSet<Arc<RelPath>>,
+    ) -> Result<()> {
+        let (content, file_count) = {
+            let files = files.read();
+            let content = encode_segment(
+                SegmentKind::Changes,
+                paths.iter().map(|path| (path, files.get(path))),
+            );
+            (content, files.len())
+        };
+        self.write_segment(fs, &content).await?;
+        self.record_count += paths.len();
+
+        if self.segments.len() > MAX_SEGMENTS || self.record_count > 2 * file_count.max(1) {
+            self.compact(fs, files).await?;
+        }
+        Ok(())
+    }
+
+    /// Replaces all segments with a single one holding every indexed file.
+    async fn compact(
+        &mut self,
+        fs: &dyn Fs,
+        files: &RwLock<HashMap<Arc<RelPath>, FileTrigrams>>,
+    ) -> Result<()> {
+        let (content, file_count) = {
+            let files = files.read();
+            let content = encode_segment(
+                SegmentKind::Full,
+                files.iter().map(|(path, file)| (path, Some(file))),
+            );
+            (content, files.len())
+        };
+        let replaced_segments = self.segments.clone();
+        self.write_segment(fs, &content).await?;
+        self.segments.retain(|id| !replaced_segments.contains(id));
+        self.record_count = file_count;
+        for id in replaced_segments {
+            fs.remove_file(&self.segment_path(id), RemoveOptions::default())
+                .await
+                .log_err();
+        }
+        Ok(())
+    }
+
+    async fn write_segment(&mut self, fs: &dyn Fs, content: &[u8]) -> Result<()> {
+        let id = self.segments.last().map_or(0, |id| id + 1);
+        let segment_path = self.segment_path(id);
+        fs.create_dir(&self.dir).await?;
+        // Segments are written to a temporary file first, so that a partially written one is
+        // never loaded.
+        let temp_path = segment_path.with_extension("tmp");
+        fs.write(&temp_path, content).await?;
+        if let Err(error) = fs
+            .rename(&temp_path, &segment_path, RenameOptions::default())
+            .await
+        {
+            fs.remove_file(&temp_path, RemoveOptions::default())
+                .await
+                .log_err();
+            return Err(error);
+        }
+        self.segments.push(id);
+        Ok(())
+    }
+
+    fn segment_path(&self, id: u64) -> PathBuf {
+        self.dir.join(format!("{id:016x}.{SEGMENT_EXTENSION}"))
+    }
+}
+
+#[repr(u8)]
+enum SegmentKind {
+    Changes = 0,
+    Full = 1,
+}
+
+/// Encodes a segment with a record for each file, where `None` marks a file that was removed
+/// from the index.
+fn encode_segment<'a>(
+    kind: SegmentKind,
+    files: impl ExactSizeIterator<Item = (&'a Arc<RelPath>, Option<&'a FileTrigrams>)>,
+) -> Vec<u8> {
+    let mut content = Vec::new();
+    content.extend_from_slice(INDEX_FILE_MAGIC);
+    content.extend_from_slice(&INDEX_FILE_VERSION.to_le_bytes());
+    content.push(kind as u8);
+    content.extend_from_slice(&(files.len() as u64).to_le_bytes());
+    for (path, file) in files {
+        let path = path.as_unix_str();
+        content.extend_from_slice(&(path.len() as u32).to_le_bytes());
+        content.extend_from_slice(path.as_bytes());
+        let Some((file, (seconds, nanos))) = file.and_then(|file| {
+            let mtime = file.mtime.to_seconds_and_nanos_for_persistence()?;
+            Some((file, mtime))
+        }) else {
+            content.push(0);
+            continue;
+        };
+        content.push(1);
+        content.extend_from_slice(&seconds.to_le_bytes());
+        content.extend_from_slice(&nanos.to_le_bytes());
+        for word in file.bits.iter() {
+            content.extend_from_slice(&word.to_le_bytes());
+        }
+    }
+    content
+}
+
+struct IndexReader<'a> {
+    content: &'a [u8],
+}
+
+impl<'a> IndexReader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.content.len() < len {
+            bail!("unexpected end of search index");
+        }
+        let (bytes, rest) = self.content.split_at(len);
+        self.content = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into()?))
+    }
+}
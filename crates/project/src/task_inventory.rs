@@ -8,7 +8,7 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet, VecDeque};
 use dap::DapRegistry;
 use gpui::{App, AppContext as _, Context, Entity, SharedString, Task, WeakEntity};
@@ -21,8 +21,8 @@ use lsp::{LanguageServerId, LanguageServerName};
 use paths::{debug_task_file_name, task_file_name};
 use settings::{InvalidSettingsError, parse_json_with_comments};
 use task::{
-    DebugScenario, ResolvedTask, SharedTaskContext, TaskContext, TaskId, TaskTemplate,
-    TaskTemplates, TaskVariables, VariableName,
+    DebugScenario, DependsOrder, ResolvedTask, SharedTaskContext, TaskContext, TaskId,
+    TaskTemplate, TaskTemplates, TaskVariables, VariableName,
 };
 use text::{BufferId, Point, ToPoint};
 use util::{NumericPrefixWithSuffix, ResultExt as _, post_inc, rel_path::RelPath};
@@ -155,6 +155,22 @@ pub enum TaskSourceKind {
     },
 }
 
+/// Tasks that have to finish successfully before the task that depends on them is spawned.
+#[derive(Debug, Clone, Default)]
+pub struct TaskDependencies {
+    /// Whether to run the tasks one after another or all at once.
+    pub order: DependsOrder,
+    pub tasks: Vec<TaskDependency>,
+}
+
+/// A task that another task depends on, along with the tasks it depends on itself.
+#[derive(Debug, Clone)]
+pub struct TaskDependency {
+    pub task_source_kind: TaskSourceKind,
+    pub resolved_task: ResolvedTask,
+    pub dependencies: TaskDependencies,
+}
+
 /// A collection of task contexts, derived from the current state of the workspace.
 /// Only contains worktrees that are visible and with their root being a directory.
 #[derive(Debug, Default)]
@@ -373,6 +389,45 @@ impl Inventory {
         })
     }
 
+    /// Resolves the tasks that the given task depends on, recursively, with the same context the task was resolved with.
+    ///
+    /// Dependencies are looked up by their labels among the tasks of the worktree the task comes from
+    /// (or all worktrees, if the task does not come from one), then among the global tasks.
+    /// Fails if any dependency cannot be found or resolved, or if the dependencies form a cycle.
+    pub fn resolve_task_dependencies(
+        &self,
+        task_source_kind: &TaskSourceKind,
+        resolved_task: &ResolvedTask,
+    ) -> Result<TaskDependencies> {
+        let Some(task_context) = resolved_task
+            .task_context()
+            .filter(|_| !resolved_task.original_task().depends_on.is_empty())
+        else {
+            return Ok(TaskDependencies::default());
+        };
+        let worktrees = match task_source_kind {
+            TaskSourceKind::Worktree { id, .. } => vec![*id],
+            _ => self
+                .templates_from_settings
+                .worktree
+                .keys()
+                .copied()
+                .collect(),
+        };
+        let candidates = worktrees
+            .into_iter()
+            .flat_map(|worktree| self.worktree_templates_from_settings(worktree))
+            .chain(self.global_templates_from_settings())
+            .collect::<Vec<_>>();
+        let original_task = resolved_task.original_task();
+        resolve_dependencies(
+            original_task,
+            &candidates,
+            task_context,
+            &mut vec![original_task.label.clone()],
+        )
+    }
+
     /// Pulls its task sources relevant to the worktree and the language given,
     /// returns all task templates with their source kinds, worktree tasks first, language tasks second
     /// and global tasks last. No specific order inside source kinds groups.
@@ -861,6 +916,53 @@ fn task_lru_comparator(
         })
 }
 
+fn resolve_dependencies(
+    template: &TaskTemplate,
+    candidates: &[(TaskSourceKind, TaskTemplate)],
+    task_context: &TaskContext,
+    dependency_chain: &mut Vec<String>,
+) -> Result<TaskDependencies> {
+    let mut tasks = Vec::with_capacity(template.depends_on.len());
+    for label in &template.depends_on {
+        anyhow::ensure!(
+            !dependency_chain.contains(label),
+            "Task dependency cycle: {} -> {label}",
+            dependency_chain.join(" -> ")
+        );
+        let (task_source_kind, dependency_template) = candidates
+            .iter()
+            .find(|(_, candidate)| &candidate.label == label)
+            .with_context(|| {
+                format!(
+                    "Task `{}` depends on an unknown task `{label}`",
+                    template.label
+                )
+            })?;
+        let resolved_task = dependency_template
+            .resolve_task(&task_source_kind.to_id_base(), task_context)
+            .with_context(|| format!("Could not resolve task `{label}`"))?;
+
+        dependency_chain.push(label.clone());
+        let dependencies = resolve_dependencies(
+            dependency_template,
+            candidates,
+            task_context,
+            dependency_chain,
+        )?;
+        dependency_chain.pop();
+
+        tasks.push(TaskDependency {
+            task_source_kind: task_source_kind.clone(),
+            resolved_task,
+            dependencies,
+        });
+    }
+    Ok(TaskDependencies {
+        order: template.depends_order,
+        tasks,
+    })
+}
+
 pub fn task_source_kind_preference(kind: &TaskSourceKind) -> u32 {
     match kind {
         TaskSourceKind::Lsp { .. } => 0,
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use context_server::test::create_fake_transport;
+use context_server::types::{self, requests};
 use context_server::{ContextServer, ContextServerId};
 use gpui::{AppContext, AsyncApp, Entity, Subscription, Task, TestAppContext, UpdateGlobal as _};
 use http_client::{FakeHttpClient, Response};
@@ -147,6 +148,91 @@ async fn test_context_server_status_events(cx: &mut TestAppContext) {
         .unwrap();
 }
 
+#[gpui::test]
+async fn test_context_server_client_requests(cx: &mut TestAppContext) {
+    const SERVER_ID: &str = "mcp-1";
+
+    let (_fs, project) = setup_context_server_test(cx, json!({"code.rs": ""}), vec![]).await;
+
+    let registry = cx.new(|_| ContextServerDescriptorRegistry::new());
+    let store = cx.new(|cx| {
+        ContextServerStore::test(
+            registry.clone(),
+            project.read(cx).worktree_store(),
+            Some(project.downgrade()),
+            cx,
+        )
+    });
+
+    let transport = Arc::new(create_fake_transport(SERVER_ID, cx.executor()));
+    let server = Arc::new(ContextServer::new(
+        ContextServerId(SERVER_ID.into()),
+        transport.clone(),
+    ));
+    store.update(cx, |store, cx| store.test_start_server(server, cx));
+    cx.run_until_parked();
+
+    let roots = cx.executor().spawn({
+        let transport = transport.clone();
+        async move { transport.request::<requests::ListRoots>(()).await }
+    });
+    cx.run_until_parked();
+    let roots = roots.await.unwrap();
+    assert_eq!(
+        roots
+            .roots
+            .iter()
+            .map(|root| (root.uri.to_file_path().unwrap(), root.name.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![(PathBuf::from(path!("/test")), Some("test"))]
+    );
+
+    let _subscription = cx.update(|cx| {
+        cx.subscribe(&store, |_, event: &ContextServerRequestEvent, cx| {
+            if let ContextServerRequestEvent::Elicitation(request) = event {
+                let request = request.clone();
+                cx.background_spawn(
+                    request.respond(types::ElicitResult {
+                        action: types::ElicitAction::Accept,
+                        content: Some(
+                            [("name".to_string(), json!("octocat"))]
+                                .into_iter()
+                                .collect(),
+                        ),
+                        meta: None,
+                    }),
+                )
+                .detach();
+            }
+        })
+    });
+    let elicitation = cx.executor().spawn({
+        let transport = transport.clone();
+        async move {
+            transport
+                .request::<requests::Elicit>(
+                    serde_json::from_value(json!({
+                        "message": "What is your GitHub username?",
+                        "requestedSchema": {
+                            "type": "object",
+                            "properties": { "name": { "type": "string" } },
+                            "required": ["name"],
+                        },
+                    }))
+                    .unwrap(),
+                )
+                .await
+        }
+    });
+    cx.run_until_parked();
+    let elicitation = elicitation.await.unwrap();
+    assert_eq!(elicitation.action, types::ElicitAction::Accept);
+    assert_eq!(
+        elicitation.content.unwrap().get("name"),
+        Some(&json!("octocat"))
+    );
+}
+
 #[gpui::test(iterations = 25)]
 async fn test_context_server_concurrent_starts(cx: &mut TestAppContext) {
     const SERVER_1_ID: &str = "mcp-1";
@@ -98,6 +98,40 @@ mod conflict_set_tests {
         assert_eq!(conflicts_in_range.len(), 0);
     }
 
+    #[test]
+    fn test_conflict_labels_for_replayed_commits() {
+        let test_content = r#"
+            <<<<<<< HEAD
+            ours
+            =======
+            theirs
+            >>>>>>> 1a2b3c4 (Fix the (important) bug)
+            <<<<<<< HEAD
+            ours
+            =======
+            theirs
+            >>>>>>> parent of 1a2b3c4 (Fix the (important) bug)
+            <<<<<<< HEAD
+            ours
+            =======
+            theirs
+            >>>>>>> feature (wip)
+        "#
+        .unindent();
+
+        let buffer_id = BufferId::new(1).unwrap();
+        let buffer = Buffer::new(ReplicaId::LOCAL, buffer_id, test_content);
+        let conflict_snapshot = ConflictSet::parse(&buffer.snapshot());
+        assert_eq!(
+            conflict_snapshot
+                .conflicts
+                .iter()
+                .map(|conflict| conflict.theirs_branch_name.as_ref())
+                .collect::<Vec<_>>(),
+            ["1a2b3c4", "parent of 1a2b3c4", "feature (wip)"]
+        );
+    }
+
     #[test]
     fn test_nested_conflict_markers() {
         // Create a buffer with nested conflict markers
@@ -461,6 +495,235 @@ mod conflict_set_tests {
             assert_eq!(conflict_range, Point::new(1, 0)..Point::new(6, 0));
         });
     }
+
+    #[gpui::test]
+    async fn test_conflicts_from_cherry_pick(
+        executor: BackgroundExecutor,
+        cx: &mut TestAppContext,
+    ) {
+        zlog::init_test();
+        cx.update(|cx| {
+            settings::init(cx);
+        });
+
+        let conflicted_text = "
+            one
+            <<<<<<< HEAD
+            two
+            =======
+            TWO
+            >>>>>>> 1a2b3c4 (Capitalize two)
+            three
+        "
+        .unindent();
+
+        let fs = FakeFs::new(executor);
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.txt": conflicted_text,
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (git_store, buffer) = project.update(cx, |project, cx| {
+            (
+                project.git_store().clone(),
+                project.open_local_buffer(path!("/project/a.txt"), cx),
+            )
+        });
+        let buffer = buffer.await.unwrap();
+        let conflict_set = git_store.update(cx, |git_store, cx| {
+            git_store.open_conflict_set(buffer.clone(), cx)
+        });
+        cx.run_until_parked();
+        conflict_set.read_with(cx, |conflict_set, _| {
+            assert!(!conflict_set.has_conflict);
+            assert!(conflict_set.snapshot().conflicts.is_empty());
+        });
+
+        fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+            state.simulated_replay_conflicts.insert(
+                repo_path("a.txt"),
+                UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                },
+            );
+        })
+        .unwrap();
+
+        let repository = project.read_with(cx, |project, cx| {
+            project.repositories(cx).values().next().unwrap().clone()
+        });
+        repository
+            .update(cx, |repository, cx| {
+                repository.cherry_pick("1a2b3c4".into(), cx)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+
+        cx.run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            assert!(
+                repository
+                    .merge
+                    .conflicted_paths
+                    .contains(&repo_path("a.txt"))
+            );
+        });
+        conflict_set.read_with(cx, |conflict_set, _| {
+            assert!(conflict_set.has_conflict);
+            let conflicts = conflict_set.snapshot().conflicts;
+            assert_eq!(conflicts.len(), 1);
+            assert_eq!(conflicts[0].ours_branch_name.as_ref(), "HEAD");
+            assert_eq!(conflicts[0].theirs_branch_name.as_ref(), "1a2b3c4");
+        });
+
+        // Starting another cherry-pick before resolving the conflicts fails.
+        repository
+            .update(cx, |repository, cx| {
+                repository.cherry_pick("5d6e7f8".into(), cx)
+            })
+            .await
+            .unwrap()
+            .unwrap_err();
+    }
+
+    #[gpui::test]
+    async fn test_cherry_pick_and_revert_without_conflicts(
+        executor: BackgroundExecutor,
+        cx: &mut TestAppContext,
+    ) {
+        zlog::init_test();
+        cx.update(|cx| {
+            settings::init(cx);
+        });
+
+        let fs = FakeFs::new(executor);
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        let head = "1".repeat(40);
+        fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+            state.refs.insert("HEAD".into(), head.clone());
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let repository = project.read_with(cx, |project, cx| {
+            project.repositories(cx).values().next().unwrap().clone()
+        });
+        let head_and_replay_refs = || {
+            fs.with_git_state(path!("/project/.git").as_ref(), false, |state| {
+                (
+                    state.refs["HEAD"].clone(),
+                    state.refs.contains_key("CHERRY_PICK_HEAD")
+                        || state.refs.contains_key("REVERT_HEAD"),
+                )
+            })
+            .unwrap()
+        };
+
+        repository
+            .update(cx, |repository, cx| {
+                repository.cherry_pick("1a2b3c4".into(), cx)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        let (cherry_picked, in_progress) = head_and_replay_refs();
+        assert_ne!(cherry_picked, head);
+        assert!(!in_progress);
+
+        repository
+            .update(cx, |repository, cx| {
+                repository.revert(cherry_picked.clone(), cx)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        let (reverted, in_progress) = head_and_replay_refs();
+        assert_ne!(reverted, cherry_picked);
+        assert!(!in_progress);
+        fs.with_git_state(path!("/project/.git").as_ref(), false, |state| {
+            let parents = |sha: &str| {
+                state
+                    .graph_commits
+                    .iter()
+                    .find(|commit| commit.sha.to_string() == sha)
+                    .map(|commit| commit.parents.iter().map(ToString::to_string).collect())
+            };
+            assert_eq!(parents(&reverted), Some(vec![cherry_picked.clone()]));
+            assert_eq!(parents(&cherry_picked), Some(vec![head.clone()]));
+        })
+        .unwrap();
+    }
+}
+
+mod tag_tests {
+    use crate::Project;
+
+    use askpass::AskPassDelegate;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_push_tag(cx: &mut TestAppContext) {
+        zlog::init_test();
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+            state
+                .remotes
+                .insert("origin".into(), "git@example.com:project.git".into());
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let repository = project.read_with(cx, |project, cx| {
+            project.repositories(cx).values().next().unwrap().clone()
+        });
+        let push_tag = |name: &'static str, remote: &'static str, cx: &mut TestAppContext| {
+            repository.update(cx, |repository, cx| {
+                let askpass = AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {});
+                repository.push_tag(name.into(), remote.into(), askpass, cx)
+            })
+        };
+
+        // Only existing tags can be pushed, and only to existing remotes.
+        push_tag("v1", "origin", cx).await.unwrap().unwrap_err();
+        repository
+            .update(cx, |repository, cx| {
+                repository.create_tag("v1".into(), None, None, cx)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        push_tag("v1", "upstream", cx).await.unwrap().unwrap_err();
+
+        let output = push_tag("v1", "origin", cx).await.unwrap().unwrap();
+        assert!(output.stderr.contains("[new tag]"));
+        let output = push_tag("v1", "origin", cx).await.unwrap().unwrap();
+        assert_eq!(output.stderr, "Everything up-to-date\n");
+        fs.with_git_state(path!("/project/.git").as_ref(), false, |state| {
+            assert!(state.pushed_tags["origin"].contains("v1"));
+        })
+        .unwrap();
+    }
 }
 
 mod git_traversal {
@@ -1012,3 +1275,292 @@ mod git_traversal {
         pretty_assertions::assert_eq!(found_statuses, expected_statuses);
     }
 }
+
+mod submodule_tests {
+    use std::path::Path;
+
+    use crate::Project;
+
+    use askpass::AskPassDelegate;
+    use fs::FakeFs;
+    use git::{
+        Oid,
+        repository::repo_path,
+        submodule::{Submodule, SubmoduleStatus},
+    };
+    use gpui::TestAppContext;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use unindent::Unindent as _;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_submodules(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let recorded: Oid = "6ff077a41291f933143271bedb8dd351a4dd69a3".parse().unwrap();
+        let updated: Oid = "aef2fd53a022f55b2714c55d128c8465de5e6936".parse().unwrap();
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                ".gitmodules": r#"
+                    [submodule "docs"]
+                    	path = docs
+                    	url = https://example.com/docs.git
+                    [submodule "lib"]
+                    	path = vendor/lib
+                    	url = ../lib
+                "#.unindent(),
+                "vendor": {
+                    "lib": {
+                        ".git": {},
+                        "lib.rs": "",
+                    },
+                },
+            }),
+        )
+        .await;
+        fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+            state.submodule_commits.insert(repo_path("docs"), recorded);
+            state
+                .submodule_commits
+                .insert(repo_path("vendor/lib"), recorded);
+        })
+        .unwrap();
+        fs.with_git_state(path!("/project/vendor/lib/.git").as_ref(), true, |state| {
+            state.refs.insert("HEAD".into(), recorded.to_string());
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+
+        let (parent, submodule_repo) = project.read_with(cx, |project, cx| {
+            let repository_at = |abs_path: &str| {
+                project
+                    .repositories(cx)
+                    .values()
+                    .find(|repo| *repo.read(cx).work_directory_abs_path == *Path::new(abs_path))
+                    .unwrap()
+                    .clone()
+            };
+            (
+                repository_at(path!("/project")),
+                repository_at(path!("/project/vendor/lib")),
+            )
+        });
+
+        let statuses = |cx: &mut TestAppContext| {
+            parent.read_with(cx, |parent, _| {
+                parent
+                    .submodules
+                    .iter()
+                    .map(|submodule| (submodule.path.as_unix_str().to_string(), submodule.status))
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            statuses(cx),
+            [
+                ("docs".to_string(), SubmoduleStatus::Uninitialized),
+                ("vendor/lib".to_string(), SubmoduleStatus::Current),
+            ]
+        );
+
+        // The nested repository is linked back to its entry in the parent.
+        project.read_with(cx, |project, cx| {
+            let git_store = project.git_store().read(cx);
+            let (repo, submodule) = git_store
+                .parent_repository(submodule_repo.read(cx).id, cx)
+                .unwrap();
+            assert_eq!(repo, parent);
+            assert_eq!(
+                submodule,
+                Submodule {
+                    name: "lib".into(),
+                    path: repo_path("vendor/lib"),
+                    url: Some("../lib".into()),
+                    recorded_sha: Some(recorded),
+                    checked_out_sha: Some(recorded),
+                    status: SubmoduleStatus::Current,
+                }
+            );
+            assert_eq!(
+                git_store.submodule_repository(&parent.read(cx).snapshot(), &submodule, cx),
+                Some(submodule_repo.clone())
+            );
+        });
+
+        // Recording a new commit in the parent leaves the checkout behind.
+        fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+            state
+                .submodule_commits
+                .insert(repo_path("vendor/lib"), updated);
+        })
+        .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            statuses(cx),
+            [
+                ("docs".to_string(), SubmoduleStatus::Uninitialized),
+                ("vendor/lib".to_string(), SubmoduleStatus::Modified),
+            ]
+        );
+
+        let output = parent
+            .update(cx, |parent, cx| {
+                let askpass = AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {});
+                parent.update_submodules(vec![repo_path("vendor/lib")], askpass, cx)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            output.stdout,
+            format!("Submodule path 'vendor/lib': checked out '{updated}'\n")
+        );
+        cx.run_until_parked();
+        assert_eq!(
+            statuses(cx),
+            [
+                ("docs".to_string(), SubmoduleStatus::Uninitialized),
+                ("vendor/lib".to_string(), SubmoduleStatus::Current),
+            ]
+        );
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        zlog::init_test();
+
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+    }
+}
+
+mod bisect_tests {
+    use std::sync::Arc;
+
+    use crate::Project;
+
+    use fs::FakeFs;
+    use git::{
+        Oid,
+        repository::{BisectProgress, BisectTerm, InitialGraphCommitData},
+    };
+    use gpui::{SharedString, TestAppContext};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_bisect(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        // A linear history, newest first.
+        let shas = (0..8)
+            .map(|ix| format!("{:040x}", 8 - ix).parse::<Oid>().unwrap())
+            .collect::<Vec<_>>();
+        let sha = |ix: usize| SharedString::from(shas[ix].to_string());
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        fs.set_graph_commits(
+            path!("/project/.git").as_ref(),
+            shas.iter()
+                .enumerate()
+                .map(|(ix, sha)| {
+                    Arc::new(InitialGraphCommitData {
+                        sha: *sha,
+                        parents: shas.get(ix + 1).copied().into_iter().collect(),
+                        ref_names: Vec::new(),
+                    })
+                })
+                .collect(),
+        );
+        fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+            state.refs.insert("HEAD".into(), shas[0].to_string());
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let repository = project.read_with(cx, |project, cx| {
+            project.repositories(cx).values().next().unwrap().clone()
+        });
+        let head = |cx: &mut TestAppContext| {
+            fs.with_git_state(path!("/project/.git").as_ref(), false, |state| {
+                SharedString::from(state.refs["HEAD"].clone())
+            })
+            .unwrap()
+        };
+
+        repository
+            .update(cx, |repository, cx| {
+                repository.bisect_start(Some(shas[0].to_string()), vec![shas[7].to_string()], cx)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            repository.read_with(cx, |repository, _| repository.bisect.clone()),
+            Some(BisectProgress {
+                bad: Some(sha(0)),
+                good: vec![sha(7)],
+                skipped: Vec::new(),
+                remaining: (0..7).map(sha).collect(),
+                first_bad: None,
+            })
+        );
+        assert_eq!(head(cx), sha(4));
+
+        for (term, next_head) in [(BisectTerm::Good, sha(2)), (BisectTerm::Bad, sha(3))] {
+            repository
+                .update(cx, |repository, cx| repository.bisect_mark(term, None, cx))
+                .await
+                .unwrap()
+                .unwrap();
+            cx.run_until_parked();
+            assert_eq!(head(cx), next_head);
+        }
+
+        repository
+            .update(cx, |repository, cx| {
+                repository.bisect_mark(BisectTerm::Good, None, cx)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        let bisect = repository.read_with(cx, |repository, _| repository.bisect.clone().unwrap());
+        assert_eq!(bisect.first_bad, Some(sha(2)));
+        assert_eq!(bisect.remaining_steps(), 0);
+
+        repository
+            .update(cx, |repository, cx| repository.bisect_reset(cx))
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            repository.read_with(cx, |repository, _| repository.bisect.clone()),
+            None
+        );
+        assert_eq!(head(cx), sha(0));
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        zlog::init_test();
+
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+    }
+}
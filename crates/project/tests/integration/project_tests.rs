@@ -3610,6 +3610,128 @@ async fn test_diagnostics_from_multiple_language_servers(cx: &mut gpui::TestAppC
     });
 }
 
+#[gpui::test]
+async fn test_task_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({ "src": { "a.rs": "one two three", "b.rs": "four five" } }),
+    )
+    .await;
+
+    let project = Project::test(fs, [Path::new(path!("/dir"))], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+    let problem = |file: &str, line, column, severity, message: &str| task::Problem {
+        file: PathBuf::from(file),
+        line,
+        column: Some(column),
+        end_line: None,
+        end_column: None,
+        severity,
+        code: None,
+        message: message.to_string(),
+        source: Some("rustc".to_string()),
+    };
+
+    lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.update_task_diagnostics(
+                task::TaskId("build".to_string()),
+                Some(PathBuf::from(path!("/dir"))),
+                vec![
+                    problem("src/a.rs", 1, 5, task::ProblemSeverity::Error, "error a"),
+                    problem(
+                        "src/b.rs",
+                        1,
+                        1,
+                        task::ProblemSeverity::Warning,
+                        "warning b",
+                    ),
+                    problem(
+                        path!("/elsewhere/c.rs"),
+                        1,
+                        1,
+                        task::ProblemSeverity::Error,
+                        "error c",
+                    ),
+                ],
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    lsp_store.read_with(cx, |lsp_store, cx| {
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 1,
+                warning_count: 1,
+            },
+            "Problems outside of the project should be ignored"
+        );
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/src/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    buffer.update(cx, |buffer, _| {
+        let chunks = chunks_with_diagnostics(buffer, 0..buffer.len());
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|(s, d)| (s.as_str(), *d))
+                .collect::<Vec<_>>(),
+            &[
+                ("one ", None),
+                ("t", Some(DiagnosticSeverity::ERROR)),
+                ("wo three", None),
+            ]
+        );
+    });
+
+    lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.update_task_diagnostics(
+                task::TaskId("build".to_string()),
+                Some(PathBuf::from(path!("/dir"))),
+                vec![problem(
+                    "src/b.rs",
+                    1,
+                    1,
+                    task::ProblemSeverity::Error,
+                    "error b",
+                )],
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    lsp_store.read_with(cx, |lsp_store, cx| {
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 1,
+                warning_count: 0,
+            },
+            "Rerunning the task should replace its previous diagnostics"
+        );
+    });
+    buffer.update(cx, |buffer, _| {
+        assert_eq!(
+            buffer
+                .snapshot()
+                .diagnostics_in_range::<_, usize>(0..buffer.len(), false)
+                .count(),
+            0
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4158,6 +4280,270 @@ async fn test_definition(cx: &mut gpui::TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_call_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() { b() }",
+            "b.rs": "fn b() { c() }\nfn c() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/b.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    let lsp_item = |name: &str, path: &str, line: u32| lsp::CallHierarchyItem {
+        name: name.to_string(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: None,
+        detail: None,
+        uri: lsp::Uri::from_file_path(path).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(line, 0), lsp::Position::new(line, 14)),
+        selection_range: lsp::Range::new(lsp::Position::new(line, 3), lsp::Position::new(line, 4)),
+        data: None,
+    };
+    let b_item = lsp_item("b", path!("/dir/b.rs"), 0);
+
+    fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>({
+        let b_item = b_item.clone();
+        move |params, _| {
+            let b_item = b_item.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 3)
+                );
+                Ok(Some(vec![b_item]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.item.name, "b");
+            Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                from: lsp_item("a", path!("/dir/a.rs"), 0),
+                from_ranges: vec![lsp::Range::new(
+                    lsp::Position::new(0, 9),
+                    lsp::Position::new(0, 12),
+                )],
+            }]))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.item.name, "b");
+            Ok(Some(vec![lsp::CallHierarchyOutgoingCall {
+                to: lsp_item("c", path!("/dir/b.rs"), 1),
+                from_ranges: vec![lsp::Range::new(
+                    lsp::Position::new(0, 9),
+                    lsp::Position::new(0, 12),
+                )],
+            }]))
+        },
+    );
+
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_call_hierarchy(&buffer, 3, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let item = items.into_iter().next().unwrap();
+    assert_eq!(item.name, "b");
+    assert_eq!(item.location.buffer, buffer);
+    cx.update(|cx| {
+        assert_eq!(item.selection_range.to_offset(buffer.read(cx)), 3..4);
+    });
+
+    let incoming = project
+        .update(cx, |project, cx| project.incoming_calls(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(incoming.len(), 1);
+    cx.update(|cx| {
+        let caller = &incoming[0];
+        assert_eq!(caller.item.name, "a");
+        let caller_buffer = caller.item.location.buffer.read(cx);
+        assert_eq!(
+            caller_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/a.rs")),
+        );
+        assert_eq!(caller.call_sites.len(), 1);
+        assert_eq!(caller.call_sites[0].buffer, caller.item.location.buffer);
+        assert_eq!(caller.call_sites[0].range.to_offset(caller_buffer), 9..12);
+    });
+
+    let outgoing = project
+        .update(cx, |project, cx| project.outgoing_calls(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(outgoing.len(), 1);
+    cx.update(|cx| {
+        let callee = &outgoing[0];
+        assert_eq!(callee.item.name, "c");
+        assert_eq!(callee.call_sites.len(), 1);
+        assert_eq!(callee.call_sites[0].buffer, buffer);
+        assert_eq!(callee.call_sites[0].range.to_offset(buffer.read(cx)), 9..12);
+    });
+}
+
+#[gpui::test]
+async fn test_type_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "trait A {}",
+            "b.rs": "trait B: A {}",
+            "c.rs": "struct C;",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                type_hierarchy_provider: Some(lsp::TypeHierarchyServerCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/b.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    let lsp_item = |name: &str, path: &str, start: u32, end: u32| lsp::TypeHierarchyItem {
+        name: name.to_string(),
+        kind: lsp::SymbolKind::INTERFACE,
+        tags: None,
+        detail: None,
+        uri: lsp::Uri::from_file_path(path).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, end)),
+        selection_range: lsp::Range::new(
+            lsp::Position::new(0, start),
+            lsp::Position::new(0, start + 1),
+        ),
+        data: None,
+    };
+    let b_item = lsp_item("B", path!("/dir/b.rs"), 6, 13);
+
+    fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>({
+        let b_item = b_item.clone();
+        move |params, _| {
+            let b_item = b_item.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 6)
+                );
+                Ok(Some(vec![b_item]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.item.name, "B");
+            Ok(Some(vec![lsp_item("A", path!("/dir/a.rs"), 6, 10)]))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.item.name, "B");
+            Ok(Some(vec![lsp_item("C", path!("/dir/c.rs"), 7, 9)]))
+        },
+    );
+
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, 6, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let item = items.into_iter().next().unwrap();
+    assert_eq!(item.name, "B");
+    assert_eq!(item.location.buffer, buffer);
+    cx.update(|cx| {
+        assert_eq!(item.selection_range.to_offset(buffer.read(cx)), 6..7);
+    });
+
+    let supertypes = project
+        .update(cx, |project, cx| project.supertypes(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(supertypes.len(), 1);
+    cx.update(|cx| {
+        let supertype = &supertypes[0];
+        assert_eq!(supertype.name, "A");
+        let supertype_buffer = supertype.location.buffer.read(cx);
+        assert_eq!(
+            supertype_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/a.rs")),
+        );
+        assert_eq!(supertype.selection_range.to_offset(supertype_buffer), 6..7);
+    });
+
+    let subtypes = project
+        .update(cx, |project, cx| project.subtypes(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(subtypes.len(), 1);
+    assert_eq!(subtypes[0].name, "C");
+}
+
 #[gpui::test]
 async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4844,6 +5230,127 @@ async fn test_save_file(cx: &mut gpui::TestAppContext) {
     assert_eq!(new_text, buffer.update(cx, |buffer, _| buffer.text()));
 }
 
+#[gpui::test]
+async fn test_local_history_snapshots(cx: &mut gpui::TestAppContext) {
+    // The local history is written on the database's own thread.
+    cx.executor().allow_parking();
+    init_test(cx);
+    let set_local_history_enabled = |enabled, cx: &mut gpui::TestAppContext| {
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings
+                        .project
+                        .local_history
+                        .get_or_insert_default()
+                        .enabled = Some(enabled);
+                });
+            });
+        });
+    };
+    set_local_history_enabled(true, cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "file1": "one",
+            ".env": "SECRET=1",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let (recorded_tx, mut recorded_rx) = futures::channel::mpsc::unbounded();
+    let buffer_store = project.read_with(cx, |project, _| project.buffer_store().clone());
+    let _subscription = cx.update(|cx| {
+        cx.subscribe(&buffer_store, move |_, event, _| {
+            if let buffer_store::BufferStoreEvent::LocalHistoryRecorded { abs_path } = event {
+                recorded_tx.unbounded_send(abs_path.clone()).ok();
+            }
+        })
+    });
+
+    let file1 = PathBuf::from(path!("/dir/file1"));
+    let buffer = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/file1"), cx))
+        .await
+        .unwrap();
+    buffer.update(cx, |buffer, cx| buffer.edit([(3..3, " two")], None, cx));
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+    assert_eq!(recorded_rx.next().await, Some(file1.clone()));
+
+    // The contents before the external change are the saved ones, so only the new contents are
+    // recorded.
+    fs.save(
+        path!("/dir/file1").as_ref(),
+        &"three".into(),
+        LineEnding::Unix,
+    )
+    .await
+    .unwrap();
+    assert_eq!(recorded_rx.next().await, Some(file1.clone()));
+
+    let entries = db::local_history::LOCAL_HISTORY.entries(&file1).unwrap();
+    assert_eq!(
+        entries.iter().map(|entry| entry.source).collect::<Vec<_>>(),
+        [
+            db::local_history::LocalHistorySource::ExternalChange,
+            db::local_history::LocalHistorySource::Save,
+        ]
+    );
+    let contents = entries
+        .iter()
+        .map(|entry| {
+            db::local_history::LOCAL_HISTORY
+                .content(entry.id)
+                .unwrap()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(contents, ["three", "one two"]);
+
+    // Private files are left out of the local history.
+    let env_buffer = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/.env"), cx))
+        .await
+        .unwrap();
+    env_buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "#")], None, cx));
+    project
+        .update(cx, |project, cx| {
+            project.save_buffer(env_buffer.clone(), cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    assert!(
+        db::local_history::LOCAL_HISTORY
+            .entries(Path::new(path!("/dir/.env")))
+            .unwrap()
+            .is_empty()
+    );
+
+    // Nothing is recorded when the local history is disabled.
+    set_local_history_enabled(false, cx);
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "four ")], None, cx));
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    assert_eq!(
+        db::local_history::LOCAL_HISTORY
+            .entries(&file1)
+            .unwrap()
+            .len(),
+        2
+    );
+    assert!(recorded_rx.try_next().is_err());
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_save_file_spawns_language_server(cx: &mut gpui::TestAppContext) {
     // Issue: #24349
@@ -6401,6 +6908,94 @@ async fn test_search(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_search_with_index(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.editor.search.get_or_insert_default().index = Some(true);
+            });
+        });
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "one.rs": "const ONE: usize = 1;",
+            "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+            "three.rs": "const THREE: usize = one::ONE + two::TWO;",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    cx.run_until_parked();
+
+    let query = || {
+        SearchQuery::text(
+            "TWO",
+            false,
+            true,
+            false,
+            Default::default(),
+            Default::default(),
+            false,
+            None,
+        )
+        .unwrap()
+    };
+    assert_eq!(
+        search(&project, query(), cx).await.unwrap(),
+        HashMap::from_iter([
+            (path!("dir/two.rs").to_string(), vec![6..9]),
+            (path!("dir/three.rs").to_string(), vec![37..40])
+        ])
+    );
+
+    // Files changed after being indexed are still found.
+    fs.insert_file(
+        path!("/dir/one.rs"),
+        b"const ONE: usize = two::TWO - 1;".to_vec(),
+    )
+    .await;
+    assert_eq!(
+        search(&project, query(), cx).await.unwrap(),
+        HashMap::from_iter([
+            (path!("dir/one.rs").to_string(), vec![24..27]),
+            (path!("dir/two.rs").to_string(), vec![6..9]),
+            (path!("dir/three.rs").to_string(), vec![37..40])
+        ])
+    );
+    cx.run_until_parked();
+    assert_eq!(
+        search(&project, query(), cx).await.unwrap(),
+        HashMap::from_iter([
+            (path!("dir/one.rs").to_string(), vec![24..27]),
+            (path!("dir/two.rs").to_string(), vec![6..9]),
+            (path!("dir/three.rs").to_string(), vec![37..40])
+        ])
+    );
+
+    // The index is written to disk once changes settle, and later changes are appended to it
+    // as another segment.
+    let segment_count = || {
+        fs.files()
+            .iter()
+            .filter(|path| path.starts_with(paths::search_index_dir()))
+            .count()
+    };
+    cx.executor().advance_clock(Duration::from_secs(60));
+    cx.run_until_parked();
+    assert_eq!(segment_count(), 1);
+
+    fs.insert_file(path!("/dir/two.rs"), b"const TWO: usize = 2;".to_vec())
+        .await;
+    cx.executor().advance_clock(Duration::from_secs(60));
+    cx.run_until_parked();
+    assert_eq!(segment_count(), 2);
+}
+
 #[gpui::test]
 async fn test_search_with_inclusions(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -1,4 +1,6 @@
-use project::search::SearchQuery;
+use language::{Buffer, LanguageRegistry};
+use project::{search::SearchQuery, search_index::required_literals};
+use std::sync::Arc;
 use text::Rope;
 use util::{
     paths::{PathMatcher, PathStyle},
@@ -145,7 +147,6 @@ async fn test_multiline_regex(cx: &mut gpui::TestAppContext) {
     )
     .expect("Should be able to create a regex SearchQuery");
 
-    use language::Buffer;
     let text = Rope::from("hello\nworld\nhello\nworld");
     let snapshot = cx
         .update(|app| Buffer::build_snapshot(text, None, None, app))
@@ -154,3 +155,136 @@ async fn test_multiline_regex(cx: &mut gpui::TestAppContext) {
     let results = search_query.search(&snapshot, None).await;
     assert_eq!(results, vec![0..6, 12..18]);
 }
+
+#[gpui::test]
+async fn test_structural_search_and_replace(cx: &mut gpui::TestAppContext) {
+    let search_query = SearchQuery::structural(
+        "$X.lock().unwrap()",
+        false,
+        Default::default(),
+        Default::default(),
+        false,
+        None,
+    )
+    .expect("Should be able to create a structural SearchQuery")
+    .with_replacement("$X.lock()".to_string());
+
+    let text = Rope::from(
+        "fn main() {\n    let a = state.lock().unwrap();\n    let b = self.inner\n        .lock()\n        .unwrap();\n    let c = state.lock();\n}\n",
+    );
+    let snapshot = cx
+        .update(|app| Buffer::build_snapshot(text, Some(language::rust_lang()), None, app))
+        .await;
+
+    let results = search_query.search(&snapshot, None).await;
+    let matched_text = results
+        .iter()
+        .map(|range| snapshot.text_for_range(range.clone()).collect::<String>())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        matched_text,
+        vec![
+            "state.lock().unwrap()",
+            "self.inner\n        .lock()\n        .unwrap()"
+        ]
+    );
+    assert_eq!(
+        search_query
+            .replacement_for(&matched_text[1], snapshot.language())
+            .map(|replacement| replacement.into_owned()),
+        Some("self.inner.lock()".to_string())
+    );
+    assert_eq!(search_query.replacement_for(&matched_text[1], None), None);
+
+    let results = search_query.search(&snapshot, Some(20..70)).await;
+    assert_eq!(results, vec![4..25]);
+}
+
+#[gpui::test]
+async fn test_structural_search_in_injected_language(cx: &mut gpui::TestAppContext) {
+    let search_query = SearchQuery::structural(
+        "$X.lock().unwrap()",
+        false,
+        Default::default(),
+        Default::default(),
+        false,
+        None,
+    )
+    .unwrap();
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    language_registry.add(language::rust_lang());
+    let text = Rope::from(
+        "Call state.lock().unwrap() here:\n\n```rust\nlet a = state.lock().unwrap();\n```\n",
+    );
+    let snapshot = cx
+        .update(|app| {
+            Buffer::build_snapshot(
+                text,
+                Some(language::markdown_lang()),
+                Some(language_registry),
+                app,
+            )
+        })
+        .await;
+
+    // Only the code block is parsed as Rust, so the prose doesn't match.
+    let results = search_query.search(&snapshot, None).await;
+    let matched_text = results
+        .iter()
+        .map(|range| snapshot.text_for_range(range.clone()).collect::<String>())
+        .collect::<Vec<_>>();
+    assert_eq!(matched_text, vec!["state.lock().unwrap()"]);
+    assert_eq!(results[0].start, 50);
+}
+
+#[test]
+fn test_required_literals_for_search_index() {
+    let regex = |query: &str| {
+        SearchQuery::regex(
+            query,
+            false,
+            true,
+            false,
+            false,
+            Default::default(),
+            Default::default(),
+            false,
+            None,
+        )
+        .unwrap()
+    };
+    let literals = |query: SearchQuery| required_literals(&query);
+
+    assert_eq!(literals(regex(r"fn \w+_test\(")), vec!["fn ", "_test("]);
+    assert_eq!(literals(regex(r"colou?r: \d+")), vec!["colo", "r: "]);
+    // The common prefix of the alternation is required as well.
+    assert_eq!(
+        literals(regex(r"foo(bar|baz)+qux")),
+        vec!["foo", "ba", "qux"]
+    );
+    assert_eq!(
+        literals(regex(r"(prefix_a|prefix_bb)_suffix")),
+        vec!["prefix_", "_suffix"]
+    );
+    assert_eq!(literals(regex(r"x(ab|cd)y")), vec!["x", "y"]);
+    assert_eq!(literals(regex(r"ab{0,2}c[xyz]*end")), vec!["a", "c", "end"]);
+    assert_eq!(literals(regex(r"\x41BCD\p{Greek}EF")), vec!["ABCD", "EF"]);
+    assert_eq!(literals(regex(r"^foo\b")), vec!["foo"]);
+    assert_eq!(literals(regex(r"foo|bar")), Vec::<String>::new());
+    assert_eq!(literals(regex(r"(?x) foo bar")), vec!["foobar"]);
+    assert_eq!(literals(regex(r"(?i)foo")), Vec::<String>::new());
+    // Backreferences are only supported by fancy-regex.
+    assert_eq!(literals(regex(r"(\w+) \1")), Vec::<String>::new());
+
+    let structural = SearchQuery::structural(
+        "$X.lock().unwrap()",
+        false,
+        Default::default(),
+        Default::default(),
+        false,
+        None,
+    )
+    .unwrap();
+    assert_eq!(literals(structural), vec!["lock", "unwrap"]);
+}
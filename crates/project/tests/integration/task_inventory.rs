@@ -6,6 +6,7 @@ use serde_json::json;
 use settings::SettingsLocation;
 use std::path::Path;
 use std::sync::Arc;
+use task::DependsOrder;
 use util::rel_path::rel_path;
 
 use project::task_store::{TaskSettingsLocation, TaskStore};
@@ -560,6 +561,133 @@ async fn test_inventory_static_task_filters(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_resolve_task_dependencies(cx: &mut TestAppContext) {
+    init_test(cx);
+    let inventory = cx.update(|cx| Inventory::new(cx));
+    let worktree_id = WorktreeId::from_usize(1);
+
+    inventory.update(cx, |inventory, _| {
+        inventory
+            .update_file_based_tasks(
+                TaskSettingsLocation::Global(tasks_file()),
+                Some(
+                    &json!([
+                        { "label": "lint", "command": "cargo", "args": ["clippy"] },
+                        { "label": "loop_a", "command": "echo", "depends_on": ["loop_b"] },
+                        { "label": "loop_b", "command": "echo", "depends_on": ["loop_a"] },
+                        { "label": "broken", "command": "echo", "depends_on": ["missing"] },
+                    ])
+                    .to_string(),
+                ),
+            )
+            .unwrap();
+        inventory
+            .update_file_based_tasks(
+                TaskSettingsLocation::Worktree(SettingsLocation {
+                    worktree_id,
+                    path: rel_path(".zed"),
+                }),
+                Some(
+                    &json!([
+                        { "label": "build", "command": "cargo", "args": ["build"] },
+                        {
+                            "label": "test",
+                            "command": "cargo",
+                            "args": ["test"],
+                            "depends_on": ["build"]
+                        },
+                        {
+                            "label": "check",
+                            "depends_on": ["test", "lint"],
+                            "depends_order": "parallel"
+                        },
+                        {
+                            "label": "bench",
+                            "command": "cargo",
+                            "args": ["bench"],
+                            "depends_on": ["build"]
+                        },
+                        { "label": "ci", "depends_on": ["test", "bench"] },
+                    ])
+                    .to_string(),
+                ),
+            )
+            .unwrap();
+    });
+
+    let tasks = inventory
+        .update(cx, |inventory, cx| {
+            inventory.list_tasks(None, None, Some(worktree_id), cx)
+        })
+        .await;
+    let resolve_dependencies = |label: &str, cx: &mut TestAppContext| {
+        let (task_source_kind, template) = tasks
+            .iter()
+            .find(|(_, template)| template.label == label)
+            .unwrap_or_else(|| panic!("Failed to find task with name {label}"));
+        let resolved_task = template
+            .resolve_task(&task_source_kind.to_id_base(), &Default::default())
+            .unwrap_or_else(|| panic!("Failed to resolve task with name {label}"));
+        inventory.read_with(cx, |inventory, _| {
+            inventory.resolve_task_dependencies(task_source_kind, &resolved_task)
+        })
+    };
+
+    let dependencies = resolve_dependencies("build", cx).unwrap();
+    assert!(dependencies.tasks.is_empty());
+
+    let dependencies = resolve_dependencies("check", cx).unwrap();
+    assert_eq!(dependencies.order, DependsOrder::Parallel);
+    assert_eq!(
+        dependencies
+            .tasks
+            .iter()
+            .map(|dependency| dependency.resolved_task.original_task().label.as_str())
+            .collect::<Vec<_>>(),
+        vec!["test", "lint"],
+    );
+    let test_dependencies = &dependencies.tasks[0].dependencies;
+    assert_eq!(test_dependencies.order, DependsOrder::Sequence);
+    assert_eq!(
+        test_dependencies
+            .tasks
+            .iter()
+            .map(|dependency| dependency.resolved_task.original_task().label.as_str())
+            .collect::<Vec<_>>(),
+        vec!["build"],
+    );
+    assert!(dependencies.tasks[1].dependencies.tasks.is_empty());
+
+    // `build` is shared by both dependencies of `ci`, and each of them still waits for it.
+    let dependencies = resolve_dependencies("ci", cx).unwrap();
+    let labels = |dependencies: &TaskDependencies| {
+        dependencies
+            .tasks
+            .iter()
+            .map(|dependency| dependency.resolved_task.original_task().label.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(labels(&dependencies), vec!["test", "bench"]);
+    assert_eq!(labels(&dependencies.tasks[0].dependencies), vec!["build"]);
+    assert_eq!(labels(&dependencies.tasks[1].dependencies), vec!["build"]);
+    assert_eq!(
+        dependencies.tasks[0].dependencies.tasks[0].resolved_task.id,
+        dependencies.tasks[1].dependencies.tasks[0].resolved_task.id,
+    );
+
+    let error = resolve_dependencies("loop_a", cx).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Task dependency cycle: loop_a -> loop_b -> loop_a"
+    );
+    let error = resolve_dependencies("broken", cx).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Task `broken` depends on an unknown task `missing`"
+    );
+}
+
 fn init_test(_cx: &mut TestAppContext) {
     zlog::init_test();
     TaskStore::init(None);
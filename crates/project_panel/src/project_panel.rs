@@ -16,8 +16,10 @@ use editor::{
 use file_icons::FileIcons;
 use git;
 use git::status::GitSummary;
+use git::submodule::{Submodule, SubmoduleStatus};
 use git_ui;
 use git_ui::file_diff_view::FileDiffView;
+use git_ui::git_panel::GitPanel;
 use gpui::{
     Action, AnyElement, App, AsyncWindowContext, Bounds, ClipboardItem, Context, CursorStyle,
     DismissEvent, Div, DragMoveEvent, Entity, EventEmitter, ExternalPaths, FocusHandle, Focusable,
@@ -247,6 +249,7 @@ struct EntryDetails {
     filename_text_color: Color,
     diagnostic_severity: Option<DiagnosticSeverity>,
     git_status: GitSummary,
+    submodule: Option<Submodule>,
     is_private: bool,
     worktree_id: WorktreeId,
     canonical_path: Option<Arc<Path>>,
@@ -364,6 +367,8 @@ actions!(
         SelectPrevDirectory,
         /// Opens a diff view to compare two marked files.
         CompareMarkedFiles,
+        /// Initializes the selected submodule and checks out its recorded commit.
+        UpdateSubmodule,
     ]
 );
 
@@ -625,6 +630,7 @@ impl ProjectPanel {
                 window,
                 |this, _, event, window, cx| match event {
                     GitStoreEvent::RepositoryUpdated(_, RepositoryEvent::StatusesChanged, _)
+                    | GitStoreEvent::RepositoryUpdated(_, RepositoryEvent::SubmodulesChanged, _)
                     | GitStoreEvent::RepositoryAdded
                     | GitStoreEvent::RepositoryRemoved(_) => {
                         this.update_visible_entries(None, false, false, window, cx);
@@ -1117,6 +1123,12 @@ impl ProjectPanel {
                     || (settings.hide_root && visible_worktrees_count == 1));
             let should_show_compare = !is_dir && self.file_abs_paths_to_diff(cx).is_some();
 
+            let submodule = if is_dir {
+                self.submodule_for_entry(worktree_id, entry, cx)
+            } else {
+                None
+            };
+
             let has_git_repo = !is_dir && {
                 let project_path = project::ProjectPath {
                     worktree_id,
@@ -1200,6 +1212,18 @@ impl ProjectPanel {
                                 menu.separator()
                                     .action("View File History", Box::new(git::FileHistory))
                             })
+                            .when_some(submodule, |menu, submodule| {
+                                let label = if submodule.status == SubmoduleStatus::Uninitialized {
+                                    "Initialize Submodule"
+                                } else {
+                                    "Update Submodule"
+                                };
+                                menu.separator().action_disabled_when(
+                                    is_collab,
+                                    label,
+                                    Box::new(UpdateSubmodule),
+                                )
+                            })
                             .when(!should_hide_rename, |menu| {
                                 menu.separator().action("Rename", Box::new(Rename))
                             })
@@ -3404,6 +3428,58 @@ impl ProjectPanel {
         }
     }
 
+    fn update_submodule(
+        &mut self,
+        _: &UpdateSubmodule,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((worktree, entry)) = self.selected_sub_entry(cx) else {
+            return;
+        };
+        let project_path = ProjectPath {
+            worktree_id: worktree.read(cx).id(),
+            path: entry.path.clone(),
+        };
+        let Some((repository, submodule)) = self
+            .project
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .submodule_for_project_path(&project_path, cx)
+        else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                if let Some(panel) = workspace.panel::<GitPanel>(cx) {
+                    panel.update(cx, |panel, cx| {
+                        panel.update_submodules(repository, vec![submodule.path], window, cx);
+                    });
+                }
+            })
+            .ok();
+    }
+
+    fn submodule_for_entry(
+        &self,
+        worktree_id: WorktreeId,
+        entry: &Entry,
+        cx: &App,
+    ) -> Option<Submodule> {
+        let project_path = ProjectPath {
+            worktree_id,
+            path: entry.path.clone(),
+        };
+        let (_, submodule) = self
+            .project
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .submodule_for_project_path(&project_path, cx)?;
+        Some(submodule)
+    }
+
     fn open_system(&mut self, _: &OpenWithSystem, _: &mut Window, cx: &mut Context<Self>) {
         if let Some((worktree, entry)) = self.selected_entry(cx) {
             let abs_path = worktree.absolutize(&entry.path);
@@ -5031,6 +5107,7 @@ impl ProjectPanel {
 
         let filename_text_color = details.filename_text_color;
         let diagnostic_severity = details.diagnostic_severity;
+        let submodule = details.submodule.clone();
         let item_colors = get_item_color(is_sticky, cx);
 
         let canonical_path = details
@@ -5485,6 +5562,14 @@ impl ProjectPanel {
                                 .into_any_element(),
                         )
                     })
+                    .when_some(submodule, |this, submodule| {
+                        this.end_slot::<AnyElement>(
+                            div()
+                                .pr_3()
+                                .child(git_ui::submodule_badge("submodule_icon", &submodule))
+                                .into_any_element(),
+                        )
+                    })
                     .child(if let Some(icon) = &icon {
                         if let Some((_, decoration_color)) =
                             entry_diagnostic_aware_icon_decoration_and_color(diagnostic_severity)
@@ -5897,6 +5982,11 @@ impl ProjectPanel {
         let filename_text_color =
             entry_git_aware_label_color(git_status, entry.is_ignored, is_marked);
 
+        let submodule = entry
+            .is_dir()
+            .then(|| self.submodule_for_entry(worktree_id, entry, cx))
+            .flatten();
+
         let is_cut = self
             .clipboard
             .as_ref()
@@ -5919,6 +6009,7 @@ impl ProjectPanel {
             filename_text_color,
             diagnostic_severity,
             git_status,
+            submodule,
             is_private: entry.is_private,
             worktree_id,
             canonical_path: entry.canonical_path.clone(),
@@ -6329,6 +6420,7 @@ impl Render for ProjectPanel {
                 .on_action(cx.listener(Self::fold_directory))
                 .on_action(cx.listener(Self::remove_from_project))
                 .on_action(cx.listener(Self::compare_marked_files))
+                .on_action(cx.listener(Self::update_submodule))
                 .when(!project.is_read_only(cx), |el| {
                     el.on_action(cx.listener(Self::new_file))
                         .on_action(cx.listener(Self::new_directory))
@@ -231,6 +231,24 @@ messages!(
     (GetColorPresentationResponse, Background),
     (GetFoldingRanges, Background),
     (GetFoldingRangesResponse, Background),
+    (GetDocumentLinks, Background),
+    (GetDocumentLinksResponse, Background),
+    (ResolveDocumentLink, Background),
+    (ResolveDocumentLinkResponse, Background),
+    (PrepareCallHierarchy, Background),
+    (PrepareCallHierarchyResponse, Background),
+    (GetIncomingCalls, Background),
+    (GetIncomingCallsResponse, Background),
+    (GetOutgoingCalls, Background),
+    (GetOutgoingCallsResponse, Background),
+    (PrepareTypeHierarchy, Background),
+    (PrepareTypeHierarchyResponse, Background),
+    (GetSupertypes, Background),
+    (GetSupertypesResponse, Background),
+    (GetSubtypes, Background),
+    (GetSubtypesResponse, Background),
+    (GetSelectionRanges, Background),
+    (GetSelectionRangesResponse, Background),
     (RefreshCodeLens, Background),
     (GetCodeLens, Background),
     (GetCodeLensResponse, Background),
@@ -256,6 +274,9 @@ messages!(
     (SyncExtensionsResponse, Background),
     (BreakpointsForFile, Background),
     (ToggleBreakpoint, Foreground),
+    (FunctionBreakpoints, Background),
+    (SetFunctionBreakpoints, Foreground),
+    (UpdateTaskDiagnostics, Background),
     (SynchronizeBuffers, Foreground),
     (SynchronizeBuffersResponse, Foreground),
     (SynchronizeContexts, Foreground),
@@ -271,6 +292,21 @@ messages!(
     (StashPop, Background),
     (StashApply, Background),
     (StashDrop, Background),
+    (GitRebaseTodo, Background),
+    (GitRebaseTodoResponse, Background),
+    (GitRebaseInteractive, Background),
+    (GitControlRebase, Background),
+    (GitBisectStart, Background),
+    (GitBisectMark, Background),
+    (GitBisectReset, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitGetTags, Background),
+    (GitTagsResponse, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
+    (GitPushTag, Background),
+    (GitUpdateSubmodules, Background),
     (UpdateBuffer, Foreground),
     (UpdateBufferFile, Foreground),
     (UpdateChannelBuffer, Foreground),
@@ -453,6 +489,15 @@ request_messages!(
     (ResolveInlayHint, ResolveInlayHintResponse),
     (GetDocumentColor, GetDocumentColorResponse),
     (GetFoldingRanges, GetFoldingRangesResponse),
+    (GetDocumentLinks, GetDocumentLinksResponse),
+    (ResolveDocumentLink, ResolveDocumentLinkResponse),
+    (PrepareCallHierarchy, PrepareCallHierarchyResponse),
+    (GetIncomingCalls, GetIncomingCallsResponse),
+    (GetOutgoingCalls, GetOutgoingCallsResponse),
+    (PrepareTypeHierarchy, PrepareTypeHierarchyResponse),
+    (GetSupertypes, GetSupertypesResponse),
+    (GetSubtypes, GetSubtypesResponse),
+    (GetSelectionRanges, GetSelectionRangesResponse),
     (GetColorPresentation, GetColorPresentationResponse),
     (RespondToChannelInvite, Ack),
     (RespondToContactRequest, Ack),
@@ -473,6 +518,19 @@ request_messages!(
     (StashPop, Ack),
     (StashApply, Ack),
     (StashDrop, Ack),
+    (GitRebaseTodo, GitRebaseTodoResponse),
+    (GitRebaseInteractive, Ack),
+    (GitControlRebase, Ack),
+    (GitBisectStart, Ack),
+    (GitBisectMark, Ack),
+    (GitBisectReset, Ack),
+    (GitCherryPick, Ack),
+    (GitRevert, Ack),
+    (GitGetTags, GitTagsResponse),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
+    (GitPushTag, RemoteMessageResponse),
+    (GitUpdateSubmodules, RemoteMessageResponse),
     (UpdateBuffer, Ack),
     (UpdateParticipantLocation, Ack),
     (UpdateProject, Ack),
@@ -536,6 +594,8 @@ request_messages!(
     (GitDiff, GitDiffResponse),
     (GitInit, Ack),
     (ToggleBreakpoint, Ack),
+    (SetFunctionBreakpoints, Ack),
+    (UpdateTaskDiagnostics, Ack),
     (GetDebugAdapterBinary, DebugAdapterBinary),
     (RunDebugLocators, DebugRequest),
     (GetDocumentDiagnostics, GetDocumentDiagnosticsResponse),
@@ -561,6 +621,7 @@ lsp_messages!(
     (GetReferences, GetReferencesResponse, true),
     (GetDocumentColor, GetDocumentColorResponse, true),
     (GetFoldingRanges, GetFoldingRangesResponse, true),
+    (GetDocumentLinks, GetDocumentLinksResponse, true),
     (GetDocumentSymbols, GetDocumentSymbolsResponse, true),
     (GetHover, GetHoverResponse, true),
     (GetCodeActions, GetCodeActionsResponse, true),
@@ -596,6 +657,15 @@ entity_messages!(
     CreateProjectEntry,
     GetDocumentColor,
     GetFoldingRanges,
+    GetDocumentLinks,
+    ResolveDocumentLink,
+    PrepareCallHierarchy,
+    GetIncomingCalls,
+    GetOutgoingCalls,
+    PrepareTypeHierarchy,
+    GetSupertypes,
+    GetSubtypes,
+    GetSelectionRanges,
     DeleteProjectEntry,
     ExpandProjectEntry,
     ExpandAllForProjectEntry,
@@ -655,6 +725,19 @@ entity_messages!(
     StashPop,
     StashApply,
     StashDrop,
+    GitRebaseTodo,
+    GitRebaseInteractive,
+    GitControlRebase,
+    GitBisectStart,
+    GitBisectMark,
+    GitBisectReset,
+    GitCherryPick,
+    GitRevert,
+    GitGetTags,
+    GitCreateTag,
+    GitDeleteTag,
+    GitPushTag,
+    GitUpdateSubmodules,
     UpdateBuffer,
     UpdateBufferFile,
     UpdateDiagnosticSummary,
@@ -720,6 +803,9 @@ entity_messages!(
     GitInit,
     BreakpointsForFile,
     ToggleBreakpoint,
+    FunctionBreakpoints,
+    SetFunctionBreakpoints,
+    UpdateTaskDiagnostics,
     RunDebugLocators,
     GetDebugAdapterBinary,
     LogToDebugConsole,
@@ -927,6 +1013,7 @@ impl LspQuery {
             Some(lsp_query::Request::GetReferences(_)) => ("GetReferences", false),
             Some(lsp_query::Request::GetDocumentColor(_)) => ("GetDocumentColor", false),
             Some(lsp_query::Request::GetFoldingRanges(_)) => ("GetFoldingRanges", false),
+            Some(lsp_query::Request::GetDocumentLinks(_)) => ("GetDocumentLinks", false),
             Some(lsp_query::Request::GetDocumentSymbols(_)) => ("GetDocumentSymbols", false),
             Some(lsp_query::Request::InlayHints(_)) => ("InlayHints", false),
             Some(lsp_query::Request::SemanticTokens(_)) => ("SemanticTokens", false),
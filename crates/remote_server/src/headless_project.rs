@@ -23,6 +23,7 @@ use project::{
     lsp_store::log_store::{self, GlobalLogStore, LanguageServerKind, LogKind},
     project_settings::SettingsObserver,
     search::SearchQuery,
+    search_index::SearchIndex,
     task_store::TaskStore,
     trusted_worktrees::{PathTrust, RemoteHostLocation, TrustedWorktrees},
     worktree_store::{WorktreeIdCounter, WorktreeStore},
@@ -62,6 +63,7 @@ pub struct HeadlessProject {
     pub extensions: Entity<HeadlessExtensionStore>,
     pub git_store: Entity<GitStore>,
     pub environment: Entity<ProjectEnvironment>,
+    pub search_index: Entity<SearchIndex>,
     // Used mostly to keep alive the toolchain store for RPC handlers.
     // Local variant is used within LSP store, but that's a separate entity.
     pub _toolchain_store: Entity<ToolchainStore>,
@@ -169,6 +171,8 @@ impl HeadlessProject {
             store
         });
 
+        let search_index = cx.new(|cx| SearchIndex::new(fs.clone(), worktree_store.clone(), cx));
+
         let prettier_store = cx.new(|cx| {
             PrettierStore::new(
                 node_runtime.clone(),
@@ -344,6 +348,7 @@ impl HeadlessProject {
             extensions,
             git_store,
             environment,
+            search_index,
             _toolchain_store: toolchain_store,
         }
     }
@@ -914,6 +919,7 @@ impl HeadlessProject {
                     this.fs.clone(),
                     this.buffer_store.clone(),
                     this.worktree_store.clone(),
+                    Some(this.search_index.clone()),
                     message.limit as _,
                     cx,
                 )
@@ -1150,6 +1150,70 @@ async fn test_remote_resolve_abs_path(cx: &mut TestAppContext, server_cx: &mut T
     assert!(path.is_none());
 }
 
+#[gpui::test]
+async fn test_remote_task_diagnostics(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        path!("/code"),
+        json!({
+            "project1": {
+                "src": {
+                    "lib.rs": "fn one() -> usize { 1 }"
+                }
+            },
+        }),
+    )
+    .await;
+
+    let (project, headless) = init_test(&fs, cx, server_cx).await;
+    project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(path!("/code/project1"), true, cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+    lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.update_task_diagnostics(
+                task::TaskId("build".to_string()),
+                Some(PathBuf::from(path!("/code/project1"))),
+                vec![task::Problem {
+                    file: PathBuf::from("src/lib.rs"),
+                    line: 1,
+                    column: Some(4),
+                    end_line: None,
+                    end_column: None,
+                    severity: task::ProblemSeverity::Warning,
+                    code: None,
+                    message: "function `one` is never used".to_string(),
+                    source: Some("rustc".to_string()),
+                }],
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    let expected_summary = project::DiagnosticSummary {
+        error_count: 0,
+        warning_count: 1,
+    };
+    headless.read_with(server_cx, |headless, cx| {
+        assert_eq!(
+            headless.lsp_store.read(cx).diagnostic_summary(false, cx),
+            expected_summary,
+            "The host should report the problems found by the task"
+        );
+    });
+    project.read_with(cx, |project, cx| {
+        assert_eq!(project.diagnostic_summary(false, cx), expected_summary);
+    });
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_canceling_buffer_opening(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
     let fs = FakeFs::new(server_cx.executor());
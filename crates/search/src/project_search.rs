@@ -1,7 +1,8 @@
 use crate::{
     BufferSearchBar, FocusSearch, HighlightKey, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll,
     ReplaceNext, SearchOption, SearchOptions, SearchSource, SelectNextMatch, SelectPreviousMatch,
-    ToggleCaseSensitive, ToggleIncludeIgnored, ToggleRegex, ToggleReplace, ToggleWholeWord,
+    ToggleCaseSensitive, ToggleIncludeIgnored, ToggleRegex, ToggleReplace, ToggleStructural,
+    ToggleWholeWord,
     buffer_search::Deploy,
     search_bar::{
         ActionButtonState, alignment_element, input_base_styles, render_action_button,
@@ -132,6 +133,12 @@ pub fn init(cx: &mut App) {
         register_workspace_action(workspace, move |search_bar, _: &ToggleRegex, window, cx| {
             search_bar.toggle_search_option(SearchOptions::REGEX, window, cx);
         });
+        register_workspace_action(
+            workspace,
+            move |search_bar, _: &ToggleStructural, window, cx| {
+                search_bar.toggle_search_option(SearchOptions::STRUCTURAL, window, cx);
+            },
+        );
         register_workspace_action(
             workspace,
             move |search_bar, action: &ToggleReplace, window, cx| {
@@ -721,6 +728,12 @@ impl ProjectSearchView {
 
     fn toggle_search_option(&mut self, option: SearchOptions, cx: &mut Context<Self>) {
         self.search_options.toggle(option);
+        // Regex and structural patterns are different query syntaxes, so only one can be used.
+        if option.contains(SearchOptions::REGEX) {
+            self.search_options.remove(SearchOptions::STRUCTURAL);
+        } else if option.contains(SearchOptions::STRUCTURAL) {
+            self.search_options.remove(SearchOptions::REGEX);
+        }
         ActiveSettings::update_global(cx, |settings, cx| {
             settings.0.insert(
                 self.entity.read(cx).project.downgrade(),
@@ -1323,7 +1336,35 @@ impl ProjectSearchView {
             .count()
             > 1;
 
-        let query = if self.search_options.contains(SearchOptions::REGEX) {
+        let query = if self.search_options.contains(SearchOptions::STRUCTURAL) {
+            match SearchQuery::structural(
+                text,
+                self.search_options.contains(SearchOptions::INCLUDE_IGNORED),
+                included_files,
+                excluded_files,
+                match_full_paths,
+                open_buffers,
+            ) {
+                Ok(query) => {
+                    let should_unmark_error = self.panels_with_errors.remove(&InputPanel::Query);
+                    if should_unmark_error.is_some() {
+                        cx.notify();
+                    }
+
+                    Some(query)
+                }
+                Err(e) => {
+                    let should_mark_error = self
+                        .panels_with_errors
+                        .insert(InputPanel::Query, e.to_string());
+                    if should_mark_error.is_none() {
+                        cx.notify();
+                    }
+
+                    None
+                }
+            }
+        } else if self.search_options.contains(SearchOptions::REGEX) {
             match SearchQuery::regex(
                 text,
                 self.search_options.contains(SearchOptions::WHOLE_WORD),
@@ -1626,6 +1667,20 @@ impl ProjectSearchView {
                         window.dispatch_action(ToggleRegex.boxed_clone(), cx)
                     }),
             )
+            .child(
+                Button::new("structural", "Match syntax patterns")
+                    .icon(IconName::Code)
+                    .icon_position(IconPosition::Start)
+                    .icon_size(IconSize::Small)
+                    .key_binding(KeyBinding::for_action_in(
+                        &ToggleStructural,
+                        &focus_handle,
+                        cx,
+                    ))
+                    .on_click(|_event, window, cx| {
+                        window.dispatch_action(ToggleStructural.boxed_clone(), cx)
+                    }),
+            )
             .child(
                 Button::new("match-case", "Match case")
                     .icon(IconName::CaseSensitive)
@@ -2119,6 +2174,11 @@ impl Render for ProjectSearchBar {
                         search.search_options,
                         SearchSource::Project(cx),
                         focus_handle.clone(),
+                    ))
+                    .child(SearchOption::Structural.as_button(
+                        search.search_options,
+                        SearchSource::Project(cx),
+                        focus_handle.clone(),
                     )),
             );
 
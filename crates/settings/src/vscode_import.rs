@@ -507,6 +507,7 @@ impl VsCodeSettings {
             context_servers: self.context_servers(),
             context_server_timeout: None,
             load_direnv: None,
+            local_history: None,
             slash_commands: None,
             git_hosting_providers: None,
             disable_ai: None,
@@ -563,6 +564,7 @@ impl VsCodeSettings {
                     }
                 }),
             document_folding_ranges: None,
+            document_selection_ranges: None,
             document_symbols: None,
             linked_edits: self.read_bool("editor.linkedEditing"),
             preferred_line_length: self.read_u32("editor.wordWrapColumn"),
@@ -122,6 +122,8 @@ pub struct AgentSettingsContent {
     /// `always_confirm`) match against the tool's text input (command, path,
     /// URL, etc.).
     pub tool_permissions: Option<ToolPermissionsContent>,
+    /// Sandboxing for commands run by the agent's terminal tool.
+    pub terminal_sandbox: Option<TerminalSandboxContent>,
 }
 
 impl AgentSettingsContent {
@@ -513,6 +515,27 @@ pub enum CustomAgentServerSettings {
     },
 }
 
+#[with_fallible_options]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema, MergeFrom)]
+pub struct TerminalSandboxContent {
+    /// Whether to run the agent's terminal commands in a sandbox that can only
+    /// write inside the project's worktrees. Only supported for local projects
+    /// on Linux, and requires bubblewrap (`bwrap`) to be installed.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// Paths that sandboxed commands may read, in addition to the project's
+    /// worktrees and the system directories (e.g. `~/.cargo` or `~/.npm`).
+    /// Everything else in the home directory is hidden from the sandbox.
+    ///
+    /// Default: []
+    pub read_only_paths: Option<Vec<String>>,
+    /// Whether sandboxed commands may access the network.
+    ///
+    /// Default: true
+    pub allow_network: Option<bool>,
+}
+
 #[with_fallible_options]
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema, MergeFrom)]
 pub struct ToolPermissionsContent {
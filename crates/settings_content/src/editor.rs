@@ -828,6 +828,9 @@ pub struct SearchSettingsContent {
     pub regex: Option<bool>,
     /// Whether to center the cursor on each search match when navigating.
     pub center_on_match: Option<bool>,
+    /// Whether to keep an on-disk trigram index of project files, used to skip files
+    /// that can't contain a match when searching the project.
+    pub index: Option<bool>,
 }
 
 #[with_fallible_options]
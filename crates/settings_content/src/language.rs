@@ -6,7 +6,10 @@ use serde::{Deserialize, Serialize, de::Error as _};
 use settings_macros::{MergeFrom, with_fallible_options};
 use std::sync::Arc;
 
-use crate::{DocumentFoldingRanges, DocumentSymbols, ExtendingVec, SemanticTokens, merge_from};
+use crate::{
+    DocumentFoldingRanges, DocumentSelectionRanges, DocumentSymbols, ExtendingVec, SemanticTokens,
+    merge_from,
+};
 
 /// The state of the modifier keys at some point in time
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, MergeFrom)]
@@ -438,6 +441,15 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: "off"
     pub document_folding_ranges: Option<DocumentFoldingRanges>,
+    /// Controls whether selection ranges from language servers are used by the
+    /// `editor::SelectLargerSyntaxNode` and `editor::SelectSmallerSyntaxNode` actions.
+    ///
+    /// Options:
+    /// - "off": Only use tree-sitter to expand and shrink selections (default).
+    /// - "on": Use LSP selection ranges wherever possible, falling back to tree-sitter when no results were returned by the server.
+    ///
+    /// Default: "off"
+    pub document_selection_ranges: Option<DocumentSelectionRanges>,
     /// Controls the source of document symbols used for outlines and breadcrumbs.
     ///
     /// Options:
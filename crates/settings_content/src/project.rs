@@ -74,6 +74,9 @@ pub struct ProjectSettingsContent {
     /// Configuration for how direnv configuration should be loaded
     pub load_direnv: Option<DirenvSettings>,
 
+    /// Configuration for the local history of files, kept independently of version control.
+    pub local_history: Option<LocalHistorySettingsContent>,
+
     /// Settings for slash commands.
     pub slash_commands: Option<SlashCommandSettings>,
 
@@ -313,6 +316,25 @@ pub struct DapSettingsContent {
     pub env: Option<HashMap<String, String>>,
 }
 
+#[with_fallible_options]
+#[derive(
+    Default, Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema, MergeFrom,
+)]
+pub struct LocalHistorySettingsContent {
+    /// Whether to snapshot files as they are saved or changed on disk.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// The maximum number of snapshots to keep for each file.
+    ///
+    /// Default: 50
+    pub max_entries: Option<usize>,
+    /// The number of days after which snapshots are removed.
+    ///
+    /// Default: 30
+    pub max_age_days: Option<u64>,
+}
+
 #[with_fallible_options]
 #[derive(
     Default, Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema, MergeFrom,
@@ -861,6 +861,37 @@ impl DocumentFoldingRanges {
     }
 }
 
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentSelectionRanges {
+    /// Only use tree-sitter to expand and shrink selections.
+    #[default]
+    Off,
+    /// Use the language server's selection ranges wherever possible, falling back to tree-sitter
+    /// when the server does not support them or returned no results.
+    On,
+}
+
+impl DocumentSelectionRanges {
+    /// Returns true if LSP selection ranges should be requested from language servers.
+    pub fn enabled(&self) -> bool {
+        self != &Self::Off
+    }
+}
+
 #[derive(
     Debug,
     PartialEq,
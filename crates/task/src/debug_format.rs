@@ -106,6 +106,49 @@ impl LaunchRequest {
     }
 }
 
+/// Represents a request to replay an execution recorded with `rr record`, so that it can be
+/// debugged backwards as well as forwards. Debug adapters that support it (currently GDB) accept
+/// it as `"request": "replay"`.
+#[derive(Deserialize, Serialize, Default, PartialEq, Eq, JsonSchema, Clone, Debug)]
+pub struct ReplayRequest {
+    /// The trace directory to replay
+    ///
+    /// Default: the latest recording
+    #[serde(default)]
+    pub trace_dir: Option<PathBuf>,
+    /// The recorded process to debug, as a pid or a command name, e.g. the test binary when
+    /// `cargo test` was recorded
+    ///
+    /// Default: the first process in the recording
+    #[serde(default)]
+    pub process: Option<String>,
+    /// The `rr` executable to replay with
+    ///
+    /// Default: `rr` from your `PATH`
+    #[serde(default)]
+    pub rr_path: Option<PathBuf>,
+}
+
+impl ReplayRequest {
+    /// Arguments for `rr` to replay the recording under `debugger`, which is started with `debugger_args`.
+    pub fn rr_args(&self, debugger: &str, debugger_args: Vec<String>) -> Vec<String> {
+        let mut args = vec![
+            "replay".to_owned(),
+            "--debugger".to_owned(),
+            debugger.to_owned(),
+        ];
+        if let Some(process) = &self.process {
+            args.extend(["--onprocess".to_owned(), process.clone()]);
+        }
+        if let Some(trace_dir) = &self.trace_dir {
+            args.push(trace_dir.to_string_lossy().into_owned());
+        }
+        args.push("--".to_owned());
+        args.extend(debugger_args);
+        args
+    }
+}
+
 /// Represents the type that will determine which request to call on the debug adapter
 #[derive(Deserialize, Serialize, PartialEq, Eq, JsonSchema, Clone, Debug)]
 #[serde(rename_all = "lowercase", tag = "request")]
@@ -408,7 +451,7 @@ impl DebugTaskFile {
 
 #[cfg(test)]
 mod tests {
-    use crate::DebugScenario;
+    use crate::{DebugScenario, ReplayRequest};
     use serde_json::json;
 
     #[test]
@@ -437,6 +480,39 @@ mod tests {
         assert_eq!("Build & debug rust", deserialized.label.as_ref());
     }
 
+    #[test]
+    fn test_replay_request_rr_args() {
+        let json = r#"{
+            "label": "Replay flaky test",
+            "adapter": "GDB",
+            "request": "replay",
+            "trace_dir": "/tmp/rr/my_test-0",
+            "process": "my_test"
+        }"#;
+
+        let deserialized: DebugScenario = serde_json::from_str(json).unwrap();
+        let replay: ReplayRequest = serde_json::from_value(deserialized.config).unwrap();
+        assert_eq!(
+            replay.rr_args("gdb", vec!["-i=dap".into()]),
+            [
+                "replay",
+                "--debugger",
+                "gdb",
+                "--onprocess",
+                "my_test",
+                "/tmp/rr/my_test-0",
+                "--",
+                "-i=dap"
+            ]
+        );
+
+        let replay = ReplayRequest::default();
+        assert_eq!(
+            replay.rr_args("gdb", vec!["-i=dap".into()]),
+            ["replay", "--debugger", "gdb", "--", "-i=dap"]
+        );
+    }
+
     #[test]
     fn test_empty_scenario_has_none_request() {
         let json = r#"{
@@ -0,0 +1,658 @@
+use std::path::PathBuf;
+
+use anyhow::{Context as _, Result};
+use regex::{Captures, Regex};
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// A problem matcher of a task, used to find problems in the task's output.
+/// Either a name of a built-in problem matcher (e.g. `$rustc`), or a custom problem matcher definition.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemMatcherTemplate {
+    /// A name of a built-in problem matcher:
+    /// * `$rustc` (or `$cargo`) — Rust compiler errors and warnings
+    /// * `$tsc` (or `$tsc-watch`) — TypeScript compiler errors and warnings
+    /// * `$gcc` — GCC and Clang errors and warnings
+    /// * `$eslint-compact` — ESLint output in the `compact` format
+    /// * `$eslint-stylish` — ESLint output in the `stylish` format
+    BuiltIn(String),
+    /// A custom problem matcher, possibly based on a built-in one.
+    Custom(ProblemMatcher),
+}
+
+impl ProblemMatcherTemplate {
+    /// Turns the template into a problem matcher, looking up the built-in problem matchers it refers to.
+    pub fn resolve(&self) -> Result<ProblemMatcher> {
+        match self {
+            Self::BuiltIn(name) => built_in_problem_matcher(name),
+            Self::Custom(matcher) => {
+                let Some(base) = matcher.base.as_deref() else {
+                    anyhow::ensure!(
+                        !matcher.pattern.is_empty(),
+                        "Problem matcher has neither a `base` nor a `pattern`"
+                    );
+                    return Ok(matcher.clone());
+                };
+                let mut resolved = built_in_problem_matcher(base)?;
+                if !matcher.pattern.is_empty() {
+                    resolved.pattern = matcher.pattern.clone();
+                }
+                if matcher.severity.is_some() {
+                    resolved.severity = matcher.severity;
+                }
+                if matcher.source.is_some() {
+                    resolved.source = matcher.source.clone();
+                }
+                if matcher.file_location.is_some() {
+                    resolved.file_location = matcher.file_location.clone();
+                }
+                Ok(resolved)
+            }
+        }
+    }
+}
+
+/// A definition of how to find problems in a task's output.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemMatcher {
+    /// A name of a built-in problem matcher to base this one on; the other fields override its values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// Patterns to match the output lines with, one per consecutive line of a single problem.
+    #[serde(default, deserialize_with = "one_or_many")]
+    #[schemars(schema_with = "one_or_many_schema::<ProblemPattern>")]
+    pub pattern: Vec<ProblemPattern>,
+    /// Severity of the problems whose patterns do not capture one. Defaults to `error`.
+    #[serde(default)]
+    pub severity: Option<ProblemSeverity>,
+    /// A name to show as the source of the problems.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// A directory to resolve relative file paths of the problems against.
+    /// Defaults to the task's working directory.
+    #[serde(default)]
+    pub file_location: Option<String>,
+}
+
+/// A regular expression to match a task's output line with, along with the indices of its capture groups
+/// that contain the parts of a problem.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemPattern {
+    /// A regular expression to match an output line with.
+    pub regexp: String,
+    /// A capture group with the problem's file path.
+    #[serde(default)]
+    pub file: Option<usize>,
+    /// A capture group with the problem's 1-based line.
+    #[serde(default)]
+    pub line: Option<usize>,
+    /// A capture group with the problem's 1-based column.
+    #[serde(default)]
+    pub column: Option<usize>,
+    /// A capture group with the problem's 1-based end line.
+    #[serde(default)]
+    pub end_line: Option<usize>,
+    /// A capture group with the problem's 1-based end column.
+    #[serde(default)]
+    pub end_column: Option<usize>,
+    /// A capture group with the problem's severity.
+    #[serde(default)]
+    pub severity: Option<usize>,
+    /// A capture group with the problem's code.
+    #[serde(default)]
+    pub code: Option<usize>,
+    /// A capture group with the problem's message. Defaults to the whole matched line.
+    #[serde(default)]
+    pub message: Option<usize>,
+    /// Whether the last pattern of a problem matcher may match several consecutive lines,
+    /// each producing a separate problem along with the parts captured by the previous patterns.
+    #[serde(default, rename = "loop")]
+    pub r#loop: bool,
+}
+
+/// Severity of a problem found in a task's output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl ProblemSeverity {
+    pub fn to_proto(self) -> proto::task_problem::Severity {
+        match self {
+            Self::Error => proto::task_problem::Severity::Error,
+            Self::Warning => proto::task_problem::Severity::Warning,
+            Self::Info => proto::task_problem::Severity::Info,
+            Self::Hint => proto::task_problem::Severity::Hint,
+        }
+    }
+
+    pub fn from_proto(severity: proto::task_problem::Severity) -> Self {
+        match severity {
+            proto::task_problem::Severity::Error => Self::Error,
+            proto::task_problem::Severity::Warning => Self::Warning,
+            proto::task_problem::Severity::Info => Self::Info,
+            proto::task_problem::Severity::Hint => Self::Hint,
+        }
+    }
+
+    fn parse(severity: &str) -> Option<Self> {
+        match severity.trim().to_lowercase().as_str() {
+            "error" | "err" | "fatal" | "fatal error" | "e" => Some(Self::Error),
+            "warning" | "warn" | "w" => Some(Self::Warning),
+            "info" | "information" | "note" | "i" => Some(Self::Info),
+            "hint" | "help" => Some(Self::Hint),
+            _ => None,
+        }
+    }
+}
+
+/// A problem found in a task's output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// A path to the file with the problem, relative to the task's working directory if not absolute.
+    pub file: PathBuf,
+    /// 1-based line of the problem's start.
+    pub line: u32,
+    /// 1-based column of the problem's start.
+    pub column: Option<u32>,
+    /// 1-based line of the problem's end.
+    pub end_line: Option<u32>,
+    /// 1-based column of the problem's end.
+    pub end_column: Option<u32>,
+    pub severity: ProblemSeverity,
+    pub code: Option<String>,
+    pub message: String,
+    /// A name of the problem matcher that found the problem.
+    pub source: Option<String>,
+}
+
+impl Problem {
+    pub fn to_proto(&self) -> proto::TaskProblem {
+        proto::TaskProblem {
+            file: self.file.to_string_lossy().into_owned(),
+            line: self.line,
+            column: self.column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+            severity: self.severity.to_proto().into(),
+            code: self.code.clone(),
+            message: self.message.clone(),
+            source: self.source.clone(),
+        }
+    }
+
+    pub fn from_proto(problem: proto::TaskProblem) -> Self {
+        Self {
+            severity: ProblemSeverity::from_proto(problem.severity()),
+            file: PathBuf::from(problem.file),
+            line: problem.line,
+            column: problem.column,
+            end_line: problem.end_line,
+            end_column: problem.end_column,
+            code: problem.code,
+            message: problem.message,
+            source: problem.source,
+        }
+    }
+}
+
+impl ProblemMatcher {
+    /// Finds all problems in the given task output.
+    /// Fails if any of the matcher's patterns is not a valid regular expression.
+    pub fn find_problems(&self, output: &str) -> Result<Vec<Problem>> {
+        let patterns = self
+            .pattern
+            .iter()
+            .map(|pattern| {
+                Regex::new(&pattern.regexp)
+                    .with_context(|| format!("Invalid problem pattern `{}`", pattern.regexp))
+                    .map(|regex| (regex, pattern))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let Some((last_regex, last_pattern)) = patterns.last() else {
+            return Ok(Vec::new());
+        };
+
+        let mut problems = Vec::new();
+        let mut parts = ProblemParts::default();
+        let mut next_pattern = 0;
+        // Parts captured by the leading patterns, for the last pattern to loop over.
+        let mut looping_parts = None::<ProblemParts>;
+        for line in output.lines() {
+            if let Some(leading_parts) = &looping_parts {
+                if let Some(captures) = last_regex.captures(line) {
+                    let mut parts = leading_parts.clone();
+                    parts.capture(last_pattern, &captures);
+                    problems.extend(self.problem(parts));
+                    continue;
+                }
+                looping_parts = None;
+            }
+
+            let mut captured = self.capture_line(
+                &patterns,
+                next_pattern,
+                line,
+                &mut parts,
+                &mut looping_parts,
+                &mut problems,
+            );
+            if !captured && next_pattern > 0 {
+                // The line did not continue the problem: start over, maybe it begins a new one.
+                parts = ProblemParts::default();
+                next_pattern = 0;
+                captured = self.capture_line(
+                    &patterns,
+                    next_pattern,
+                    line,
+                    &mut parts,
+                    &mut looping_parts,
+                    &mut problems,
+                );
+            }
+            if captured {
+                next_pattern += 1;
+                if next_pattern == patterns.len() {
+                    parts = ProblemParts::default();
+                    next_pattern = 0;
+                }
+            }
+        }
+        Ok(problems)
+    }
+
+    fn capture_line(
+        &self,
+        patterns: &[(Regex, &ProblemPattern)],
+        pattern_ix: usize,
+        line: &str,
+        parts: &mut ProblemParts,
+        looping_parts: &mut Option<ProblemParts>,
+        problems: &mut Vec<Problem>,
+    ) -> bool {
+        let (regex, pattern) = &patterns[pattern_ix];
+        let Some(captures) = regex.captures(line) else {
+            return false;
+        };
+        let is_last = pattern_ix + 1 == patterns.len();
+        if is_last && pattern.r#loop {
+            *looping_parts = Some(parts.clone());
+        }
+        parts.capture(pattern, &captures);
+        if is_last {
+            problems.extend(self.problem(parts.clone()));
+        }
+        true
+    }
+
+    fn problem(&self, parts: ProblemParts) -> Option<Problem> {
+        let file = parts.file?;
+        let file = match &self.file_location {
+            Some(file_location) => PathBuf::from(file_location).join(file),
+            None => PathBuf::from(file),
+        };
+        Some(Problem {
+            file,
+            line: parts.line?.parse().ok()?,
+            column: parts.column.and_then(|column| column.parse().ok()),
+            end_line: parts.end_line.and_then(|line| line.parse().ok()),
+            end_column: parts.end_column.and_then(|column| column.parse().ok()),
+            severity: parts
+                .severity
+                .as_deref()
+                .and_then(ProblemSeverity::parse)
+                .or(self.severity)
+                .unwrap_or_default(),
+            code: parts.code,
+            message: parts.message?,
+            source: self.source.clone(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct ProblemParts {
+    file: Option<String>,
+    line: Option<String>,
+    column: Option<String>,
+    end_line: Option<String>,
+    end_column: Option<String>,
+    severity: Option<String>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl ProblemParts {
+    fn capture(&mut self, pattern: &ProblemPattern, captures: &Captures) {
+        let group = |group: Option<usize>| {
+            let capture = captures.get(group?)?.as_str().trim();
+            (!capture.is_empty()).then(|| capture.to_owned())
+        };
+        for (part, group_ix) in [
+            (&mut self.file, pattern.file),
+            (&mut self.line, pattern.line),
+            (&mut self.column, pattern.column),
+            (&mut self.end_line, pattern.end_line),
+            (&mut self.end_column, pattern.end_column),
+            (&mut self.severity, pattern.severity),
+            (&mut self.code, pattern.code),
+        ] {
+            if let Some(value) = group(group_ix) {
+                *part = Some(value);
+            }
+        }
+        match pattern.message {
+            Some(message) => {
+                if let Some(message) = group(Some(message)) {
+                    self.message = Some(message);
+                }
+            }
+            None if self.message.is_none() => {
+                self.message = captures.get(0).map(|line| line.as_str().trim().to_owned());
+            }
+            None => {}
+        }
+    }
+}
+
+fn built_in_problem_matcher(name: &str) -> Result<ProblemMatcher> {
+    let pattern = |regexp: &str| ProblemPattern {
+        regexp: regexp.to_owned(),
+        ..ProblemPattern::default()
+    };
+    let (source, pattern) = match name {
+        "$rustc" | "$cargo" => (
+            "rustc",
+            vec![
+                ProblemPattern {
+                    severity: Some(1),
+                    code: Some(2),
+                    message: Some(3),
+                    ..pattern(r"^(warning|warn|error)(?:\[(\S+)\])?: (.*)$")
+                },
+                ProblemPattern {
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    ..pattern(r"^\s*-->\s+(.+?):(\d+):(\d+)\s*$")
+                },
+            ],
+        ),
+        "$tsc" | "$tsc-watch" => (
+            "tsc",
+            vec![ProblemPattern {
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                code: Some(5),
+                message: Some(6),
+                ..pattern(
+                    r"^([^\s].*)[\(:](\d+)[,:](\d+)(?:\):\s+|\s+-\s+)(error|warning|info)\s+(TS\d+)\s*:\s*(.*)$",
+                )
+            }],
+        ),
+        "$gcc" => (
+            "gcc",
+            vec![ProblemPattern {
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                message: Some(5),
+                ..pattern(r"^(.+?):(\d+):(\d*):?\s+(?:fatal\s+)?(warning|error|note):\s+(.*)$")
+            }],
+        ),
+        "$eslint-compact" => (
+            "eslint",
+            vec![ProblemPattern {
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                message: Some(5),
+                code: Some(6),
+                ..pattern(
+                    r"^(.+):\sline\s(\d+),\scol\s(\d+),\s(Error|Warning|Info)\s-\s(.+)\s\((.+)\)$",
+                )
+            }],
+        ),
+        "$eslint-stylish" => (
+            "eslint",
+            vec![
+                ProblemPattern {
+                    file: Some(1),
+                    ..pattern(r"^((?:[a-zA-Z]:)?[./\\].*?)\s*$")
+                },
+                ProblemPattern {
+                    line: Some(1),
+                    column: Some(2),
+                    severity: Some(3),
+                    message: Some(4),
+                    code: Some(5),
+                    r#loop: true,
+                    ..pattern(r"^\s+(\d+):(\d+)\s+(error|warning|info)\s+(.*?)(?:\s\s+(\S+))?$")
+                },
+            ],
+        ),
+        _ => anyhow::bail!("Unknown problem matcher `{name}`"),
+    };
+    Ok(ProblemMatcher {
+        base: None,
+        pattern,
+        severity: None,
+        source: Some(source.to_owned()),
+        file_location: None,
+    })
+}
+
+/// A schema for the fields deserialized with [`one_or_many`], accepting either a single value or a list of them.
+pub(crate) fn one_or_many_schema<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    let schema = generator.subschema_for::<T>();
+    json_schema!({
+        "anyOf": [
+            schema.clone(),
+            {
+                "type": "array",
+                "items": schema
+            }
+        ]
+    })
+}
+
+/// Deserializes either a single value or a list of them.
+pub(crate) fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        One(T),
+        Many(Vec<T>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find_problems(matcher: &str, output: &str) -> Vec<Problem> {
+        ProblemMatcherTemplate::BuiltIn(matcher.to_owned())
+            .resolve()
+            .unwrap()
+            .find_problems(output)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rustc_problem_matcher() {
+        let output = r#"   Compiling zed v0.1.0 (/zed)
+warning: unused variable: `x`
+ --> src/main.rs:2:9
+  |
+2 |     let x = 5;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_x`
+
+error[E0308]: mismatched types
+  --> crates/zed/src/lib.rs:10:18
+   |
+error: could not compile `zed` (bin "zed") due to 1 previous error; 1 warning emitted
+"#;
+        assert_eq!(
+            find_problems("$rustc", output),
+            vec![
+                Problem {
+                    file: PathBuf::from("src/main.rs"),
+                    line: 2,
+                    column: Some(9),
+                    end_line: None,
+                    end_column: None,
+                    severity: ProblemSeverity::Warning,
+                    code: None,
+                    message: "unused variable: `x`".to_owned(),
+                    source: Some("rustc".to_owned()),
+                },
+                Problem {
+                    file: PathBuf::from("crates/zed/src/lib.rs"),
+                    line: 10,
+                    column: Some(18),
+                    end_line: None,
+                    end_column: None,
+                    severity: ProblemSeverity::Error,
+                    code: Some("E0308".to_owned()),
+                    message: "mismatched types".to_owned(),
+                    source: Some("rustc".to_owned()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_line_problem_matchers() {
+        let problems = find_problems(
+            "$tsc",
+            "src/index.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+             Found 1 error.",
+        );
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].file, PathBuf::from("src/index.ts"));
+        assert_eq!((problems[0].line, problems[0].column), (3, Some(7)));
+        assert_eq!(problems[0].code.as_deref(), Some("TS2322"));
+
+        let problems = find_problems(
+            "$gcc",
+            "main.c:4:5: warning: implicit declaration of function 'foo'\n\
+             main.c:9:1: fatal error: expected ';' before '}' token",
+        );
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (problem.line, problem.severity))
+                .collect::<Vec<_>>(),
+            vec![(4, ProblemSeverity::Warning), (9, ProblemSeverity::Error)]
+        );
+    }
+
+    #[test]
+    fn test_looping_problem_matcher() {
+        let output = "\n/app/src/a.js\n  1:10  error    'foo' is defined but never used  no-unused-vars\n  \
+            2:1   warning  Unexpected console statement     no-console\n\n/app/src/b.js\n  \
+            7:3  error  Missing semicolon  semi\n\n✖ 3 problems (2 errors, 1 warning)\n";
+        let problems = find_problems("$eslint-stylish", output);
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (
+                    problem.file.to_string_lossy().into_owned(),
+                    problem.line,
+                    problem.severity,
+                    problem.code.clone().unwrap_or_default(),
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "/app/src/a.js".to_owned(),
+                    1,
+                    ProblemSeverity::Error,
+                    "no-unused-vars".to_owned()
+                ),
+                (
+                    "/app/src/a.js".to_owned(),
+                    2,
+                    ProblemSeverity::Warning,
+                    "no-console".to_owned()
+                ),
+                (
+                    "/app/src/b.js".to_owned(),
+                    7,
+                    ProblemSeverity::Error,
+                    "semi".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_problem_matcher() {
+        let matcher: ProblemMatcherTemplate = serde_json::from_value(serde_json::json!({
+            "pattern": {
+                "regexp": "^(\\S+):(\\d+): (.*)$",
+                "file": 1,
+                "line": 2,
+                "message": 3
+            },
+            "severity": "warning",
+            "file_location": "/project"
+        }))
+        .unwrap();
+        let problems = matcher
+            .resolve()
+            .unwrap()
+            .find_problems("lint.sh:12: trailing whitespace\nall done")
+            .unwrap();
+        assert_eq!(
+            problems,
+            vec![Problem {
+                file: PathBuf::from("/project/lint.sh"),
+                line: 12,
+                column: None,
+                end_line: None,
+                end_column: None,
+                severity: ProblemSeverity::Warning,
+                code: None,
+                message: "trailing whitespace".to_owned(),
+                source: None,
+            }]
+        );
+
+        let based: ProblemMatcherTemplate = serde_json::from_value(serde_json::json!({
+            "base": "$tsc",
+            "source": "typescript"
+        }))
+        .unwrap();
+        assert_eq!(
+            based.resolve().unwrap().source.as_deref(),
+            Some("typescript")
+        );
+        assert!(
+            ProblemMatcherTemplate::BuiltIn("$unknown".to_owned())
+                .resolve()
+                .is_err()
+        );
+    }
+}
@@ -2,6 +2,7 @@
 
 mod adapter_schema;
 mod debug_format;
+mod problem_matcher;
 mod serde_helpers;
 pub mod static_source;
 mod task_template;
@@ -20,11 +21,14 @@ use std::sync::Arc;
 pub use adapter_schema::{AdapterSchema, AdapterSchemas};
 pub use debug_format::{
     AttachRequest, BuildTaskDefinition, DebugRequest, DebugScenario, DebugTaskFile, LaunchRequest,
-    Request, TcpArgumentsTemplate, ZedDebugConfig,
+    ReplayRequest, Request, TcpArgumentsTemplate, ZedDebugConfig,
+};
+pub use problem_matcher::{
+    Problem, ProblemMatcher, ProblemMatcherTemplate, ProblemPattern, ProblemSeverity,
 };
 pub use task_template::{
-    DebugArgsRequest, HideStrategy, RevealStrategy, TaskTemplate, TaskTemplates,
-    substitute_variables_in_map, substitute_variables_in_str,
+    DebugArgsRequest, DependsOrder, HideStrategy, RevealStrategy, TaskInput, TaskInputKind,
+    TaskTemplate, TaskTemplates, substitute_variables_in_map, substitute_variables_in_str,
 };
 pub use util::shell::{Shell, ShellKind};
 pub use util::shell_builder::ShellBuilder;
@@ -75,6 +79,12 @@ pub struct SpawnInTerminal {
     pub show_command: bool,
     /// Whether to show the rerun button in the terminal tab.
     pub show_rerun: bool,
+    /// Problem matchers to find problems in the task's output with, once it finishes.
+    pub problem_matchers: Vec<ProblemMatcher>,
+    /// Id of the diagnostics reported by the task's problem matchers, based on the task's source and label.
+    /// Unlike `id`, it does not depend on the task's context, so rerunning the task in another context
+    /// replaces the diagnostics of its previous run.
+    pub diagnostics_id: TaskId,
 }
 
 impl SpawnInTerminal {
@@ -118,6 +128,12 @@ pub struct ResolvedTask {
     pub id: TaskId,
     /// A template the task got resolved from.
     original_task: TaskTemplate,
+    /// A context the task got resolved with, kept to resolve the tasks it depends on,
+    /// or to resolve the task again with the values of its inputs.
+    /// Only present for tasks with dependencies or pending inputs.
+    task_context: Option<TaskContext>,
+    /// Inputs of the task that were not provided in the context, and got resolved with their default values.
+    pending_inputs: Vec<TaskInput>,
     /// Full, unshortened label of the task after all resolutions are made.
     pub resolved_label: String,
     /// Variables that were substituted during the task template resolution.
@@ -138,6 +154,34 @@ impl ResolvedTask {
         &self.substituted_variables
     }
 
+    /// A context the task got resolved with, if it has any dependencies or pending inputs.
+    pub fn task_context(&self) -> Option<&TaskContext> {
+        self.task_context.as_ref()
+    }
+
+    /// Inputs the user should be asked for before the task is spawned.
+    /// Until then, the task is resolved with their default values.
+    pub fn pending_inputs(&self) -> &[TaskInput] {
+        &self.pending_inputs
+    }
+
+    /// Resolves the task again, with the values provided for its inputs, keyed by input ids.
+    pub fn with_inputs(
+        &self,
+        id_base: &str,
+        values: HashMap<String, String>,
+    ) -> Option<ResolvedTask> {
+        let mut task_context = self.task_context.clone().unwrap_or_default();
+        for (id, value) in values {
+            task_context
+                .task_variables
+                .insert(VariableName::Input(Cow::Owned(id)), value);
+        }
+        let mut resolved_task = self.original_task.resolve_task(id_base, &task_context)?;
+        resolved_task.resolved.reveal_target = self.resolved.reveal_target;
+        Some(resolved_task)
+    }
+
     /// A human-readable label to display in the UI.
     pub fn display_label(&self) -> &str {
         self.resolved.label.as_str()
@@ -177,6 +221,9 @@ pub enum VariableName {
     /// Open a Picker to select a process ID to use in place
     /// Can only be used to debug configurations
     PickProcessId,
+    /// A value of the task's input, provided by the user when the task gets spawned.
+    /// Will be printed with `INPUT_` prefix.
+    Input(Cow<'static, str>),
     /// Custom variable, provided by the plugin or other external source.
     /// Will be printed with `CUSTOM_` prefix to avoid potential conflicts with other variables.
     Custom(Cow<'static, str>),
@@ -216,6 +263,10 @@ impl FromStr for VariableName {
                     without_prefix.strip_prefix(ZED_CUSTOM_VARIABLE_NAME_PREFIX)
                 {
                     Self::Custom(Cow::Owned(custom_name.to_owned()))
+                } else if let Some(input_id) =
+                    without_prefix.strip_prefix(ZED_INPUT_VARIABLE_NAME_PREFIX)
+                {
+                    Self::Input(Cow::Owned(input_id.to_owned()))
                 } else {
                     return Err(());
                 }
@@ -228,6 +279,7 @@ impl FromStr for VariableName {
 /// A prefix that all [`VariableName`] variants are prefixed with when used in environment variables and similar template contexts.
 pub const ZED_VARIABLE_NAME_PREFIX: &str = "ZED_";
 const ZED_CUSTOM_VARIABLE_NAME_PREFIX: &str = "CUSTOM_";
+const ZED_INPUT_VARIABLE_NAME_PREFIX: &str = "INPUT_";
 
 impl std::fmt::Display for VariableName {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -245,6 +297,10 @@ impl std::fmt::Display for VariableName {
             Self::SelectedText => write!(f, "{ZED_VARIABLE_NAME_PREFIX}SELECTED_TEXT"),
             Self::RunnableSymbol => write!(f, "{ZED_VARIABLE_NAME_PREFIX}RUNNABLE_SYMBOL"),
             Self::PickProcessId => write!(f, "{ZED_VARIABLE_NAME_PREFIX}PICK_PID"),
+            Self::Input(s) => write!(
+                f,
+                "{ZED_VARIABLE_NAME_PREFIX}{ZED_INPUT_VARIABLE_NAME_PREFIX}{s}"
+            ),
             Self::Custom(s) => write!(
                 f,
                 "{ZED_VARIABLE_NAME_PREFIX}{ZED_CUSTOM_VARIABLE_NAME_PREFIX}{s}"
@@ -371,10 +427,12 @@ pub fn shell_to_proto(shell: Shell) -> proto::Shell {
 type VsCodeEnvVariable = String;
 type VsCodeCommand = String;
 type ZedEnvVariable = String;
+type VsCodeInputId = String;
 
 struct EnvVariableReplacer {
     variables: HashMap<VsCodeEnvVariable, ZedEnvVariable>,
     commands: HashMap<VsCodeCommand, ZedEnvVariable>,
+    inputs: HashSet<VsCodeInputId>,
 }
 
 impl EnvVariableReplacer {
@@ -382,9 +440,15 @@ impl EnvVariableReplacer {
         Self {
             variables,
             commands: HashMap::default(),
+            inputs: HashSet::default(),
         }
     }
 
+    fn with_inputs(mut self, inputs: impl IntoIterator<Item = VsCodeInputId>) -> Self {
+        self.inputs = inputs.into_iter().collect();
+        self
+    }
+
     fn with_commands(
         mut self,
         commands: impl IntoIterator<Item = (VsCodeCommand, ZedEnvVariable)>,
@@ -421,6 +485,12 @@ impl EnvVariableReplacer {
                 if let Some(replacement_command) = self.commands.get(command_name) {
                     return Some(format!("${{{replacement_command}}}"));
                 }
+            } else if left == "input" && !right.is_empty() {
+                let input_id = &right[1..];
+                if self.inputs.contains(input_id) {
+                    let input_variable = VariableName::Input(Cow::Owned(input_id.to_owned()));
+                    return Some(format!("${{{input_variable}}}"));
+                }
             }
 
             let (variable_name, default) = (left, right);
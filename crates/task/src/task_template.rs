@@ -3,14 +3,16 @@ use collections::{HashMap, HashSet};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
+use std::{borrow::Cow, path::PathBuf};
 use util::schemars::{AllowTrailingCommas, DefaultDenyUnknownFields};
 use util::serde::default_true;
 use util::{ResultExt, truncate_and_remove_front};
 
 use crate::{
     AttachRequest, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext, TaskId,
-    VariableName, ZED_VARIABLE_NAME_PREFIX, serde_helpers::non_empty_string_vec,
+    VariableName, ZED_VARIABLE_NAME_PREFIX,
+    problem_matcher::{ProblemMatcherTemplate, one_or_many, one_or_many_schema},
+    serde_helpers::non_empty_string_vec,
 };
 
 /// A template definition of a Zed task to run.
@@ -25,6 +27,7 @@ pub struct TaskTemplate {
     /// Human readable name of the task to display in the UI.
     pub label: String,
     /// Executable command to spawn.
+    #[serde(default)]
     pub command: String,
     /// Arguments to the command.
     #[serde(default)]
@@ -72,6 +75,26 @@ pub struct TaskTemplate {
     /// Whether to show the command line in the task output.
     #[serde(default = "default_true")]
     pub show_command: bool,
+    /// Labels of the tasks that have to finish successfully before this task is spawned.
+    /// If any of them fails, this task is not spawned.
+    /// A task with dependencies may omit its command, to only run the tasks it depends on.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// How to run the tasks listed in `depends_on`:
+    /// * `sequence` — one after another, in the order they are listed (default)
+    /// * `parallel` — all at the same time
+    #[serde(default)]
+    pub depends_order: DependsOrder,
+    /// Problem matchers to find problems in the task's output with, once it finishes, and show them as diagnostics.
+    /// Either a name of a built-in problem matcher (`$rustc`, `$tsc`, `$gcc`, `$eslint-compact`, `$eslint-stylish`),
+    /// a custom problem matcher, or a list of those.
+    #[serde(default, deserialize_with = "one_or_many")]
+    #[schemars(schema_with = "one_or_many_schema::<ProblemMatcherTemplate>")]
+    pub problem_matcher: Vec<ProblemMatcherTemplate>,
+    /// Values to ask the user for before the task is spawned.
+    /// The task refers to an input's value with `$ZED_INPUT_<id>`.
+    #[serde(default)]
+    pub inputs: Vec<TaskInput>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone, Debug)]
@@ -109,6 +132,69 @@ pub enum HideStrategy {
     OnSuccess,
 }
 
+/// How to run the tasks a task depends on.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependsOrder {
+    /// Run the tasks one after another, in the order they are listed, stopping at the first failure.
+    #[default]
+    Sequence,
+    /// Run all tasks at the same time.
+    Parallel,
+}
+
+/// A value to ask the user for before the task is spawned.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TaskInput {
+    /// Identifier of the input, the task refers to its value with `$ZED_INPUT_<id>`.
+    pub id: String,
+    /// Description to show when asking for the value.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// How to ask for the value.
+    #[serde(flatten)]
+    pub kind: TaskInputKind,
+}
+
+/// How to ask the user for a task input's value.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaskInputKind {
+    /// Ask for an arbitrary string.
+    PromptString {
+        /// The value to use if the user does not enter any.
+        #[serde(default)]
+        default: Option<String>,
+    },
+    /// Ask to pick one of the options.
+    PickString {
+        /// The options to pick from.
+        options: Vec<String>,
+        /// The option to preselect, defaults to the first one.
+        #[serde(default)]
+        default: Option<String>,
+    },
+}
+
+impl TaskInput {
+    /// The value to resolve the task with, until the user provides one.
+    pub fn default_value(&self) -> String {
+        match &self.kind {
+            TaskInputKind::PromptString { default } => default.clone().unwrap_or_default(),
+            TaskInputKind::PickString { options, default } => default
+                .clone()
+                .or_else(|| options.first().cloned())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The name of the variable the task refers to the input's value with.
+    pub fn variable_name(&self) -> VariableName {
+        VariableName::Input(Cow::Owned(self.id.clone()))
+    }
+}
+
 /// A group of Tasks defined in a JSON file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct TaskTemplates(pub Vec<TaskTemplate>);
@@ -132,10 +218,32 @@ impl TaskTemplate {
     /// Every [`ResolvedTask`] gets a [`TaskId`], based on the `id_base` (to avoid collision with various task sources),
     /// and hashes of its template and [`TaskContext`], see [`ResolvedTask`] fields' documentation for more details.
     pub fn resolve_task(&self, id_base: &str, cx: &TaskContext) -> Option<ResolvedTask> {
-        if self.label.trim().is_empty() || self.command.trim().is_empty() {
+        if self.label.trim().is_empty()
+            || (self.command.trim().is_empty() && self.depends_on.is_empty())
+        {
             return None;
         }
 
+        let pending_inputs = self
+            .inputs
+            .iter()
+            .filter(|input| cx.task_variables.get(&input.variable_name()).is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+        let original_cx = cx;
+        let cx_with_default_inputs;
+        let cx = if pending_inputs.is_empty() {
+            cx
+        } else {
+            let mut cx = cx.clone();
+            for input in &pending_inputs {
+                cx.task_variables
+                    .insert(input.variable_name(), input.default_value());
+            }
+            cx_with_default_inputs = cx;
+            &cx_with_default_inputs
+        };
+
         let mut variable_names = HashMap::default();
         let mut substituted_variables = HashSet::default();
         let task_variables = cx
@@ -205,6 +313,9 @@ impl TaskTemplate {
             &variable_names,
             &mut substituted_variables,
         )?;
+        if command.trim().is_empty() && self.depends_on.is_empty() {
+            return None;
+        }
         let args_with_substitutions = substitute_all_template_variables_in_vec(
             &self.args,
             &task_variables,
@@ -212,6 +323,27 @@ impl TaskTemplate {
             &mut substituted_variables,
         )?;
 
+        let mut problem_matchers = Vec::with_capacity(self.problem_matcher.len());
+        for problem_matcher in &self.problem_matcher {
+            let Some(mut problem_matcher) = problem_matcher
+                .resolve()
+                .with_context(|| format!("resolving problem matcher of task `{}`", self.label))
+                .log_err()
+            else {
+                continue;
+            };
+            problem_matcher.file_location = match problem_matcher.file_location.as_deref() {
+                Some(file_location) => Some(substitute_all_template_variables_in_str(
+                    file_location,
+                    &task_variables,
+                    &variable_names,
+                    &mut substituted_variables,
+                )?),
+                None => None,
+            };
+            problem_matchers.push(problem_matcher);
+        }
+
         let task_hash = to_hex_hash(self)
             .context("hashing task template")
             .log_err()?;
@@ -244,6 +376,9 @@ impl TaskTemplate {
             id: id.clone(),
             substituted_variables,
             original_task: self.clone(),
+            task_context: (!self.depends_on.is_empty() || !pending_inputs.is_empty())
+                .then(|| original_cx.clone()),
+            pending_inputs,
             resolved_label: full_label.clone(),
             resolved: SpawnInTerminal {
                 id,
@@ -270,6 +405,8 @@ impl TaskTemplate {
                 show_summary: self.show_summary,
                 show_command: self.show_command,
                 show_rerun: true,
+                problem_matchers,
+                diagnostics_id: TaskId(format!("{id_base}_{}", self.label)),
             },
         })
     }
@@ -485,6 +622,7 @@ mod tests {
     use std::{borrow::Cow, path::Path};
 
     use crate::{TaskVariables, VariableName};
+    use serde_json::json;
 
     use super::*;
 
@@ -523,6 +661,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolving_templates_without_command() {
+        let resolve = |template: serde_json::Value| {
+            serde_json::from_value::<TaskTemplate>(template)
+                .unwrap()
+                .resolve_task(TEST_ID_BASE, &TaskContext::default())
+        };
+
+        assert_eq!(resolve(json!({ "label": "no command" })), None);
+        assert_eq!(
+            resolve(json!({ "label": "blank command", "command": "${ZED_SELECTED_TEXT:}" })),
+            None,
+            "should not resolve a task whose command is blank after substitution"
+        );
+
+        let task = resolve(json!({ "label": "group", "depends_on": ["build"] }))
+            .expect("tasks with dependencies do not need a command");
+        assert_eq!(task.resolved.command.as_deref(), Some(""));
+    }
+
     #[test]
     fn test_template_cwd_resolution() {
         let task_without_cwd = TaskTemplate {
@@ -1074,4 +1232,131 @@ mod tests {
 
         assert!(task.unknown_variables().is_empty());
     }
+
+    #[test]
+    fn test_task_inputs() {
+        let task = TaskTemplate {
+            label: "deploy to $ZED_INPUT_target".to_string(),
+            command: "deploy".to_string(),
+            args: vec![
+                "--target".to_string(),
+                "$ZED_INPUT_target".to_string(),
+                "--message".to_string(),
+                "${ZED_INPUT_message}".to_string(),
+            ],
+            inputs: vec![
+                TaskInput {
+                    id: "target".to_string(),
+                    description: Some("Where to deploy".to_string()),
+                    kind: TaskInputKind::PickString {
+                        options: vec!["staging".to_string(), "production".to_string()],
+                        default: None,
+                    },
+                },
+                TaskInput {
+                    id: "message".to_string(),
+                    description: None,
+                    kind: TaskInputKind::PromptString {
+                        default: Some("no message".to_string()),
+                    },
+                },
+            ],
+            ..TaskTemplate::default()
+        };
+
+        let resolved = task
+            .resolve_task(TEST_ID_BASE, &TaskContext::default())
+            .expect("Should resolve the task with default input values");
+        assert_eq!(
+            resolved.resolved.args,
+            vec!["--target", "staging", "--message", "no message"]
+        );
+        assert_eq!(
+            resolved
+                .pending_inputs()
+                .iter()
+                .map(|input| input.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["target", "message"]
+        );
+
+        let with_inputs = resolved
+            .with_inputs(
+                TEST_ID_BASE,
+                HashMap::from_iter([
+                    ("target".to_string(), "production".to_string()),
+                    ("message".to_string(), "hello".to_string()),
+                ]),
+            )
+            .expect("Should resolve the task with the provided input values");
+        assert_eq!(with_inputs.resolved_label, "deploy to production");
+        assert_eq!(
+            with_inputs.resolved.args,
+            vec!["--target", "production", "--message", "hello"]
+        );
+        assert!(with_inputs.pending_inputs().is_empty());
+        assert_ne!(with_inputs.id, resolved.id);
+
+        let context_with_input = TaskContext {
+            task_variables: TaskVariables::from_iter([(
+                VariableName::Input(Cow::Borrowed("target")),
+                "local".to_string(),
+            )]),
+            ..TaskContext::default()
+        };
+        let resolved = task
+            .resolve_task(TEST_ID_BASE, &context_with_input)
+            .expect("Should resolve the task with the input value from the context");
+        assert_eq!(resolved.resolved_label, "deploy to local");
+        assert_eq!(
+            resolved
+                .pending_inputs()
+                .iter()
+                .map(|input| input.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["message"]
+        );
+        assert!(task.unknown_variables().is_empty());
+    }
+
+    #[test]
+    fn test_problem_matchers() {
+        let schema = TaskTemplates::generate_json_schema();
+        for (definition, property) in [
+            ("TaskTemplate", "problem_matcher"),
+            ("ProblemMatcher", "pattern"),
+        ] {
+            let property_schema = &schema["$defs"][definition]["properties"][property];
+            assert_eq!(
+                property_schema["anyOf"][1]["type"], "array",
+                "`{property}` should accept either a single value or a list of them: {property_schema}"
+            );
+            assert_eq!(
+                property_schema["anyOf"][0],
+                property_schema["anyOf"][1]["items"]
+            );
+        }
+
+        let task = TaskTemplate {
+            label: "check $ZED_FILE".to_string(),
+            command: "cargo".to_string(),
+            args: vec!["check".to_string()],
+            problem_matcher: vec![ProblemMatcherTemplate::BuiltIn("$rustc".to_string())],
+            ..TaskTemplate::default()
+        };
+        let resolve = |file: &str| {
+            let cx = TaskContext {
+                task_variables: TaskVariables::from_iter([(VariableName::File, file.to_string())]),
+                ..TaskContext::default()
+            };
+            task.resolve_task(TEST_ID_BASE, &cx).unwrap().resolved
+        };
+        let (first_run, second_run) = (resolve("a.rs"), resolve("b.rs"));
+        assert_eq!(first_run.problem_matchers.len(), 1);
+        assert_ne!(first_run.id, second_run.id);
+        assert_eq!(
+            first_run.diagnostics_id, second_run.diagnostics_id,
+            "Diagnostics of a task should not depend on its context"
+        );
+    }
 }
@@ -3,7 +3,11 @@ use collections::HashMap;
 use serde::Deserialize;
 use util::ResultExt;
 
-use crate::{EnvVariableReplacer, TaskTemplate, TaskTemplates, VariableName};
+use crate::{
+    DependsOrder, EnvVariableReplacer, ProblemMatcher, ProblemMatcherTemplate, ProblemPattern,
+    ProblemSeverity, TaskInput, TaskInputKind, TaskTemplate, TaskTemplates, VariableName,
+    problem_matcher::one_or_many,
+};
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -86,23 +90,101 @@ fn generate_label(command: &Option<Command>) -> String {
 }
 
 impl VsCodeTaskDefinition {
+    /// Labels of the tasks from `dependsOn`, which is either a single label or a list of them.
+    fn depends_on(&self) -> Vec<String> {
+        match self.other_attributes.get("dependsOn") {
+            Some(serde_json_lenient::Value::String(label)) => vec![label.clone()],
+            Some(serde_json_lenient::Value::Array(labels)) => labels
+                .iter()
+                .filter_map(|label| match label {
+                    serde_json_lenient::Value::String(label) => Some(label.clone()),
+                    _ => {
+                        log::warn!(
+                            "Skipping an unsupported `dependsOn` entry {label} of a task `{}`",
+                            self.label
+                        );
+                        None
+                    }
+                })
+                .collect(),
+            Some(other) => {
+                log::warn!(
+                    "Skipping an unsupported `dependsOn` value {other} of a task `{}`",
+                    self.label
+                );
+                Vec::new()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Problem matchers from `problemMatcher`, which is either a single problem matcher or a list of them.
+    fn problem_matchers(&self, replacer: &EnvVariableReplacer) -> Vec<ProblemMatcherTemplate> {
+        let problem_matchers = match self.other_attributes.get("problemMatcher") {
+            Some(serde_json_lenient::Value::Array(problem_matchers)) => {
+                problem_matchers.iter().collect()
+            }
+            Some(problem_matcher) => vec![problem_matcher],
+            None => Vec::new(),
+        };
+        problem_matchers
+            .into_iter()
+            .filter_map(|problem_matcher| {
+                match serde_json_lenient::from_value::<VsCodeProblemMatcher>(
+                    problem_matcher.clone(),
+                ) {
+                    Ok(problem_matcher) => Some(problem_matcher.into_zed_format(replacer)),
+                    Err(e) => {
+                        log::warn!(
+                            "Skipping an unsupported problem matcher {problem_matcher} of a task `{}`: {e}",
+                            self.label
+                        );
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
     fn into_zed_format(
         self,
         replacer: &EnvVariableReplacer,
     ) -> anyhow::Result<Option<TaskTemplate>> {
-        if self.other_attributes.contains_key("dependsOn") {
-            log::warn!(
-                "Skipping deserializing of a task `{}` with the unsupported `dependsOn` key",
+        // Labels are not substituted, and `dependsOn` refers to tasks by their literal labels.
+        if replacer
+            .inputs
+            .iter()
+            .any(|input_id| self.label.contains(&format!("${{input:{input_id}}}")))
+        {
+            bail!(
+                "Task `{}` refers to an input in its label, which does not support variables",
                 self.label
             );
-            return Ok(None);
         }
-        // `type` might not be set in e.g. tasks that use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
+        let depends_on = self.depends_on();
+        let depends_order = match self
+            .other_attributes
+            .get("dependsOrder")
+            .and_then(|order| order.as_str())
+        {
+            // Code runs the dependencies in parallel, unless told otherwise.
+            None | Some("parallel") if !depends_on.is_empty() => DependsOrder::Parallel,
+            _ => DependsOrder::Sequence,
+        };
+        let problem_matcher = self.problem_matchers(replacer);
+        // `type` might not be set in tasks that only use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
         // as that way we can provide more specific description of why deserialization failed.
-        // E.g. if the command is missing due to `dependsOn` presence, we can check other_attributes first before doing this (and provide nice error message)
-        // catch-all if on value.command presence.
         let Some(command) = self.command else {
-            bail!("Missing `type` field in task");
+            if depends_on.is_empty() {
+                bail!("Missing `type` field in task");
+            }
+            return Ok(Some(TaskTemplate {
+                label: self.label,
+                depends_on,
+                depends_order,
+                problem_matcher,
+                ..TaskTemplate::default()
+            }));
         };
 
         let (command, args) = match command {
@@ -117,26 +199,208 @@ impl VsCodeTaskDefinition {
             label: self.label,
             command,
             args,
+            depends_on,
+            depends_order,
+            problem_matcher,
             ..TaskTemplate::default()
         };
         if let Some(options) = self.options {
             template.cwd = options.cwd.map(|cwd| replacer.replace(&cwd));
-            template.env = options.env;
+            template.env = options
+                .env
+                .into_iter()
+                .map(|(name, value)| (name, replacer.replace(&value)))
+                .collect();
         }
         Ok(Some(template))
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum VsCodeProblemMatcher {
+    Named(String),
+    Custom {
+        #[serde(default)]
+        base: Option<String>,
+        #[serde(default)]
+        source: Option<String>,
+        #[serde(default)]
+        severity: Option<ProblemSeverity>,
+        #[serde(default, rename = "fileLocation")]
+        file_location: Option<VsCodeFileLocation>,
+        #[serde(default, deserialize_with = "one_or_many")]
+        pattern: Vec<VsCodeProblemPattern>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum VsCodeFileLocation {
+    Kind(String),
+    KindWithPath(String, String),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeProblemPattern {
+    regexp: String,
+    #[serde(default)]
+    file: Option<usize>,
+    #[serde(default)]
+    line: Option<usize>,
+    #[serde(default)]
+    column: Option<usize>,
+    #[serde(default)]
+    end_line: Option<usize>,
+    #[serde(default)]
+    end_column: Option<usize>,
+    #[serde(default)]
+    severity: Option<usize>,
+    #[serde(default)]
+    code: Option<usize>,
+    #[serde(default)]
+    message: Option<usize>,
+    #[serde(default, rename = "loop")]
+    r#loop: bool,
+}
+
+impl VsCodeProblemMatcher {
+    fn into_zed_format(self, replacer: &EnvVariableReplacer) -> ProblemMatcherTemplate {
+        let (base, source, severity, file_location, pattern) = match self {
+            Self::Named(name) => return ProblemMatcherTemplate::BuiltIn(name),
+            Self::Custom {
+                base,
+                source,
+                severity,
+                file_location,
+                pattern,
+            } => (base, source, severity, file_location, pattern),
+        };
+        let file_location = match file_location {
+            Some(VsCodeFileLocation::KindWithPath(_, path)) => Some(replacer.replace(&path)),
+            Some(VsCodeFileLocation::Kind(kind)) if kind == "relative" => {
+                Some(format!("${{{}}}", VariableName::WorktreeRoot))
+            }
+            Some(VsCodeFileLocation::Kind(_)) | None => None,
+        };
+        ProblemMatcherTemplate::Custom(ProblemMatcher {
+            base,
+            pattern: pattern
+                .into_iter()
+                .map(|pattern| ProblemPattern {
+                    regexp: pattern.regexp,
+                    file: pattern.file,
+                    line: pattern.line,
+                    column: pattern.column,
+                    end_line: pattern.end_line,
+                    end_column: pattern.end_column,
+                    severity: pattern.severity,
+                    code: pattern.code,
+                    message: pattern.message,
+                    r#loop: pattern.r#loop,
+                })
+                .collect(),
+            severity,
+            source,
+            file_location,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum VsCodeInput {
+    PromptString {
+        id: String,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default)]
+        default: Option<String>,
+    },
+    PickString {
+        id: String,
+        #[serde(default)]
+        description: Option<String>,
+        options: Vec<VsCodePickStringOption>,
+        #[serde(default)]
+        default: Option<String>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum VsCodePickStringOption {
+    Value(String),
+    Labeled { value: String },
+}
+
+impl VsCodeInput {
+    fn into_zed_format(self) -> TaskInput {
+        match self {
+            Self::PromptString {
+                id,
+                description,
+                default,
+            } => TaskInput {
+                id,
+                description,
+                kind: TaskInputKind::PromptString { default },
+            },
+            Self::PickString {
+                id,
+                description,
+                options,
+                default,
+            } => TaskInput {
+                id,
+                description,
+                kind: TaskInputKind::PickString {
+                    options: options
+                        .into_iter()
+                        .map(|option| match option {
+                            VsCodePickStringOption::Value(value)
+                            | VsCodePickStringOption::Labeled { value } => value,
+                        })
+                        .collect(),
+                    default,
+                },
+            },
+        }
+    }
+}
+
 /// [`VsCodeTaskFile`] is a superset of Code's task definition format.
 #[derive(Debug, Deserialize, PartialEq)]
 pub struct VsCodeTaskFile {
     tasks: Vec<VsCodeTaskDefinition>,
+    #[serde(default)]
+    inputs: Vec<serde_json_lenient::Value>,
+}
+
+impl VsCodeTaskFile {
+    /// Inputs that the tasks may refer to with `${input:<id>}`, skipping the kinds of inputs Zed does not support.
+    fn inputs(&self) -> Vec<TaskInput> {
+        self.inputs
+            .iter()
+            .filter_map(|input| {
+                match serde_json_lenient::from_value::<VsCodeInput>(input.clone()) {
+                    Ok(input) => Some(input.into_zed_format()),
+                    Err(e) => {
+                        log::warn!("Skipping an unsupported task input {input}: {e}");
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
 }
 
 impl TryFrom<VsCodeTaskFile> for TaskTemplates {
     type Error = anyhow::Error;
 
     fn try_from(value: VsCodeTaskFile) -> Result<Self, Self::Error> {
+        let inputs = value.inputs();
         let replacer = EnvVariableReplacer::new(HashMap::from_iter([
             (
                 "workspaceFolder".to_owned(),
@@ -148,7 +412,8 @@ impl TryFrom<VsCodeTaskFile> for TaskTemplates {
                 "selectedText".to_owned(),
                 VariableName::SelectedText.to_string(),
             ),
-        ]));
+        ]))
+        .with_inputs(inputs.iter().map(|input| input.id.clone()));
         let templates = value
             .tasks
             .into_iter()
@@ -158,16 +423,34 @@ impl TryFrom<VsCodeTaskFile> for TaskTemplates {
                     .log_err()
                     .flatten()
             })
+            .map(|mut template| {
+                template.inputs = inputs
+                    .iter()
+                    .filter(|input| references_input(&template, input))
+                    .cloned()
+                    .collect();
+                template
+            })
             .collect();
         Ok(Self(templates))
     }
 }
 
+fn references_input(template: &TaskTemplate, input: &TaskInput) -> bool {
+    let input_variable = format!("${{{}}}", input.variable_name());
+    std::iter::once(&template.command)
+        .chain(&template.args)
+        .chain(&template.cwd)
+        .chain(template.env.values())
+        .any(|value| value.contains(&input_variable))
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
     use crate::{
+        DependsOrder, ProblemMatcher, ProblemMatcherTemplate, TaskInput, TaskInputKind,
         TaskTemplate, TaskTemplates, VsCodeTaskFile,
         vscode_format::{Command, VsCodeTaskDefinition},
     };
@@ -271,6 +554,7 @@ mod tests {
                 label: "gulp: tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matcher: vec![ProblemMatcherTemplate::BuiltIn("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
@@ -282,18 +566,21 @@ mod tests {
                     "${ZED_WORKTREE_ROOT}/src".to_string(),
                     "--watch".to_string(),
                 ],
+                problem_matcher: vec![ProblemMatcherTemplate::BuiltIn("$tsc-watch".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:compiler".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:compiler".to_string()],
+                problem_matcher: vec![ProblemMatcherTemplate::BuiltIn("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matcher: vec![ProblemMatcherTemplate::BuiltIn("$tsc".to_string())],
                 ..Default::default()
             },
         ];
@@ -374,28 +661,62 @@ mod tests {
                 label: "Build Extension in Background".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "watch".to_string()],
+                problem_matcher: vec![ProblemMatcherTemplate::Custom(ProblemMatcher {
+                    base: Some("$tsc-watch".to_string()),
+                    file_location: Some("${ZED_WORKTREE_ROOT}/editors/code/".to_string()),
+                    ..Default::default()
+                })],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Extension".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build".to_string()],
+                problem_matcher: vec![ProblemMatcherTemplate::Custom(ProblemMatcher {
+                    base: Some("$tsc".to_string()),
+                    file_location: Some("${ZED_WORKTREE_ROOT}/editors/code/".to_string()),
+                    ..Default::default()
+                })],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server".to_string(),
                 command: "cargo build --package rust-analyzer".to_string(),
+                problem_matcher: vec![ProblemMatcherTemplate::BuiltIn("$rustc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server (Release)".to_string(),
                 command: "cargo build --release --package rust-analyzer".to_string(),
+                problem_matcher: vec![ProblemMatcherTemplate::BuiltIn("$rustc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Pretest".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "pretest".to_string()],
+                problem_matcher: vec![ProblemMatcherTemplate::Custom(ProblemMatcher {
+                    base: Some("$tsc".to_string()),
+                    file_location: Some("${ZED_WORKTREE_ROOT}/editors/code/".to_string()),
+                    ..Default::default()
+                })],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server and Extension".to_string(),
+                depends_on: vec!["Build Server".to_string(), "Build Extension".to_string()],
+                depends_order: DependsOrder::Parallel,
+                problem_matcher: vec![ProblemMatcherTemplate::BuiltIn("$rustc".to_string())],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server (Release) and Extension".to_string(),
+                depends_on: vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ],
+                depends_order: DependsOrder::Parallel,
+                problem_matcher: vec![ProblemMatcherTemplate::BuiltIn("$rustc".to_string())],
                 ..Default::default()
             },
         ];
@@ -416,6 +737,70 @@ mod tests {
         assert_eq!(vscode_definitions.tasks[3].label, "echo hello");
     }
 
+    #[test]
+    fn can_deserialize_tasks_with_inputs() {
+        const TASKS_WITH_INPUTS: &str = include_str!("../test_data/tasks-with-inputs.json");
+        let vscode_definitions: VsCodeTaskFile =
+            serde_json_lenient::from_str(TASKS_WITH_INPUTS).unwrap();
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+
+        let target = TaskInput {
+            id: "target".to_string(),
+            description: Some("Where to deploy".to_string()),
+            kind: TaskInputKind::PickString {
+                options: vec!["staging".to_string(), "production".to_string()],
+                default: Some("staging".to_string()),
+            },
+        };
+        let message = TaskInput {
+            id: "message".to_string(),
+            description: Some("Deployment message".to_string()),
+            kind: TaskInputKind::PromptString {
+                default: Some("no message".to_string()),
+            },
+        };
+        let expected = vec![
+            TaskTemplate {
+                label: "deploy".to_string(),
+                command: "./deploy.sh".to_string(),
+                args: vec![
+                    "--target".to_string(),
+                    "${ZED_INPUT_target}".to_string(),
+                    "--message".to_string(),
+                    "${ZED_INPUT_message}".to_string(),
+                ],
+                inputs: vec![target, message.clone()],
+                ..TaskTemplate::default()
+            },
+            // Inputs of unsupported types are left as is.
+            TaskTemplate {
+                label: "open".to_string(),
+                command: "open ${input:page}".to_string(),
+                cwd: Some("${ZED_WORKTREE_ROOT}".to_string()),
+                ..TaskTemplate::default()
+            },
+            TaskTemplate {
+                label: "announce".to_string(),
+                command: "./announce.sh".to_string(),
+                env: [(
+                    "ANNOUNCEMENT".to_string(),
+                    "${ZED_INPUT_message}".to_string(),
+                )]
+                .into_iter()
+                .collect(),
+                inputs: vec![message],
+                ..TaskTemplate::default()
+            },
+            // Tasks with inputs in their labels are skipped.
+            TaskTemplate {
+                label: "build".to_string(),
+                command: "make".to_string(),
+                ..TaskTemplate::default()
+            },
+        ];
+        assert_eq!(tasks.0, expected);
+    }
+
     #[test]
     fn test_generate_label() {
         assert_eq!(
@@ -0,0 +1,238 @@
+use std::sync::Arc;
+
+use collections::HashMap;
+use futures::channel::oneshot;
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{App, Context, DismissEvent, Task, Window, rems};
+use picker::{Picker, PickerDelegate};
+use task::{TaskInput, TaskInputKind};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use workspace::Workspace;
+
+pub(crate) fn register(
+    workspace: &mut Workspace,
+    _: Option<&mut Window>,
+    _: &mut Context<Workspace>,
+) {
+    workspace.set_prompt_for_task_inputs(Box::new(|workspace, inputs, window, cx| {
+        let (tx, rx) = oneshot::channel();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Picker::uniform_list(TaskInputsDelegate::new(inputs, tx), window, cx).width(rems(34.))
+        });
+        rx
+    }));
+}
+
+/// A picker that asks for the values of the task's inputs, one input at a time.
+struct TaskInputsDelegate {
+    inputs: Vec<TaskInput>,
+    current_input: usize,
+    values: HashMap<String, String>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    query: String,
+    tx: Option<oneshot::Sender<Option<HashMap<String, String>>>>,
+}
+
+impl TaskInputsDelegate {
+    fn new(inputs: Vec<TaskInput>, tx: oneshot::Sender<Option<HashMap<String, String>>>) -> Self {
+        Self {
+            inputs,
+            current_input: 0,
+            values: HashMap::default(),
+            matches: Vec::new(),
+            selected_index: 0,
+            query: String::new(),
+            tx: Some(tx),
+        }
+    }
+
+    /// The value the current input would get if confirmed now.
+    fn current_value(&self) -> Option<String> {
+        let input = self.inputs.get(self.current_input)?;
+        match &input.kind {
+            TaskInputKind::PromptString { default } => Some(if self.query.is_empty() {
+                default.clone().unwrap_or_default()
+            } else {
+                self.query.clone()
+            }),
+            TaskInputKind::PickString { .. } => self
+                .matches
+                .get(self.selected_index)
+                .map(|option| option.string.clone()),
+        }
+    }
+}
+
+impl PickerDelegate for TaskInputsDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _: &mut App) -> Arc<str> {
+        let Some(input) = self.inputs.get(self.current_input) else {
+            return Arc::default();
+        };
+        match (&input.description, &input.kind) {
+            (Some(description), _) => Arc::from(description.as_str()),
+            (None, TaskInputKind::PromptString { .. }) => {
+                Arc::from(format!("Enter a value for `{}`", input.id))
+            }
+            (None, TaskInputKind::PickString { .. }) => {
+                Arc::from(format!("Pick a value for `{}`", input.id))
+            }
+        }
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        self.query = query.clone();
+        let Some(input) = self.inputs.get(self.current_input) else {
+            self.matches.clear();
+            return Task::ready(());
+        };
+        match &input.kind {
+            TaskInputKind::PromptString { .. } => {
+                let value = self.current_value().unwrap_or_default();
+                self.matches = vec![StringMatch {
+                    candidate_id: 0,
+                    score: 0.0,
+                    positions: Vec::new(),
+                    string: value,
+                }];
+                self.selected_index = 0;
+                Task::ready(())
+            }
+            TaskInputKind::PickString { options, default } => {
+                let candidates = options
+                    .iter()
+                    .enumerate()
+                    .map(|(id, option)| StringMatchCandidate::new(id, option))
+                    .collect::<Vec<_>>();
+                let default_index = default
+                    .as_ref()
+                    .and_then(|default| options.iter().position(|option| option == default));
+                cx.spawn(async move |picker, cx| {
+                    let matches = if query.is_empty() {
+                        candidates
+                            .into_iter()
+                            .map(|candidate| StringMatch {
+                                candidate_id: candidate.id,
+                                score: 0.0,
+                                positions: Vec::new(),
+                                string: candidate.string,
+                            })
+                            .collect()
+                    } else {
+                        fuzzy::match_strings(
+                            &candidates,
+                            &query,
+                            true,
+                            true,
+                            1000,
+                            &Default::default(),
+                            cx.background_executor().clone(),
+                        )
+                        .await
+                    };
+                    picker
+                        .update(cx, |picker, _| {
+                            let delegate = &mut picker.delegate;
+                            delegate.selected_index = if query.is_empty() {
+                                default_index
+                                    .and_then(|default_index| {
+                                        matches
+                                            .iter()
+                                            .position(|option| option.candidate_id == default_index)
+                                    })
+                                    .unwrap_or(0)
+                            } else {
+                                0
+                            };
+                            delegate.matches = matches;
+                        })
+                        .ok();
+                })
+            }
+        }
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(value) = self.current_value() else {
+            return;
+        };
+        let Some(input) = self.inputs.get(self.current_input) else {
+            return;
+        };
+        self.values.insert(input.id.clone(), value);
+        self.current_input += 1;
+
+        if self.current_input < self.inputs.len() {
+            cx.defer_in(window, |picker, window, cx| {
+                picker.set_query("", window, cx);
+                picker.refresh_placeholder(window, cx);
+                picker.refresh(window, cx);
+            });
+        } else {
+            if let Some(tx) = self.tx.take() {
+                tx.send(Some(std::mem::take(&mut self.values))).ok();
+            }
+            cx.emit(DismissEvent);
+        }
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(tx) = self.tx.take() {
+            tx.send(None).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let hit = self.matches.get(ix)?;
+        let input = self.inputs.get(self.current_input)?;
+        let label = match &input.kind {
+            TaskInputKind::PromptString { .. } if hit.string.is_empty() => {
+                Label::new("Use an empty value")
+                    .color(Color::Muted)
+                    .into_any_element()
+            }
+            TaskInputKind::PromptString { .. } => Label::new(hit.string.clone()).into_any_element(),
+            TaskInputKind::PickString { .. } => {
+                HighlightedLabel::new(hit.string.clone(), hit.positions.clone()).into_any_element()
+            }
+        };
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(label),
+        )
+    }
+}
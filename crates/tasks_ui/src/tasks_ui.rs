@@ -8,6 +8,7 @@ use task::{RevealTarget, TaskContext, TaskId, TaskTemplate, TaskVariables, Varia
 use workspace::Workspace;
 
 mod modal;
+mod task_inputs;
 
 pub use modal::{Rerun, ShowAttachModal, Spawn, TaskOverrides, TasksModal};
 
@@ -93,6 +94,7 @@ pub fn init(cx: &mut App) {
         },
     )
     .detach();
+    cx.observe_new(task_inputs::register).detach();
 }
 
 fn spawn_task_or_modal(
@@ -0,0 +1,683 @@
+//! Shell integration through semantic prompt marks.
+//!
+//! Shells with integration enabled wrap their prompt and the commands they run in
+//! `OSC 133` (FinalTerm) or `OSC 633` (VS Code) sequences, which tell where the prompt,
+//! the command line and the command output start, and how the command exited.
+//! Alacritty ignores these sequences, so they are picked out of the PTY output before
+//! it reaches the parser, and resolved to grid lines once the parser caught up.
+//!
+//! The lines of the resolved marks are counted from the first line of output, including
+//! the lines that have since been dropped from the top of a full scrollback, so that they
+//! stay put while the scrollback rotates.
+
+use std::{io, sync::Arc};
+
+use alacritty_terminal::{
+    event::{OnResize, WindowSize},
+    tty::{ChildEvent, EventedPty, EventedReadWrite},
+};
+use parking_lot::Mutex;
+use polling::{Event, PollMode, Poller};
+use unicode_width::UnicodeWidthChar as _;
+
+/// Longest OSC payload kept while scanning, anything longer is not a mark.
+const MAX_OSC_LEN: usize = 4096;
+/// Longest CSI parameters kept while scanning, enough for the alternate screen modes.
+const MAX_CSI_LEN: usize = 16;
+/// How many commands are remembered per terminal.
+const MAX_COMMAND_BLOCKS: usize = 1024;
+
+/// A semantic mark sent by the shell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ShellMark {
+    /// `A`: the prompt is about to be printed.
+    PromptStart,
+    /// `B`: the prompt was printed, and the user's input starts.
+    CommandStart,
+    /// `C`: the command was submitted, and its output starts.
+    OutputStart,
+    /// `D`: the command finished, with its exit code when the shell reports one.
+    CommandFinished { exit_code: Option<i32> },
+    /// `633;E`: the command line the shell is about to run.
+    CommandLine(String),
+}
+
+impl ShellMark {
+    fn parse(payload: &[u8]) -> Option<Self> {
+        let payload = std::str::from_utf8(payload).ok()?;
+        let mut params = payload.split(';');
+        let protocol = params.next()?;
+        if protocol != "133" && protocol != "633" {
+            return None;
+        }
+
+        match params.next()? {
+            "A" => Some(Self::PromptStart),
+            "B" => Some(Self::CommandStart),
+            "C" => Some(Self::OutputStart),
+            "D" => Some(Self::CommandFinished {
+                exit_code: params.next().and_then(|code| code.trim().parse().ok()),
+            }),
+            "E" if protocol == "633" => Some(Self::CommandLine(unescape_command_line(
+                params.next().unwrap_or_default(),
+            ))),
+            _ => None,
+        }
+    }
+}
+
+/// Undoes the escaping VS Code's shell integration scripts apply to `633;E` command lines,
+/// where `\\` is a backslash and `\xAB` is the byte with that hex value.
+fn unescape_command_line(escaped: &str) -> String {
+    let mut bytes = Vec::with_capacity(escaped.len());
+    let mut rest = escaped.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+        match rest {
+            [b'\\', tail @ ..] => {
+                bytes.push(b'\\');
+                rest = tail;
+            }
+            [b'x', high, low, tail @ ..] => {
+                match std::str::from_utf8(&[*high, *low])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    Some(unescaped) => {
+                        bytes.push(unescaped);
+                        rest = tail;
+                    }
+                    None => bytes.push(b'\\'),
+                }
+            }
+            _ => bytes.push(b'\\'),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// A mark seen in the output, along with where the output was when the mark was sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PendingMark {
+    pub mark: ShellMark,
+    /// The value of [`ShellMarks::lines`] when the mark was sent.
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The marks the PTY reader saw, waiting for the terminal to place them on its grid.
+#[derive(Debug, Default)]
+pub(crate) struct ShellMarks {
+    /// How many lines the output advanced by since the terminal started.
+    pub lines: usize,
+    /// Whether the output is on the alternate screen, where full screen programs draw.
+    pub alternate_screen: bool,
+    pub pending: Vec<PendingMark>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ScanState {
+    Ground,
+    Escape,
+    Csi,
+    Osc,
+    OscEscape,
+}
+
+/// Finds the shell marks in a stream of PTY output, keeping a rough track of the cursor
+/// so that the marks can later be placed on the grid.
+///
+/// Only line feeds, carriage returns and line wrapping move the tracked cursor, which is
+/// accurate for the kind of output shells print around their marks. Output on the
+/// alternate screen, where full screen programs draw, doesn't move it either.
+#[derive(Debug)]
+pub(crate) struct MarkScanner {
+    state: ScanState,
+    osc: Vec<u8>,
+    osc_overflowed: bool,
+    csi: Vec<u8>,
+    /// The bytes of the UTF-8 encoded character being scanned.
+    utf8: Vec<u8>,
+    utf8_remaining: usize,
+    alternate_screen: bool,
+    lines: usize,
+    column: usize,
+    columns: usize,
+}
+
+impl MarkScanner {
+    pub fn new(columns: usize) -> Self {
+        Self {
+            state: ScanState::Ground,
+            osc: Vec::new(),
+            osc_overflowed: false,
+            csi: Vec::new(),
+            utf8: Vec::new(),
+            utf8_remaining: 0,
+            alternate_screen: false,
+            lines: 0,
+            column: 0,
+            columns: columns.max(1),
+        }
+    }
+
+    pub fn set_columns(&mut self, columns: usize) {
+        self.columns = columns.max(1);
+        self.column = self.column.min(self.columns);
+    }
+
+    pub fn scan(&mut self, bytes: &[u8], marks: &mut ShellMarks) {
+        for &byte in bytes {
+            match self.state {
+                ScanState::Ground => self.advance_ground(byte),
+                ScanState::Escape => match byte {
+                    b'[' => self.start_csi(),
+                    b']' => self.start_osc(),
+                    0x1b => {}
+                    // Intermediate bytes, the sequence continues.
+                    0x20..=0x2f => {}
+                    _ => self.state = ScanState::Ground,
+                },
+                ScanState::Csi => match byte {
+                    0x40..=0x7e => self.finish_csi(byte),
+                    0x18 | 0x1a => self.state = ScanState::Ground,
+                    0x1b => self.state = ScanState::Escape,
+                    _ if self.csi.len() < MAX_CSI_LEN => self.csi.push(byte),
+                    _ => {}
+                },
+                ScanState::Osc => match byte {
+                    0x07 => self.finish_osc(marks),
+                    0x1b => self.state = ScanState::OscEscape,
+                    0x18 | 0x1a => self.state = ScanState::Ground,
+                    _ if self.osc.len() < MAX_OSC_LEN => self.osc.push(byte),
+                    _ => self.osc_overflowed = true,
+                },
+                ScanState::OscEscape => match byte {
+                    b'\\' => self.finish_osc(marks),
+                    // The OSC was cut short by another escape sequence.
+                    b'[' => self.start_csi(),
+                    b']' => self.start_osc(),
+                    _ => self.state = ScanState::Ground,
+                },
+            }
+        }
+        marks.lines = self.lines;
+        marks.alternate_screen = self.alternate_screen;
+    }
+
+    fn advance_ground(&mut self, byte: u8) {
+        if !(0x80..=0xbf).contains(&byte) {
+            self.utf8_remaining = 0;
+        }
+        match byte {
+            0x1b => self.state = ScanState::Escape,
+            _ if self.alternate_screen => {}
+            b'\n' | 0x0b | 0x0c => self.lines += 1,
+            b'\r' => self.column = 0,
+            0x08 => self.column = self.column.saturating_sub(1),
+            b'\t' => self.column = ((self.column / 8 + 1) * 8).min(self.columns),
+            0x20..=0x7e => self.advance_cells(1),
+            0xc0..=0xf7 => {
+                self.utf8.clear();
+                self.utf8.push(byte);
+                self.utf8_remaining = byte.leading_ones() as usize - 1;
+            }
+            0x80..=0xbf if self.utf8_remaining > 0 => {
+                self.utf8.push(byte);
+                self.utf8_remaining -= 1;
+                if self.utf8_remaining == 0
+                    && let Some(character) = std::str::from_utf8(&self.utf8)
+                        .ok()
+                        .and_then(|utf8| utf8.chars().next())
+                {
+                    // Wide characters, like most CJK characters and emoji, take two cells.
+                    self.advance_cells(character.width().unwrap_or(0));
+                }
+            }
+            // Other control characters and invalid UTF-8 don't take a cell.
+            _ => {}
+        }
+    }
+
+    fn advance_cells(&mut self, width: usize) {
+        if width == 0 {
+            return;
+        }
+        // Like the terminal, wrap before a character that doesn't fit on the line.
+        if self.column + width > self.columns {
+            self.lines += 1;
+            self.column = 0;
+        }
+        self.column += width;
+    }
+
+    fn start_csi(&mut self) {
+        self.state = ScanState::Csi;
+        self.csi.clear();
+    }
+
+    fn finish_csi(&mut self, final_byte: u8) {
+        self.state = ScanState::Ground;
+        if !matches!(final_byte, b'h' | b'l') {
+            return;
+        }
+        let Some(modes) = self.csi.strip_prefix(b"?") else {
+            return;
+        };
+        if modes
+            .split(|byte| *byte == b';')
+            .any(|mode| matches!(mode, b"47" | b"1047" | b"1049"))
+        {
+            self.alternate_screen = final_byte == b'h';
+        }
+    }
+
+    fn start_osc(&mut self) {
+        self.state = ScanState::Osc;
+        self.osc.clear();
+        self.osc_overflowed = false;
+    }
+
+    fn finish_osc(&mut self, marks: &mut ShellMarks) {
+        self.state = ScanState::Ground;
+        if self.osc_overflowed {
+            return;
+        }
+        if let Some(mark) = ShellMark::parse(&self.osc) {
+            marks.pending.push(PendingMark {
+                mark,
+                line: self.lines,
+                column: self.column,
+            });
+        }
+    }
+}
+
+/// A PTY whose output is scanned for shell marks on its way to the terminal.
+pub(crate) struct ShellIntegrationPty<T> {
+    pty: T,
+    scanner: MarkScanner,
+    marks: Arc<Mutex<ShellMarks>>,
+}
+
+impl<T> ShellIntegrationPty<T> {
+    pub fn new(pty: T, window_size: WindowSize, marks: Arc<Mutex<ShellMarks>>) -> Self {
+        Self {
+            pty,
+            scanner: MarkScanner::new(window_size.num_cols as usize),
+            marks,
+        }
+    }
+}
+
+impl<T: EventedReadWrite> io::Read for ShellIntegrationPty<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.pty.reader().read(buf)?;
+        self.scanner.scan(&buf[..read], &mut self.marks.lock());
+        Ok(read)
+    }
+}
+
+impl<T: EventedReadWrite> EventedReadWrite for ShellIntegrationPty<T> {
+    type Reader = Self;
+    type Writer = T::Writer;
+
+    unsafe fn register(
+        &mut self,
+        poller: &Arc<Poller>,
+        interest: Event,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        // SAFETY: the wrapped PTY lives exactly as long as this wrapper.
+        unsafe { self.pty.register(poller, interest, mode) }
+    }
+
+    fn reregister(
+        &mut self,
+        poller: &Arc<Poller>,
+        interest: Event,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        self.pty.reregister(poller, interest, mode)
+    }
+
+    fn deregister(&mut self, poller: &Arc<Poller>) -> io::Result<()> {
+        self.pty.deregister(poller)
+    }
+
+    fn reader(&mut self) -> &mut Self::Reader {
+        self
+    }
+
+    fn writer(&mut self) -> &mut Self::Writer {
+        self.pty.writer()
+    }
+}
+
+impl<T: EventedPty> EventedPty for ShellIntegrationPty<T> {
+    fn next_child_event(&mut self) -> Option<ChildEvent> {
+        self.pty.next_child_event()
+    }
+}
+
+impl<T: OnResize> OnResize for ShellIntegrationPty<T> {
+    fn on_resize(&mut self, window_size: WindowSize) {
+        self.scanner.set_columns(window_size.num_cols as usize);
+        self.pty.on_resize(window_size);
+    }
+}
+
+/// A command the shell ran, delimited by its shell marks.
+///
+/// Lines are counted from the first line of output, including the lines that have since been
+/// dropped from the top of the scrollback.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommandBlock {
+    pub prompt_line: usize,
+    pub command_line: Option<usize>,
+    pub output_line: Option<usize>,
+    /// Where the cursor was when the command finished, as a line and a column.
+    pub end: Option<(usize, usize)>,
+    /// The command line, when the shell reports it.
+    pub command: Option<String>,
+    pub exit_code: Option<i32>,
+}
+
+impl CommandBlock {
+    fn new(prompt_line: usize) -> Self {
+        Self {
+            prompt_line,
+            command_line: None,
+            output_line: None,
+            end: None,
+            command: None,
+            exit_code: None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.end.is_some()
+    }
+}
+
+/// Where the terminal's cursor was after the output counted by the [`MarkScanner`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct GridPosition {
+    /// The value of [`ShellMarks::lines`] at the time.
+    pub output_lines: usize,
+    pub history_size: usize,
+    /// The cursor's line on the screen.
+    pub cursor_line: usize,
+    /// Whether the scrollback reached its size limit, so that every line scrolling into
+    /// it drops one from its top.
+    pub history_full: bool,
+}
+
+/// The commands run in a terminal, oldest first.
+#[derive(Debug, Default)]
+pub(crate) struct CommandBlocks {
+    blocks: Vec<CommandBlock>,
+    /// How many lines were dropped from the top of the scrollback.
+    lines_scrolled_out: usize,
+    last_position: Option<GridPosition>,
+}
+
+impl CommandBlocks {
+    pub fn blocks(&self) -> &[CommandBlock] {
+        &self.blocks
+    }
+
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+    }
+
+    /// Keeps track of the lines dropped from the top of the scrollback since the last
+    /// position, which has to be taken once the terminal parsed all of the scanned output.
+    pub fn sync(&mut self, position: GridPosition) {
+        let last_position = self.last_position.replace(position).unwrap_or_default();
+        if position.history_size < last_position.history_size {
+            // The scrollback was cleared, so the lines of the known commands are gone.
+            self.clear();
+        } else if position.history_full {
+            // Once the scrollback is full, the output advancing further than the cursor
+            // means that lines were dropped.
+            let output_advance = position
+                .output_lines
+                .saturating_sub(last_position.output_lines);
+            let cursor_advance = (position.history_size + position.cursor_line)
+                .saturating_sub(last_position.history_size + last_position.cursor_line);
+            self.lines_scrolled_out += output_advance.saturating_sub(cursor_advance);
+            let lines_scrolled_out = self.lines_scrolled_out;
+            self.blocks.retain(|block| {
+                block
+                    .end
+                    .is_none_or(|(end_line, _)| end_line >= lines_scrolled_out)
+            });
+        }
+    }
+
+    /// The line counted from the top of the scrollback that a block's line is on, or `None`
+    /// if it was dropped from the scrollback.
+    pub fn scrollback_line(&self, line: usize) -> Option<usize> {
+        line.checked_sub(self.lines_scrolled_out)
+    }
+
+    /// The block line for a line counted from the top of the scrollback.
+    pub fn block_line(&self, scrollback_line: usize) -> usize {
+        scrollback_line + self.lines_scrolled_out
+    }
+
+    /// Applies a mark placed at the given line and column, returning the exit status
+    /// of the command it finished, if any.
+    pub fn apply(&mut self, mark: ShellMark, line: usize, column: usize) -> Option<Option<i32>> {
+        match mark {
+            ShellMark::PromptStart => {
+                // A prompt that was redrawn, or submitted without running anything.
+                if self
+                    .blocks
+                    .last()
+                    .is_some_and(|block| !block.is_finished() && block.output_line.is_none())
+                {
+                    self.blocks.pop();
+                }
+                self.blocks.push(CommandBlock::new(line));
+                if self.blocks.len() > MAX_COMMAND_BLOCKS {
+                    self.blocks.remove(0);
+                }
+            }
+            ShellMark::CommandStart => self.current_block(line).command_line = Some(line),
+            ShellMark::CommandLine(command) => self.current_block(line).command = Some(command),
+            ShellMark::OutputStart => self.current_block(line).output_line = Some(line),
+            ShellMark::CommandFinished { exit_code } => {
+                let block = self
+                    .blocks
+                    .last_mut()
+                    .filter(|block| !block.is_finished())?;
+                if block.output_line.is_none() {
+                    // Shells report an empty command line as finished too.
+                    self.blocks.pop();
+                    return None;
+                }
+                block.end = Some((line, column));
+                block.exit_code = exit_code;
+                return Some(exit_code);
+            }
+        }
+        None
+    }
+
+    fn current_block(&mut self, line: usize) -> &mut CommandBlock {
+        if self.blocks.last().is_none_or(|block| block.is_finished()) {
+            self.blocks.push(CommandBlock::new(line));
+        }
+        self.blocks.last_mut().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(scanner: &mut MarkScanner, output: &[u8]) -> Vec<PendingMark> {
+        let mut marks = ShellMarks::default();
+        scanner.scan(output, &mut marks);
+        marks.pending
+    }
+
+    fn mark(mark: ShellMark, line: usize, column: usize) -> PendingMark {
+        PendingMark { mark, line, column }
+    }
+
+    #[test]
+    fn test_scan_marks() {
+        let mut scanner = MarkScanner::new(80);
+        let marks = scan(
+            &mut scanner,
+            b"\x1b]133;A\x07$ \x1b]133;B\x07ls\r\n\x1b]133;C\x07a\r\nb\r\n\x1b]133;D;1\x1b\\",
+        );
+        assert_eq!(
+            marks,
+            vec![
+                mark(ShellMark::PromptStart, 0, 0),
+                mark(ShellMark::CommandStart, 0, 2),
+                mark(ShellMark::OutputStart, 1, 0),
+                mark(ShellMark::CommandFinished { exit_code: Some(1) }, 3, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_marks_across_reads() {
+        let mut scanner = MarkScanner::new(80);
+        assert_eq!(scan(&mut scanner, b"out\r\n\x1b]63"), vec![]);
+        assert_eq!(scan(&mut scanner, b"3;E;echo a\\x3bb\\\\\x1b"), vec![]);
+        assert_eq!(
+            scan(&mut scanner, b"\\\x1b]633;D\x07"),
+            vec![
+                mark(ShellMark::CommandLine("echo a;b\\".into()), 1, 0),
+                mark(ShellMark::CommandFinished { exit_code: None }, 1, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_ignores_other_sequences() {
+        let mut scanner = MarkScanner::new(4);
+        let marks = scan(
+            &mut scanner,
+            b"\x1b]0;title\x07\x1b[31mabcdef\x1b[0m\x1b]133;X\x07\x1b]133;C\x07",
+        );
+        // `abcdef` wraps once on a 4 column wide terminal.
+        assert_eq!(marks, vec![mark(ShellMark::OutputStart, 1, 2)]);
+    }
+
+    #[test]
+    fn test_scan_wide_chars() {
+        let mut scanner = MarkScanner::new(5);
+        // The third character doesn't fit on the first line, and wraps.
+        let marks = scan(&mut scanner, "中文字é\x1b]133;C\x07".as_bytes());
+        assert_eq!(marks, vec![mark(ShellMark::OutputStart, 1, 3)]);
+    }
+
+    #[test]
+    fn test_scan_skips_alternate_screen() {
+        let mut scanner = MarkScanner::new(80);
+        let mut marks = ShellMarks::default();
+        scanner.scan(b"vim\r\n\x1b[?1049h\x1b[Ha\r\nb\r\n", &mut marks);
+        assert!(marks.alternate_screen);
+        scanner.scan(b"\x1b[?1049l\x1b]133;D;0\x07", &mut marks);
+        assert!(!marks.alternate_screen);
+        assert_eq!(
+            marks.pending,
+            vec![mark(
+                ShellMark::CommandFinished { exit_code: Some(0) },
+                1,
+                0
+            )]
+        );
+    }
+
+    #[test]
+    fn test_command_blocks() {
+        let mut blocks = CommandBlocks::default();
+        assert_eq!(blocks.apply(ShellMark::PromptStart, 0, 0), None);
+        assert_eq!(blocks.apply(ShellMark::CommandStart, 0, 2), None);
+        assert_eq!(
+            blocks.apply(ShellMark::CommandFinished { exit_code: Some(0) }, 1, 0),
+            None,
+            "an empty command line doesn't make a block"
+        );
+        assert!(blocks.blocks().is_empty());
+
+        blocks.apply(ShellMark::PromptStart, 1, 0);
+        blocks.apply(ShellMark::CommandStart, 1, 2);
+        blocks.apply(ShellMark::OutputStart, 2, 0);
+        assert_eq!(
+            blocks.apply(ShellMark::CommandFinished { exit_code: Some(2) }, 5, 0),
+            Some(Some(2))
+        );
+        blocks.apply(ShellMark::PromptStart, 5, 0);
+
+        assert_eq!(
+            blocks.blocks(),
+            &[
+                CommandBlock {
+                    prompt_line: 1,
+                    command_line: Some(1),
+                    output_line: Some(2),
+                    end: Some((5, 0)),
+                    command: None,
+                    exit_code: Some(2),
+                },
+                CommandBlock::new(5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_command_blocks_in_full_scrollback() {
+        let position = |output_lines, history_size, cursor_line, history_full| GridPosition {
+            output_lines,
+            history_size,
+            cursor_line,
+            history_full,
+        };
+        let mut blocks = CommandBlocks::default();
+        blocks.sync(position(5, 0, 5, false));
+        for (line, mark) in [
+            (5, ShellMark::PromptStart),
+            (5, ShellMark::CommandStart),
+            (6, ShellMark::OutputStart),
+            (8, ShellMark::CommandFinished { exit_code: None }),
+            (8, ShellMark::PromptStart),
+        ] {
+            blocks.apply(mark, line, 0);
+        }
+
+        // 19 more lines of output on a 10 line screen fill the scrollback of 10 lines, and
+        // push 5 lines out of it.
+        blocks.sync(position(24, 10, 9, true));
+        assert_eq!(blocks.scrollback_line(5), Some(0));
+        assert_eq!(blocks.block_line(3), 8);
+        assert_eq!(blocks.blocks().len(), 2);
+
+        // Lines keep being dropped without the scrollback growing.
+        blocks.sync(position(29, 10, 9, true));
+        assert_eq!(blocks.scrollback_line(5), None);
+        assert_eq!(blocks.scrollback_line(10), Some(0));
+        assert_eq!(
+            blocks.blocks(),
+            &[CommandBlock::new(8)],
+            "the commands that were dropped are forgotten"
+        );
+
+        // Clearing the scrollback forgets the commands, but keeps counting the lines.
+        blocks.sync(position(30, 0, 0, false));
+        assert!(blocks.blocks().is_empty());
+        assert_eq!(blocks.block_line(0), 10);
+    }
+}
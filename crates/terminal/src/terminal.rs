@@ -1,8 +1,10 @@
 pub mod mappings;
 
 pub use alacritty_terminal;
+pub use shell_integration::CommandBlock;
 
 mod pty_info;
+mod shell_integration;
 mod terminal_hyperlinks;
 pub mod terminal_settings;
 
@@ -41,9 +43,11 @@ use mappings::mouse::{
 
 use collections::{HashMap, VecDeque};
 use futures::StreamExt;
+use parking_lot::Mutex;
 use pty_info::{ProcessIdGetter, PtyProcessInfo};
 use serde::{Deserialize, Serialize};
 use settings::Settings;
+use shell_integration::{CommandBlocks, GridPosition, ShellIntegrationPty, ShellMarks};
 use smol::channel::{Receiver, Sender};
 use task::{HideStrategy, Shell, SpawnInTerminal};
 use terminal_hyperlinks::RegexSearches;
@@ -103,6 +107,12 @@ actions!(
         ScrollToTop,
         /// Scrolls to the bottom of the terminal buffer.
         ScrollToBottom,
+        /// Scrolls to the previous command run by the shell.
+        ScrollToPreviousCommand,
+        /// Scrolls to the next command run by the shell.
+        ScrollToNextCommand,
+        /// Copies the output of the last command run by the shell to the clipboard.
+        CopyLastCommandOutput,
         /// Toggles vi mode in the terminal.
         ToggleViMode,
         /// Selects all text in the terminal.
@@ -140,6 +150,10 @@ pub enum Event {
     SelectionsChanged,
     NewNavigationTarget(Option<MaybeNavigationTarget>),
     Open(MaybeNavigationTarget),
+    /// A command run by the shell finished, as reported by its shell integration.
+    CommandFinished {
+        exit_code: Option<i32>,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -415,6 +429,7 @@ impl TerminalBuilder {
             event_loop_task: Task::ready(Ok(())),
             background_executor: background_executor.clone(),
             path_style,
+            command_blocks: CommandBlocks::default(),
         };
 
         Ok(TerminalBuilder {
@@ -586,6 +601,14 @@ impl TerminalBuilder {
 
             let pty_info = PtyProcessInfo::new(&pty);
 
+            // Pick the shell integration marks out of the output before Alacritty drops them
+            let shell_marks = Arc::new(Mutex::new(ShellMarks::default()));
+            let pty = ShellIntegrationPty::new(
+                pty,
+                TerminalBounds::default().into(),
+                shell_marks.clone(),
+            );
+
             //And connect them together
             let event_loop = EventLoop::new(
                 term.clone(),
@@ -605,6 +628,7 @@ impl TerminalBuilder {
                 terminal_type: TerminalType::Pty {
                     pty_tx: Notifier(pty_tx),
                     info: Arc::new(pty_info),
+                    shell_marks,
                 },
                 completion_tx,
                 term,
@@ -646,6 +670,7 @@ impl TerminalBuilder {
                 event_loop_task: Task::ready(Ok(())),
                 background_executor,
                 path_style,
+                command_blocks: CommandBlocks::default(),
             };
 
             if !activation_script.is_empty() && no_task {
@@ -794,6 +819,14 @@ pub struct TerminalContent {
     pub last_hovered_word: Option<HoveredWord>,
     pub scrolled_to_top: bool,
     pub scrolled_to_bottom: bool,
+    pub command_markers: Vec<CommandMarker>,
+}
+
+/// The exit status of a finished command, shown next to the line it was run on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommandMarker {
+    pub line: Line,
+    pub exit_code: Option<i32>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -820,6 +853,7 @@ impl Default for TerminalContent {
             last_hovered_word: None,
             scrolled_to_top: false,
             scrolled_to_bottom: false,
+            command_markers: Vec::new(),
         }
     }
 }
@@ -834,6 +868,7 @@ enum TerminalType {
     Pty {
         pty_tx: Notifier,
         info: Arc<PtyProcessInfo>,
+        shell_marks: Arc<Mutex<ShellMarks>>,
     },
     DisplayOnly,
 }
@@ -870,6 +905,7 @@ pub struct Terminal {
     event_loop_task: Task<Result<(), anyhow::Error>>,
     background_executor: BackgroundExecutor,
     path_style: PathStyle,
+    command_blocks: CommandBlocks,
 }
 
 struct CopyTemplate {
@@ -973,6 +1009,7 @@ impl Terminal {
                 //NOOP, Handled in render
             }
             AlacTermEvent::Wakeup => {
+                self.resolve_shell_marks(cx);
                 cx.emit(Event::Wakeup);
 
                 if let TerminalType::Pty { info, .. } = &self.terminal_type {
@@ -1032,6 +1069,7 @@ impl Terminal {
             }
             InternalEvent::Clear => {
                 trace!("Clearing");
+                self.command_blocks.clear();
                 // Clear back buffer
                 term.clear_screen(ClearMode::Saved);
 
@@ -1367,6 +1405,141 @@ impl Terminal {
             .push_back(InternalEvent::SetSelection(selection));
     }
 
+    /// Places the shell marks seen since the last wakeup on the grid.
+    fn resolve_shell_marks(&mut self, cx: &mut Context<Self>) {
+        let TerminalType::Pty { shell_marks, .. } = &self.terminal_type else {
+            return;
+        };
+        // Most wakeups come without marks, so the terminal is only locked for the ones that
+        // have some. Marks sent around full screen programs are placed once the output is
+        // back on the main screen.
+        {
+            let shell_marks = shell_marks.lock();
+            if shell_marks.pending.is_empty() || shell_marks.alternate_screen {
+                return;
+            }
+        }
+        // Unlike the unfair lock, this waits for the event loop to finish parsing what it
+        // read, so that the lines counted by the reader match the grid.
+        let term = self.term.lock();
+        let (lines, pending) = {
+            let mut shell_marks = shell_marks.lock();
+            // A full screen program may have started while waiting for the lock.
+            if shell_marks.alternate_screen {
+                return;
+            }
+            (shell_marks.lines, std::mem::take(&mut shell_marks.pending))
+        };
+        let cursor = term.grid().cursor.point;
+        let history_size = term.history_size();
+        let position = GridPosition {
+            output_lines: lines,
+            history_size,
+            cursor_line: cursor.line.0.max(0) as usize,
+            history_full: history_size >= self.term_config.scrolling_history,
+        };
+        drop(term);
+
+        self.command_blocks.sync(position);
+        let cursor_line = history_size + position.cursor_line;
+        for pending_mark in pending {
+            let lines_since = lines.saturating_sub(pending_mark.line);
+            let line = self
+                .command_blocks
+                .block_line(cursor_line.saturating_sub(lines_since));
+            let column = if lines_since == 0 {
+                cursor.column.0
+            } else {
+                pending_mark.column
+            };
+            if let Some(exit_code) = self.command_blocks.apply(pending_mark.mark, line, column) {
+                cx.emit(Event::CommandFinished { exit_code });
+            }
+        }
+    }
+
+    /// The commands the shell reported through its shell integration, oldest first.
+    pub fn command_blocks(&self) -> &[CommandBlock] {
+        self.command_blocks.blocks()
+    }
+
+    /// The output of the last command that finished, if the shell reports its commands.
+    pub fn last_command_output(&self) -> Option<String> {
+        let block = self
+            .command_blocks
+            .blocks()
+            .iter()
+            .rev()
+            .find(|block| block.is_finished())?;
+        let output_line = self.command_blocks.scrollback_line(block.output_line?)?;
+        let (end_line, end_column) = block.end?;
+        let end_line = self.command_blocks.scrollback_line(end_line)?;
+
+        let term = self.term.lock_unfair();
+        let history_size = term.history_size() as i32;
+        let start = AlacPoint::new(Line(output_line as i32 - history_size), Column(0));
+        let end = if end_column > 0 {
+            AlacPoint::new(
+                Line(end_line as i32 - history_size),
+                Column(end_column - 1).min(term.last_column()),
+            )
+        } else if end_line > output_line {
+            AlacPoint::new(Line(end_line as i32 - history_size - 1), term.last_column())
+        } else {
+            return Some(String::new());
+        };
+        if start.line < term.topmost_line() || end.line > term.bottommost_line() {
+            return None;
+        }
+
+        let output = term.bounds_to_string(start, end);
+        Some(output.trim_end().to_string())
+    }
+
+    /// Scrolls up to the prompt of the command above the top of the viewport.
+    pub fn scroll_to_previous_command(&mut self) {
+        let top_line = self.viewport_top_line();
+        if let Some(block) = self
+            .command_blocks
+            .blocks()
+            .iter()
+            .rev()
+            .find(|block| block.prompt_line < top_line)
+        {
+            self.scroll_to_line(block.prompt_line);
+        }
+    }
+
+    /// Scrolls down to the prompt of the command below the top of the viewport.
+    pub fn scroll_to_next_command(&mut self) {
+        let top_line = self.viewport_top_line();
+        match self
+            .command_blocks
+            .blocks()
+            .iter()
+            .find(|block| block.prompt_line > top_line)
+        {
+            Some(block) => self.scroll_to_line(block.prompt_line),
+            None => self.scroll_to_bottom(),
+        }
+    }
+
+    /// The block line at the top of the viewport.
+    fn viewport_top_line(&self) -> usize {
+        let history_size = self.term.lock_unfair().history_size();
+        self.command_blocks
+            .block_line(history_size.saturating_sub(self.last_content.display_offset))
+    }
+
+    fn scroll_to_line(&mut self, line: usize) {
+        let history_size = self.term.lock_unfair().history_size();
+        let line = self.command_blocks.scrollback_line(line).unwrap_or(0);
+        let display_offset = history_size.saturating_sub(line);
+        let delta = display_offset as i32 - self.last_content.display_offset as i32;
+        self.events
+            .push_back(InternalEvent::Scroll(AlacScroll::Delta(delta)));
+    }
+
     pub fn copy(&mut self, keep_selection: Option<bool>) {
         self.events.push_back(InternalEvent::Copy(keep_selection));
     }
@@ -1603,6 +1776,28 @@ impl Terminal {
         }
 
         self.last_content = Self::make_content(&terminal, &self.last_content);
+        self.last_content.command_markers = self.visible_command_markers(&terminal);
+    }
+
+    fn visible_command_markers(&self, term: &Term<ZedListener>) -> Vec<CommandMarker> {
+        let history_size = term.history_size() as i32;
+        let display_offset = term.grid().display_offset() as i32;
+        let visible_lines = -display_offset..term.screen_lines() as i32 - display_offset;
+        self.command_blocks
+            .blocks()
+            .iter()
+            .filter(|block| block.is_finished())
+            .filter_map(|block| {
+                let line = self
+                    .command_blocks
+                    .scrollback_line(block.command_line.unwrap_or(block.prompt_line))?;
+                let line = line as i32 - history_size;
+                visible_lines.contains(&line).then_some(CommandMarker {
+                    line: Line(line),
+                    exit_code: block.exit_code,
+                })
+            })
+            .collect()
     }
 
     fn make_content(term: &Term<ZedListener>, last_content: &TerminalContent) -> TerminalContent {
@@ -2386,7 +2581,7 @@ unsafe fn append_text_to_term(term: &mut Term<ZedListener>, text_lines: &[&str])
 
 impl Drop for Terminal {
     fn drop(&mut self) {
-        if let TerminalType::Pty { pty_tx, info } =
+        if let TerminalType::Pty { pty_tx, info, .. } =
             std::mem::replace(&mut self.terminal_type, TerminalType::DisplayOnly)
         {
             pty_tx.0.send(Msg::Shutdown).ok();
@@ -50,6 +50,8 @@ pub struct LayoutState {
     display_offset: usize,
     hyperlink_tooltip: Option<AnyElement>,
     gutter: Pixels,
+    /// Exit status markers of the finished commands, by display line.
+    command_markers: Vec<(i32, Hsla)>,
     block_below_cursor_element: Option<AnyElement>,
     base_text_style: TextStyle,
     content_mode: ContentMode,
@@ -1030,10 +1032,22 @@ impl Element for TerminalElement {
                     cursor_char,
                     selection,
                     cursor,
+                    command_markers,
                     ..
                 } = &self.terminal.read(cx).last_content;
                 let mode = *mode;
                 let display_offset = *display_offset;
+                let command_markers = command_markers
+                    .iter()
+                    .map(|marker| {
+                        let color = match marker.exit_code {
+                            Some(0) => theme.status().success,
+                            Some(_) => theme.status().error,
+                            None => theme.status().ignored,
+                        };
+                        (marker.line.0 + display_offset as i32, color)
+                    })
+                    .collect();
 
                 // searches, highlights to a single range representations
                 let mut relative_highlighted_ranges = Vec::new();
@@ -1219,6 +1233,7 @@ impl Element for TerminalElement {
                     display_offset,
                     hyperlink_tooltip,
                     gutter,
+                    command_markers,
                     block_below_cursor_element,
                     base_text_style: text_style,
                     content_mode,
@@ -1302,6 +1317,18 @@ impl Element for TerminalElement {
                         rect.paint(origin, &layout.dimensions, window);
                     }
 
+                    for (line, color) in &layout.command_markers {
+                        let marker_width = (layout.gutter / 4.).max(px(2.));
+                        let marker_bounds = Bounds::new(
+                            point(
+                                origin.x - layout.gutter + (layout.gutter - marker_width) / 2.,
+                                origin.y + *line as f32 * layout.dimensions.line_height,
+                            ),
+                            size(marker_width, layout.dimensions.line_height),
+                        );
+                        window.paint_quad(fill(marker_bounds, *color));
+                    }
+
                     for (relative_highlighted_range, color) in &layout.relative_highlighted_ranges {
                         if let Some((start_y, highlighted_range_lines)) =
                             to_highlighted_range_lines(relative_highlighted_range, layout, origin)
@@ -627,6 +627,38 @@ impl TerminalPanel {
         cx.spawn(async move |_, _| rx.await?)
     }
 
+    /// Finds problems in the output of a finished task with the task's problem matchers,
+    /// and reports them as the project's diagnostics, replacing the ones from the task's previous run.
+    fn report_task_problems(
+        &self,
+        task: SpawnInTerminal,
+        terminal: &Entity<Terminal>,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return Task::ready(());
+        };
+        let lsp_store = workspace.read(cx).project().read(cx).lsp_store();
+        let output = terminal.read(cx).get_content();
+        let problem_matchers = task.problem_matchers;
+        let problems = cx.background_spawn(async move {
+            problem_matchers
+                .iter()
+                .filter_map(|problem_matcher| problem_matcher.find_problems(&output).log_err())
+                .flatten()
+                .collect::<Vec<_>>()
+        });
+        cx.spawn(async move |_, cx| {
+            let problems = problems.await;
+            lsp_store
+                .update(cx, |lsp_store, cx| {
+                    lsp_store.update_task_diagnostics(task.diagnostics_id, task.cwd, problems, cx)
+                })
+                .await
+                .log_err();
+        })
+    }
+
     fn spawn_in_new_terminal(
         &mut self,
         spawn_task: SpawnInTerminal,
@@ -1812,6 +1844,15 @@ impl workspace::TerminalProvider for TerminalProvider {
                         .read_with(cx, |terminal, cx| terminal.wait_for_completed_task(cx))
                         .ok()?
                         .await?;
+                    if !task.problem_matchers.is_empty()
+                        && let Some(terminal) = terminal.upgrade()
+                    {
+                        terminal_panel
+                            .update(cx, |terminal_panel, cx| {
+                                terminal_panel.report_task_problems(task, &terminal, cx)
+                            })
+                            .await;
+                    }
                     Some(Ok(exit_status))
                 }
                 Err(e) => Some(Err(e)),
@@ -415,7 +415,7 @@ impl ButtonCommon for Button {
 
 impl RenderOnce for Button {
     #[allow(refining_impl_trait)]
-    fn render(self, _window: &mut Window, cx: &mut App) -> ButtonLike {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> ButtonLike {
         let is_disabled = self.base.disabled;
         let is_selected = self.base.selected;
 
@@ -432,6 +432,10 @@ impl RenderOnce for Button {
             self.label_color.unwrap_or_default()
         };
 
+        self.base
+            .accessibility_label
+            .get_or_insert_with(|| label.clone());
+
         self.base.child(
             h_flex()
                 .gap(DynamicSpacing::Base04.rems(cx))
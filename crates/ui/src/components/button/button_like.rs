@@ -1,8 +1,8 @@
 use documented::Documented;
 use gpui::{
-    AnyElement, AnyView, ClickEvent, CursorStyle, DefiniteLength, FocusHandle, Hsla, MouseButton,
-    MouseClickEvent, MouseDownEvent, MouseUpEvent, Rems, StyleRefinement, relative,
-    transparent_black,
+    AccessibilityRole, AnyElement, AnyView, ClickEvent, CursorStyle, DefiniteLength, FocusHandle,
+    Hsla, MouseButton, MouseClickEvent, MouseDownEvent, MouseUpEvent, Rems, StyleRefinement,
+    relative, transparent_black,
 };
 use smallvec::SmallVec;
 
@@ -466,6 +466,7 @@ pub struct ButtonLike {
     on_right_click: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
     children: SmallVec<[AnyElement; 2]>,
     focus_handle: Option<FocusHandle>,
+    pub(super) accessibility_label: Option<SharedString>,
 }
 
 impl ButtonLike {
@@ -490,6 +491,7 @@ impl ButtonLike {
             layer: None,
             tab_index: None,
             focus_handle: None,
+            accessibility_label: None,
         }
     }
 
@@ -535,6 +537,12 @@ impl ButtonLike {
         self.hoverable_tooltip = Some(Box::new(tooltip));
         self
     }
+
+    /// Sets the name screen readers announce for this button.
+    pub fn accessibility_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.accessibility_label = Some(label.into());
+        self
+    }
 }
 
 impl Disableable for ButtonLike {
@@ -646,6 +654,10 @@ impl RenderOnce for ButtonLike {
         self.base
             .h_flex()
             .id(self.id.clone())
+            .accessibility_role(AccessibilityRole::Button)
+            .when_some(self.accessibility_label, |this, label| {
+                this.accessibility_label(label)
+            })
             .when_some(self.tab_index, |this, tab_index| this.tab_index(tab_index))
             .when_some(self.focus_handle, |this, focus_handle| {
                 this.track_focus(&focus_handle)
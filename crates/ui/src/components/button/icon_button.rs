@@ -78,6 +78,12 @@ impl IconButton {
         self
     }
 
+    /// Sets the name screen readers announce for this button, in place of the icon's name.
+    pub fn accessibility_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.base = self.base.accessibility_label(label);
+        self
+    }
+
     /// Sets the icon color used when the button is in a selected state.
     pub fn selected_icon_color(mut self, color: impl Into<Option<Color>>) -> Self {
         self.selected_icon_color = color.into();
@@ -189,7 +195,13 @@ impl VisibleOnHover for IconButton {
 
 impl RenderOnce for IconButton {
     #[allow(refining_impl_trait)]
-    fn render(self, window: &mut Window, cx: &mut App) -> ButtonLike {
+    fn render(mut self, window: &mut Window, cx: &mut App) -> ButtonLike {
+        // Icon buttons have no visible label, so fall back to the icon's name.
+        self.base.accessibility_label.get_or_insert_with(|| {
+            let icon_name: &'static str = self.icon.into();
+            icon_name.replace('_', " ").into()
+        });
+
         let is_disabled = self.base.disabled;
         let is_selected = self.base.selected;
         let selected_style = self.base.selected_style;
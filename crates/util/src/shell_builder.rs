@@ -12,6 +12,8 @@ pub struct ShellBuilder {
     interactive: bool,
     /// Whether to redirect stdin to /dev/null for the spawned command as a subshell.
     redirect_stdin: bool,
+    /// Whether to surround the command's output with shell integration marks.
+    mark_output: bool,
     kind: ShellKind,
 }
 
@@ -31,6 +33,7 @@ impl ShellBuilder {
             interactive: true,
             kind,
             redirect_stdin: false,
+            mark_output: false,
         }
     }
     pub fn non_interactive(mut self) -> Self {
@@ -73,6 +76,14 @@ impl ShellBuilder {
         self
     }
 
+    /// Makes the spawned command print the `OSC 133` marks delimiting its output, along with
+    /// its exit code, so that the terminal can tell the output apart from anything else.
+    /// Only POSIX shells and fish are supported, other shells run the command as is.
+    pub fn mark_command_output(mut self) -> Self {
+        self.mark_output = true;
+        self
+    }
+
     /// Returns the program and arguments to run this task in a shell.
     pub fn build(
         mut self,
@@ -122,6 +133,31 @@ impl ShellBuilder {
                     }
                 }
             }
+            if self.mark_output {
+                match self.kind {
+                    ShellKind::Posix => {
+                        combined_command = format!(
+                            "printf '\\033]133;C\\007'; {combined_command}\n\
+                             __zed_status=$?; printf '\\033]133;D;%d\\007' \"$__zed_status\"; exit \"$__zed_status\""
+                        );
+                    }
+                    ShellKind::Fish => {
+                        combined_command = format!(
+                            "printf '\\x1b]133;C\\x07'; {combined_command}\n\
+                             set -l __zed_status $status; printf '\\x1b]133;D;%d\\x07' $__zed_status; exit $__zed_status"
+                        );
+                    }
+                    ShellKind::Csh
+                    | ShellKind::Tcsh
+                    | ShellKind::Rc
+                    | ShellKind::Nushell
+                    | ShellKind::Xonsh
+                    | ShellKind::Elvish
+                    | ShellKind::PowerShell
+                    | ShellKind::Pwsh
+                    | ShellKind::Cmd => {}
+                }
+            }
 
             self.args
                 .extend(self.kind.args_for_shell(self.interactive, combined_command));
@@ -289,6 +325,37 @@ mod test {
         assert_eq!(args, vec!["-i", "-c", "begin; echo test; end </dev/null"]);
     }
 
+    #[test]
+    fn mark_command_output() {
+        let shell = Shell::Program("bash".to_owned());
+        let shell_builder = ShellBuilder::new(&shell, false);
+
+        let (program, args) = shell_builder
+            .redirect_stdin_to_dev_null()
+            .mark_command_output()
+            .build(Some("echo".into()), &["test".to_string()]);
+
+        assert_eq!(program, "bash");
+        assert_eq!(
+            args,
+            vec![
+                "-i",
+                "-c",
+                "printf '\\033]133;C\\007'; (echo test) </dev/null\n\
+                 __zed_status=$?; printf '\\033]133;D;%d\\007' \"$__zed_status\"; exit \"$__zed_status\""
+            ]
+        );
+
+        let shell = Shell::Program("pwsh".to_owned());
+        let shell_builder = ShellBuilder::new(&shell, false);
+
+        let (_, args) = shell_builder
+            .mark_command_output()
+            .build(Some("echo test".into()), &[]);
+
+        assert_eq!(args, vec!["-C", "echo test"]);
+    }
+
     #[test]
     fn does_not_quote_sole_command_only() {
         let shell = Shell::Program("fish".to_owned());
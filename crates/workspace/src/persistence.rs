@@ -12,6 +12,7 @@ use fs::Fs;
 
 use anyhow::{Context as _, Result, bail};
 use collections::{HashMap, HashSet, IndexSet};
+use dap::DataBreakpointAccessType;
 use db::{
     kvp::KEY_VALUE_STORE,
     query,
@@ -20,7 +21,9 @@ use db::{
 };
 use gpui::{Axis, Bounds, Task, WindowBounds, WindowId, point, size};
 use project::{
-    debugger::breakpoint_store::{BreakpointState, SourceBreakpoint},
+    debugger::breakpoint_store::{
+        BreakpointState, DataBreakpoint, FunctionBreakpoint, SourceBreakpoint,
+    },
     trusted_worktrees::{DbTrustedPaths, RemoteHostLocation},
 };
 
@@ -402,6 +405,27 @@ impl Column for BreakpointStateWrapper<'_> {
     }
 }
 
+/// Stores access types by their name in the Debug Adapter Protocol.
+fn access_type_name(access_type: DataBreakpointAccessType) -> &'static str {
+    match access_type {
+        DataBreakpointAccessType::Read => "read",
+        DataBreakpointAccessType::Write => "write",
+        DataBreakpointAccessType::ReadWrite => "readWrite",
+    }
+}
+
+fn parse_access_type(name: &str) -> Option<DataBreakpointAccessType> {
+    match name {
+        "read" => Some(DataBreakpointAccessType::Read),
+        "write" => Some(DataBreakpointAccessType::Write),
+        "readWrite" => Some(DataBreakpointAccessType::ReadWrite),
+        _ => {
+            log::error!("Invalid data breakpoint access type {name}");
+            None
+        }
+    }
+}
+
 impl sqlez::bindable::StaticColumnCount for Breakpoint {
     fn column_count() -> usize {
         // Position, log message, condition message, and hit condition message
@@ -963,6 +987,32 @@ impl Domain for WorkspaceDb {
         sql!(
             ALTER TABLE remote_connections ADD COLUMN use_podman BOOLEAN;
         ),
+        sql!(
+            CREATE TABLE function_breakpoints (
+                workspace_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                condition TEXT,
+                hit_condition TEXT,
+                state INTEGER NOT NULL,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            );
+        ),
+        sql!(
+            CREATE TABLE data_breakpoints (
+                workspace_id INTEGER NOT NULL,
+                data_id TEXT NOT NULL,
+                label TEXT NOT NULL,
+                access_type TEXT,
+                condition TEXT,
+                hit_condition TEXT,
+                state INTEGER NOT NULL,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            );
+        ),
     ];
 
     // Allow recovering from bad migration that was initially shipped to nightly
@@ -1098,6 +1148,8 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            function_breakpoints: self.function_breakpoints(workspace_id),
+            data_breakpoints: self.data_breakpoints(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
         })
@@ -1188,11 +1240,89 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            function_breakpoints: self.function_breakpoints(workspace_id),
+            data_breakpoints: self.data_breakpoints(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
         })
     }
 
+    fn function_breakpoints(&self, workspace_id: WorkspaceId) -> Vec<FunctionBreakpoint> {
+        let breakpoints: Result<
+            Vec<(
+                Arc<str>,
+                Option<Arc<str>>,
+                Option<Arc<str>>,
+                BreakpointStateWrapper,
+            )>,
+        > = self
+            .select_bound(sql! {
+                SELECT name, condition, hit_condition, state
+                FROM function_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(breakpoints) => breakpoints
+                .into_iter()
+                .map(
+                    |(name, condition, hit_condition, state)| FunctionBreakpoint {
+                        name,
+                        condition,
+                        hit_condition,
+                        state: state.0.into_owned(),
+                    },
+                )
+                .collect(),
+            Err(msg) => {
+                log::error!("Function breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
+    fn data_breakpoints(&self, workspace_id: WorkspaceId) -> Vec<DataBreakpoint> {
+        let breakpoints: Result<
+            Vec<(
+                Arc<str>,
+                Arc<str>,
+                Option<String>,
+                Option<Arc<str>>,
+                Option<Arc<str>>,
+                BreakpointStateWrapper,
+            )>,
+        > = self
+            .select_bound(sql! {
+                SELECT data_id, label, access_type, condition, hit_condition, state
+                FROM data_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(breakpoints) => breakpoints
+                .into_iter()
+                .map(
+                    |(data_id, label, access_type, condition, hit_condition, state)| {
+                        DataBreakpoint {
+                            data_id,
+                            label,
+                            access_type: access_type.as_deref().and_then(parse_access_type),
+                            condition,
+                            hit_condition,
+                            state: state.0.into_owned(),
+                        }
+                    },
+                )
+                .collect(),
+            Err(msg) => {
+                log::error!("Data breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     fn breakpoints(&self, workspace_id: WorkspaceId) -> BTreeMap<Arc<Path>, Vec<SourceBreakpoint>> {
         let breakpoints: Result<Vec<(PathBuf, Breakpoint)>> = self
             .select_bound(sql! {
@@ -1367,6 +1497,54 @@ impl WorkspaceDb {
                     }
                 }
 
+                conn.exec_bound(
+                    sql!(
+                        DELETE FROM function_breakpoints WHERE workspace_id = ?1;
+                    )
+                )?(workspace.id).context("Clearing old function breakpoints")?;
+
+                for bp in workspace.function_breakpoints {
+                    let state = BreakpointStateWrapper::from(bp.state);
+                    if let Err(err) = conn.exec_bound(sql!(
+                        INSERT INTO function_breakpoints (workspace_id, name, condition, hit_condition, state)
+                        VALUES (?1, ?2, ?3, ?4, ?5);))?
+
+                    ((
+                        workspace.id,
+                        bp.name,
+                        bp.condition,
+                        bp.hit_condition,
+                        state,
+                    )) {
+                        log::error!("{err}");
+                    }
+                }
+
+                conn.exec_bound(
+                    sql!(
+                        DELETE FROM data_breakpoints WHERE workspace_id = ?1;
+                    )
+                )?(workspace.id).context("Clearing old data breakpoints")?;
+
+                for bp in workspace.data_breakpoints {
+                    let state = BreakpointStateWrapper::from(bp.state);
+                    if let Err(err) = conn.exec_bound(sql!(
+                        INSERT INTO data_breakpoints (workspace_id, data_id, label, access_type, condition, hit_condition, state)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);))?
+
+                    ((
+                        workspace.id,
+                        bp.data_id,
+                        bp.label,
+                        bp.access_type.map(access_type_name),
+                        bp.condition,
+                        bp.hit_condition,
+                        state,
+                    )) {
+                        log::error!("{err}");
+                    }
+                }
+
                 conn.exec_bound(
                     sql!(
                         DELETE FROM user_toolchains WHERE workspace_id = ?1;
@@ -2462,6 +2640,8 @@ mod tests {
                 );
                 map
             },
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2583,6 +2763,8 @@ mod tests {
                 );
                 map
             },
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2617,6 +2799,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: collections::BTreeMap::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2633,6 +2817,116 @@ mod tests {
         assert!(empty_breakpoints.is_none());
     }
 
+    #[gpui::test]
+    async fn test_function_breakpoints() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_function_breakpoints").await;
+        let id = db.next_id().await.unwrap();
+
+        let function_breakpoints = vec![
+            FunctionBreakpoint {
+                name: "main".into(),
+                condition: None,
+                hit_condition: None,
+                state: BreakpointState::Enabled,
+            },
+            FunctionBreakpoint {
+                name: "std::panicking::begin_panic".into(),
+                condition: Some("x > 5".into()),
+                hit_condition: Some(">= 3".into()),
+                state: BreakpointState::Disabled,
+            },
+        ];
+        let workspace = SerializedWorkspace {
+            id,
+            paths: PathList::new(&["/tmp"]),
+            location: SerializedWorkspaceLocation::Local,
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            function_breakpoints: function_breakpoints.clone(),
+            data_breakpoints: Default::default(),
+            session_id: None,
+            window_id: None,
+            user_toolchains: Default::default(),
+        };
+
+        db.save_workspace(workspace.clone()).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.function_breakpoints, function_breakpoints);
+
+        db.save_workspace(SerializedWorkspace {
+            function_breakpoints: Vec::new(),
+            ..workspace
+        })
+        .await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert!(loaded.function_breakpoints.is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_data_breakpoints() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_data_breakpoints").await;
+        let id = db.next_id().await.unwrap();
+
+        let data_breakpoints = vec![
+            DataBreakpoint {
+                data_id: "0x1000".into(),
+                label: "Address: 0x1000 (4 bytes)".into(),
+                access_type: None,
+                condition: None,
+                hit_condition: None,
+                state: BreakpointState::Enabled,
+            },
+            DataBreakpoint {
+                data_id: "counter".into(),
+                label: "Variable: counter".into(),
+                access_type: Some(DataBreakpointAccessType::ReadWrite),
+                condition: Some("counter > 5".into()),
+                hit_condition: Some("3".into()),
+                state: BreakpointState::Disabled,
+            },
+        ];
+        let workspace = SerializedWorkspace {
+            id,
+            paths: PathList::new(&["/tmp"]),
+            location: SerializedWorkspaceLocation::Local,
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: data_breakpoints.clone(),
+            session_id: None,
+            window_id: None,
+            user_toolchains: Default::default(),
+        };
+
+        db.save_workspace(workspace.clone()).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.data_breakpoints, data_breakpoints);
+
+        db.save_workspace(SerializedWorkspace {
+            data_breakpoints: Vec::new(),
+            ..workspace
+        })
+        .await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert!(loaded.data_breakpoints.is_empty());
+    }
+
     #[gpui::test]
     async fn test_next_id_stability() {
         zlog::init_test();
@@ -2715,6 +3009,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2730,6 +3026,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2834,6 +3132,8 @@ mod tests {
             center_group,
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2868,6 +3168,8 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2886,6 +3188,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
             user_toolchains: Default::default(),
@@ -2925,6 +3229,8 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2966,6 +3272,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
             user_toolchains: Default::default(),
@@ -2981,6 +3289,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
             user_toolchains: Default::default(),
@@ -2996,6 +3306,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
             user_toolchains: Default::default(),
@@ -3011,6 +3323,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -3037,6 +3351,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
             user_toolchains: Default::default(),
@@ -3049,6 +3365,8 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -3108,6 +3426,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             centered_layout: false,
             session_id: None,
             window_id: None,
@@ -3151,6 +3471,8 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             window_id: Some(window_id),
             user_toolchains: Default::default(),
         })
@@ -3263,6 +3585,8 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             window_id: Some(window_id),
             user_toolchains: Default::default(),
         })
@@ -3622,6 +3946,8 @@ mod tests {
             display: None,
             docks: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             centered_layout: false,
             session_id: None,
             window_id: None,
@@ -3700,6 +4026,8 @@ mod tests {
                 centered_layout: false,
                 session_id: Some("test-session".to_owned()),
                 breakpoints: Default::default(),
+                function_breakpoints: Default::default(),
+                data_breakpoints: Default::default(),
                 window_id: Some(*window_id),
                 user_toolchains: Default::default(),
             })
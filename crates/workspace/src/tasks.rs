@@ -1,12 +1,18 @@
-use std::process::ExitStatus;
+use std::{cell::RefCell, process::ExitStatus, rc::Rc, sync::Arc};
 
-use anyhow::Result;
-use gpui::{AppContext, Context, Entity, Task};
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use futures::{
+    FutureExt as _,
+    future::{LocalBoxFuture, Shared},
+};
+use gpui::{AppContext, AsyncWindowContext, Context, Entity, Task, WeakEntity};
 use language::Buffer;
-use project::{TaskSourceKind, WorktreeId};
+use project::{TaskDependencies, TaskDependency, TaskSourceKind, WorktreeId};
 use remote::ConnectionState;
 use task::{
-    DebugScenario, ResolvedTask, SharedTaskContext, SpawnInTerminal, TaskContext, TaskTemplate,
+    DebugScenario, DependsOrder, ResolvedTask, SharedTaskContext, SpawnInTerminal, TaskContext,
+    TaskId, TaskTemplate,
 };
 use ui::Window;
 
@@ -56,7 +62,25 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) {
+        if !resolved_task.pending_inputs().is_empty() && self.on_prompt_for_task_inputs.is_some() {
+            self.prompt_for_task_inputs(task_source_kind, resolved_task, omit_history, window, cx);
+            return;
+        }
+
         let spawn_in_terminal = resolved_task.resolved.clone();
+        let dependencies = (!resolved_task.original_task().depends_on.is_empty()).then(|| {
+            self.project
+                .read(cx)
+                .task_store()
+                .read(cx)
+                .task_inventory()
+                .context("No task inventory to resolve task dependencies with")
+                .and_then(|inventory| {
+                    inventory
+                        .read(cx)
+                        .resolve_task_dependencies(&task_source_kind, &resolved_task)
+                })
+        });
         if !omit_history {
             if let Some(debugger_provider) = self.debugger_provider.as_ref() {
                 debugger_provider.task_scheduled(cx);
@@ -73,8 +97,13 @@ impl Workspace {
             });
         }
 
-        if let Some(terminal_provider) = self.terminal_provider.as_ref() {
-            let task_status = terminal_provider.spawn(spawn_in_terminal, window, cx);
+        if self.terminal_provider.is_some() {
+            let task_status = match dependencies {
+                Some(dependencies) => {
+                    self.spawn_with_dependencies(dependencies, spawn_in_terminal, window, cx)
+                }
+                None => self.spawn_in_terminal(spawn_in_terminal, window, cx),
+            };
 
             let task = cx.spawn(async |w, cx| {
                 let res = cx.background_spawn(task_status).await;
@@ -100,6 +129,81 @@ impl Workspace {
         }
     }
 
+    /// Asks the user for the values of the task's pending inputs, and schedules the task resolved with them.
+    /// Does nothing if the user dismisses the prompt.
+    fn prompt_for_task_inputs(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        omit_history: bool,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(prompt) = self.on_prompt_for_task_inputs.take() else {
+            return;
+        };
+        let values = prompt(self, resolved_task.pending_inputs().to_vec(), window, cx);
+        self.on_prompt_for_task_inputs = Some(prompt);
+
+        cx.spawn_in(window, async move |workspace, cx| {
+            let Ok(Some(values)) = values.await else {
+                return;
+            };
+            let Some(resolved_task) =
+                resolved_task.with_inputs(&task_source_kind.to_id_base(), values)
+            else {
+                log::error!(
+                    "Failed to resolve task `{}` with the provided inputs",
+                    resolved_task.resolved_label
+                );
+                return;
+            };
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.schedule_resolved_task(
+                        task_source_kind,
+                        resolved_task,
+                        omit_history,
+                        window,
+                        cx,
+                    );
+                })
+                .ok();
+        })
+        .detach();
+    }
+
+    /// Runs the tasks the task depends on first, and spawns the task itself only if all of them succeed.
+    fn spawn_with_dependencies(
+        &mut self,
+        dependencies: Result<TaskDependencies>,
+        spawn_in_terminal: SpawnInTerminal,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Option<Result<ExitStatus>>> {
+        let dependencies = match dependencies {
+            Ok(dependencies) => dependencies,
+            Err(e) => return Task::ready(Some(Err(e))),
+        };
+        cx.spawn_in(window, async move |workspace, cx| {
+            let runs = DependencyRuns::default();
+            if let Err(e) =
+                run_task_dependencies(dependencies, &runs, workspace.clone(), cx.clone()).await
+            {
+                return Some(Err(e));
+            }
+            if !has_command(&spawn_in_terminal) {
+                return Some(Ok(ExitStatus::default()));
+            }
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.spawn_in_terminal(spawn_in_terminal, window, cx)
+                })
+                .ok()?
+                .await
+        })
+    }
+
     pub fn start_debug_session(
         &mut self,
         scenario: DebugScenario,
@@ -134,3 +238,91 @@ impl Workspace {
         }
     }
 }
+
+fn has_command(spawn_in_terminal: &SpawnInTerminal) -> bool {
+    spawn_in_terminal
+        .command
+        .as_deref()
+        .is_some_and(|command| !command.trim().is_empty())
+}
+
+/// The runs of the dependencies started so far, so that a task several tasks depend on runs once,
+/// and all of them wait for that run.
+type DependencyRuns =
+    Rc<RefCell<HashMap<TaskId, Shared<LocalBoxFuture<'static, Result<(), Arc<anyhow::Error>>>>>>>;
+
+fn run_task_dependencies(
+    dependencies: TaskDependencies,
+    runs: &DependencyRuns,
+    workspace: WeakEntity<Workspace>,
+    cx: AsyncWindowContext,
+) -> LocalBoxFuture<'static, Result<()>> {
+    let runs = dependencies
+        .tasks
+        .into_iter()
+        .map(|dependency| run_task_dependency(dependency, runs, workspace.clone(), cx.clone()))
+        .collect::<Vec<_>>();
+    async move {
+        match dependencies.order {
+            DependsOrder::Sequence => {
+                for run in runs {
+                    run.await?;
+                }
+            }
+            DependsOrder::Parallel => {
+                futures::future::try_join_all(runs).await?;
+            }
+        }
+        Ok(())
+    }
+    .boxed_local()
+}
+
+fn run_task_dependency(
+    dependency: TaskDependency,
+    runs: &DependencyRuns,
+    workspace: WeakEntity<Workspace>,
+    cx: AsyncWindowContext,
+) -> LocalBoxFuture<'static, Result<()>> {
+    let run = runs
+        .borrow_mut()
+        .entry(dependency.resolved_task.id.clone())
+        .or_insert_with(|| {
+            let runs = runs.clone();
+            async move {
+                spawn_task_dependency(dependency, &runs, workspace, cx)
+                    .await
+                    .map_err(Arc::new)
+            }
+            .boxed_local()
+            .shared()
+        })
+        .clone();
+    async move { run.await.map_err(|e| anyhow::anyhow!("{e:#}")) }.boxed_local()
+}
+
+async fn spawn_task_dependency(
+    dependency: TaskDependency,
+    runs: &DependencyRuns,
+    workspace: WeakEntity<Workspace>,
+    mut cx: AsyncWindowContext,
+) -> Result<()> {
+    run_task_dependencies(dependency.dependencies, runs, workspace.clone(), cx.clone()).await?;
+
+    let spawn_in_terminal = dependency.resolved_task.resolved;
+    if !has_command(&spawn_in_terminal) {
+        return Ok(());
+    }
+    let label = spawn_in_terminal.label.clone();
+    let status = workspace
+        .update_in(&mut cx, |workspace, window, cx| {
+            workspace.spawn_in_terminal(spawn_in_terminal, window, cx)
+        })?
+        .await;
+    match status {
+        Some(Ok(status)) if status.success() => Ok(()),
+        Some(Ok(status)) => anyhow::bail!("Task `{label}` failed with {status}"),
+        Some(Err(e)) => Err(e.context(format!("Failed to spawn task `{label}`"))),
+        None => anyhow::bail!("Task `{label}` got cancelled"),
+    }
+}
@@ -1194,6 +1194,15 @@ type PromptForOpenPath = Box<
     ) -> oneshot::Receiver<Option<Vec<PathBuf>>>,
 >;
 
+type PromptForTaskInputs = Box<
+    dyn Fn(
+        &mut Workspace,
+        Vec<task::TaskInput>,
+        &mut Window,
+        &mut Context<Workspace>,
+    ) -> oneshot::Receiver<Option<HashMap<String, String>>>,
+>;
+
 #[derive(Default)]
 struct DispatchingKeystrokes {
     dispatched: HashSet<Vec<Keystroke>>,
@@ -1251,6 +1260,7 @@ pub struct Workspace {
     bounds_save_task_queued: Option<Task<()>>,
     on_prompt_for_new_path: Option<PromptForNewPath>,
     on_prompt_for_open_path: Option<PromptForOpenPath>,
+    on_prompt_for_task_inputs: Option<PromptForTaskInputs>,
     terminal_provider: Option<Box<dyn TerminalProvider>>,
     debugger_provider: Option<Arc<dyn DebuggerProvider>>,
     serializable_items_tx: UnboundedSender<Box<dyn SerializableItemHandle>>,
@@ -1433,7 +1443,9 @@ impl Workspace {
             window,
             |workspace, _, event, window, cx| match event {
                 BreakpointStoreEvent::BreakpointsUpdated(_, _)
-                | BreakpointStoreEvent::BreakpointsCleared(_) => {
+                | BreakpointStoreEvent::BreakpointsCleared(_)
+                | BreakpointStoreEvent::FunctionBreakpointsUpdated
+                | BreakpointStoreEvent::DataBreakpointsUpdated => {
                     workspace.serialize_workspace(window, cx);
                 }
                 BreakpointStoreEvent::SetDebugLine | BreakpointStoreEvent::ClearDebugLines => {}
@@ -1678,6 +1690,7 @@ impl Workspace {
             bounds_save_task_queued: None,
             on_prompt_for_new_path: None,
             on_prompt_for_open_path: None,
+            on_prompt_for_task_inputs: None,
             terminal_provider: None,
             debugger_provider: None,
             serializable_items_tx,
@@ -2375,6 +2388,10 @@ impl Workspace {
         self.on_prompt_for_open_path = Some(prompt)
     }
 
+    pub fn set_prompt_for_task_inputs(&mut self, prompt: PromptForTaskInputs) {
+        self.on_prompt_for_task_inputs = Some(prompt)
+    }
+
     pub fn set_terminal_provider(&mut self, provider: impl TerminalProvider + 'static) {
         self.terminal_provider = Some(Box::new(provider));
     }
@@ -5994,12 +6011,16 @@ impl Workspace {
 
         match self.serialize_workspace_location(cx) {
             WorkspaceLocation::Location(location, paths) => {
-                let breakpoints = self.project.update(cx, |project, cx| {
-                    project
-                        .breakpoint_store()
-                        .read(cx)
-                        .all_source_breakpoints(cx)
-                });
+                let (breakpoints, function_breakpoints, data_breakpoints) =
+                    self.project.update(cx, |project, cx| {
+                        let breakpoint_store = project.breakpoint_store();
+                        let breakpoint_store = breakpoint_store.read(cx);
+                        (
+                            breakpoint_store.all_source_breakpoints(cx),
+                            breakpoint_store.function_breakpoints().to_vec(),
+                            breakpoint_store.data_breakpoints().to_vec(),
+                        )
+                    });
                 let user_toolchains = self
                     .project
                     .read(cx)
@@ -6021,6 +6042,8 @@ impl Workspace {
                     centered_layout: self.centered_layout,
                     session_id: self.session_id.clone(),
                     breakpoints,
+                    function_breakpoints,
+                    data_breakpoints,
                     window_id: Some(window.window_handle().window_id().as_u64()),
                     user_toolchains,
                 };
@@ -6230,6 +6253,14 @@ impl Workspace {
                     project
                         .breakpoint_store()
                         .update(cx, |breakpoint_store, cx| {
+                            breakpoint_store.with_serialized_function_breakpoints(
+                                serialized_workspace.function_breakpoints,
+                                cx,
+                            );
+                            breakpoint_store.with_serialized_data_breakpoints(
+                                serialized_workspace.data_breakpoints,
+                                cx,
+                            );
                             breakpoint_store
                                 .with_serialized_breakpoints(serialized_workspace.breakpoints, cx)
                         })
@@ -657,6 +657,7 @@ fn main() {
         project_symbols::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
+        hierarchy_view::init(cx);
         tasks_ui::init(cx);
         snippets_ui::init(cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
@@ -1082,6 +1083,7 @@ fn handle_open_request(request: OpenRequest, app_state: Arc<AppState>, cx: &mut
                         &paths_with_position,
                         &[],
                         false,
+                        None,
                         app_state,
                         workspace::OpenOptions::default(),
                         cx,
@@ -1150,6 +1152,7 @@ fn handle_open_request(request: OpenRequest, app_state: Arc<AppState>, cx: &mut
                 &paths_with_position,
                 &request.diff_paths,
                 request.diff_all,
+                None,
                 app_state,
                 workspace::OpenOptions::default(),
                 cx,
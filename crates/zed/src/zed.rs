@@ -4796,6 +4796,7 @@ mod tests {
                 "bedrock",
                 "branches",
                 "buffer_search",
+                "call_hierarchy",
                 "channel_modal",
                 "cli",
                 "client",
@@ -4866,6 +4867,7 @@ mod tests {
                 "theme_selector",
                 "toast",
                 "toolchain",
+                "type_hierarchy",
                 "variable_list",
                 "vim",
                 "window",
@@ -13,7 +13,7 @@ use futures::channel::{mpsc, oneshot};
 use futures::future;
 use futures::future::join_all;
 use futures::{FutureExt, SinkExt, StreamExt};
-use git_ui::{file_diff_view::FileDiffView, multi_diff_view::MultiDiffView};
+use git_ui::{file_diff_view::FileDiffView, merge_view::MergeView, multi_diff_view::MultiDiffView};
 use gpui::{App, AsyncApp, Global, WindowHandle};
 use language::Point;
 use onboarding::FIRST_OPEN;
@@ -333,6 +333,7 @@ pub async fn open_paths_with_positions(
     path_positions: &[PathWithPosition],
     diff_paths: &[[String; 2]],
     diff_all: bool,
+    merge_paths: Option<&[String; 4]>,
     app_state: Arc<AppState>,
     open_options: workspace::OpenOptions,
     cx: &mut AsyncApp,
@@ -388,6 +389,27 @@ pub async fn open_paths_with_positions(
         }
     }
 
+    if let Some(merge_paths) = merge_paths {
+        let [base_path, local_path, remote_path, merged_path] =
+            merge_paths.clone().map(PathBuf::from);
+        let merge_view = multi_workspace.update(cx, |multi_workspace, window, cx| {
+            MergeView::open(
+                base_path,
+                local_path,
+                remote_path,
+                merged_path,
+                multi_workspace.workspace().downgrade(),
+                window,
+                cx,
+            )
+        })?;
+        items.push(Some(
+            merge_view
+                .await
+                .map(|merge_view| Box::new(merge_view) as Box<dyn ItemHandle>),
+        ));
+    }
+
     for (item, path) in items.iter_mut().zip(&paths) {
         if let Some(Err(error)) = item {
             *error = anyhow!("error opening {path:?}: {error}");
@@ -425,6 +447,7 @@ pub async fn handle_cli_connection(
                 paths,
                 diff_paths,
                 diff_all,
+                merge_paths,
                 wait,
                 wsl,
                 open_new_workspace,
@@ -464,6 +487,7 @@ pub async fn handle_cli_connection(
                     paths,
                     diff_paths,
                     diff_all,
+                    merge_paths,
                     open_new_workspace,
                     reuse,
                     &responses,
@@ -485,6 +509,7 @@ async fn open_workspaces(
     paths: Vec<String>,
     diff_paths: Vec<[String; 2]>,
     diff_all: bool,
+    merge_paths: Option<[String; 4]>,
     open_new_workspace: Option<bool>,
     reuse: bool,
     responses: &IpcSender<CliResponse>,
@@ -493,12 +518,16 @@ async fn open_workspaces(
     env: Option<collections::HashMap<String, String>>,
     cx: &mut AsyncApp,
 ) -> Result<()> {
-    if paths.is_empty() && diff_paths.is_empty() && open_new_workspace != Some(true) {
+    if paths.is_empty()
+        && diff_paths.is_empty()
+        && merge_paths.is_none()
+        && open_new_workspace != Some(true)
+    {
         return restore_or_create_workspace(app_state, cx).await;
     }
 
     let grouped_locations: Vec<(SerializedWorkspaceLocation, PathList)> =
-        if paths.is_empty() && diff_paths.is_empty() {
+        if paths.is_empty() && diff_paths.is_empty() && merge_paths.is_none() {
             Vec::new()
         } else {
             vec![(
@@ -542,6 +571,7 @@ async fn open_workspaces(
                         workspace_paths,
                         diff_paths.clone(),
                         diff_all,
+                        merge_paths.clone(),
                         open_new_workspace,
                         reuse,
                         wait,
@@ -590,6 +620,7 @@ async fn open_local_workspace(
     workspace_paths: Vec<String>,
     diff_paths: Vec<[String; 2]>,
     diff_all: bool,
+    merge_paths: Option<[String; 4]>,
     open_new_workspace: Option<bool>,
     reuse: bool,
     wait: bool,
@@ -615,6 +646,7 @@ async fn open_local_workspace(
         &paths_with_position,
         &diff_paths,
         diff_all,
+        merge_paths.as_ref(),
         app_state.clone(),
         workspace::OpenOptions {
             open_new_workspace,
@@ -641,10 +673,12 @@ async fn open_local_workspace(
     let mut errored = false;
     let mut item_release_futures = Vec::new();
     let mut subscriptions = Vec::new();
+    let mut merge_resolved = None;
 
     // If --wait flag is used with no paths, or a directory, then wait until
-    // the entire workspace is closed.
-    if wait {
+    // the entire workspace is closed. A merge only waits for its merge view,
+    // as the CLI passes the working directory along with the merge paths.
+    if wait && merge_paths.is_none() {
         let mut wait_for_window_close = paths_with_position.is_empty() && diff_paths.is_empty();
         for path_with_position in &paths_with_position {
             if app_state.fs.is_dir(&path_with_position.path).await {
@@ -678,6 +712,16 @@ async fn open_local_workspace(
                             }),
                         )
                     })));
+
+                    if let Some(merge_view) = item.downcast::<MergeView>() {
+                        let (resolved_tx, resolved_rx) = oneshot::channel();
+                        merge_resolved = Some(resolved_rx);
+                        subscriptions.push(Ok(cx.update(|cx| {
+                            cx.observe_release(&merge_view, move |merge_view, cx| {
+                                resolved_tx.send(merge_view.is_resolved(cx)).ok();
+                            })
+                        })));
+                    }
                 }
             }
             Some(Err(err)) => {
@@ -714,6 +758,17 @@ async fn open_local_workspace(
                 }
             }
         }
+
+        if let Some((mut merge_resolved, merge_paths)) = merge_resolved.zip(merge_paths)
+            && !matches!(merge_resolved.try_recv(), Ok(Some(true)))
+        {
+            responses
+                .send(CliResponse::Stderr {
+                    message: format!("merge conflicts in {} were not resolved", merge_paths[3]),
+                })
+                .log_err();
+            errored = true;
+        }
     }
 
     errored
@@ -753,7 +808,7 @@ mod tests {
     use serde_json::json;
     use std::{sync::Arc, task::Poll};
     use util::path;
-    use workspace::{AppState, MultiWorkspace};
+    use workspace::{AppState, MultiWorkspace, SaveIntent};
 
     #[gpui::test]
     fn test_parse_ssh_url(cx: &mut TestAppContext) {
@@ -962,6 +1017,70 @@ mod tests {
                     vec![],
                     false,
                     None,
+                    None,
+                    false,
+                    true,
+                    &response_tx,
+                    None,
+                    &app_state,
+                    &mut cx,
+                )
+                .await;
+                let _ = done_tx.send(errored);
+            }
+        })
+        .detach();
+
+        cx.background_executor.run_until_parked();
+        assert_eq!(cx.windows().len(), 1);
+        assert!(matches!(poll!(&mut done_rx), Poll::Pending));
+
+        let window = cx.windows()[0];
+        cx.update_window(window, |_, window, _| window.remove_window())
+            .unwrap();
+        cx.background_executor.run_until_parked();
+
+        let errored = done_rx.await.unwrap();
+        assert!(!errored);
+    }
+
+    #[gpui::test]
+    async fn test_wait_for_unresolved_merge(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(
+                path!("/root"),
+                json!({
+                    "file_BASE.txt": "base\n",
+                    "file_LOCAL.txt": "ours\n",
+                    "file_REMOTE.txt": "theirs\n",
+                    "file.txt": "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> feature\n",
+                }),
+            )
+            .await;
+
+        let (response_tx, _) = ipc::channel::<CliResponse>().unwrap();
+        let workspace_paths = vec![path!("/root").to_owned()];
+        let merge_paths = [
+            path!("/root/file_BASE.txt").to_owned(),
+            path!("/root/file_LOCAL.txt").to_owned(),
+            path!("/root/file_REMOTE.txt").to_owned(),
+            path!("/root/file.txt").to_owned(),
+        ];
+
+        let (done_tx, mut done_rx) = futures::channel::oneshot::channel();
+        cx.spawn({
+            let app_state = app_state.clone();
+            move |mut cx| async move {
+                let errored = open_local_workspace(
+                    workspace_paths,
+                    vec![],
+                    false,
+                    Some(merge_paths),
+                    None,
                     false,
                     true,
                     &response_tx,
@@ -979,11 +1098,103 @@ mod tests {
         assert_eq!(cx.windows().len(), 1);
         assert!(matches!(poll!(&mut done_rx), Poll::Pending));
 
+        // Closing the merge view with the conflict still in place fails the merge.
         let window = cx.windows()[0];
         cx.update_window(window, |_, window, _| window.remove_window())
             .unwrap();
         cx.background_executor.run_until_parked();
 
+        let errored = done_rx.await.unwrap();
+        assert!(errored);
+    }
+
+    #[gpui::test]
+    async fn test_wait_for_resolved_merge(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(
+                path!("/root"),
+                json!({
+                    "file_BASE.txt": "base\n",
+                    "file_LOCAL.txt": "ours\n",
+                    "file_REMOTE.txt": "theirs\n",
+                    "file.txt": "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> feature\n",
+                }),
+            )
+            .await;
+
+        let (response_tx, _) = ipc::channel::<CliResponse>().unwrap();
+        let workspace_paths = vec![path!("/root").to_owned()];
+        let merge_paths = [
+            path!("/root/file_BASE.txt").to_owned(),
+            path!("/root/file_LOCAL.txt").to_owned(),
+            path!("/root/file_REMOTE.txt").to_owned(),
+            path!("/root/file.txt").to_owned(),
+        ];
+
+        let (done_tx, mut done_rx) = futures::channel::oneshot::channel();
+        cx.spawn({
+            let app_state = app_state.clone();
+            move |mut cx| async move {
+                let errored = open_local_workspace(
+                    workspace_paths,
+                    vec![],
+                    false,
+                    Some(merge_paths),
+                    None,
+                    false,
+                    true,
+                    &response_tx,
+                    None,
+                    &app_state,
+                    &mut cx,
+                )
+                .await;
+                let _ = done_tx.send(errored);
+            }
+        })
+        .detach();
+
+        cx.background_executor.run_until_parked();
+        assert_eq!(cx.windows().len(), 1);
+        assert!(matches!(poll!(&mut done_rx), Poll::Pending));
+
+        // Resolve the conflict in the merged editor and save it.
+        let window = cx.windows()[0];
+        let save_task = window
+            .downcast::<MultiWorkspace>()
+            .unwrap()
+            .update(cx, |multi_workspace, window, cx| {
+                multi_workspace.workspace().update(cx, |workspace, cx| {
+                    let merge_view = workspace.active_item_as::<MergeView>(cx).unwrap();
+                    let merged_editor = merge_view.act_as::<Editor>(cx).unwrap();
+                    merged_editor.update(cx, |editor, cx| {
+                        editor.set_text("ours\ntheirs\n", window, cx)
+                    });
+                    workspace.save_active_item(SaveIntent::Save, window, cx)
+                })
+            })
+            .unwrap();
+        save_task.await.unwrap();
+        cx.background_executor.run_until_parked();
+        assert_eq!(
+            app_state
+                .fs
+                .load(path!("/root/file.txt").as_ref())
+                .await
+                .unwrap(),
+            "ours\ntheirs\n"
+        );
+        assert!(matches!(poll!(&mut done_rx), Poll::Pending));
+
+        // Closing the resolved merge view completes the merge.
+        cx.update_window(window, |_, window, _| window.remove_window())
+            .unwrap();
+        cx.background_executor.run_until_parked();
+
         let errored = done_rx.await.unwrap();
         assert!(!errored);
     }
@@ -1059,6 +1270,7 @@ mod tests {
                     workspace_paths,
                     vec![],
                     false,
+                    None,
                     open_new_workspace,
                     false,
                     false,
@@ -1134,6 +1346,7 @@ mod tests {
                         vec![],
                         false,
                         None,
+                        None,
                         false,
                         false,
                         &response_tx,
@@ -1158,6 +1371,7 @@ mod tests {
                         workspace_paths_reuse,
                         vec![],
                         false,
+                        None,
                         None, // open_new_workspace will be overridden by reuse logic
                         true, // reuse = true
                         false,
@@ -1310,6 +1524,7 @@ mod tests {
                         Vec::new(),
                         false,
                         None,
+                        None,
                         false,
                         false,
                         &response_tx,
@@ -1336,6 +1551,7 @@ mod tests {
                         workspace_paths_2,
                         Vec::new(),
                         false,
+                        None,
                         Some(true), // Force new window
                         false,
                         false,
@@ -1382,6 +1598,7 @@ mod tests {
                         workspace_paths_add,
                         Vec::new(),
                         false,
+                        None,
                         Some(false), // --add flag: open_new_workspace = Some(false)
                         false,
                         false,
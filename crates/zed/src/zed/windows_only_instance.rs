@@ -156,6 +156,7 @@ fn send_args_to_instance(args: &Args) -> anyhow::Result<()> {
             urls,
             diff_paths,
             diff_all: false,
+            merge_paths: None,
             wait: false,
             wsl: args.wsl.clone(),
             open_new_workspace: None,
@@ -264,6 +264,8 @@ pub mod git {
             Branch,
             /// Opens the git stash selector.
             ViewStash,
+            /// Opens the git tag selector.
+            ViewTags,
             /// Opens the git worktree selector.
             Worktree,
             /// Creates a pull request for the current branch.
@@ -621,6 +623,34 @@ pub mod outline {
     pub static TOGGLE_OUTLINE: OnceLock<fn(AnyView, &mut Window, &mut App)> = OnceLock::new();
 }
 
+pub mod call_hierarchy {
+    use gpui::actions;
+
+    actions!(
+        call_hierarchy,
+        [
+            /// Shows the functions that call the symbol under the cursor.
+            ShowIncomingCalls,
+            /// Shows the functions called by the symbol under the cursor.
+            ShowOutgoingCalls,
+        ]
+    );
+}
+
+pub mod type_hierarchy {
+    use gpui::actions;
+
+    actions!(
+        type_hierarchy,
+        [
+            /// Shows the types the type under the cursor extends or implements.
+            ShowSupertypes,
+            /// Shows the types extending or implementing the type under the cursor.
+            ShowSubtypes,
+        ]
+    );
+}
+
 actions!(
     zed_predict_onboarding,
     [